@@ -1,4 +1,8 @@
-use clap::Parser;
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::{Args, Parser, Subcommand};
+use devfetch::config::Config;
+use devfetch::types::Severity;
+use devfetch::Scanner;
 use std::path::PathBuf;
 
 /// devfetch: A discovery engine for developer tools and project ecosystems
@@ -12,6 +16,51 @@ use std::path::PathBuf;
                   and analyzes project directories for ecosystem-specific information."
 )]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[command(flatten)]
+    pub scan: ScanArgs,
+
+    /// Output in JSON format
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// Verbose output for debugging
+    #[arg(short, long, global = true)]
+    pub verbose: bool,
+
+    /// Disable colored output (useful for piping)
+    #[arg(long = "no-color", global = true)]
+    pub no_color: bool,
+
+    /// Language for headers and labels (default: from LANG)
+    #[arg(long, global = true, value_name = "LANG", value_parser = ["en", "es", "de", "zh"])]
+    pub lang: Option<String>,
+
+    /// Exit 1 when doctor or the PATH audit reports a finding this severe or worse
+    #[arg(
+        long = "error-on",
+        global = true,
+        value_name = "SEVERITY",
+        default_value = "error",
+        value_parser = PossibleValuesParser::new(["info", "warning", "error"]).map(severity)
+    )]
+    pub error_on: Severity,
+}
+
+/// A `--error-on` value clap has already checked against the three names
+fn severity(name: String) -> Severity {
+    match name.as_str() {
+        "info" => Severity::Info,
+        "warning" => Severity::Warning,
+        _ => Severity::Error,
+    }
+}
+
+/// Options controlling a scan, shared by `devfetch` and `devfetch scan`
+#[derive(Args, Debug, Clone)]
+pub struct ScanArgs {
     /// Target directory to inspect (default: current directory)
     #[arg(value_name = "PATH")]
     pub path: Option<PathBuf>,
@@ -24,20 +73,333 @@ pub struct Cli {
     #[arg(long, conflicts_with = "global")]
     pub local: bool,
 
-    /// Output in JSON format
+    /// Also look this many directory levels down for sub-projects (monorepos);
+    /// dependency, build and hidden directories are skipped
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    pub depth: usize,
+
+    /// Extra directories to scan for tools not on PATH (comma-separated)
+    #[arg(long = "extra-dirs", value_name = "DIRS", value_delimiter = ',')]
+    pub extra_dirs: Vec<String>,
+
+    /// Resolve PATH through the user's login shell instead of inheriting it
+    #[arg(long = "login-path")]
+    pub login_path: bool,
+
+    /// PATH entry to exclude from scanning (repeatable)
+    #[arg(long = "skip-dir", value_name = "PATH")]
+    pub skip_dirs: Vec<PathBuf>,
+
+    /// Verify tool binaries against package manager records (dpkg, rpm, Homebrew)
+    #[arg(long = "verify-integrity")]
+    pub verify_integrity: bool,
+
+    /// Check whether VMs/instances of detected virtualization tools are running
+    #[arg(long = "vm-status")]
+    pub vm_status: bool,
+
+    /// Report which container registries you're logged into (Docker/Podman
+    /// auth files; secrets are not read) and which the project pulls from
+    #[arg(long = "registry-auth")]
+    pub registry_auth: bool,
+
+    /// Check that private registries, package indexes and git remotes named in
+    /// the project's package settings and lockfiles are reachable (VPN up?)
     #[arg(long)]
-    pub json: bool,
+    pub network: bool,
 
-    /// Verbose output for debugging
-    #[arg(short, long)]
-    pub verbose: bool,
+    /// Check the ssh-agent, loaded keys and known_hosts entries for the
+    /// project's SSH git remotes (key material is never read)
+    #[arg(long)]
+    pub ssh: bool,
 
-    /// Disable colored output (useful for piping)
-    #[arg(long = "no-color")]
-    pub no_color: bool,
+    /// Ask gh, glab and tea which hosts they're logged into
+    #[arg(long = "git-auth")]
+    pub git_auth: bool,
+
+    /// Return what has been found when this time runs out, e.g. 2s or 500ms.
+    /// Project probes and common toolchains go first; the result is marked partial
+    #[arg(long, value_name = "DURATION", value_parser = devfetch::core::exec::parse_duration)]
+    pub budget: Option<std::time::Duration>,
+
+    /// Reuse the last scan's tools while no tool directory has changed, probing
+    /// only binaries that are new or changed (kept in the probe cache)
+    #[arg(long, conflicts_with = "no_cache")]
+    pub incremental: bool,
+
+    /// Re-probe every tool instead of trusting the probe cache, and cache the new results
+    #[arg(long, conflicts_with = "no_cache")]
+    pub refresh: bool,
+
+    /// Neither read nor write the probe cache (~/.cache/devfetch/probes.json)
+    #[arg(long = "no-cache")]
+    pub no_cache: bool,
+
+    /// Take global tools from a running `devfetch daemon` (falls back to scanning)
+    #[arg(long = "from-daemon")]
+    pub from_daemon: bool,
+
+    /// Hide personal details before output, upload or notify: home (paths become ~),
+    /// hostname, username (comma-separated)
+    #[arg(long, value_name = "WHAT", value_delimiter = ',', value_parser = ["home", "hostname", "username"])]
+    pub redact: Vec<String>,
+
+    /// Reproducible output for golden files: project and home paths become . and ~,
+    /// no hostname, zeroed timestamps and durations, every list sorted
+    #[arg(long)]
+    pub deterministic: bool,
+
+    /// Also write the scan to a file (alongside the terminal output)
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Format for --output: json (overwrite) or jsonl (append one line per scan);
+    /// or tmux to print the project's primary runtime for a status line
+    #[arg(long, value_name = "FORMAT", value_parser = ["json", "jsonl", "tmux"])]
+    pub format: Option<String>,
+
+    /// Upload the scan as gzipped JSON to an HTTP(S) endpoint or s3:// URL
+    #[arg(long, value_name = "URL")]
+    pub push: Option<String>,
+
+    /// Extra HTTP header for --push, e.g. "Authorization: Bearer $TOKEN" (repeatable)
+    #[arg(long = "push-header", value_name = "HEADER", requires = "push")]
+    pub push_headers: Vec<String>,
+
+    /// Show what --push would send without uploading
+    #[arg(long = "dry-run", requires = "push")]
+    pub dry_run: bool,
+
+    #[command(flatten)]
+    pub notify: NotifyArgs,
+}
+
+/// Options for posting a summary to a chat webhook
+#[derive(Args, Debug, Clone)]
+pub struct NotifyArgs {
+    /// Post a compact summary to a Slack, Discord or Teams webhook URL
+    #[arg(long, value_name = "URL")]
+    pub notify: Option<String>,
+
+    /// Webhook message format (detected from the URL when omitted)
+    #[arg(long = "notify-format", value_name = "FORMAT", value_parser = ["slack", "discord", "teams"], requires = "notify")]
+    pub notify_format: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Scan global tools and the project (the default when no subcommand is given)
+    Scan(ScanArgs),
+
+    /// Check installed tools against the project's pinned and required tools
+    Doctor {
+        #[command(flatten)]
+        scan: ScanArgs,
+    },
+
+    /// List PATH entries in search order and audit them for security problems
+    Path,
+
+    /// Show how a scan sees one tool: path, category, scope and version
+    Tool {
+        /// Tool name looked up on PATH, or a path to a binary
+        #[arg(value_name = "NAME")]
+        name: String,
+
+        /// Also say why: the classifier rule that matched, each version flag
+        /// tried, and whether the probe cache would be used
+        #[arg(long)]
+        explain: bool,
+    },
+
+    /// Check that tools are installed at the required versions, e.g.
+    /// `devfetch check "node>=18" "python~3.11" docker`; exits 1 on failure
+    Check {
+        /// TOOL or TOOL<op>VERSION, with op one of >= <= > < = != ^ ~
+        #[arg(value_name = "CONSTRAINT", required = true)]
+        constraints: Vec<String>,
+    },
+
+    /// Compare two scans side by side (e.g. before and after an upgrade, or
+    /// your machine vs a CI image)
+    Diff {
+        /// Baseline: a saved snapshot name or a scan file from `devfetch --json`
+        #[arg(value_name = "OLD")]
+        old: String,
+
+        /// Snapshot name or scan file to compare against; defaults to a fresh scan
+        #[arg(value_name = "NEW")]
+        new: Option<String>,
+
+        /// Only show tools whose versions differ or that exist on one side
+        #[arg(long = "mismatched-only")]
+        mismatched_only: bool,
+    },
+
+    /// Dump scan results as osquery-compatible tables (devfetch_tools, devfetch_projects)
+    Osquery {
+        /// Emit the rows of a single table as a JSON array
+        #[arg(long, value_name = "TABLE", value_parser = ["devfetch_tools", "devfetch_projects"])]
+        table: Option<String>,
+
+        /// Print the table schemas as CREATE TABLE statements and exit
+        #[arg(long)]
+        schema: bool,
+
+        #[command(flatten)]
+        scan: ScanArgs,
+    },
+
+    /// Keep a warm global scan in memory and serve it over a unix socket
+    /// to `devfetch --from-daemon`, rescanning when PATH directories change
+    Daemon {
+        /// Seconds between checks of the PATH directories
+        #[arg(long, value_name = "SECONDS", default_value = "5")]
+        interval: u64,
+
+        #[command(flatten)]
+        scan: ScanArgs,
+    },
+
+    /// Print a shell hook that shows a one-line project summary (ecosystems
+    /// and requirement mismatches) on every cd, e.g. `eval "$(devfetch hook zsh)"`
+    Hook {
+        /// Shell to generate the hook for
+        #[arg(value_name = "SHELL", value_parser = ["zsh", "bash", "fish"], required_unless_present = "summary")]
+        shell: Option<String>,
+
+        /// Print the cached summary for the current directory (called by the hook)
+        #[arg(long, hide = true)]
+        summary: bool,
+
+        #[command(flatten)]
+        scan: ScanArgs,
+    },
+
+    /// Serve scan, project and doctor requests as JSON-RPC over stdio
+    /// (Content-Length framing, as in LSP) for editor extensions
+    LspExt {
+        #[command(flatten)]
+        scan: ScanArgs,
+    },
+
+    /// Inspect the cache of version probe results
+    Cache {
+        #[command(subcommand)]
+        action: CacheCommand,
+    },
+
+    /// Show or send the opt-in usage statistics (`[stats] enabled = true` in the config)
+    Stats {
+        #[command(subcommand)]
+        action: StatsCommand,
+    },
+
+    /// Save scans under a name to diff against later
+    Snapshot {
+        #[command(subcommand)]
+        action: SnapshotCommand,
+    },
+
+    /// Aggregate scans from many machines
+    Fleet {
+        #[command(subcommand)]
+        action: FleetCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheCommand {
+    /// Entry count, size on disk, last scan's hit rate and entry ages
+    Stats,
+
+    /// Show what is cached for a tool (every PATH copy) or an exact binary path
+    Show {
+        #[arg(value_name = "TOOL")]
+        tool: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum StatsCommand {
+    /// Print the recorded totals: scans, durations and tool counts
+    Show,
+
+    /// Send the recorded totals to the `[stats] endpoint` from the config
+    Push {
+        /// Print what would be sent without sending it
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+    },
+}
+
+// Parsed once per run, so boxing the scan options would buy nothing
+#[allow(clippy::large_enum_variant)]
+#[derive(Subcommand, Debug)]
+pub enum SnapshotCommand {
+    /// Scan and store the result, named after today's date unless NAME is given
+    Save {
+        #[arg(value_name = "NAME")]
+        name: Option<String>,
+
+        /// Replace a snapshot already saved under NAME
+        #[arg(long)]
+        force: bool,
+
+        #[command(flatten)]
+        scan: ScanArgs,
+    },
+
+    /// List saved snapshots, oldest first
+    List,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum FleetCommand {
+    /// Merge scan JSON files into per-tool version distributions
+    Merge {
+        /// Scan files produced by `devfetch --json`
+        #[arg(value_name = "FILES", required = true)]
+        files: Vec<PathBuf>,
+
+        /// Tool every machine must have (repeatable); defaults to tools on most machines
+        #[arg(long = "require", value_name = "TOOL")]
+        require: Vec<String>,
+
+        #[command(flatten)]
+        notify: NotifyArgs,
+    },
+
+    /// Generate a static HTML dashboard from scan JSON files
+    Dashboard {
+        /// Scan files; scans/<team>/<host>.json layouts enable per-team drill-down
+        #[arg(value_name = "FILES", required = true)]
+        files: Vec<PathBuf>,
+
+        /// Tool every machine must have (repeatable)
+        #[arg(long = "require", value_name = "TOOL")]
+        require: Vec<String>,
+
+        /// Where to write the HTML page
+        #[arg(short, long, value_name = "FILE", default_value = "fleet-dashboard.html")]
+        output: PathBuf,
+    },
 }
 
 impl Cli {
+    /// Scan options, whether given at top level or to `devfetch scan`
+    pub fn scan_args(&self) -> &ScanArgs {
+        match &self.command {
+            Some(Command::Scan(scan))
+            | Some(Command::Osquery { scan, .. })
+            | Some(Command::Doctor { scan })
+            | Some(Command::Snapshot { action: SnapshotCommand::Save { scan, .. } }) => scan,
+            _ => &self.scan,
+        }
+    }
+}
+
+impl ScanArgs {
     pub fn should_scan_global(&self) -> bool {
         !self.local
     }
@@ -46,6 +408,32 @@ impl Cli {
         !self.global
     }
 
+    /// A library scanner set up with these options
+    pub fn scanner(&self, config: Config) -> Scanner<'static> {
+        let scanner = Scanner::new()
+            .global(self.should_scan_global())
+            .local(self.should_scan_local())
+            .project_path(self.target_path())
+            .depth(self.depth)
+            .config(config)
+            .extra_dirs(self.extra_dirs.clone())
+            .skip_dirs(self.skip_dirs.clone())
+            .from_daemon(self.from_daemon)
+            .incremental(self.incremental)
+            .refresh(self.refresh)
+            .cache(!self.no_cache)
+            .verify_integrity(self.verify_integrity)
+            .vm_status(self.vm_status)
+            .git_auth(self.git_auth)
+            .registry_auth(self.registry_auth)
+            .network(self.network)
+            .ssh(self.ssh);
+        match self.budget {
+            Some(budget) => scanner.budget(budget),
+            None => scanner,
+        }
+    }
+
     pub fn target_path(&self) -> PathBuf {
         self.path
             .clone()
@@ -0,0 +1,217 @@
+use anyhow::{Context, Result};
+use crate::types::{MarkerOverride, ServiceRequirement, SystemRequirements, ToolCategory};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// User configuration loaded from `~/.config/devfetch/config.toml`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// PATH entries to exclude from scanning (e.g. network mounts, /mnt/c under WSL)
+    pub skip_dirs: Vec<String>,
+    /// Headers sent with every `--push` upload, e.g. "Authorization: Bearer ..."
+    pub push_headers: Vec<String>,
+    /// Probe command changes for built-in markers and extra markers, keyed by file name or glob
+    pub markers: BTreeMap<String, MarkerOverride>,
+    /// How long a tool gets to answer each version flag, in milliseconds
+    pub probe_timeout_ms: Option<u64>,
+    /// Tool names (globs) to leave out of scans
+    pub ignore_tools: Vec<String>,
+    /// Tool names (globs) to scan for besides the built-in developer tools
+    pub tools: Vec<String>,
+    /// Categories for tools by name (glob), ahead of the built-in classifier
+    pub categories: BTreeMap<String, ToolCategory>,
+    /// Project directories (and everything below them) whose devfetch.toml may
+    /// run commands: marker probe commands and extra tool patterns
+    pub trusted_projects: Vec<String>,
+    /// Opt-in usage statistics
+    pub stats: StatsConfig,
+}
+
+/// `[stats]`: nothing is recorded unless `enabled = true`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StatsConfig {
+    /// Record scan durations and tool counts locally
+    pub enabled: bool,
+    /// Where `devfetch stats push` sends the recorded totals (http or https)
+    pub endpoint: Option<String>,
+}
+
+impl Config {
+    /// Location of the user config file, honoring XDG_CONFIG_HOME
+    pub fn path() -> Option<PathBuf> {
+        env::var_os("XDG_CONFIG_HOME")
+            .filter(|d| !d.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| crate::core::paths::home_dir().map(|h| h.join(".config")))
+            .map(|dir| dir.join("devfetch").join("config.toml"))
+    }
+
+    /// Load the user config; a missing file yields the defaults
+    pub fn load() -> Result<Self> {
+        let path = match Self::path() {
+            Some(p) if p.is_file() => p,
+            _ => return Ok(Self::default()),
+        };
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Invalid config file {}", path.display()))
+    }
+
+    /// This config with a project's overrides applied: the project's markers
+    /// and categories replace same-named user ones, its probe timeout wins, and
+    /// ignored and extra tools from both apply
+    pub fn with_project(&self, project: &ProjectConfig) -> Config {
+        let mut config = self.clone();
+        config.markers.extend(project.markers.clone());
+        config.probe_timeout_ms = project.probe_timeout_ms.or(self.probe_timeout_ms);
+        config.ignore_tools.extend(project.ignore_tools.iter().cloned());
+        config.tools.extend(project.tools.iter().cloned());
+        config.categories.extend(project.categories.clone());
+        config
+    }
+
+    /// Whether `dir` is in or below one of `trusted_projects`
+    pub fn trusts(&self, dir: &Path) -> bool {
+        let canonical = |path: &Path| fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let dir = canonical(dir);
+        self.trusted_projects
+            .iter()
+            .any(|trusted| dir.starts_with(canonical(&crate::core::paths::expand_tilde(trusted))))
+    }
+
+    /// `categories` with the tool names compiled to globs
+    pub fn category_overrides(&self) -> Result<Vec<(glob::Pattern, ToolCategory)>> {
+        self.categories
+            .iter()
+            .map(|(name, category)| Ok((tool_pattern(name, "categories")?, category.clone())))
+            .collect()
+    }
+}
+
+/// A tool name glob from the config; `setting` names where it came from in errors
+pub fn tool_pattern(pattern: &str, setting: &str) -> Result<glob::Pattern> {
+    glob::Pattern::new(pattern).with_context(|| format!("Invalid {} pattern {:?}", setting, pattern))
+}
+
+/// Project requirements and scan settings from `devfetch.toml` (or
+/// `.devfetch.toml`) in the project root
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectConfig {
+    /// Disk, memory, OS and kernel requirements checked by `devfetch doctor`
+    pub system: SystemRequirements,
+    /// Services that must be running locally, by name
+    pub services: BTreeMap<String, ServiceRequirement>,
+    /// Marker overrides and extra markers, on top of the user's
+    pub markers: BTreeMap<String, MarkerOverride>,
+    /// Overrides the user's probe timeout while scanning this project
+    pub probe_timeout_ms: Option<u64>,
+    /// Tool names (globs) to leave out, in addition to the user's
+    pub ignore_tools: Vec<String>,
+    /// Tool names (globs) to scan for, in addition to the user's
+    pub tools: Vec<String>,
+    /// Categories for tools by name (glob), replacing the user's for the same name
+    pub categories: BTreeMap<String, ToolCategory>,
+}
+
+impl ProjectConfig {
+    pub const FILE_NAMES: [&'static str; 2] = ["devfetch.toml", ".devfetch.toml"];
+
+    /// The project's config file, if it has one
+    pub fn file(dir: &Path) -> Option<PathBuf> {
+        Self::FILE_NAMES.iter().map(|name| dir.join(name)).find(|path| path.is_file())
+    }
+
+    /// Load `devfetch.toml` or `.devfetch.toml` from `dir`; a missing file yields the defaults
+    pub fn load(dir: &Path) -> Result<Self> {
+        let Some(path) = Self::file(dir) else {
+            return Ok(Self::default());
+        };
+        let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Invalid {}", path.display()))
+    }
+}
+
+impl ProjectConfig {
+    /// Drop the settings that make devfetch run programs (marker `commands` and
+    /// `extra_commands`, extra `tools` patterns) unless `user` trusts `dir`:
+    /// merely entering a cloned repo must not execute anything it names.
+    /// Returns whether anything was dropped.
+    pub fn restrict(&mut self, user: &Config, dir: &Path) -> bool {
+        if user.trusts(dir) {
+            return false;
+        }
+        let mut dropped = !self.tools.is_empty();
+        self.tools.clear();
+        for marker in self.markers.values_mut() {
+            dropped |= marker.commands.take().is_some_and(|c| !c.is_empty()) || !marker.extra_commands.is_empty();
+            marker.extra_commands.clear();
+        }
+        dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+    use crate::types::CommandParser;
+
+    #[test]
+    fn test_parse_config() {
+        let config: Config = toml::from_str("skip_dirs = [\"/mnt/c\", \"~/net\"]\n").unwrap();
+        assert_eq!(config.skip_dirs, vec!["/mnt/c", "~/net"]);
+
+        let empty: Config = toml::from_str("").unwrap();
+        assert!(empty.skip_dirs.is_empty());
+
+        assert!(toml::from_str::<Config>("skip_dir = []\n").is_err());
+
+        let config: Config = toml::from_str(
+            "[markers.\"requirements.txt\"]\ncommands = [{ tool = \"uv\", args = [\"pip\", \"list\", \"--format=json\"], parser = \"json\" }]\n",
+        )
+        .unwrap();
+        let commands = config.markers["requirements.txt"].commands.as_ref().unwrap();
+        assert_eq!(commands[0].tool, "uv");
+        assert!(matches!(commands[0].parser, CommandParser::Json));
+    }
+
+    #[test]
+    fn test_with_project() {
+        let user: Config = toml::from_str("probe_timeout_ms = 1000\nignore_tools = [\"gcc-*\"]\n[markers.\"Gemfile\"]\ncommands = []\n").unwrap();
+        let project: ProjectConfig = toml::from_str("probe_timeout_ms = 5000\nignore_tools = [\"pylint\"]\n[markers.\"Gemfile\"]\necosystem = \"Ruby\"\n").unwrap();
+        let merged = user.with_project(&project);
+        let project: ProjectConfig = toml::from_str("tools = [\"acme-*\"]\n[categories]\nacme-build = \"build_system\"\n").unwrap();
+        let overrides = user.with_project(&project).category_overrides().unwrap();
+        assert_eq!((overrides[0].0.as_str(), &overrides[0].1), ("acme-build", &ToolCategory::BuildSystem));
+        assert_eq!(merged.probe_timeout_ms, Some(5000));
+        assert_eq!(merged.ignore_tools, vec!["gcc-*", "pylint"]);
+        assert!(merged.markers["Gemfile"].commands.is_none());
+        assert_eq!(user.with_project(&ProjectConfig::default()).probe_timeout_ms, Some(1000));
+    }
+
+    #[test]
+    fn test_restrict_untrusted_project() {
+        let dir = TempDir::new("trust");
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        let file = "tools = [\"*\"]\n[markers.\"build.sbt\"]\necosystem = \"Scala\"\ncommands = [{ tool = \"sh\", args = [\"-c\", \"touch pwned\"] }]\n";
+
+        let mut project: ProjectConfig = toml::from_str(file).unwrap();
+        assert!(project.restrict(&Config::default(), &dir));
+        assert!(project.tools.is_empty());
+        assert!(project.markers["build.sbt"].commands.is_none());
+        assert_eq!(project.markers["build.sbt"].ecosystem.as_deref(), Some("Scala"));
+
+        let user = Config { trusted_projects: vec![dir.display().to_string()], ..Config::default() };
+        let mut project: ProjectConfig = toml::from_str(file).unwrap();
+        assert!(!project.restrict(&user, &dir.join("sub")));
+        assert_eq!(project.tools, ["*"]);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::types::Tool;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Command families installed under versioned names, and whether the bare
+/// major version is itself a command (python3, pip3)
+static VERSIONED_FAMILIES: &[(&str, bool)] = &[
+    ("python", true),
+    ("pip", true),
+    ("pydoc", true),
+    ("idle", true),
+    ("ruby", false),
+    ("gem", false),
+    ("irb", false),
+    ("perl", false),
+    ("php", false),
+    ("lua", false),
+    ("node", false),
+    ("go", false),
+    ("gcc", false),
+    ("g++", false),
+    ("cpp", false),
+    ("gfortran", false),
+    ("clang", false),
+    ("clang++", false),
+    ("clangd", false),
+    ("clang-format", false),
+    ("clang-tidy", false),
+    ("lldb", false),
+    ("lld", false),
+    ("llvm-config", false),
+    ("tclsh", false),
+    ("wish", false),
+];
+
+/// Collapse PATH names that resolve to the same binary (python3 and python3.11,
+/// gcc and gcc-13) into one tool with `aliases`, and fill in `provides`
+pub fn merge_aliases(tools: &mut Vec<Tool>) {
+    // Only names of the same family are merged: version manager shims (mise)
+    // and multi-call binaries (busybox) resolve many unrelated names to one file
+    let mut groups: HashMap<(PathBuf, String), Vec<usize>> = HashMap::new();
+    for (i, tool) in tools.iter().enumerate() {
+        if let Ok(target) = fs::canonicalize(&tool.path) {
+            groups.entry((target, family(&tool.name).to_string())).or_default().push(i);
+        }
+    }
+
+    let mut merged = Vec::new();
+    for members in groups.values().filter(|m| m.len() > 1) {
+        // The shortest name is what people type and what lookups expect
+        let primary = *members.iter().min_by_key(|&&i| (tools[i].name.len(), &tools[i].name)).unwrap();
+        let mut aliases: Vec<String> =
+            members.iter().filter(|&&i| i != primary).map(|&i| tools[i].name.clone()).collect();
+        aliases.sort();
+        tools[primary].aliases = aliases;
+        merged.extend(members.iter().copied().filter(|&i| i != primary));
+    }
+    merged.sort_unstable();
+    for i in merged.into_iter().rev() {
+        tools.remove(i);
+    }
+
+    for tool in tools.iter_mut() {
+        tool.provides = provides(tool);
+    }
+}
+
+/// Generic commands a tool stands in for, from its name and aliases:
+/// python3.11 provides python3 and python, gcc-13 provides gcc
+fn provides(tool: &Tool) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for name in std::iter::once(&tool.name).chain(&tool.aliases) {
+        if name != &tool.name {
+            names.push(name.clone());
+        }
+        names.extend(generic_names(name));
+    }
+    names.sort();
+    names.dedup();
+    names.retain(|n| n != &tool.name);
+    names
+}
+
+/// Unversioned names for a versioned command of a known family
+fn generic_names(name: &str) -> Vec<String> {
+    let versioned = Regex::new(r"^(.+?)-?(\d+)(?:\.\d+)*$").unwrap();
+    let Some(caps) = versioned.captures(name) else {
+        return Vec::new();
+    };
+    let base = &caps[1];
+    let Some(&(_, keeps_major)) = VERSIONED_FAMILIES.iter().find(|(f, _)| *f == base) else {
+        return Vec::new();
+    };
+
+    let mut names = vec![base.to_string()];
+    let major = format!("{}{}", base, &caps[2]);
+    if keeps_major && major != name {
+        names.push(major);
+    }
+    names
+}
+
+/// Name with any version suffix removed, for grouping
+fn family(name: &str) -> &str {
+    let trimmed = name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    trimmed.strip_suffix('-').unwrap_or(trimmed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+
+    #[test]
+    fn test_generic_names() {
+        assert_eq!(generic_names("python3.11"), vec!["python", "python3"]);
+        assert_eq!(generic_names("gcc-13"), vec!["gcc"]);
+        assert_eq!(generic_names("python3"), vec!["python"]);
+        assert!(generic_names("base64").is_empty());
+        assert!(generic_names("node").is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_merge_aliases() {
+        let dir = TempDir::new("aliases");
+        fs::write(dir.join("python3.11"), "").unwrap();
+        std::os::unix::fs::symlink(dir.join("python3.11"), dir.join("python3")).unwrap();
+        fs::write(dir.join("shim"), "").unwrap();
+        std::os::unix::fs::symlink(dir.join("shim"), dir.join("node")).unwrap();
+        std::os::unix::fs::symlink(dir.join("shim"), dir.join("ruby")).unwrap();
+
+        let mut tools: Vec<Tool> = ["node", "python3", "python3.11", "ruby"]
+            .iter()
+            .map(|n| Tool::new(*n, dir.join(n)))
+            .collect();
+        merge_aliases(&mut tools);
+
+        let names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["node", "python3", "ruby"]);
+        assert_eq!(tools[1].aliases, vec!["python3.11"]);
+        assert_eq!(tools[1].provides, vec!["python", "python3.11"]);
+        assert!(tools[1].provides_command("python3.11"));
+        assert!(!tools[1].provides_command("python"));
+        assert!(!tools[1].provides_command("python3.1"));
+    }
+}
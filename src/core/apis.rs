@@ -0,0 +1,122 @@
+use crate::core::exec;
+use crate::types::{ApiInfo, ApiSpec, ApiTool};
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// Directories that hold dependencies or build output rather than the project's own specs
+static SKIP_DIRS: &[&str] = &["node_modules", ".git", "target", "vendor", "dist", "build", ".venv", "venv"];
+
+/// Specs larger than this are almost certainly generated bundles
+const MAX_SPEC_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Spec kind -> (tool, binaries that provide it)
+static TOOLS: &[(&str, &str, &[&str])] = &[
+    ("OpenAPI", "openapi-generator", &["openapi-generator", "openapi-generator-cli"]),
+    ("OpenAPI", "spectral", &["spectral"]),
+    ("OpenAPI", "redocly", &["redocly"]),
+    ("GraphQL", "graphql-codegen", &["graphql-codegen"]),
+    ("Protobuf", "protoc", &["protoc"]),
+    ("Protobuf", "buf", &["buf"]),
+];
+
+/// Find OpenAPI/Swagger documents, GraphQL schemas and .proto files, and
+/// check for the tools that generate code from or validate them
+pub fn detect_api_specs(dir: &Path) -> Option<ApiInfo> {
+    let openapi = Regex::new(r#"(?m)^\s*"?(openapi|swagger)"?\s*:\s*["']?(\d+(?:\.\d+)*)"#).unwrap();
+    let proto_syntax = Regex::new(r#"(?m)^\s*(syntax|edition)\s*=\s*"([^"]+)""#).unwrap();
+    let mut specs: Vec<ApiSpec> = Vec::new();
+
+    let files = WalkDir::new(dir)
+        .max_depth(6)
+        .into_iter()
+        .filter_entry(|e| !e.file_type().is_dir() || !SKIP_DIRS.contains(&e.file_name().to_str().unwrap_or("")))
+        .flatten()
+        .filter(|e| e.file_type().is_file() && e.metadata().is_ok_and(|m| m.len() <= MAX_SPEC_BYTES));
+
+    for entry in files {
+        let name = entry.file_name().to_string_lossy();
+        let extension = name.rsplit('.').next().unwrap_or("");
+        let relative = entry.path().strip_prefix(dir).unwrap_or(entry.path()).display().to_string();
+
+        let found = match extension {
+            "yaml" | "yml" | "json" => fs::read_to_string(entry.path()).ok().and_then(|content| {
+                let caps = openapi.captures(&content)?;
+                let kind = if &caps[1] == "swagger" { "Swagger" } else { "OpenAPI" };
+                Some(("OpenAPI", Some(format!("{} {}", kind, &caps[2]))))
+            }),
+            "graphql" | "graphqls" | "gql" => Some(("GraphQL", None)),
+            "proto" => {
+                let content = fs::read_to_string(entry.path()).unwrap_or_default();
+                // Files without a syntax line are proto2
+                let version = proto_syntax
+                    .captures(&content)
+                    .map(|caps| if &caps[1] == "edition" { format!("edition {}", &caps[2]) } else { caps[2].to_string() })
+                    .unwrap_or_else(|| "proto2".to_string());
+                Some(("Protobuf", Some(version)))
+            }
+            _ => None,
+        };
+
+        if let Some((kind, version)) = found {
+            match specs.iter_mut().find(|s| s.kind == kind && s.version == version) {
+                Some(spec) => spec.files.push(relative),
+                None => specs.push(ApiSpec { kind: kind.to_string(), version, files: vec![relative] }),
+            }
+        }
+    }
+
+    if specs.is_empty() {
+        return None;
+    }
+    specs.sort_by(|a, b| (&a.kind, &a.version).cmp(&(&b.kind, &b.version)));
+
+    let tools = TOOLS
+        .iter()
+        .filter(|(kind, ..)| specs.iter().any(|s| s.kind == *kind))
+        .map(|(kind, name, binaries)| ApiTool {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            installed: binaries
+                .iter()
+                .any(|bin| dir.join("node_modules/.bin").join(bin).exists() || exec::command_exists(bin)),
+        })
+        .collect();
+
+    Some(ApiInfo { specs, tools })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_detect_api_specs() {
+        let dir = TempDir::new("apis");
+        fs::create_dir_all(dir.join("api/proto")).unwrap();
+        fs::create_dir_all(dir.join("node_modules/pkg")).unwrap();
+        fs::write(dir.join("api/openapi.yaml"), "openapi: 3.0.3\ninfo:\n  title: Pets\n").unwrap();
+        fs::write(dir.join("api/legacy.json"), "{\n  \"swagger\": \"2.0\",\n  \"info\": {}\n}").unwrap();
+        fs::write(dir.join("node_modules/pkg/openapi.yaml"), "openapi: 3.1.0\n").unwrap();
+        fs::write(dir.join("config.yaml"), "port: 8080\n").unwrap();
+        fs::write(dir.join("api/proto/pets.proto"), "syntax = \"proto3\";\npackage pets;\n").unwrap();
+        fs::write(dir.join("api/proto/owners.proto"), "syntax = \"proto3\";\n").unwrap();
+        fs::write(dir.join("schema.graphql"), "type Query { pets: [String] }\n").unwrap();
+
+        let info = detect_api_specs(&dir).unwrap();
+        let specs: Vec<(&str, Option<&str>, usize)> =
+            info.specs.iter().map(|s| (s.kind.as_str(), s.version.as_deref(), s.files.len())).collect();
+        assert_eq!(
+            specs,
+            vec![
+                ("GraphQL", None, 1),
+                ("OpenAPI", Some("OpenAPI 3.0.3"), 1),
+                ("OpenAPI", Some("Swagger 2.0"), 1),
+                ("Protobuf", Some("proto3"), 2),
+            ]
+        );
+        assert!(info.tools.iter().any(|t| t.name == "buf"));
+    }
+}
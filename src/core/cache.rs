@@ -0,0 +1,288 @@
+use crate::core::paths;
+use crate::types::{CacheEntry, CacheStats, CacheUsage, ProbeResult, ScanWarning, Tool};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Bumped when the on-disk layout changes; older caches are discarded
+const CACHE_FORMAT: u32 = 1;
+
+/// Age buckets for `devfetch cache stats`, in seconds
+static AGE_BUCKETS: &[(&str, u64)] = &[
+    ("< 1 hour", 3600),
+    ("< 1 day", 86_400),
+    ("< 1 week", 7 * 86_400),
+    ("< 30 days", 30 * 86_400),
+];
+
+/// Version probe results keyed by executable path, reused while the binary
+/// (after following symlinks) keeps the same target, size and mtime
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ProbeCache {
+    #[serde(default)]
+    format: u32,
+    pub entries: BTreeMap<PathBuf, CacheEntry>,
+    /// Hits and misses of the most recent scan
+    #[serde(default)]
+    pub last_scan: Option<CacheUsage>,
+    /// Tools found by the last full scan, for --incremental
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    snapshot: Option<ToolSnapshot>,
+    /// --refresh: answer nothing from the cache, but keep what this scan probes
+    #[serde(skip)]
+    refresh: bool,
+}
+
+/// The scanned directories with their mtimes, and the tools listed from them
+#[derive(Debug, Serialize, Deserialize)]
+struct ToolSnapshot {
+    dirs: Vec<(PathBuf, u64)>,
+    /// Tool name patterns that were left out
+    #[serde(default)]
+    ignored: Vec<String>,
+    /// Extra tool name patterns that were listed
+    #[serde(default)]
+    tool_patterns: Vec<String>,
+    tools: Vec<Tool>,
+    /// What the scan warned about (unreadable dirs, probe timeouts), so a rescan
+    /// that reuses the tools still reports it
+    #[serde(default)]
+    warnings: Vec<ScanWarning>,
+}
+
+/// $XDG_CACHE_HOME/devfetch, else ~/.cache/devfetch
+pub fn cache_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CACHE_HOME")
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| paths::home_dir().map(|h| h.join(".cache")))
+        .map(|d| d.join("devfetch"))
+}
+
+pub fn cache_file() -> Option<PathBuf> {
+    cache_dir().map(|d| d.join("probes.json"))
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Resolved target, size and mtime (nanoseconds) of a binary
+fn fingerprint(path: &Path) -> Option<(PathBuf, u64, u64)> {
+    let target = fs::canonicalize(path).ok()?;
+    let metadata = fs::metadata(&target).ok()?;
+    let mtime = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?.as_nanos() as u64;
+    Some((target, metadata.len(), mtime))
+}
+
+impl ProbeCache {
+    /// The user's cache, or an empty one when missing, unreadable or outdated
+    pub fn load() -> Self {
+        cache_file().map(|f| Self::load_from(&f)).unwrap_or_default()
+    }
+
+    pub fn load_from(file: &Path) -> Self {
+        fs::read_to_string(file)
+            .ok()
+            .and_then(|c| serde_json::from_str::<ProbeCache>(&c).ok())
+            .filter(|c| c.format == CACHE_FORMAT)
+            .unwrap_or_default()
+    }
+
+    pub fn save(&mut self) -> Result<()> {
+        let file = cache_file().context("No home directory for the cache")?;
+        self.save_to(&file)
+    }
+
+    /// Write the cache, dropping entries for binaries that no longer exist. It
+    /// is written beside the old one and renamed over it, so a concurrent scan
+    /// (or the daemon) never reads a half-written file
+    pub fn save_to(&mut self, file: &Path) -> Result<()> {
+        self.format = CACHE_FORMAT;
+        self.entries.retain(|path, _| path.exists());
+        if let Some(dir) = file.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let mut temp = file.as_os_str().to_owned();
+        temp.push(format!(".{}.tmp", std::process::id()));
+        let temp = PathBuf::from(temp);
+        fs::write(&temp, serde_json::to_string(self)?).with_context(|| format!("Failed to write {}", temp.display()))?;
+        fs::rename(&temp, file).with_context(|| {
+            let _ = fs::remove_file(&temp);
+            format!("Failed to replace {}", file.display())
+        })
+    }
+
+    /// Re-probe every binary this scan, replacing the cached results (--refresh)
+    pub fn refresh(&mut self) {
+        self.refresh = true;
+    }
+
+    /// The cached probe for `path`, if the binary is unchanged since it was probed
+    pub fn get(&self, path: &Path) -> Option<ProbeResult> {
+        if self.refresh {
+            return None;
+        }
+        let entry = self.entries.get(path)?;
+        is_fresh(path, entry).then(|| entry.probe())
+    }
+
+    pub fn insert(&mut self, path: &Path, probe: &ProbeResult) {
+        // A timeout may be a slow start (cold disk, network mount); try again next scan
+        if probe.timed_out || switches_version(path) {
+            return;
+        }
+        let Some((target, size, mtime)) = fingerprint(path) else {
+            return;
+        };
+        self.entries.insert(
+            path.to_path_buf(),
+            CacheEntry {
+                target,
+                size,
+                mtime,
+                probed_at: now(),
+                success: probe.success,
+                version: probe.version.clone(),
+                output: probe.output.clone(),
+            },
+        );
+    }
+
+    /// The last scan's tools, if it listed exactly these directories, none of
+    /// them has changed since, and it left out and added the same tools
+    pub fn previous_tools(
+        &self,
+        dirs: &[(PathBuf, u64)],
+        ignored: &[String],
+        tool_patterns: &[String],
+    ) -> Option<(Vec<Tool>, Vec<ScanWarning>)> {
+        self.snapshot
+            .as_ref()
+            .filter(|s| !self.refresh && s.dirs == dirs && s.ignored == ignored && s.tool_patterns == tool_patterns)
+            .map(|s| (s.tools.clone(), s.warnings.clone()))
+    }
+
+    pub fn remember_tools(
+        &mut self,
+        dirs: Vec<(PathBuf, u64)>,
+        ignored: Vec<String>,
+        tool_patterns: Vec<String>,
+        tools: &[Tool],
+        warnings: &[ScanWarning],
+    ) {
+        self.snapshot = Some(ToolSnapshot { dirs, ignored, tool_patterns, tools: tools.to_vec(), warnings: warnings.to_vec() });
+    }
+
+    pub fn record_usage(&mut self, hits: usize, misses: usize) {
+        self.last_scan = Some(CacheUsage { at: now(), hits, misses });
+    }
+
+    /// Entry count, file size, last scan's hit rate and how old the entries are
+    pub fn stats(&self, file: &Path) -> CacheStats {
+        let now = now();
+        let mut ages: Vec<(String, usize)> = AGE_BUCKETS.iter().map(|(label, _)| (label.to_string(), 0)).collect();
+        ages.push(("older".to_string(), 0));
+        for entry in self.entries.values() {
+            let age = now.saturating_sub(entry.probed_at);
+            let bucket = AGE_BUCKETS.iter().position(|(_, limit)| age < *limit).unwrap_or(AGE_BUCKETS.len());
+            ages[bucket].1 += 1;
+        }
+
+        CacheStats {
+            path: file.to_path_buf(),
+            entries: self.entries.len(),
+            stale: self.entries.iter().filter(|(path, entry)| !is_fresh(path, entry)).count(),
+            size_bytes: fs::metadata(file).map(|m| m.len()).unwrap_or(0),
+            last_scan: self.last_scan.clone(),
+            ages,
+        }
+    }
+
+    /// Whether a scan would reuse the cached probe for `path`, and if not, why
+    pub fn describe(&self, path: &Path) -> String {
+        match self.entries.get(path) {
+            _ if switches_version(path) => {
+                "never cached: shims and rustup proxies report the version the environment selects".to_string()
+            }
+            Some(entry) if is_fresh(path, entry) => "hit: the binary is unchanged since it was probed".to_string(),
+            Some(_) => "stale: the binary changed since it was probed".to_string(),
+            None => "miss: not probed yet".to_string(),
+        }
+    }
+
+    /// Entries for a tool name (every PATH copy) or an exact path, with freshness
+    pub fn lookup(&self, tool: &str) -> Vec<(&PathBuf, &CacheEntry, bool)> {
+        self.entries
+            .iter()
+            .filter(|(path, _)| path.as_os_str() == tool || path.file_name().is_some_and(|n| n == tool))
+            .map(|(path, entry)| (path, entry, is_fresh(path, entry)))
+            .collect()
+    }
+}
+
+/// Version manager shims and rustup proxies report whichever version the
+/// current directory or environment selects, so the file alone can't key them
+fn switches_version(path: &Path) -> bool {
+    let text = path.to_string_lossy();
+    if path.components().any(|c| c.as_os_str() == "shims") || text.contains(".volta/bin") {
+        return true;
+    }
+    let (Some(dir), Ok(metadata)) = (path.parent(), fs::metadata(path)) else {
+        return false;
+    };
+    // rustup installs its proxies (cargo, rustc, ...) as copies of itself
+    path.file_name().is_some_and(|n| n != "rustup")
+        && fs::metadata(dir.join("rustup")).is_ok_and(|rustup| rustup.len() == metadata.len())
+}
+
+fn is_fresh(path: &Path, entry: &CacheEntry) -> bool {
+    fingerprint(path).is_some_and(|(target, size, mtime)| {
+        target == entry.target && size == entry.size && mtime == entry.mtime
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_probe_cache() {
+        let dir = TempDir::new("cache");
+        let binary = dir.join("tool");
+        fs::write(&binary, "v1").unwrap();
+        let probe = ProbeResult { success: true, output: "tool 1.2.3".to_string(), version: Some("1.2.3".to_string()), timed_out: false, error: None };
+
+        let mut cache = ProbeCache::default();
+        cache.insert(&binary, &probe);
+        cache.record_usage(0, 1);
+        cache.save_to(&dir.join("probes.json")).unwrap();
+        // Written through a temporary file that is renamed into place
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+
+        let mut cache = ProbeCache::load_from(&dir.join("probes.json"));
+        assert_eq!(cache.get(&binary).and_then(|p| p.version).as_deref(), Some("1.2.3"));
+        assert_eq!(cache.lookup("tool").len(), 1);
+        let stats = cache.stats(&dir.join("probes.json"));
+        assert_eq!((stats.entries, stats.stale, stats.ages[0].1), (1, 0, 1));
+
+        // --refresh ignores what is cached
+        cache.refresh();
+        assert!(cache.get(&binary).is_none());
+        cache.refresh = false;
+
+        // Shims are never cached
+        fs::create_dir_all(dir.join("shims")).unwrap();
+        fs::write(dir.join("shims/node"), "").unwrap();
+        assert!(switches_version(&dir.join("shims/node")));
+
+        // A changed binary is a miss
+        fs::write(&binary, "v2 is longer").unwrap();
+        assert!(cache.get(&binary).is_none());
+    }
+}
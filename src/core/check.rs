@@ -0,0 +1,91 @@
+use crate::core::doctor::{compare_versions, version_matches};
+use crate::core::probe;
+use crate::types::ConstraintCheck;
+use anyhow::{bail, Result};
+use rayon::prelude::*;
+use regex::Regex;
+
+/// A parsed constraint: tool name plus an optional (operator, version)
+pub type Constraint = (String, Option<(String, String)>);
+
+/// Resolve and probe just the named tools and evaluate each constraint,
+/// e.g. "node>=18", "python~3.11", "docker"
+pub fn check_constraints(constraints: &[String]) -> Result<Vec<ConstraintCheck>> {
+    let parsed: Vec<Constraint> = constraints.iter().map(|c| parse_constraint(c)).collect::<Result<_>>()?;
+
+    Ok(constraints
+        .par_iter()
+        .zip(parsed.par_iter())
+        .map(|(text, (tool, requirement))| {
+            let path = which::which(tool).ok();
+            let version = path.as_ref().and_then(|p| probe::probe_version(&p.to_string_lossy()).version);
+            let satisfied = match (requirement, &version) {
+                _ if path.is_none() => false,
+                (None, _) => true,
+                (Some((op, wanted)), Some(actual)) => satisfies(op, wanted, actual),
+                (Some(_), None) => false,
+            };
+            ConstraintCheck { constraint: text.clone(), tool: tool.clone(), path, version, satisfied }
+        })
+        .collect())
+}
+
+pub fn parse_constraint(text: &str) -> Result<Constraint> {
+    let re = Regex::new(r"^([A-Za-z0-9_.+-]+?)\s*(>=|<=|==|!=|>|<|=|\^|~|@)\s*v?(\d[\w.+-]*)$").unwrap();
+    if let Some(caps) = re.captures(text.trim()) {
+        return Ok((caps[1].to_string(), Some((caps[2].to_string(), caps[3].to_string()))));
+    }
+    if Regex::new(r"^[A-Za-z0-9_.+-]+$").unwrap().is_match(text.trim()) {
+        return Ok((text.trim().to_string(), None));
+    }
+    bail!("Invalid constraint '{}': expected TOOL or TOOL<op>VERSION (ops: >= <= > < = != ^ ~)", text)
+}
+
+/// Whether `actual` meets `op wanted`. `=`/`@` match by prefix ("18" accepts
+/// 18.19.0), `^` keeps the major (minor for 0.x) and `~` keeps major.minor.
+pub fn satisfies(op: &str, wanted: &str, actual: &str) -> bool {
+    let order = compare_versions(actual, wanted);
+    let leading = |v: &str, n: usize| -> Vec<String> { v.split('.').take(n).map(String::from).collect() };
+    match op {
+        ">=" => order.is_ge(),
+        "<=" => order.is_le(),
+        ">" => order.is_gt(),
+        "<" => order.is_lt(),
+        "=" | "==" | "@" => version_matches(wanted, actual),
+        "!=" => !version_matches(wanted, actual),
+        "^" => {
+            let width = if wanted.starts_with("0.") { 2 } else { 1 };
+            order.is_ge() && leading(wanted, width) == leading(actual, width)
+        }
+        "~" => order.is_ge() && leading(wanted, 2).iter().zip(leading(actual, 2)).all(|(w, a)| *w == a),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_constraint() {
+        assert_eq!(
+            parse_constraint("node>=18").unwrap(),
+            ("node".to_string(), Some((">=".to_string(), "18".to_string())))
+        );
+        assert_eq!(parse_constraint("docker").unwrap(), ("docker".to_string(), None));
+        assert!(parse_constraint("node>=").is_err());
+    }
+
+    #[test]
+    fn test_satisfies() {
+        assert!(satisfies(">=", "18", "20.11.0"));
+        assert!(!satisfies(">=", "3.11", "3.10.12"));
+        assert!(satisfies("<", "4", "3.9"));
+        assert!(satisfies("=", "20", "20.11.0"));
+        assert!(satisfies("^", "1.2", "1.9.0"));
+        assert!(!satisfies("^", "0.7", "0.8.1"));
+        assert!(satisfies("~", "3.11", "3.11.4"));
+        assert!(!satisfies("~", "3.11", "3.12.0"));
+        assert!(satisfies("!=", "2", "3.0.0"));
+    }
+}
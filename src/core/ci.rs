@@ -0,0 +1,70 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Single-file CI configurations, relative to the project root
+static CI_FILES: &[&str] = &[
+    ".gitlab-ci.yml",
+    ".circleci/config.yml",
+    ".travis.yml",
+    "azure-pipelines.yml",
+    "bitbucket-pipelines.yml",
+    "Jenkinsfile",
+    ".drone.yml",
+];
+
+/// Directories whose YAML files are all CI workflow definitions
+static CI_DIRS: &[&str] = &[".github/workflows", ".forgejo/workflows", ".gitea/workflows"];
+
+/// Locate the CI configuration files of a project
+pub fn ci_config_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = CI_FILES
+        .iter()
+        .map(|rel| dir.join(rel))
+        .filter(|p| p.is_file())
+        .collect();
+
+    for rel in CI_DIRS {
+        if let Ok(entries) = fs::read_dir(dir.join(rel)) {
+            let mut workflows: Vec<PathBuf> = entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| matches!(p.extension().and_then(|e| e.to_str()), Some("yml" | "yaml")))
+                .collect();
+            workflows.sort();
+            files.extend(workflows);
+        }
+    }
+
+    files
+}
+
+/// Read every CI configuration file, paired with its project-relative path
+pub fn read_ci_configs(dir: &Path) -> Vec<(String, String)> {
+    ci_config_files(dir)
+        .into_iter()
+        .filter_map(|path| {
+            let content = fs::read_to_string(&path).ok()?;
+            let rel = path.strip_prefix(dir).unwrap_or(&path).to_string_lossy().to_string();
+            Some((rel, content))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_ci_config_files() {
+        let dir = TempDir::new("ci");
+        fs::create_dir_all(dir.join(".github/workflows")).unwrap();
+        fs::write(dir.join(".github/workflows/test.yml"), "on: push\n").unwrap();
+        fs::write(dir.join(".github/workflows/README.md"), "docs\n").unwrap();
+        fs::write(dir.join(".gitlab-ci.yml"), "test:\n").unwrap();
+
+        let configs = read_ci_configs(&dir);
+        let names: Vec<&str> = configs.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec![".gitlab-ci.yml", ".github/workflows/test.yml"]);
+    }
+}
@@ -1,43 +1,118 @@
-use crate::types::{Tool, ToolCategory};
-use std::path::Path;
+use crate::core::paths;
+use crate::types::{InstallScope, Tool, ToolCategory};
+use std::fs;
+use std::path::{Path, PathBuf};
 
-/// Classify tools based on heuristics
-pub fn classify_tools(tools: &mut [Tool]) {
+/// Path fragments that mark a project-local tool environment
+static PROJECT_LOCAL_INDICATORS: &[&str] = &["/node_modules/.bin/", "/.venv/", "/venv/", "/vendor/bin/"];
+
+/// Classify tools based on heuristics, unless `overrides` (name glob and
+/// category, from the config) names them. `project_root` lets tools living
+/// inside the scanned project be labeled project-local.
+pub fn classify_tools(tools: &mut [Tool], project_root: Option<&Path>, overrides: &[(glob::Pattern, ToolCategory)]) {
+    let home = paths::home_dir();
+    let project_root = project_root.map(canonical_root);
     for tool in tools.iter_mut() {
-        tool.category = classify_tool(&tool.name, &tool.path);
+        tool.category = classify_tool(&tool.name, &tool.path, overrides);
+        tool.scope = classify_scope(&tool.path, project_root.as_deref(), home.as_deref());
+    }
+}
+
+/// The project root as an absolute path without `.` or `..`, so tool paths
+/// (absolute, from PATH) can be compared against it; `--path .` would match none
+pub fn canonical_root(root: &Path) -> PathBuf {
+    fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf())
+}
+
+/// Decide whether a tool is system-provisioned, user-managed, or project-local
+fn classify_scope(path: &Path, project_root: Option<&Path>, home: Option<&Path>) -> InstallScope {
+    explain_scope(path, project_root, home).0
+}
+
+/// The scope and which rule decided it, for `devfetch tool --explain`
+pub fn explain_scope(path: &Path, project_root: Option<&Path>, home: Option<&Path>) -> (InstallScope, String) {
+    let path_str = path.to_string_lossy().replace('\\', "/");
+
+    if let Some(root) = project_root.filter(|root| path.starts_with(root)) {
+        return (InstallScope::Project, format!("inside the project at {}", root.display()));
+    }
+    if let Some(indicator) = PROJECT_LOCAL_INDICATORS.iter().find(|i| path_str.contains(*i)) {
+        return (InstallScope::Project, format!("path contains \"{}\"", indicator));
+    }
+
+    if let Some(home) = home.filter(|home| path.starts_with(home)) {
+        return (InstallScope::User, format!("under the home directory {}", home.display()));
     }
+
+    (InstallScope::System, "outside the home directory and the project".to_string())
 }
 
 /// Classify a single tool based on name, path, and output patterns
-fn classify_tool(name: &str, path: &Path) -> ToolCategory {
+fn classify_tool(name: &str, path: &Path, overrides: &[(glob::Pattern, ToolCategory)]) -> ToolCategory {
+    explain_tool(name, path, overrides).0
+}
+
+/// The category and the override, pattern or path indicator that picked it
+pub fn explain_tool(name: &str, path: &Path, overrides: &[(glob::Pattern, ToolCategory)]) -> (ToolCategory, String) {
+    // An exact name beats a glob that also matches
+    let configured = overrides
+        .iter()
+        .find(|(pattern, _)| pattern.as_str() == name)
+        .or_else(|| overrides.iter().find(|(pattern, _)| pattern.matches(name)));
+    if let Some((pattern, category)) = configured {
+        return (category.clone(), format!("configured categories entry \"{}\"", pattern));
+    }
     let name_lower = name.to_lowercase();
     let path_str = path.to_string_lossy().to_lowercase();
 
+    // Formatters & Linters - checked first since names like clang-format and
+    // gofmt would otherwise match toolchain prefixes
+    if let Some(reason) = is_formatter_or_linter(&name_lower) {
+        return (ToolCategory::FormatterLinter, reason);
+    }
+
+    // Coverage & Profiling - also ahead of toolchains (cargo-llvm-cov lives in ~/.cargo)
+    if let Some(reason) = is_coverage_or_profiler(&name_lower) {
+        return (ToolCategory::CoverageProfiler, reason);
+    }
+
     // Language Toolchains - compilers, interpreters, runtimes
-    if is_language_toolchain(&name_lower, &path_str) {
-        return ToolCategory::LanguageToolchain;
+    if let Some(reason) = is_language_toolchain(&name_lower, &path_str) {
+        return (ToolCategory::LanguageToolchain, reason);
     }
 
     // Package Managers
-    if is_package_manager(&name_lower) {
-        return ToolCategory::PackageManager;
+    if let Some(reason) = is_package_manager(&name_lower) {
+        return (ToolCategory::PackageManager, reason);
     }
 
     // Build Systems
-    if is_build_system(&name_lower) {
-        return ToolCategory::BuildSystem;
+    if let Some(reason) = is_build_system(&name_lower) {
+        return (ToolCategory::BuildSystem, reason);
     }
 
     // Developer Tools
-    if is_developer_tool(&name_lower, &path_str) {
-        return ToolCategory::DeveloperTool;
+    if let Some(reason) = is_developer_tool(&name_lower, &path_str) {
+        return (ToolCategory::DeveloperTool, reason);
     }
 
-    ToolCategory::Unknown
+    (ToolCategory::Unknown, "no name pattern or path indicator matched".to_string())
+}
+
+fn starts_with(name: &str, patterns: &[&str]) -> Option<String> {
+    patterns.iter().find(|p| name.starts_with(*p)).map(|p| format!("name starts with \"{}\"", p))
+}
+
+fn is_one_of(name: &str, names: &[&str]) -> Option<String> {
+    names.contains(&name).then(|| format!("name is \"{}\"", name))
+}
+
+fn path_contains(path: &str, indicators: &[&str]) -> Option<String> {
+    indicators.iter().find(|i| path.contains(*i)).map(|i| format!("path contains \"{}\"", i))
 }
 
 /// Detect language toolchains based on patterns
-fn is_language_toolchain(name: &str, path: &str) -> bool {
+fn is_language_toolchain(name: &str, path: &str) -> Option<String> {
     // Common language runtime/compiler patterns
     let language_patterns = [
         // Python
@@ -98,29 +173,17 @@ fn is_language_toolchain(name: &str, path: &str) -> bool {
         "scheme", "guile",
     ];
 
-    for pattern in &language_patterns {
-        if name.starts_with(pattern) || name == *pattern {
-            return true;
-        }
-    }
-
     // Path-based detection (e.g., .sdkman, .nvm, .rbenv)
     let path_indicators = [
         ".sdkman", ".nvm", ".rbenv", ".pyenv", ".asdf", 
         ".rustup", ".cargo", ".local/share/virtualenvs"
     ];
 
-    for indicator in &path_indicators {
-        if path.contains(indicator) {
-            return true;
-        }
-    }
-
-    false
+    starts_with(name, &language_patterns).or_else(|| path_contains(path, &path_indicators))
 }
 
 /// Detect package managers
-fn is_package_manager(name: &str) -> bool {
+fn is_package_manager(name: &str) -> Option<String> {
     let package_managers = [
         // Node
         "npm", "yarn", "pnpm", "bun",
@@ -154,11 +217,11 @@ fn is_package_manager(name: &str) -> bool {
         "nix", "nix-env",
     ];
 
-    package_managers.contains(&name)
+    is_one_of(name, &package_managers)
 }
 
 /// Detect build systems
-fn is_build_system(name: &str) -> bool {
+fn is_build_system(name: &str) -> Option<String> {
     let build_systems = [
         "make", "cmake", "ninja", "meson", "bazel", "buck",
         "gradle", "maven", "ant", "sbt",
@@ -174,11 +237,48 @@ fn is_build_system(name: &str) -> bool {
         "b2", "bjam",
     ];
 
-    build_systems.contains(&name)
+    is_one_of(name, &build_systems)
+}
+
+/// Detect formatters and linters
+fn is_formatter_or_linter(name: &str) -> Option<String> {
+    let formatter_patterns = [
+        // JavaScript/CSS
+        "eslint", "prettier", "biome", "stylelint", "dprint",
+        // Python
+        "ruff", "black", "flake8", "pylint", "isort", "mypy",
+        // Ruby
+        "rubocop",
+        // Rust
+        "rustfmt", "cargo-fmt", "cargo-clippy", "clippy",
+        // Go
+        "gofmt", "goimports", "golangci-lint",
+        // C/C++
+        "clang-format", "clang-tidy",
+        // Swift/Kotlin
+        "swiftlint", "swiftformat", "ktlint",
+        // Shell/config/docs
+        "shellcheck", "shfmt", "hadolint", "yamllint", "markdownlint",
+    ];
+
+    starts_with(name, &formatter_patterns)
+}
+
+/// Detect coverage and profiling tools
+fn is_coverage_or_profiler(name: &str) -> Option<String> {
+    let coverage_patterns = [
+        // Coverage
+        "cargo-llvm-cov", "cargo-tarpaulin", "grcov", "gcov", "gcovr", "lcov", "llvm-cov",
+        "coverage", "nyc", "c8", "kcov",
+        // Profilers
+        "perf", "valgrind", "samply", "heaptrack", "flamegraph", "cargo-flamegraph", "py-spy",
+    ];
+
+    is_one_of(name, &coverage_patterns)
 }
 
 /// Detect general developer tools
-fn is_developer_tool(name: &str, path: &str) -> bool {
+fn is_developer_tool(name: &str, path: &str) -> Option<String> {
     let dev_tool_patterns = [
         // Version control
         "git", "svn", "hg", "mercurial", "fossil",
@@ -190,62 +290,93 @@ fn is_developer_tool(name: &str, path: &str) -> bool {
         "psql", "mysql", "sqlite3", "mongosh", "redis-cli",
         // Editors/IDEs
         "code", "emacs", "nvim", "neovim",
-        // Linters/Formatters
-        "eslint", "prettier", "black", "flake8", "pylint", "rubocop",
-        "rustfmt", "clippy", "gofmt",
         // Testing
         "jest", "mocha", "pytest", "rspec",
         // Cloud CLI
         "aws", "gcloud", "az", "heroku", "netlify",
         // Debuggers
         "gdb", "lldb",
-        // Profilers
-        "perf", "valgrind",
     ];
 
-    for pattern in &dev_tool_patterns {
-        if name.starts_with(pattern) || name == *pattern {
-            return true;
-        }
-    }
-
     // IDE/editor paths
-    if path.contains("visual studio code") || path.contains("jetbrains") {
-        return true;
-    }
-
-    false
+    starts_with(name, &dev_tool_patterns).or_else(|| path_contains(path, &["visual studio code", "jetbrains"]))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::test_util::TempDir;
     use std::path::Path;
 
     #[test]
     fn test_classify_language_toolchains() {
-        assert_eq!(classify_tool("python3", Path::new("/usr/bin/python3")), ToolCategory::LanguageToolchain);
-        assert_eq!(classify_tool("node", Path::new("/usr/bin/node")), ToolCategory::LanguageToolchain);
-        assert_eq!(classify_tool("rustc", Path::new("/usr/bin/rustc")), ToolCategory::LanguageToolchain);
-        assert_eq!(classify_tool("java", Path::new("/usr/bin/java")), ToolCategory::LanguageToolchain);
+        assert_eq!(classify_tool("python3", Path::new("/usr/bin/python3"), &[]), ToolCategory::LanguageToolchain);
+        assert_eq!(classify_tool("node", Path::new("/usr/bin/node"), &[]), ToolCategory::LanguageToolchain);
+        assert_eq!(classify_tool("rustc", Path::new("/usr/bin/rustc"), &[]), ToolCategory::LanguageToolchain);
+        assert_eq!(classify_tool("java", Path::new("/usr/bin/java"), &[]), ToolCategory::LanguageToolchain);
     }
 
     #[test]
     fn test_classify_package_managers() {
-        assert_eq!(classify_tool("npm", Path::new("/usr/bin/npm")), ToolCategory::PackageManager);
-        assert_eq!(classify_tool("pip", Path::new("/usr/bin/pip")), ToolCategory::PackageManager);
-        assert_eq!(classify_tool("cargo", Path::new("/usr/bin/cargo")), ToolCategory::PackageManager);
+        assert_eq!(classify_tool("npm", Path::new("/usr/bin/npm"), &[]), ToolCategory::PackageManager);
+        assert_eq!(classify_tool("pip", Path::new("/usr/bin/pip"), &[]), ToolCategory::PackageManager);
+        assert_eq!(classify_tool("cargo", Path::new("/usr/bin/cargo"), &[]), ToolCategory::PackageManager);
     }
 
     #[test]
     fn test_classify_build_systems() {
-        assert_eq!(classify_tool("cmake", Path::new("/usr/bin/cmake")), ToolCategory::BuildSystem);
-        assert_eq!(classify_tool("make", Path::new("/usr/bin/make")), ToolCategory::BuildSystem);
+        assert_eq!(classify_tool("cmake", Path::new("/usr/bin/cmake"), &[]), ToolCategory::BuildSystem);
+        assert_eq!(classify_tool("make", Path::new("/usr/bin/make"), &[]), ToolCategory::BuildSystem);
+    }
+
+    #[test]
+    fn test_classify_formatters_linters() {
+        assert_eq!(classify_tool("ruff", Path::new("/usr/bin/ruff"), &[]), ToolCategory::FormatterLinter);
+        assert_eq!(classify_tool("gofmt", Path::new("/usr/local/go/bin/gofmt"), &[]), ToolCategory::FormatterLinter);
+        assert_eq!(classify_tool("clang-format", Path::new("/usr/bin/clang-format"), &[]), ToolCategory::FormatterLinter);
+        assert_eq!(classify_tool("cargo-clippy", Path::new("/home/u/.cargo/bin/cargo-clippy"), &[]), ToolCategory::FormatterLinter);
+    }
+
+    #[test]
+    fn test_classify_coverage_profilers() {
+        assert_eq!(classify_tool("cargo-llvm-cov", Path::new("/home/u/.cargo/bin/cargo-llvm-cov"), &[]), ToolCategory::CoverageProfiler);
+        assert_eq!(classify_tool("valgrind", Path::new("/usr/bin/valgrind"), &[]), ToolCategory::CoverageProfiler);
+        assert_eq!(classify_tool("gcov", Path::new("/usr/bin/gcov"), &[]), ToolCategory::CoverageProfiler);
+    }
+
+    #[test]
+    fn test_classify_scope() {
+        let home = Path::new("/home/dev");
+        let project = Path::new("/home/dev/src/app");
+        assert_eq!(classify_scope(Path::new("/usr/bin/git"), Some(project), Some(home)), InstallScope::System);
+        assert_eq!(classify_scope(Path::new("/home/dev/.cargo/bin/cargo"), Some(project), Some(home)), InstallScope::User);
+        assert_eq!(classify_scope(Path::new("/home/dev/src/app/node_modules/.bin/eslint"), Some(project), Some(home)), InstallScope::Project);
+        assert_eq!(classify_scope(Path::new("/work/other/.venv/bin/black"), None, Some(home)), InstallScope::Project);
+
+        // A root given as a relative or non-normalized path still contains its tools
+        let temp = TempDir::new("classify");
+        let dir = canonical_root(&temp);
+        fs::create_dir_all(dir.join("tools")).unwrap();
+        let mut tools = vec![Tool::new("mytool", dir.join("tools").join("mytool"))];
+        classify_tools(&mut tools, Some(&dir.join("tools").join("..")), &[]);
+        assert_eq!(tools[0].scope, InstallScope::Project);
     }
 
     #[test]
     fn test_classify_developer_tools() {
-        assert_eq!(classify_tool("git", Path::new("/usr/bin/git")), ToolCategory::DeveloperTool);
-        assert_eq!(classify_tool("docker", Path::new("/usr/bin/docker")), ToolCategory::DeveloperTool);
+        assert_eq!(classify_tool("git", Path::new("/usr/bin/git"), &[]), ToolCategory::DeveloperTool);
+        assert_eq!(classify_tool("docker", Path::new("/usr/bin/docker"), &[]), ToolCategory::DeveloperTool);
+    }
+
+    #[test]
+    fn test_category_overrides() {
+        let overrides = vec![
+            (glob::Pattern::new("acme-*").unwrap(), ToolCategory::DeveloperTool),
+            (glob::Pattern::new("acme-build").unwrap(), ToolCategory::BuildSystem),
+        ];
+        let (category, reason) = explain_tool("acme-build", Path::new("/opt/acme/bin/acme-build"), &overrides);
+        assert_eq!((category, reason.as_str()), (ToolCategory::BuildSystem, "configured categories entry \"acme-build\""));
+        assert_eq!(classify_tool("acme-lint", Path::new("/opt/acme/bin/acme-lint"), &overrides), ToolCategory::DeveloperTool);
+        assert_eq!(classify_tool("git", Path::new("/usr/bin/git"), &overrides), ToolCategory::DeveloperTool);
     }
 }
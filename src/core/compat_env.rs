@@ -0,0 +1,173 @@
+use crate::core::exec;
+use crate::types::{CompatEnvInfo, CompatProvider, Tool};
+use std::collections::BTreeMap;
+use std::env;
+
+/// MSYS2 subsystem prefixes, as they appear in install paths
+static MSYS2_SUBSYSTEMS: &[&str] = &[
+    "mingw64", "mingw32", "ucrt64", "clang64", "clang32", "clangarm64",
+];
+
+/// Install directory names of each environment on a Windows drive
+static MSYS2_ROOTS: &[&str] = &["msys64", "msys32", "msys2", "msys"];
+static CYGWIN_ROOTS: &[&str] = &["cygwin", "cygwin64"];
+static MINGW_ROOTS: &[&str] = &["mingw", "mingw32", "mingw64", "mingw-w64"];
+
+/// The MSYS2 runtime (/usr/bin: bash, make, git) that every subsystem shell
+/// has on PATH alongside its own prefix
+const MSYS2_BASE: &str = "MSYS2 (MSYS)";
+
+/// Detect the Unix-on-Windows environment the shell is running under: MSYS2
+/// exports MSYSTEM; Cygwin exports nothing reliable (OSTYPE is a bash
+/// variable), so ask its uname
+fn active_environment() -> Option<String> {
+    if let Ok(msystem) = env::var("MSYSTEM") {
+        if !msystem.is_empty() {
+            return Some(format!("MSYS2 ({})", msystem.to_uppercase()));
+        }
+    }
+
+    let cygwin = cfg!(target_os = "cygwin")
+        || (cfg!(windows) && exec::execute_for_output("uname", &["-s"]).is_some_and(|os| os.starts_with("CYGWIN")));
+    cygwin.then(|| "Cygwin".to_string())
+}
+
+/// Attribute a tool path to the environment that installed it.
+/// `active` is used to resolve ambiguous Unix-style paths like /usr/bin.
+fn environment_for_path(path: &str, active: Option<&str>) -> Option<String> {
+    let path = path.to_lowercase().replace('\\', "/");
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    // Native Windows layout: C:/msys64/<subsystem>/bin or C:/msys64/usr/bin
+    if segments.first().is_some_and(|drive| drive.ends_with(':')) {
+        if let Some(idx) = segments.iter().position(|s| MSYS2_ROOTS.contains(s)) {
+            let subsystem = segments.get(idx + 1).copied().unwrap_or("usr");
+            return Some(msys2_label(subsystem));
+        }
+        if segments.iter().any(|s| CYGWIN_ROOTS.contains(s)) {
+            return Some("Cygwin".to_string());
+        }
+        if segments.iter().any(|s| MINGW_ROOTS.contains(s)) {
+            return Some("MinGW".to_string());
+        }
+        return None;
+    }
+
+    // Unix-style layout seen from inside an MSYS2 or Cygwin shell
+    let active = active?;
+    let first = *segments.first()?;
+    if active.starts_with("MSYS2") {
+        if MSYS2_SUBSYSTEMS.contains(&first) || first == "usr" {
+            return Some(msys2_label(first));
+        }
+    } else if active == "Cygwin" && (first == "usr" || first == "bin") {
+        return Some("Cygwin".to_string());
+    }
+
+    None
+}
+
+fn msys2_label(subsystem: &str) -> String {
+    if MSYS2_SUBSYSTEMS.contains(&subsystem) {
+        format!("MSYS2 ({})", subsystem.to_uppercase())
+    } else {
+        MSYS2_BASE.to_string()
+    }
+}
+
+/// Environments that don't belong together; the MSYS2 runtime goes with
+/// whichever MSYS2 subsystem is in use
+fn conflicting<'a>(environments: impl Iterator<Item = &'a String> + Clone) -> Vec<&'a String> {
+    let has_subsystem = environments.clone().any(|e| e.starts_with("MSYS2 (") && e != MSYS2_BASE);
+    environments.filter(|e| !(has_subsystem && *e == MSYS2_BASE)).collect()
+}
+
+/// Detect MSYS2 / MinGW / Cygwin environments and which tools each provides
+pub fn detect_compat_env(tools: &[Tool]) -> Option<CompatEnvInfo> {
+    let active = active_environment();
+    let mut by_env: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for tool in tools {
+        let path = tool.path.to_string_lossy();
+        if let Some(environment) = environment_for_path(&path, active.as_deref()) {
+            by_env.entry(environment).or_default().push(tool.name.clone());
+        }
+    }
+
+    if active.is_none() && by_env.is_empty() {
+        return None;
+    }
+
+    let mut warnings = Vec::new();
+    if conflicting(by_env.keys()).len() > 1 {
+        let summary: Vec<String> = by_env
+            .iter()
+            .map(|(environment, names)| format!("{} from {}", names.join(", "), environment))
+            .collect();
+        warnings.push(format!(
+            "Tools from different environments are mixed in PATH: {}",
+            summary.join("; ")
+        ));
+    }
+
+    let providers = by_env
+        .into_iter()
+        .map(|(environment, tools)| CompatProvider { environment, tools })
+        .collect();
+
+    Some(CompatEnvInfo {
+        active,
+        providers,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_environment_for_windows_paths() {
+        assert_eq!(
+            environment_for_path(r"C:\msys64\ucrt64\bin\gcc.exe", None),
+            Some("MSYS2 (UCRT64)".to_string())
+        );
+        assert_eq!(
+            environment_for_path(r"C:\msys64\usr\bin\make.exe", None),
+            Some("MSYS2 (MSYS)".to_string())
+        );
+        assert_eq!(
+            environment_for_path(r"C:\cygwin64\bin\make.exe", None),
+            Some("Cygwin".to_string())
+        );
+        assert_eq!(
+            environment_for_path(r"C:\MinGW\bin\gcc.exe", None),
+            Some("MinGW".to_string())
+        );
+        assert_eq!(environment_for_path("/usr/bin/gcc", None), None);
+        // Whole components only: a checkout named msys-tools is not MSYS2
+        assert_eq!(environment_for_path(r"C:\src\msys-tools\bin\gcc.exe", None), None);
+        assert_eq!(environment_for_path("/home/u/cygwin/bin/make", None), None);
+    }
+
+    #[test]
+    fn test_environment_for_unix_style_paths() {
+        assert_eq!(
+            environment_for_path("/mingw64/bin/gcc", Some("MSYS2 (MINGW64)")),
+            Some("MSYS2 (MINGW64)".to_string())
+        );
+        assert_eq!(
+            environment_for_path("/usr/bin/make", Some("Cygwin")),
+            Some("Cygwin".to_string())
+        );
+
+        // A UCRT64 shell runs UCRT64 compilers and the MSYS2 runtime's make
+        let ucrt = [
+            environment_for_path("/ucrt64/bin/gcc", Some("MSYS2 (UCRT64)")).unwrap(),
+            environment_for_path("/usr/bin/make", Some("MSYS2 (UCRT64)")).unwrap(),
+        ];
+        assert_eq!(conflicting(ucrt.iter()).len(), 1);
+        let mixed = ["MSYS2 (UCRT64)".to_string(), "MSYS2 (MINGW64)".to_string(), MSYS2_BASE.to_string()];
+        assert_eq!(conflicting(mixed.iter()).len(), 2);
+    }
+}
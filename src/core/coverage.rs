@@ -0,0 +1,64 @@
+use crate::core::ci;
+use crate::types::CiToolRequirement;
+use std::path::Path;
+
+/// Coverage/profiling tools and the CI snippets that show a project relies on them
+static CI_TOOL_USAGE: &[(&str, &[&str])] = &[
+    ("cargo-llvm-cov", &["cargo llvm-cov", "cargo-llvm-cov"]),
+    ("cargo-tarpaulin", &["cargo tarpaulin", "cargo-tarpaulin"]),
+    ("grcov", &["grcov "]),
+    ("gcovr", &["gcovr"]),
+    ("lcov", &["lcov ", "genhtml"]),
+    ("gcov", &["gcov "]),
+    ("coverage", &["coverage run", "coverage report", "coverage xml"]),
+    ("nyc", &["nyc "]),
+    ("c8", &["c8 "]),
+    ("kcov", &["kcov "]),
+    ("valgrind", &["valgrind"]),
+    ("perf", &["perf record", "perf stat"]),
+    ("samply", &["samply record"]),
+    ("py-spy", &["py-spy"]),
+];
+
+/// Find coverage/profiling tools the project's CI expects and whether they are installed
+pub fn ci_expected_tools(dir: &Path) -> Vec<CiToolRequirement> {
+    let configs = ci::read_ci_configs(dir);
+    let mut requirements = Vec::new();
+
+    for (tool, needles) in CI_TOOL_USAGE {
+        let referenced_in = configs
+            .iter()
+            .find(|(_, content)| mentions_tool(content, needles));
+
+        if let Some((file, _)) = referenced_in {
+            requirements.push(CiToolRequirement {
+                tool: tool.to_string(),
+                ci_file: file.clone(),
+                installed: which::which(tool).is_ok(),
+            });
+        }
+    }
+
+    requirements
+}
+
+/// Whether any non-comment line of a CI config mentions one of the needles
+fn mentions_tool(content: &str, needles: &[&str]) -> bool {
+    content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .any(|line| needles.iter().any(|n| line.contains(n)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mentions_tool() {
+        let workflow = "steps:\n  - run: cargo llvm-cov --lcov --output-path lcov.info\n  # - run: valgrind ./a.out\n";
+        assert!(mentions_tool(workflow, &["cargo llvm-cov"]));
+        assert!(!mentions_tool(workflow, &["valgrind"]));
+        assert!(!mentions_tool(workflow, &["nyc "]));
+    }
+}
@@ -0,0 +1,145 @@
+use crate::core::exec;
+use crate::types::CrossCompileInfo;
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+
+/// Enumerate installed cross-compilation targets across toolchains
+pub fn detect_cross_targets() -> Option<CrossCompileInfo> {
+    let info = CrossCompileInfo {
+        rust_targets: rust_targets(),
+        zig_targets: zig_targets(),
+        gcc_cross_prefixes: gcc_cross_prefixes(),
+        go_targets: go_targets(),
+    };
+
+    if info.rust_targets.is_empty()
+        && info.zig_targets.is_empty()
+        && info.gcc_cross_prefixes.is_empty()
+        && info.go_targets.is_empty()
+    {
+        return None;
+    }
+
+    Some(info)
+}
+
+/// Rust targets installed through rustup
+fn rust_targets() -> Vec<String> {
+    if !exec::command_exists("rustup") {
+        return Vec::new();
+    }
+
+    exec::execute_for_output("rustup", &["target", "list", "--installed"])
+        .map(|output| parse_lines(&output))
+        .unwrap_or_default()
+}
+
+/// libc targets bundled with zig (`zig targets` prints JSON)
+fn zig_targets() -> Vec<String> {
+    if !exec::command_exists("zig") {
+        return Vec::new();
+    }
+
+    exec::execute_for_output("zig", &["targets"])
+        .map(|output| parse_zig_targets(&output))
+        .unwrap_or_default()
+}
+
+fn parse_zig_targets(json_str: &str) -> Vec<String> {
+    let parsed: serde_json::Value = match serde_json::from_str(json_str) {
+        Ok(v) => v,
+        Err(_) => return Vec::new(),
+    };
+
+    parsed
+        .get("libc")
+        .and_then(|l| l.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// GOOS/GOARCH pairs the installed Go toolchain can build for
+fn go_targets() -> Vec<String> {
+    if !exec::command_exists("go") {
+        return Vec::new();
+    }
+
+    exec::execute_for_output("go", &["tool", "dist", "list"])
+        .map(|output| parse_lines(&output))
+        .unwrap_or_default()
+}
+
+/// Cross gcc prefixes found in PATH (e.g. arm-none-eabi-gcc -> arm-none-eabi)
+fn gcc_cross_prefixes() -> Vec<String> {
+    let path_var = match env::var("PATH") {
+        Ok(p) => p,
+        Err(_) => return Vec::new(),
+    };
+
+    // The native compiler often also ships as <host-triple>-gcc; skip it
+    let host = exec::execute_for_output("gcc", &["-dumpmachine"])
+        .map(|s| s.trim().to_string());
+
+    let mut prefixes = BTreeSet::new();
+    for dir in env::split_paths(&path_var) {
+        if let Ok(entries) = fs::read_dir(&dir) {
+            for entry in entries.flatten() {
+                if let Some(prefix) = entry.file_name().to_str().and_then(cross_gcc_prefix) {
+                    if host.as_deref() != Some(prefix.as_str()) {
+                        prefixes.insert(prefix);
+                    }
+                }
+            }
+        }
+    }
+
+    prefixes.into_iter().collect()
+}
+
+/// Extract the target triple from a cross gcc binary name
+fn cross_gcc_prefix(name: &str) -> Option<String> {
+    let name = name.strip_suffix(".exe").unwrap_or(name);
+    let prefix = name.strip_suffix("-gcc")?;
+
+    // Target triples always contain at least one dash (rules out c89-gcc)
+    if prefix.contains('-') {
+        Some(prefix.to_string())
+    } else {
+        None
+    }
+}
+
+fn parse_lines(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cross_gcc_prefix() {
+        assert_eq!(cross_gcc_prefix("arm-none-eabi-gcc"), Some("arm-none-eabi".to_string()));
+        assert_eq!(cross_gcc_prefix("aarch64-linux-gnu-gcc"), Some("aarch64-linux-gnu".to_string()));
+        assert_eq!(cross_gcc_prefix("c89-gcc"), None);
+        assert_eq!(cross_gcc_prefix("gcc"), None);
+        assert_eq!(cross_gcc_prefix("x86_64-linux-gnu-gcc-ar"), None);
+    }
+
+    #[test]
+    fn test_parse_zig_targets() {
+        let json = r#"{"arch": ["x86_64"], "libc": ["aarch64-linux-gnu", "x86_64-windows-gnu"]}"#;
+        assert_eq!(parse_zig_targets(json), vec!["aarch64-linux-gnu", "x86_64-windows-gnu"]);
+        assert!(parse_zig_targets("not json").is_empty());
+    }
+}
@@ -0,0 +1,379 @@
+use crate::core::{doctor, exec, probe};
+use crate::types::{CCompiler, CMakePreset, CxxLibrary, CxxToolchain};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// C drivers and the C++ driver that goes with each
+static COMPILERS: &[(&str, &str)] = &[("gcc", "g++"), ("clang", "clang++")];
+
+/// pkg_check_modules options that aren't module names
+static PKG_OPTIONS: &[&str] = &["REQUIRED", "QUIET", "IMPORTED_TARGET", "GLOBAL", "NO_CMAKE_PATH", "NO_CMAKE_ENVIRONMENT_PATH"];
+
+/// Programs CMake generators drive, by generator name prefix
+static GENERATORS: &[(&str, &str)] = &[
+    ("Ninja", "ninja"),
+    ("Unix Makefiles", "make"),
+    ("MSYS Makefiles", "make"),
+    ("MinGW Makefiles", "mingw32-make"),
+    ("NMake Makefiles", "nmake"),
+    ("Watcom WMake", "wmake"),
+    ("Visual Studio", "msbuild"),
+    ("Xcode", "xcodebuild"),
+];
+
+/// Oldest cmake that reads each CMakePresets.json schema version
+static PRESET_SCHEMAS: &[(u64, &str)] =
+    &[(1, "3.19"), (2, "3.20"), (3, "3.21"), (4, "3.23"), (5, "3.24"), (6, "3.25"), (7, "3.27"), (8, "3.28"), (9, "3.30"), (10, "3.31")];
+
+/// Compilers on PATH (and $CC/$CXX) with their target and defaults, pkg-config,
+/// and whether the libraries CMakeLists.txt asks for can be found
+pub fn inspect(dir: &Path) -> CxxToolchain {
+    let mut pairs: Vec<(String, Option<String>)> = Vec::new();
+    if let Some(cc) = env::var("CC").ok().filter(|c| !c.is_empty()) {
+        pairs.push((cc, env::var("CXX").ok().filter(|c| !c.is_empty())));
+    }
+    for (c, cxx) in COMPILERS {
+        if !pairs.iter().any(|(name, _)| name == c) {
+            pairs.push((c.to_string(), Some(cxx.to_string())));
+        }
+    }
+    let compilers: Vec<CCompiler> = pairs
+        .iter()
+        .filter(|(c, _)| exec::command_exists(c))
+        .filter_map(|(c, cxx)| probe_compiler(c, cxx.as_deref().filter(|x| exec::command_exists(x))))
+        .collect();
+
+    let pkg_config = exec::command_exists("pkg-config")
+        .then(|| exec::execute_for_output("pkg-config", &["--version"]).and_then(|out| probe::extract_version(&out)))
+        .map(|v| v.unwrap_or_default());
+    let triple = compilers.iter().find_map(|c| c.target.clone());
+    let cmake_lists = fs::read_to_string(dir.join("CMakeLists.txt")).unwrap_or_default();
+    let libraries = cmake_requirements(&cmake_lists)
+        .into_iter()
+        .map(|(name, via)| find_library(name, via, pkg_config.is_some(), triple.as_deref()))
+        .collect();
+
+    let (presets, presets_required) = read_presets(dir);
+    let cmake_required = [cmake_minimum(&cmake_lists), presets_required]
+        .into_iter()
+        .flatten()
+        .max_by(|a, b| doctor::compare_versions(a, b));
+
+    CxxToolchain { compilers, pkg_config, libraries, cmake_required, presets }
+}
+
+/// The minimum of cmake_minimum_required(VERSION 3.16...3.28)
+fn cmake_minimum(content: &str) -> Option<String> {
+    Regex::new(r"(?i)cmake_minimum_required\s*\(\s*VERSION\s+([0-9]+(?:\.[0-9]+)*)")
+        .unwrap()
+        .captures(content)
+        .map(|c| c[1].to_string())
+}
+
+/// Visible configure presets from CMakePresets.json and CMakeUserPresets.json
+/// (and the files they include), with the cmake version the files need
+fn read_presets(dir: &Path) -> (Vec<CMakePreset>, Option<String>) {
+    let mut all: BTreeMap<String, Value> = BTreeMap::new();
+    let mut order: Vec<String> = Vec::new();
+    let mut required: Option<String> = None;
+    let mut pending: Vec<PathBuf> = vec![dir.join("CMakePresets.json"), dir.join("CMakeUserPresets.json")];
+    let mut loaded: Vec<PathBuf> = Vec::new();
+
+    while let Some(file) = pending.pop() {
+        if loaded.contains(&file) {
+            continue;
+        }
+        loaded.push(file.clone());
+        let Some(doc) = fs::read_to_string(&file).ok().and_then(|c| serde_json::from_str::<Value>(&c).ok()) else {
+            continue;
+        };
+        let explicit = doc.get("cmakeMinimumRequired").map(|m| {
+            let part = |k: &str| m.get(k).and_then(|v| v.as_u64()).unwrap_or(0);
+            format!("{}.{}.{}", part("major"), part("minor"), part("patch"))
+        });
+        let schema = doc
+            .get("version")
+            .and_then(|v| v.as_u64())
+            .and_then(|v| PRESET_SCHEMAS.iter().find(|(schema, _)| *schema == v))
+            .map(|(_, cmake)| cmake.to_string());
+        for candidate in [explicit, schema].into_iter().flatten() {
+            if required.as_ref().is_none_or(|r| doctor::compare_versions(&candidate, r).is_gt()) {
+                required = Some(candidate);
+            }
+        }
+        for preset in doc.get("configurePresets").and_then(|p| p.as_array()).into_iter().flatten() {
+            if let Some(name) = preset.get("name").and_then(|n| n.as_str()) {
+                order.push(name.to_string());
+                all.insert(name.to_string(), preset.clone());
+            }
+        }
+        let base = file.parent().unwrap_or(dir);
+        let includes = doc.get("include").and_then(|i| i.as_array()).into_iter().flatten().filter_map(|i| i.as_str());
+        pending.extend(includes.map(|i| base.join(i)));
+    }
+
+    let presets = order
+        .iter()
+        .filter(|name| !all[*name].get("hidden").and_then(|h| h.as_bool()).unwrap_or(false))
+        .map(|name| {
+            let generator = inherited(&all, name, &|p| p.get("generator").and_then(|g| g.as_str()).map(String::from));
+            let toolchain_file = inherited(&all, name, &|p| {
+                p.get("toolchainFile").and_then(|t| t.as_str()).map(String::from).or_else(|| {
+                    let variable = p.pointer("/cacheVariables/CMAKE_TOOLCHAIN_FILE")?;
+                    variable.as_str().or_else(|| variable.get("value")?.as_str()).map(String::from)
+                })
+            });
+            let generator_tool = generator.as_deref().and_then(|g| {
+                GENERATORS.iter().find(|(prefix, _)| g.starts_with(prefix)).map(|(_, tool)| tool.to_string())
+            });
+            CMakePreset {
+                name: name.clone(),
+                generator_installed: generator_tool.as_deref().map(exec::command_exists),
+                generator_tool,
+                generator,
+                toolchain_exists: toolchain_file
+                    .as_deref()
+                    .and_then(|t| expand_macros(t, dir))
+                    .map(|t| dir.join(t).is_file()),
+                toolchain_file,
+            }
+        })
+        .collect();
+    (presets, required)
+}
+
+/// A preset's own value, else the first one its `inherits` chain provides
+fn inherited(all: &BTreeMap<String, Value>, name: &str, get: &dyn Fn(&Value) -> Option<String>) -> Option<String> {
+    let mut queue = vec![name.to_string()];
+    let mut visited: Vec<String> = Vec::new();
+    while !queue.is_empty() {
+        let current = queue.remove(0);
+        if visited.contains(&current) {
+            continue;
+        }
+        visited.push(current.clone());
+        let preset = all.get(&current)?;
+        if let Some(value) = get(preset) {
+            return Some(value);
+        }
+        match preset.get("inherits") {
+            Some(Value::String(parent)) => queue.push(parent.clone()),
+            Some(Value::Array(parents)) => queue.extend(parents.iter().filter_map(|p| p.as_str()).map(String::from)),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Expand ${sourceDir}, ${sourceParentDir} and $env{VAR}; None when other
+/// macros (${presetName}, ...) leave the path unknown until configure time
+fn expand_macros(value: &str, dir: &Path) -> Option<String> {
+    let mut expanded = value
+        .replace("${sourceDir}", &dir.to_string_lossy())
+        .replace("${sourceParentDir}", &dir.parent().unwrap_or(dir).to_string_lossy());
+    let env_macro = Regex::new(r"\$env\{(\w+)\}").unwrap();
+    expanded = env_macro
+        .replace_all(&expanded, |caps: &regex::Captures| env::var(&caps[1]).unwrap_or_default())
+        .into_owned();
+    (!expanded.contains("${") && !expanded.contains("$penv{")).then_some(expanded)
+}
+
+fn probe_compiler(cc: &str, cxx: Option<&str>) -> Option<CCompiler> {
+    let version = exec::execute_for_output(cc, &["--version"]).and_then(|out| probe::extract_version(&out))?;
+    let first_line = |args: &[&str]| {
+        exec::execute_for_output(cc, args).and_then(|out| out.lines().next().map(|l| l.trim().to_string())).filter(|l| !l.is_empty())
+    };
+    Some(CCompiler {
+        name: cc.to_string(),
+        version,
+        target: first_line(&["-dumpmachine"]),
+        c_std: default_standard(cc, "c"),
+        cxx_std: cxx.and_then(|cxx| default_standard(cxx, "c++")),
+        // gcc prints an empty line when built without one; clang supports the flag too
+        sysroot: first_line(&["-print-sysroot"]).filter(|s| Path::new(s).is_absolute()),
+    })
+}
+
+/// Language standard the compiler uses without -std, from its predefined macros
+fn default_standard(compiler: &str, language: &str) -> Option<String> {
+    let macros = exec::execute_for_output(compiler, &["-dM", "-E", "-x", language, "/dev/null"])?;
+    standard_from_macros(&macros, language)
+}
+
+fn standard_from_macros(macros: &str, language: &str) -> Option<String> {
+    let define = |name: &str| {
+        macros.lines().find_map(|l| {
+            let mut parts = l.strip_prefix("#define ")?.split_whitespace();
+            (parts.next()? == name).then(|| parts.next().unwrap_or_default().trim_end_matches('L').to_string())
+        })
+    };
+    // GNU dialects are the default; -std=c17 and friends define __STRICT_ANSI__
+    let strict = define("__STRICT_ANSI__").is_some();
+    let year = if language == "c" {
+        match define("__STDC_VERSION__")?.parse::<u32>().ok()? {
+            v if v > 201710 => "23",
+            v if v >= 201710 => "17",
+            v if v >= 201112 => "11",
+            _ => "99",
+        }
+    } else {
+        match define("__cplusplus")?.parse::<u32>().ok()? {
+            v if v > 202002 => "23",
+            v if v > 201703 => "20",
+            v if v > 201402 => "17",
+            v if v > 201103 => "14",
+            v if v >= 201103 => "11",
+            _ => "98",
+        }
+    };
+    let dialect = match (language, strict) {
+        ("c", true) => "c",
+        ("c", false) => "gnu",
+        (_, true) => "c++",
+        (_, false) => "gnu++",
+    };
+    Some(format!("{}{}", dialect, year))
+}
+
+/// Packages named in find_package() and pkg_check_modules()/pkg_search_module(),
+/// with the command that asked for them
+fn cmake_requirements(content: &str) -> Vec<(String, &'static str)> {
+    let content: String = content.lines().map(|l| l.split('#').next().unwrap_or_default()).collect::<Vec<_>>().join("\n");
+    let mut requirements: Vec<(String, &'static str)> = Vec::new();
+    let mut add = |name: &str, via: &'static str| {
+        if !requirements.iter().any(|(n, _)| n == name) {
+            requirements.push((name.to_string(), via));
+        }
+    };
+
+    let find_package = Regex::new(r"(?i)\bfind_package\s*\(\s*([A-Za-z0-9_.+-]+)").unwrap();
+    for caps in find_package.captures_iter(&content) {
+        add(&caps[1], "find_package");
+    }
+    let pkg_modules = Regex::new(r"(?i)\bpkg_(?:check_modules|search_module)\s*\(\s*\w+([^)]*)\)").unwrap();
+    for caps in pkg_modules.captures_iter(&content) {
+        for module in caps[1].split_whitespace().filter(|m| !PKG_OPTIONS.contains(m)) {
+            // glib-2.0>=2.56 names the module glib-2.0
+            let name = module.split(['<', '>', '=']).next().unwrap_or(module).trim_matches('"');
+            if !name.is_empty() {
+                add(name, "pkg_check_modules");
+            }
+        }
+    }
+    requirements
+}
+
+/// Look a package up with pkg-config, then in CMake package directories; a
+/// package only CMake's own Find module knows about can't be checked before configuring
+fn find_library(name: String, via: &str, has_pkg_config: bool, triple: Option<&str>) -> CxxLibrary {
+    if name == "PkgConfig" {
+        return CxxLibrary { name, via: via.to_string(), found: Some(has_pkg_config), version: None };
+    }
+    if has_pkg_config {
+        let lower = name.to_lowercase();
+        let candidates = if via == "pkg_check_modules" { vec![name.clone()] } else { vec![name.clone(), lower.clone(), format!("lib{}", lower)] };
+        for candidate in candidates {
+            if let Some(version) = exec::execute_command("pkg-config", &["--modversion", &candidate])
+                .ok()
+                .filter(|o| o.status.success())
+                .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+            {
+                return CxxLibrary { name, via: via.to_string(), found: Some(true), version: Some(version).filter(|v| !v.is_empty()) };
+            }
+        }
+    }
+    if via == "pkg_check_modules" {
+        return CxxLibrary { name, via: via.to_string(), found: Some(false), version: None };
+    }
+
+    if let Some(config_dir) = cmake_package_dirs(triple).into_iter().find_map(|dir| matching_entry(&dir, &name)) {
+        let version = probe::extract_version(&config_dir.file_name().unwrap_or_default().to_string_lossy());
+        return CxxLibrary { name, via: via.to_string(), found: Some(true), version };
+    }
+    // Without cmake there's no telling which Find modules it would ship
+    let find_module = format!("Find{}.cmake", name);
+    let known_to_cmake = !exec::command_exists("cmake")
+        || ["/usr/share", "/usr/local/share", "/opt/homebrew/share"].iter().any(|share| {
+            glob::glob(&format!("{}/cmake*/Modules/{}", share, find_module)).is_ok_and(|mut m| m.any(|p| p.is_ok()))
+        });
+    CxxLibrary { name, via: via.to_string(), found: (!known_to_cmake).then_some(false), version: None }
+}
+
+/// Where CMake looks for <Name>Config.cmake packages
+fn cmake_package_dirs(triple: Option<&str>) -> Vec<PathBuf> {
+    let mut prefixes: Vec<PathBuf> = env::var_os("CMAKE_PREFIX_PATH").map(|p| env::split_paths(&p).collect()).unwrap_or_default();
+    prefixes.extend(["/usr", "/usr/local", "/opt/homebrew", "/opt/local"].map(PathBuf::from));
+    let mut dirs = Vec::new();
+    for prefix in prefixes {
+        dirs.extend(["lib/cmake", "lib64/cmake", "share/cmake"].map(|d| prefix.join(d)));
+        dirs.extend(triple.map(|t| prefix.join("lib").join(t).join("cmake")));
+    }
+    dirs
+}
+
+/// An entry of `dir` named after the package, e.g. Boost-1.83.0 for Boost
+fn matching_entry(dir: &Path, name: &str) -> Option<PathBuf> {
+    let name = name.to_lowercase();
+    fs::read_dir(dir).ok()?.flatten().map(|e| e.path()).find(|p| {
+        let entry = p.file_name().unwrap_or_default().to_string_lossy().to_lowercase();
+        entry == name || entry.strip_prefix(&name).is_some_and(|rest| rest.starts_with(['-', '_']))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_standard_from_macros() {
+        assert_eq!(standard_from_macros("#define __STDC_VERSION__ 201710L\n", "c").as_deref(), Some("gnu17"));
+        assert_eq!(standard_from_macros("#define __STRICT_ANSI__ 1\n#define __STDC_VERSION__ 201112L\n", "c").as_deref(), Some("c11"));
+        assert_eq!(standard_from_macros("#define __cplusplus 201703L\n", "c++").as_deref(), Some("gnu++17"));
+        assert_eq!(standard_from_macros("#define __cplusplus 202002L\n#define __STRICT_ANSI__ 1\n", "c++").as_deref(), Some("c++20"));
+        assert_eq!(standard_from_macros("", "c"), None);
+    }
+
+    #[test]
+    fn test_cmake_requirements() {
+        let cmake = "cmake_minimum_required(VERSION 3.20)\nfind_package(OpenSSL REQUIRED)\n# find_package(Qt6)\nfind_package(Boost 1.80 COMPONENTS system)\nfind_package(PkgConfig)\npkg_check_modules(GLIB REQUIRED IMPORTED_TARGET glib-2.0>=2.56 gio-2.0)\n";
+        let names: Vec<String> = cmake_requirements(cmake).into_iter().map(|(n, _)| n).collect();
+        assert_eq!(names, vec!["OpenSSL", "Boost", "PkgConfig", "glib-2.0", "gio-2.0"]);
+        assert_eq!(cmake_minimum(cmake).as_deref(), Some("3.20"));
+        assert_eq!(cmake_minimum("cmake_minimum_required(VERSION 3.16...3.28)").as_deref(), Some("3.16"));
+    }
+
+    #[test]
+    fn test_read_presets() {
+        let dir = TempDir::new("cxx");
+        fs::create_dir_all(dir.join("cmake")).unwrap();
+        fs::write(dir.join("cmake/arm.cmake"), "").unwrap();
+        fs::write(
+            dir.join("CMakePresets.json"),
+            r#"{
+  "version": 6,
+  "cmakeMinimumRequired": {"major": 3, "minor": 21, "patch": 0},
+  "configurePresets": [
+    {"name": "base", "hidden": true, "generator": "Ninja"},
+    {"name": "dev", "inherits": "base"},
+    {"name": "arm", "inherits": ["base"], "toolchainFile": "${sourceDir}/cmake/arm.cmake"},
+    {"name": "vs", "generator": "Visual Studio 17 2022", "cacheVariables": {"CMAKE_TOOLCHAIN_FILE": {"type": "FILEPATH", "value": "missing.cmake"}}}
+  ]
+}"#,
+        )
+        .unwrap();
+        let (presets, required) = read_presets(&dir);
+        assert_eq!(required.as_deref(), Some("3.25"));
+        let names: Vec<&str> = presets.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["dev", "arm", "vs"]);
+        assert_eq!(presets[0].generator.as_deref(), Some("Ninja"));
+        assert_eq!(presets[0].generator_tool.as_deref(), Some("ninja"));
+        assert_eq!(presets[1].toolchain_exists, Some(true));
+        assert_eq!(presets[2].generator_tool.as_deref(), Some("msbuild"));
+        assert_eq!(presets[2].toolchain_exists, Some(false));
+    }
+}
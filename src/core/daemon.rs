@@ -0,0 +1,192 @@
+use crate::core::cache;
+use crate::types::ScanResult;
+use anyhow::{bail, Context, Result};
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// How long a client waits for the daemon before scanning itself
+const CLIENT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// $XDG_RUNTIME_DIR/devfetch/daemon.sock, else the cache directory; the
+/// socket's directory is private so it is never reachable before it's ready
+pub fn socket_path() -> Option<PathBuf> {
+    env::var_os("XDG_RUNTIME_DIR")
+        .filter(|d| !d.is_empty())
+        .map(|d| PathBuf::from(d).join("devfetch"))
+        .or_else(|| cache::cache_dir().map(|d| d.join("daemon")))
+        .map(|dir| dir.join("daemon.sock"))
+}
+
+/// Keep a warm scan in memory, rescan whenever a PATH directory changes, and
+/// hand the latest scan (as JSON) to clients whose PATH is the daemon's; the
+/// scan describes the daemon's PATH, so other clients are refused and scan
+/// for themselves
+pub fn serve<F>(scan: F, interval: Duration, verbose: bool) -> Result<()>
+where
+    F: Fn() -> Result<ScanResult> + Send + 'static,
+{
+    let socket = socket_path().context("No runtime or cache directory for the daemon socket")?;
+    serve_at(&socket, scan, interval, verbose)
+}
+
+pub fn serve_at<F>(socket: &Path, scan: F, interval: Duration, verbose: bool) -> Result<()>
+where
+    F: Fn() -> Result<ScanResult> + Send + 'static,
+{
+    if socket.exists() {
+        if UnixStream::connect(socket).is_ok() {
+            bail!("A devfetch daemon is already listening on {}", socket.display());
+        }
+        fs::remove_file(socket).with_context(|| format!("Failed to remove stale socket {}", socket.display()))?;
+    }
+    if let Some(dir) = socket.parent() {
+        private_dir(dir)?;
+    }
+
+    let path = Arc::new(env::var_os("PATH").unwrap_or_default());
+    let snapshot = Arc::new(RwLock::new(serde_json::to_string(&scan()?)?));
+    let listener = UnixListener::bind(socket).with_context(|| format!("Failed to bind {}", socket.display()))?;
+    if verbose {
+        eprintln!("devfetch daemon listening on {}", socket.display());
+    }
+
+    let latest = Arc::clone(&snapshot);
+    thread::spawn(move || {
+        let mut stamps = path_stamps();
+        loop {
+            thread::sleep(interval);
+            let current = path_stamps();
+            if current == stamps {
+                continue;
+            }
+            stamps = current;
+            if verbose {
+                eprintln!("PATH changed, rescanning");
+            }
+            match scan().and_then(|r| Ok(serde_json::to_string(&r)?)) {
+                Ok(json) => *latest.write().unwrap() = json,
+                Err(e) => eprintln!("Rescan failed: {:#}", e),
+            }
+        }
+    });
+
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        // A slow client must not hold up the others
+        let (snapshot, path) = (Arc::clone(&snapshot), Arc::clone(&path));
+        thread::spawn(move || {
+            if let Err(e) = answer(stream, &snapshot, &path) {
+                if verbose {
+                    eprintln!("Client disconnected: {:#}", e);
+                }
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Read the client's PATH and send the scan if it is the one the daemon
+/// scanned; otherwise close without an answer
+fn answer(stream: UnixStream, snapshot: &RwLock<String>, path: &OsStr) -> Result<()> {
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    let mut request = Vec::new();
+    BufReader::new(&stream).read_until(b'\n', &mut request)?;
+    if request.strip_suffix(b"\n") != Some(path.as_bytes()) {
+        return Ok(());
+    }
+    let json = snapshot.read().unwrap().clone();
+    (&stream).write_all(json.as_bytes())?;
+    Ok(())
+}
+
+/// Create the socket's directory readable by the owner only, or make sure an
+/// existing one is
+fn private_dir(dir: &Path) -> Result<()> {
+    fs::DirBuilder::new().recursive(true).mode(0o700).create(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    if fs::metadata(dir)?.permissions().mode() & 0o077 != 0 {
+        bail!("{} is accessible to other users; make it 0700 or remove it", dir.display());
+    }
+    Ok(())
+}
+
+/// The daemon's latest scan, if it was taken with this process's PATH
+pub fn fetch() -> Result<ScanResult> {
+    let socket = socket_path().context("No runtime or cache directory for the daemon socket")?;
+    fetch_from(&socket)
+}
+
+pub fn fetch_from(socket: &Path) -> Result<ScanResult> {
+    request(socket, &env::var_os("PATH").unwrap_or_default())
+}
+
+fn request(socket: &Path, path: &OsStr) -> Result<ScanResult> {
+    let mut stream =
+        UnixStream::connect(socket).with_context(|| format!("No devfetch daemon on {}", socket.display()))?;
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    let mut line = OsString::from(path).into_vec();
+    line.push(b'\n');
+    stream.write_all(&line)?;
+    let mut json = String::new();
+    stream.read_to_string(&mut json)?;
+    if json.is_empty() {
+        bail!("The devfetch daemon was started with a different PATH");
+    }
+    serde_json::from_str(&json).context("Invalid response from the devfetch daemon")
+}
+
+/// Modification times of the PATH directories; installing, removing or
+/// replacing a binary updates its directory's mtime
+pub fn path_stamps() -> Vec<(PathBuf, Option<SystemTime>)> {
+    let path = env::var_os("PATH").unwrap_or_default();
+    env::split_paths(&path)
+        .map(|dir| {
+            let modified = fs::metadata(&dir).and_then(|m| m.modified()).ok();
+            (dir, modified)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_serve_and_fetch() {
+        let dir = TempDir::new("daemon");
+        // The daemon creates the socket's directory itself, private to the user
+        let socket = dir.join("run").join("daemon.sock");
+        let path = socket.clone();
+        thread::spawn(move || {
+            let scan = || {
+                let mut result = ScanResult::new();
+                result.hostname = Some("warm".to_string());
+                Ok(result)
+            };
+            serve_at(&path, scan, Duration::from_secs(60), false)
+        });
+
+        let result = (0..50)
+            .find_map(|_| {
+                thread::sleep(Duration::from_millis(20));
+                fetch_from(&socket).ok()
+            })
+            .unwrap();
+        assert_eq!(result.hostname.as_deref(), Some("warm"));
+        assert_eq!(fs::metadata(dir.join("run")).unwrap().permissions().mode() & 0o777, 0o700);
+        // A client with another PATH would get a scan of the wrong tools
+        assert!(request(&socket, OsStr::new("/devfetch/other/bin")).is_err());
+        assert!(serve_at(&socket, || Ok(ScanResult::new()), Duration::from_secs(60), false).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+use crate::types::FleetReport;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Bar colors cycled across a tool's versions
+static PALETTE: &[&str] = &[
+    "#4e79a7", "#f28e2b", "#59a14f", "#e15759", "#76b7b2", "#edc948", "#b07aa1", "#9c755f",
+];
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem;color:#222}\
+h1{margin-bottom:0}.sub{color:#666;margin-top:.25rem}\
+table{border-collapse:collapse;width:100%;margin:1rem 0}\
+td,th{padding:.35rem .5rem;border-bottom:1px solid #eee;text-align:left;vertical-align:top}\
+.bar{display:flex;height:1.1rem;min-width:240px;border-radius:3px;overflow:hidden}\
+.bar span{display:block;height:100%}.legend span{margin-right:.75rem;white-space:nowrap}\
+.swatch{display:inline-block;width:.7rem;height:.7rem;margin-right:.25rem;border-radius:2px}\
+.missing{color:#c0392b}.outlier{color:#b9770e}details{margin:.5rem 0}summary{cursor:pointer;font-weight:600}";
+
+/// Render merged fleet data as a self-contained static HTML page
+pub fn render_html(report: &FleetReport) -> String {
+    let mut html = String::new();
+    let total = report.machines.len();
+
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>devfetch fleet dashboard</title>\n<style>{}</style>\n</head>\n<body>\n\
+         <h1>Fleet dashboard</h1>\n<p class=\"sub\">{} machines · {} tools</p>\n",
+        STYLE,
+        total,
+        report.tools.len()
+    );
+
+    html.push_str("<h2>Version spread</h2>\n<table>\n<tr><th>Tool</th><th>Machines</th><th>Versions</th><th>Problems</th></tr>\n");
+    for tool in &report.tools {
+        let installed: usize = tool.versions.values().map(|m| m.len()).sum();
+        let _ = write!(html, "<tr><td>{}</td><td>{}/{}</td><td>", escape(&tool.name), installed, total);
+
+        html.push_str("<div class=\"bar\">");
+        for (i, (version, machines)) in tool.versions.iter().enumerate() {
+            let width = machines.len() as f64 * 100.0 / total.max(1) as f64;
+            let _ = write!(
+                html,
+                "<span style=\"width:{:.1}%;background:{}\" title=\"{}: {}\"></span>",
+                width,
+                PALETTE[i % PALETTE.len()],
+                escape(version),
+                escape(&machines.join(", "))
+            );
+        }
+        html.push_str("</div><div class=\"legend\">");
+        for (i, (version, machines)) in tool.versions.iter().enumerate() {
+            let _ = write!(
+                html,
+                "<span><i class=\"swatch\" style=\"background:{}\"></i>{} ×{}</span>",
+                PALETTE[i % PALETTE.len()],
+                escape(version),
+                machines.len()
+            );
+        }
+        html.push_str("</div></td><td>");
+
+        if !tool.missing_on.is_empty() {
+            let _ = write!(html, "<div class=\"missing\">missing on {}</div>", escape(&tool.missing_on.join(", ")));
+        }
+        for outlier in &tool.outliers {
+            let _ = write!(
+                html,
+                "<div class=\"outlier\">{} runs {}</div>",
+                escape(&outlier.machine),
+                escape(&outlier.version)
+            );
+        }
+        html.push_str("</td></tr>\n");
+    }
+    html.push_str("</table>\n");
+
+    render_teams(&mut html, report);
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Per-team drill-down: each team's machines and the versions they run
+fn render_teams(html: &mut String, report: &FleetReport) {
+    let mut teams: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for machine in &report.machines {
+        if let Some(team) = &machine.team {
+            teams.entry(team).or_default().push(&machine.name);
+        }
+    }
+
+    if teams.is_empty() {
+        return;
+    }
+
+    html.push_str("<h2>Teams</h2>\n");
+    for (team, machines) in teams {
+        let _ = write!(
+            html,
+            "<details>\n<summary>{} ({} machines)</summary>\n<table>\n<tr><th>Tool</th>",
+            escape(team),
+            machines.len()
+        );
+        for machine in &machines {
+            let _ = write!(html, "<th>{}</th>", escape(machine));
+        }
+        html.push_str("</tr>\n");
+
+        for tool in &report.tools {
+            let _ = write!(html, "<tr><td>{}</td>", escape(&tool.name));
+            for machine in &machines {
+                let version = tool
+                    .versions
+                    .iter()
+                    .find(|(_, ms)| ms.iter().any(|m| m == machine))
+                    .map(|(v, _)| escape(v))
+                    .unwrap_or_else(|| "<span class=\"missing\">—</span>".to_string());
+                let _ = write!(html, "<td>{}</td>", version);
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n</details>\n");
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{FleetMachine, FleetTool};
+
+    #[test]
+    fn test_render_html() {
+        let mut versions = BTreeMap::new();
+        versions.insert("20.11.0".to_string(), vec!["alice-mbp".to_string()]);
+        versions.insert("18.19.0".to_string(), vec!["ci<1>".to_string()]);
+
+        let report = FleetReport {
+            machines: vec![
+                FleetMachine { name: "alice-mbp".to_string(), team: Some("web".to_string()) },
+                FleetMachine { name: "ci<1>".to_string(), team: Some("infra".to_string()) },
+            ],
+            tools: vec![FleetTool {
+                name: "node".to_string(),
+                versions,
+                missing_on: Vec::new(),
+                outliers: Vec::new(),
+            }],
+        };
+
+        let html = render_html(&report);
+        assert!(html.contains("<td>node</td>"));
+        assert!(html.contains("ci&lt;1&gt;"));
+        assert!(!html.contains("ci<1>"));
+        assert!(html.contains("<summary>web (1 machines)</summary>"));
+    }
+}
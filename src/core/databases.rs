@@ -0,0 +1,225 @@
+use crate::core::{exec, frameworks};
+use crate::types::{DatabaseEngine, DatabaseInfo, DatabaseLibrary};
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+/// Whether a library maps objects to tables or just talks to the database
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Orm,
+    Driver,
+}
+
+/// (manifests, package, display name, kind, engine it implies)
+type LibraryRule = (&'static [&'static str], &'static str, &'static str, Kind, Option<&'static str>);
+
+static PYTHON: &[&str] = &["requirements.txt", "pyproject.toml", "Pipfile"];
+static RUBY: &[&str] = &["Gemfile.lock", "Gemfile"];
+static NODE: &[&str] = &["package.json"];
+static RUST: &[&str] = &["Cargo.toml"];
+
+static LIBRARIES: &[LibraryRule] = &[
+    (PYTHON, "sqlalchemy", "SQLAlchemy", Kind::Orm, None),
+    (PYTHON, "psycopg2", "psycopg2", Kind::Driver, Some("PostgreSQL")),
+    (PYTHON, "psycopg2-binary", "psycopg2", Kind::Driver, Some("PostgreSQL")),
+    (PYTHON, "psycopg", "psycopg", Kind::Driver, Some("PostgreSQL")),
+    (PYTHON, "asyncpg", "asyncpg", Kind::Driver, Some("PostgreSQL")),
+    (PYTHON, "mysqlclient", "mysqlclient", Kind::Driver, Some("MySQL")),
+    (PYTHON, "pymysql", "PyMySQL", Kind::Driver, Some("MySQL")),
+    (PYTHON, "pymongo", "PyMongo", Kind::Driver, Some("MongoDB")),
+    (PYTHON, "redis", "redis-py", Kind::Driver, Some("Redis")),
+    (RUBY, "activerecord", "ActiveRecord", Kind::Orm, None),
+    (RUBY, "pg", "pg", Kind::Driver, Some("PostgreSQL")),
+    (RUBY, "mysql2", "mysql2", Kind::Driver, Some("MySQL")),
+    (RUBY, "sqlite3", "sqlite3", Kind::Driver, Some("SQLite")),
+    (RUBY, "mongoid", "Mongoid", Kind::Orm, Some("MongoDB")),
+    (NODE, "@prisma/client", "Prisma", Kind::Orm, None),
+    (NODE, "typeorm", "TypeORM", Kind::Orm, None),
+    (NODE, "sequelize", "Sequelize", Kind::Orm, None),
+    (NODE, "mongoose", "Mongoose", Kind::Orm, Some("MongoDB")),
+    (NODE, "pg", "node-postgres", Kind::Driver, Some("PostgreSQL")),
+    (NODE, "mysql2", "mysql2", Kind::Driver, Some("MySQL")),
+    (NODE, "better-sqlite3", "better-sqlite3", Kind::Driver, Some("SQLite")),
+    (NODE, "mongodb", "mongodb", Kind::Driver, Some("MongoDB")),
+    (NODE, "ioredis", "ioredis", Kind::Driver, Some("Redis")),
+    (RUST, "diesel", "Diesel", Kind::Orm, None),
+    (RUST, "sea-orm", "SeaORM", Kind::Orm, None),
+    (RUST, "sqlx", "SQLx", Kind::Driver, None),
+    (RUST, "tokio-postgres", "tokio-postgres", Kind::Driver, Some("PostgreSQL")),
+    (RUST, "rusqlite", "rusqlite", Kind::Driver, Some("SQLite")),
+    (RUST, "redis", "redis-rs", Kind::Driver, Some("Redis")),
+];
+
+/// (artifact substring in pom.xml/build.gradle, display name, kind, engine)
+static JVM_LIBRARIES: &[(&str, &str, Kind, Option<&str>)] = &[
+    ("hibernate-core", "Hibernate", Kind::Orm, None),
+    ("spring-boot-starter-data-jpa", "Hibernate", Kind::Orm, None),
+    ("org.postgresql", "PostgreSQL JDBC", Kind::Driver, Some("PostgreSQL")),
+    ("mysql-connector", "MySQL Connector/J", Kind::Driver, Some("MySQL")),
+    ("com.h2database", "H2", Kind::Driver, Some("H2")),
+    ("mongodb-driver", "MongoDB Java Driver", Kind::Driver, Some("MongoDB")),
+];
+
+/// Engine -> (server binaries, client binaries); empty servers means embedded
+static ENGINES: &[(&str, &[&str], &[&str])] = &[
+    ("PostgreSQL", &["postgres", "pg_ctl"], &["psql"]),
+    ("MySQL", &["mysqld", "mariadbd"], &["mysql", "mariadb"]),
+    ("MongoDB", &["mongod"], &["mongosh", "mongo"]),
+    ("Redis", &["redis-server"], &["redis-cli"]),
+    ("SQLite", &[], &["sqlite3"]),
+    ("H2", &[], &[]),
+];
+
+/// Keywords in adapter/provider/feature names and the engine they mean
+static ENGINE_KEYWORDS: &[(&str, &str)] = &[
+    ("postgres", "PostgreSQL"),
+    ("mysql", "MySQL"),
+    ("mariadb", "MySQL"),
+    ("sqlite", "SQLite"),
+    ("mongo", "MongoDB"),
+];
+
+/// Detect ORMs and database drivers, the engines they imply and whether
+/// those engines are available on this machine
+pub fn detect_databases(dir: &Path) -> Option<DatabaseInfo> {
+    let mut libraries: Vec<DatabaseLibrary> = Vec::new();
+    let mut add = |name: &str, kind: Kind, source: &str, engines: Vec<String>| {
+        if let Some(existing) = libraries.iter_mut().find(|l| l.name == name) {
+            for engine in engines {
+                if !existing.engines.contains(&engine) {
+                    existing.engines.push(engine);
+                }
+            }
+            return;
+        }
+        libraries.push(DatabaseLibrary {
+            name: name.to_string(),
+            kind: if kind == Kind::Orm { "orm" } else { "driver" }.to_string(),
+            source: source.to_string(),
+            engines,
+        });
+    };
+
+    for (manifests, package, name, kind, engine) in LIBRARIES {
+        for manifest in manifests.iter() {
+            let Ok(content) = fs::read_to_string(dir.join(manifest)) else {
+                continue;
+            };
+            if let Some(rest) = frameworks::declaration(&content, package) {
+                // Diesel/SQLx pick their backend via cargo features on the same line
+                let engines = match engine {
+                    Some(e) => vec![e.to_string()],
+                    None => engines_mentioned(rest),
+                };
+                add(name, *kind, manifest, engines);
+                break;
+            }
+        }
+    }
+
+    for manifest in ["pom.xml", "build.gradle", "build.gradle.kts"] {
+        let Ok(content) = fs::read_to_string(dir.join(manifest)) else {
+            continue;
+        };
+        for (artifact, name, kind, engine) in JVM_LIBRARIES {
+            if content.contains(artifact) {
+                add(name, *kind, manifest, engine.iter().map(|e| e.to_string()).collect());
+            }
+        }
+    }
+
+    // Engines configured outside the dependency list
+    let config_engines = [
+        ("prisma/schema.prisma", r#"provider\s*=\s*"(\w+)""#, "Prisma"),
+        ("config/database.yml", r"adapter:\s*(\w+)", "ActiveRecord"),
+    ];
+    for (file, pattern, library) in config_engines {
+        if let Ok(content) = fs::read_to_string(dir.join(file)) {
+            let re = Regex::new(pattern).unwrap();
+            let engines: Vec<String> =
+                re.captures_iter(&content).flat_map(|caps| engines_mentioned(&caps[1])).collect();
+            if !engines.is_empty() {
+                add(library, Kind::Orm, file, engines);
+            }
+        }
+    }
+
+    if libraries.is_empty() {
+        return None;
+    }
+
+    let mut engines: Vec<DatabaseEngine> = Vec::new();
+    for library in &libraries {
+        for engine in &library.engines {
+            match engines.iter_mut().find(|e| &e.engine == engine) {
+                Some(existing) => existing.required_by.push(library.name.clone()),
+                None => engines.push(locate_engine(engine, &library.name)),
+            }
+        }
+    }
+
+    Some(DatabaseInfo { libraries, engines })
+}
+
+fn engines_mentioned(text: &str) -> Vec<String> {
+    let text = text.to_lowercase();
+    let mut engines: Vec<String> = Vec::new();
+    for (keyword, engine) in ENGINE_KEYWORDS {
+        if text.contains(keyword) && !engines.iter().any(|e| e == engine) {
+            engines.push(engine.to_string());
+        }
+    }
+    engines
+}
+
+/// Look for the engine's server and client binaries on this machine
+fn locate_engine(engine: &str, required_by: &str) -> DatabaseEngine {
+    let (servers, clients) = ENGINES
+        .iter()
+        .find(|(name, ..)| *name == engine)
+        .map(|(_, servers, clients)| (*servers, *clients))
+        .unwrap_or_default();
+    DatabaseEngine {
+        engine: engine.to_string(),
+        required_by: vec![required_by.to_string()],
+        embedded: servers.is_empty(),
+        server: servers.iter().find(|s| exec::command_exists(s)).map(|s| s.to_string()),
+        client: clients.iter().find(|c| exec::command_exists(c)).map(|c| c.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_detect_databases() {
+        let dir = TempDir::new("databases");
+        fs::create_dir_all(dir.join("prisma")).unwrap();
+        fs::write(
+            dir.join("package.json"),
+            "{\n  \"dependencies\": {\n    \"@prisma/client\": \"^5.8.0\",\n    \"ioredis\": \"^5.3.2\"\n  }\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("prisma/schema.prisma"),
+            "generator client {\n  provider = \"prisma-client-js\"\n}\ndatasource db {\n  provider = \"postgresql\"\n}\n",
+        )
+        .unwrap();
+
+        let info = detect_databases(&dir).unwrap();
+        let libraries: Vec<(&str, &str, Vec<String>)> =
+            info.libraries.iter().map(|l| (l.name.as_str(), l.kind.as_str(), l.engines.clone())).collect();
+        assert_eq!(
+            libraries,
+            vec![
+                ("Prisma", "orm", vec!["PostgreSQL".to_string()]),
+                ("ioredis", "driver", vec!["Redis".to_string()]),
+            ]
+        );
+        let engines: Vec<&str> = info.engines.iter().map(|e| e.engine.as_str()).collect();
+        assert_eq!(engines, vec!["PostgreSQL", "Redis"]);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::core::{paths, redact};
+use crate::types::ScanResult;
+use anyhow::Result;
+use serde_json::Value;
+use std::path::Path;
+
+/// Make a scan reproducible for golden files: the project directory becomes
+/// `.` and the home directory `~`, the hostname is dropped, timestamps and
+/// durations are zeroed and every list is sorted
+pub fn apply(mut result: ScanResult, project: &Path) -> Result<ScanResult> {
+    result.hostname = None;
+    let project = project.canonicalize().unwrap_or_else(|_| project.to_path_buf());
+    let rules: Vec<_> = [Some(project), paths::home_dir()]
+        .into_iter()
+        .flatten()
+        .zip([".", "~"])
+        .filter_map(|(dir, with)| redact::rule(dir.to_string_lossy().trim_end_matches(['/', '\\']), with))
+        .collect();
+    let scan = normalize(redact::rewrite(serde_json::to_value(result)?, &rules));
+    Ok(serde_json::from_value(scan)?)
+}
+
+/// Fields named like `probed_at`, `duration_ms` or `mtime` hold times
+fn is_time(key: &str) -> bool {
+    let last = key.rsplit('_').next().unwrap_or(key);
+    matches!(last, "at" | "ms" | "secs" | "duration" | "elapsed" | "mtime" | "timestamp")
+}
+
+fn normalize(value: Value) -> Value {
+    match value {
+        Value::Array(items) => {
+            let mut items: Vec<Value> = items.into_iter().map(normalize).collect();
+            items.sort_by_cached_key(|v| v.to_string());
+            Value::Array(items)
+        }
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(k, v)| match v {
+                    Value::Number(_) if is_time(&k) => (k, Value::from(0)),
+                    v => (k, normalize(v)),
+                })
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_normalize() {
+        let scan = json!({"tools": [{"name": "go", "probed_at": 1718000000}, {"name": "cargo", "duration_ms": 12}], "markers": ["b", "a"], "port": 5432});
+        assert_eq!(
+            normalize(scan),
+            json!({"tools": [{"duration_ms": 0, "name": "cargo"}, {"name": "go", "probed_at": 0}], "markers": ["a", "b"], "port": 5432})
+        );
+    }
+}
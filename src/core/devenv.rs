@@ -0,0 +1,225 @@
+use crate::core::exec;
+use crate::types::DevEnvironment;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// Detect Devbox, Flox and Conda environments declared in a project
+pub fn detect_dev_environments(dir: &Path) -> Vec<DevEnvironment> {
+    [devbox(dir), flox(dir), conda(dir)].into_iter().flatten().collect()
+}
+
+fn devbox(dir: &Path) -> Option<DevEnvironment> {
+    let content = fs::read_to_string(dir.join("devbox.json")).ok()?;
+    let manifest: Value = serde_json::from_str(&content).ok()?;
+
+    Some(DevEnvironment {
+        tool: "devbox".to_string(),
+        manifest: "devbox.json".to_string(),
+        packages: devbox_packages(&manifest),
+        cli_installed: exec::command_exists("devbox"),
+        // `devbox install`/`devbox shell` materialize the profile under .devbox
+        built: dir.join(".devbox/nix/profile/default").exists(),
+        name: None,
+        python: None,
+        locked_packages: None,
+    })
+}
+
+/// Packages are either ["go@1.21", ...] or {"go": "1.21", "nodejs": {"version": "20"}}
+fn devbox_packages(manifest: &Value) -> Vec<String> {
+    match manifest.get("packages") {
+        Some(Value::Array(items)) => items.iter().filter_map(|p| p.as_str().map(String::from)).collect(),
+        Some(Value::Object(map)) => map
+            .iter()
+            .map(|(name, spec)| {
+                let version = spec.as_str().or_else(|| spec.get("version").and_then(|v| v.as_str()));
+                match version {
+                    Some(v) if !v.is_empty() && v != "latest" => format!("{}@{}", name, v),
+                    _ => name.clone(),
+                }
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn flox(dir: &Path) -> Option<DevEnvironment> {
+    let env = dir.join(".flox/env");
+    let content = fs::read_to_string(env.join("manifest.toml")).ok()?;
+    let manifest: toml::Table = content.parse().ok()?;
+
+    Some(DevEnvironment {
+        tool: "flox".to_string(),
+        manifest: ".flox/env/manifest.toml".to_string(),
+        packages: flox_packages(&manifest),
+        cli_installed: exec::command_exists("flox"),
+        // The lockfile is written once the environment's packages are resolved and installed
+        built: env.join("manifest.lock").exists(),
+        name: None,
+        python: None,
+        locked_packages: None,
+    })
+}
+
+/// Entries of the [install] table: `id.pkg-path = "..."`, `id.version = "..."`
+fn flox_packages(manifest: &toml::Table) -> Vec<String> {
+    let Some(install) = manifest.get("install").and_then(|i| i.as_table()) else {
+        return Vec::new();
+    };
+    install
+        .iter()
+        .map(|(id, spec)| {
+            let path = spec.get("pkg-path").and_then(|p| p.as_str()).unwrap_or(id);
+            match spec.get("version").and_then(|v| v.as_str()) {
+                Some(v) => format!("{}@{}", path, v),
+                None => path.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Conda CLIs in order of preference; all accept `env list --json`
+static CONDA_CLIS: &[&str] = &["conda", "mamba", "micromamba"];
+
+static CONDA_MANIFESTS: &[&str] = &["environment.yml", "environment.yaml", "conda-lock.yml"];
+
+fn conda(dir: &Path) -> Option<DevEnvironment> {
+    let manifest = CONDA_MANIFESTS.iter().copied().find(|m| dir.join(m).is_file())?;
+    let content = fs::read_to_string(dir.join(manifest)).ok()?;
+    let lock = fs::read_to_string(dir.join("conda-lock.yml")).ok();
+
+    let (name, prefix, packages) = if manifest == "conda-lock.yml" {
+        (None, None, Vec::new())
+    } else {
+        parse_environment_yml(&content)
+    };
+    let locked = lock.as_deref().map(parse_conda_lock);
+    let python = packages
+        .iter()
+        .find_map(|p| p.strip_prefix("python@"))
+        .map(String::from)
+        .or_else(|| locked.as_ref().and_then(|(_, python)| python.clone()));
+
+    let cli = CONDA_CLIS.iter().copied().find(|c| exec::command_exists(c));
+    let built = match (cli, &name, &prefix) {
+        (_, _, Some(prefix)) => dir.join(prefix).join("conda-meta").is_dir(),
+        (Some(cli), Some(name), None) => conda_env_exists(cli, name),
+        _ => false,
+    };
+
+    Some(DevEnvironment {
+        tool: "conda".to_string(),
+        manifest: manifest.to_string(),
+        packages,
+        cli_installed: cli.is_some(),
+        built,
+        name,
+        python,
+        locked_packages: locked.map(|(count, _)| count),
+    })
+}
+
+/// `name:`, `prefix:` and the `dependencies:` list of an environment.yml,
+/// with pip's nested list flattened in; "numpy=1.26" becomes numpy@1.26
+fn parse_environment_yml(content: &str) -> (Option<String>, Option<String>, Vec<String>) {
+    let top_level = |key: &str| {
+        content.lines().find_map(|l| l.strip_prefix(key)).map(|v| v.trim().trim_matches(['"', '\'']).to_string())
+    };
+    let mut packages = Vec::new();
+    let mut in_dependencies = false;
+    for line in content.lines() {
+        if !line.starts_with([' ', '-']) && !line.trim().is_empty() {
+            in_dependencies = line.starts_with("dependencies:");
+            continue;
+        }
+        let Some(item) = line.trim().strip_prefix("- ") else {
+            continue;
+        };
+        let item = item.split('#').next().unwrap_or(item).trim().trim_matches(['"', '\'']);
+        if !in_dependencies || item.is_empty() || item.ends_with(':') {
+            continue;
+        }
+        // conda pins use = / ==, pip uses ==; channel::name prefixes are dropped
+        let item = item.rsplit("::").next().unwrap_or(item);
+        match item.split_once(['=', '>', '<', ' ']) {
+            Some((name, version)) => {
+                let version = version.trim_start_matches(['=', '>', '<', ' ']).trim_end_matches(".*");
+                packages.push(format!("{}@{}", name.trim(), version));
+            }
+            None => packages.push(item.to_string()),
+        }
+    }
+    (top_level("name:"), top_level("prefix:"), packages)
+}
+
+/// Distinct package names in a conda-lock.yml, and the locked python version
+fn parse_conda_lock(content: &str) -> (usize, Option<String>) {
+    let mut names: Vec<&str> = Vec::new();
+    let mut python = None;
+    let mut current = "";
+    for line in content.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix("- name:").or_else(|| line.strip_prefix("name:")) {
+            current = name.trim();
+            if !names.contains(&current) {
+                names.push(current);
+            }
+        } else if let Some(version) = line.strip_prefix("version:") {
+            if current == "python" && python.is_none() {
+                python = Some(version.trim().trim_matches(['"', '\'']).to_string());
+            }
+        }
+    }
+    (names.len(), python)
+}
+
+/// Whether `conda env list` knows a named environment
+fn conda_env_exists(cli: &str, name: &str) -> bool {
+    let Some(output) = exec::execute_for_output(cli, &["env", "list", "--json"]) else {
+        return false;
+    };
+    let Ok(json) = serde_json::from_str::<Value>(&output) else {
+        return false;
+    };
+    json.get("envs")
+        .and_then(|e| e.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.as_str())
+        .any(|path| Path::new(path).file_name().is_some_and(|f| f == name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_devbox_packages() {
+        let list: Value = serde_json::from_str(r#"{"packages": ["go@1.21", "nodejs@20"]}"#).unwrap();
+        assert_eq!(devbox_packages(&list), vec!["go@1.21", "nodejs@20"]);
+
+        let map: Value =
+            serde_json::from_str(r#"{"packages": {"go": "latest", "nodejs": {"version": "20"}}}"#).unwrap();
+        assert_eq!(devbox_packages(&map), vec!["go", "nodejs@20"]);
+    }
+
+    #[test]
+    fn test_flox_packages() {
+        let manifest: toml::Table = "version = 1\n\n[install]\nnodejs.pkg-path = \"nodejs_20\"\nnodejs.version = \"20.11\"\nripgrep.pkg-path = \"ripgrep\"\n"
+            .parse()
+            .unwrap();
+        assert_eq!(flox_packages(&manifest), vec!["nodejs_20@20.11", "ripgrep"]);
+    }
+
+    #[test]
+    fn test_parse_environment_yml() {
+        let yml = "name: analysis\nchannels:\n  - conda-forge\ndependencies:\n  - python=3.11\n  - numpy>=1.26\n  - conda-forge::pandas\n  - pip\n  - pip:\n      - requests==2.31.0  # http\n";
+        let (name, prefix, packages) = parse_environment_yml(yml);
+        assert_eq!(name.as_deref(), Some("analysis"));
+        assert_eq!(prefix, None);
+        assert_eq!(packages, vec!["python@3.11", "numpy@1.26", "pandas", "pip", "requests@2.31.0"]);
+
+        let lock = "package:\n  - name: python\n    version: 3.11.8\n    manager: conda\n  - name: python\n    version: 3.11.8\n    platform: osx-arm64\n  - name: numpy\n    version: 1.26.4\n";
+        assert_eq!(parse_conda_lock(lock), (2, Some("3.11.8".to_string())));
+    }
+}
@@ -0,0 +1,102 @@
+use crate::core::doctor::compare_versions;
+use crate::types::{DependencyDiff, DiffStatus, ScanDiff, ScanResult, ToolDiff};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+
+const UNKNOWN_VERSION: &str = "unknown";
+
+fn tool_versions(result: &ScanResult) -> BTreeMap<&str, String> {
+    result
+        .global_tools
+        .iter()
+        .map(|t| (t.name.as_str(), t.version.clone().unwrap_or_else(|| UNKNOWN_VERSION.to_string())))
+        .collect()
+}
+
+/// Compare the tools of two scans, one row per tool seen on either side,
+/// and the project's dependency counts
+pub fn compare(left_label: &str, left: &ScanResult, right_label: &str, right: &ScanResult) -> ScanDiff {
+    let left_versions = tool_versions(left);
+    let right_versions = tool_versions(right);
+    let names: BTreeSet<&str> = left_versions.keys().chain(right_versions.keys()).copied().collect();
+
+    let tools = names
+        .into_iter()
+        .map(|name| {
+            let l = left_versions.get(name).cloned();
+            let r = right_versions.get(name).cloned();
+            let status = match (&l, &r) {
+                (Some(a), Some(b)) if a == b => DiffStatus::Same,
+                (Some(a), Some(b)) if a == UNKNOWN_VERSION || b == UNKNOWN_VERSION => DiffStatus::Changed,
+                (Some(a), Some(b)) => match compare_versions(a, b) {
+                    Ordering::Less => DiffStatus::Upgraded,
+                    Ordering::Greater => DiffStatus::Downgraded,
+                    Ordering::Equal => DiffStatus::Changed,
+                },
+                (Some(_), None) => DiffStatus::OnlyLeft,
+                _ => DiffStatus::OnlyRight,
+            };
+            ToolDiff { name: name.to_string(), left: l, right: r, status }
+        })
+        .collect();
+
+    ScanDiff {
+        left: left_label.to_string(),
+        right: right_label.to_string(),
+        tools,
+        dependencies: dependency_changes(left, right),
+    }
+}
+
+fn dependency_counts(result: &ScanResult) -> BTreeMap<&str, usize> {
+    let ecosystems = result.project_info.iter().flat_map(|p| &p.ecosystems);
+    ecosystems.filter_map(|(name, e)| Some((name.as_str(), e.dependencies.as_ref()?.count))).collect()
+}
+
+/// Ecosystems whose dependency count differs, or that only one side has
+fn dependency_changes(left: &ScanResult, right: &ScanResult) -> Vec<DependencyDiff> {
+    let (left, right) = (dependency_counts(left), dependency_counts(right));
+    let names: BTreeSet<&str> = left.keys().chain(right.keys()).copied().collect();
+    names
+        .into_iter()
+        .map(|name| DependencyDiff { ecosystem: name.to_string(), left: left.get(name).copied(), right: right.get(name).copied() })
+        .filter(|d| d.left != d.right)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Tool;
+
+    fn scan(tools: &[(&str, &str)]) -> ScanResult {
+        let mut result = ScanResult::new();
+        for (name, version) in tools {
+            result.global_tools.push(Tool {
+                version: Some(version.to_string()),
+                ..Tool::new(*name, format!("/usr/bin/{}", name))
+            });
+        }
+        result
+    }
+
+    #[test]
+    fn test_compare() {
+        let mine = scan(&[("git", "2.43.0"), ("node", "20.11.0"), ("protoc", "25.1"), ("python3", "3.11.4")]);
+        let ci = scan(&[("git", "2.43.0"), ("node", "18.19.0"), ("go", "1.22.0"), ("python3", "3.12.1")]);
+
+        let diff = compare("laptop", &mine, "ci", &ci);
+        let statuses: Vec<(&str, DiffStatus)> = diff.tools.iter().map(|t| (t.name.as_str(), t.status)).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("git", DiffStatus::Same),
+                ("go", DiffStatus::OnlyRight),
+                ("node", DiffStatus::Downgraded),
+                ("protoc", DiffStatus::OnlyLeft),
+                ("python3", DiffStatus::Upgraded),
+            ]
+        );
+        assert!(diff.dependencies.is_empty());
+    }
+}
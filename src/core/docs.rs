@@ -0,0 +1,122 @@
+use crate::core::{exec, frameworks};
+use crate::types::ConfiguredTool;
+use std::fs;
+use std::path::Path;
+
+/// Requirements files docs tooling is usually pinned in
+static PY_REQUIREMENTS: &[&str] = &["docs/requirements.txt", "requirements-docs.txt", "requirements-dev.txt", "requirements.txt", "pyproject.toml"];
+
+/// Detect documentation generators configured in the project and how to build the docs
+pub fn detect_docs_tools(dir: &Path) -> Vec<ConfiguredTool> {
+    let mut tools = Vec::new();
+
+    if dir.join("book.toml").is_file() {
+        tools.push(configured(dir, "mdBook", "book.toml", "mdbook", None, "mdbook build"));
+    }
+
+    for conf in ["docs/conf.py", "doc/conf.py", "docs/source/conf.py", "conf.py"] {
+        if dir.join(conf).is_file() {
+            let docs_dir = Path::new(conf).parent().map(|p| p.display().to_string()).filter(|p| !p.is_empty());
+            let docs_dir = docs_dir.unwrap_or_else(|| ".".to_string());
+            // sphinx-quickstart puts a Makefile in the docs directory
+            let build = if dir.join(&docs_dir).join("Makefile").is_file() {
+                format!("make -C {} html", docs_dir)
+            } else {
+                format!("sphinx-build {} {}/_build", docs_dir, docs_dir)
+            };
+            let pin = python_pin(dir, "sphinx");
+            tools.push(configured(dir, "Sphinx", conf, "sphinx-build", pin, &build));
+            break;
+        }
+    }
+
+    if let Some(config) = ["mkdocs.yml", "mkdocs.yaml"].into_iter().find(|c| dir.join(c).is_file()) {
+        let pin = python_pin(dir, "mkdocs").or_else(|| python_pin(dir, "mkdocs-material"));
+        tools.push(configured(dir, "MkDocs", config, "mkdocs", pin, "mkdocs build"));
+    }
+
+    let manifest = fs::read_to_string(dir.join("package.json")).unwrap_or_default();
+    let docusaurus = ["docusaurus.config.js", "docusaurus.config.ts", "docusaurus.config.mjs"];
+    if let Some(config) = docusaurus.into_iter().find(|c| dir.join(c).is_file()) {
+        let pin = npm_pin(&manifest, "@docusaurus/core");
+        tools.push(configured(dir, "Docusaurus", config, "docusaurus", pin, "npx docusaurus build"));
+    }
+
+    let typedoc_pin = npm_pin(&manifest, "typedoc");
+    if let Some(config) = ["typedoc.json", "typedoc.config.js", "typedoc.config.cjs"]
+        .into_iter()
+        .find(|c| dir.join(c).is_file())
+        .or(typedoc_pin.as_ref().map(|_| "package.json"))
+    {
+        tools.push(configured(dir, "TypeDoc", config, "typedoc", typedoc_pin.clone(), "npx typedoc"));
+    }
+
+    // Every crate has rustdoc; docs.rs metadata means the docs are published
+    if let Ok(cargo) = fs::read_to_string(dir.join("Cargo.toml")) {
+        let config = if cargo.contains("[package.metadata.docs.rs]") {
+            "Cargo.toml [package.metadata.docs.rs]"
+        } else {
+            "Cargo.toml"
+        };
+        tools.push(configured(dir, "rustdoc", config, "rustdoc", None, "cargo doc --no-deps --open"));
+    }
+
+    tools
+}
+
+/// A configured tool, with its CLI looked up in node_modules/.bin and on PATH
+pub fn configured(dir: &Path, tool: &str, config: &str, cli: &str, pinned: Option<String>, command: &str) -> ConfiguredTool {
+    ConfiguredTool {
+        tool: tool.to_string(),
+        config: config.to_string(),
+        pinned_version: pinned,
+        cli: cli.to_string(),
+        cli_installed: dir.join("node_modules/.bin").join(cli).exists() || exec::command_exists(cli),
+        command: Some(command.to_string()),
+    }
+}
+
+/// Version spec of an npm package in package.json
+pub fn npm_pin(manifest: &str, package: &str) -> Option<String> {
+    let rest = frameworks::declaration(manifest, package)?;
+    Some(rest.trim_start_matches(['"', ':', ' ']).split('"').next()?.to_string()).filter(|v| !v.is_empty())
+}
+
+/// Version specifier of a Python package, e.g. "==7.2.6"
+fn python_pin(dir: &Path, package: &str) -> Option<String> {
+    PY_REQUIREMENTS.iter().find_map(|file| {
+        let content = fs::read_to_string(dir.join(file)).ok()?;
+        let rest = frameworks::declaration(&content, package)?;
+        let spec = rest.trim_start_matches(|c: char| c == '[' || c.is_alphanumeric() || c == ',' || c == ']').trim();
+        // TOML tables write `sphinx = "^7.2"`; requirement lines write `sphinx==7.2`
+        let spec = spec.strip_prefix('=').filter(|s| s.starts_with([' ', '"', '\''])).unwrap_or(spec);
+        let spec = spec.trim_matches([' ', ',', '"', '\'']);
+        Some(spec.to_string()).filter(|s| !s.is_empty())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_detect_docs_tools() {
+        let dir = TempDir::new("docs");
+        fs::create_dir_all(dir.join("docs")).unwrap();
+        fs::write(dir.join("docs/conf.py"), "project = 'demo'\n").unwrap();
+        fs::write(dir.join("docs/Makefile"), "html:\n\tsphinx-build . _build\n").unwrap();
+        fs::write(dir.join("docs/requirements.txt"), "sphinx==7.2.6\nfuro\n").unwrap();
+        fs::write(dir.join("mkdocs.yml"), "site_name: Demo\n").unwrap();
+
+        let found: Vec<(String, Option<String>, Option<String>)> =
+            detect_docs_tools(&dir).into_iter().map(|t| (t.tool, t.pinned_version, t.command)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Sphinx".to_string(), Some("==7.2.6".to_string()), Some("make -C docs html".to_string())),
+                ("MkDocs".to_string(), None, Some("mkdocs build".to_string())),
+            ]
+        );
+    }
+}
@@ -0,0 +1,973 @@
+use crate::core::{jdk, requirements};
+use crate::types::{EcosystemDetails, Finding, GitSetup, MonorepoOrchestrator, PackageManager, ProxyEnv, RequirementCheck, RequirementStatus, ScanResult, Severity, SshReadiness, SystemCheck, SystemCheckKind, Tool, ToolPin};
+
+/// Toolchain-manager package names whose binary is called something else
+static BINARY_ALIASES: &[(&str, &str)] = &[
+    ("rust", "rustc"),
+    ("python", "python3"),
+    ("nodejs", "node"),
+    ("openjdk", "java"),
+    ("golang", "go"),
+];
+
+/// Check the scan against the project's requirements
+pub fn diagnose(result: &ScanResult) -> Vec<Finding> {
+    let mut findings: Vec<Finding> = result
+        .pinned_tools
+        .iter()
+        .filter_map(|pin| check_pin(pin, &result.global_tools))
+        .collect();
+
+    for req in result.ci_tools.iter().filter(|r| !r.installed) {
+        findings.push(Finding {
+            severity: Severity::Warning,
+            check: "ci-tool".to_string(),
+            subject: req.tool.clone(),
+            issue: format!("required by {} but not installed", req.ci_file),
+            rationale: None,
+            fix: None,
+        });
+    }
+
+    for linter in result.linters.iter().filter(|l| !l.installed) {
+        if let Some(config) = &linter.config {
+            findings.push(Finding {
+                severity: Severity::Warning,
+                check: "linter".to_string(),
+                subject: linter.name.clone(),
+                issue: format!("configured in {} but not installed", config),
+                rationale: None,
+                fix: None,
+            });
+        }
+    }
+
+    for env in result.dev_environments.iter().filter(|e| e.tool == "conda" && !e.built) {
+        let what = match &env.name {
+            Some(name) => format!("environment '{}'", name),
+            None => "environment".to_string(),
+        };
+        findings.push(Finding {
+            severity: Severity::Warning,
+            check: "conda-env".to_string(),
+            subject: env.tool.clone(),
+            issue: format!("{} from {} has not been created", what, env.manifest),
+            rationale: None,
+            fix: Some(match env.manifest.as_str() {
+                "conda-lock.yml" => "conda-lock install conda-lock.yml".to_string(),
+                manifest => format!("conda env create -f {}", manifest),
+            }),
+        });
+    }
+
+    if let Some(finding) = check_msrv(result) {
+        findings.push(finding);
+    }
+    findings.extend(check_java_target(result));
+    findings.extend(check_php(result));
+    findings.extend(check_cxx(result));
+    findings.extend(check_ruby(result));
+    findings.extend(requirements::check(result).iter().filter_map(requirement_finding));
+    findings.extend(check_package_managers(result));
+
+    findings.extend(result.orchestrators.iter().filter_map(check_orchestrator));
+
+    let engines = result.databases.iter().flat_map(|d| &d.engines);
+    for engine in engines.filter(|e| !e.embedded && e.server.is_none()) {
+        findings.push(Finding {
+            severity: Severity::Info,
+            check: "database".to_string(),
+            subject: engine.engine.clone(),
+            issue: format!("used by {} but no local server found", engine.required_by.join(", ")),
+            rationale: None,
+            fix: None,
+        });
+    }
+
+    if let Some(api) = &result.api_specs {
+        let mut kinds: Vec<&str> = api.specs.iter().map(|s| s.kind.as_str()).collect();
+        kinds.dedup();
+        for kind in kinds {
+            let tools: Vec<&str> = api.tools.iter().filter(|t| t.kind == kind).map(|t| t.name.as_str()).collect();
+            if !api.tools.iter().any(|t| t.kind == kind && t.installed) {
+                findings.push(Finding {
+                    severity: Severity::Warning,
+                    check: "api-codegen".to_string(),
+                    subject: tools.join("/"),
+                    issue: format!("{} specs found but no codegen/validation tool is installed", kind),
+                    rationale: None,
+                    fix: None,
+                });
+            }
+        }
+    }
+
+    for req in result.proto_toolchain.iter().filter(|r| !r.installed) {
+        findings.push(Finding {
+            severity: Severity::Error,
+            check: "protobuf".to_string(),
+            subject: req.tool.clone(),
+            issue: format!("required by {} for protobuf codegen but not installed", req.source),
+            rationale: None,
+            fix: crate::core::protobuf::install_hint(&req.tool),
+        });
+    }
+
+    for tool in result.docs_tools.iter().filter(|t| !t.cli_installed) {
+        findings.push(Finding {
+            severity: Severity::Warning,
+            check: "docs-tool".to_string(),
+            subject: tool.cli.clone(),
+            issue: format!("needed to build the {} docs ({}) but not installed", tool.tool, tool.config),
+            rationale: None,
+            fix: None,
+        });
+    }
+
+    for tool in result.release_tools.iter().filter(|t| !t.cli_installed) {
+        findings.push(Finding {
+            severity: Severity::Info,
+            check: "release-tool".to_string(),
+            subject: tool.cli.clone(),
+            issue: format!("configured in {} but not installed (needed to cut releases locally)", tool.config),
+            rationale: None,
+            fix: None,
+        });
+    }
+
+    let registries = result.registry_auth.iter().flat_map(|a| &a.referenced);
+    for reference in registries.filter(|r| !r.logged_in) {
+        findings.push(Finding {
+            severity: Severity::Info,
+            check: "registry-login".to_string(),
+            subject: reference.registry.clone(),
+            issue: format!("referenced by {} but not logged in (fine for public images)", reference.files.join(", ")),
+            rationale: None,
+            fix: Some(format!("docker login {}", reference.registry)),
+        });
+    }
+
+    for migration in result.migrations.iter().filter(|m| !m.cli_installed) {
+        findings.push(Finding {
+            severity: Severity::Warning,
+            check: "migration-cli".to_string(),
+            subject: migration.cli.clone(),
+            issue: format!("needed to run the {} migrations in {} but not installed", migration.migrations, migration.directory),
+            rationale: None,
+            fix: None,
+        });
+    }
+
+    for check in result.system_checks.iter().filter(|c| !c.satisfied) {
+        findings.push(check_system(check));
+    }
+
+    for service in result.services.iter().filter(|s| !s.running) {
+        findings.push(Finding {
+            severity: Severity::Error,
+            check: "service".to_string(),
+            subject: service.name.clone(),
+            issue: "required by devfetch.toml but not running".to_string(),
+            rationale: None,
+            fix: service.start_hint.clone(),
+        });
+    }
+
+    for check in result.network.iter().filter(|c| !c.reachable) {
+        let issue = match (&check.error, check.resolved) {
+            (_, false) => "does not resolve".to_string(),
+            (Some(error), true) => format!("port {} unreachable ({})", check.port, error),
+            (None, true) => format!("port {} unreachable", check.port),
+        };
+        findings.push(Finding {
+            severity: Severity::Warning,
+            check: "network".to_string(),
+            subject: check.host.clone(),
+            issue: format!("{}; named in {}", issue, check.sources.join(", ")),
+            rationale: None,
+            fix: Some("connect to the VPN or network that can reach it".to_string()),
+        });
+    }
+
+    for registry in &result.package_registries {
+        if let Some(problem) = &registry.problem {
+            findings.push(Finding {
+                severity: Severity::Warning,
+                check: "package-registry".to_string(),
+                subject: registry.ecosystem.clone(),
+                issue: format!("{} in {}", problem, registry.source),
+                rationale: None,
+                fix: None,
+            });
+        }
+    }
+
+    if let Some(git) = &result.git {
+        findings.extend(check_git(git));
+    }
+
+    if let Some(ssh) = &result.ssh {
+        findings.extend(check_ssh(ssh));
+    }
+
+    if let Some(proxy) = &result.proxy {
+        findings.extend(check_proxy_env(proxy));
+    }
+
+    // One finding per task runner the project relies on but this machine lacks
+    let scripts = result.project_info.iter().flat_map(|p| &p.scripts);
+    let mut missing_runners: Vec<(&str, &str)> = Vec::new();
+    for script in scripts.filter(|s| s.runner_missing) {
+        let runner = script.run.split(' ').next().unwrap_or_default();
+        if !missing_runners.iter().any(|(r, _)| *r == runner) {
+            missing_runners.push((runner, &script.source));
+        }
+    }
+    for (runner, source) in missing_runners {
+        findings.push(Finding {
+            severity: Severity::Warning,
+            check: "task-runner".to_string(),
+            subject: runner.to_string(),
+            issue: format!("needed to run the tasks in {} but not installed", source),
+            rationale: None,
+            fix: None,
+        });
+    }
+
+    findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+    findings
+}
+
+/// Whether any finding is at least as severe as `error_on` (--error-on)
+pub fn fails(findings: &[Finding], error_on: Severity) -> bool {
+    findings.iter().any(|f| f.severity >= error_on)
+}
+
+/// An unmet devfetch.toml `[system]` requirement; one that couldn't be measured is only a warning
+fn check_system(check: &SystemCheck) -> Finding {
+    let (tool, what) = match check.kind {
+        SystemCheckKind::Module => (format!("{} module", check.required), "the kernel module".to_string()),
+        kind => (kind.display_name().to_string(), check.required.clone()),
+    };
+    match &check.actual {
+        Some(actual) => Finding {
+            severity: Severity::Error,
+            check: "system".to_string(),
+            subject: tool,
+            issue: format!("devfetch.toml requires {}, this machine has {}", what, actual),
+            rationale: None,
+            fix: (check.kind == SystemCheckKind::Module).then(|| format!("sudo modprobe {}", check.required)),
+        },
+        None => Finding {
+            severity: Severity::Warning,
+            check: "system".to_string(),
+            subject: tool,
+            issue: format!("devfetch.toml requires {} but it could not be checked here", what),
+            rationale: None,
+            fix: None,
+        },
+    }
+}
+
+/// Missing commit identity, LFS the project needs but can't get, signing
+/// without a usable key or missing where CI expects it, and CRLF conversion outside Windows
+fn check_git(git: &GitSetup) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for (key, value) in [("user.name", &git.user_name), ("user.email", &git.user_email)] {
+        if value.is_none() {
+            findings.push(Finding {
+                severity: Severity::Warning,
+                check: "git-identity".to_string(),
+                subject: "git".to_string(),
+                issue: format!("{} is not set; commits will fail", key),
+                rationale: None,
+                fix: Some(format!("git config --global {} <value>", key)),
+            });
+        }
+    }
+    if git.repo_uses_lfs && !git.lfs_installed {
+        findings.push(Finding {
+            severity: Severity::Error,
+            check: "git-lfs".to_string(),
+            subject: "git-lfs".to_string(),
+            issue: ".gitattributes stores files in LFS but git-lfs is not installed; checkouts get pointer files".to_string(),
+            rationale: None,
+            fix: None,
+        });
+    } else if git.repo_uses_lfs && !git.lfs_initialized {
+        findings.push(Finding {
+            severity: Severity::Error,
+            check: "git-lfs".to_string(),
+            subject: "git-lfs".to_string(),
+            issue: ".gitattributes stores files in LFS but the LFS filters are not configured".to_string(),
+            rationale: None,
+            fix: Some("git lfs install && git lfs pull".to_string()),
+        });
+    }
+    if git.repo_uses_lfs && git.lfs_installed && git.lfs_initialized {
+        if !git.lfs_hooks {
+            findings.push(Finding {
+                severity: Severity::Warning,
+                check: "git-lfs".to_string(),
+                subject: "git-lfs".to_string(),
+                issue: "the repository's LFS hooks are not installed; pushes won't upload LFS objects".to_string(),
+                rationale: None,
+                fix: Some("git lfs install".to_string()),
+            });
+        }
+        if git.lfs_missing > 0 {
+            findings.push(Finding {
+                severity: Severity::Warning,
+                check: "git-lfs".to_string(),
+                subject: "git-lfs".to_string(),
+                issue: format!("{} LFS files are checked out as pointers", git.lfs_missing),
+                rationale: None,
+                fix: Some("git lfs pull".to_string()),
+            });
+        }
+    }
+    let stale = |state: &str| -> Vec<&str> {
+        git.submodules.iter().filter(|s| s.state == state).map(|s| s.path.as_str()).collect()
+    };
+    for (state, severity, fix) in [
+        ("uninitialized", Severity::Error, "git submodule update --init --recursive"),
+        ("out of date", Severity::Warning, "git submodule update --recursive"),
+        ("conflict", Severity::Error, "resolve the merge conflict in the submodule"),
+    ] {
+        let paths = stale(state);
+        if !paths.is_empty() {
+            findings.push(Finding {
+                severity,
+                check: "git-submodule".to_string(),
+                subject: "git".to_string(),
+                issue: format!("submodules {}: {}", state, paths.join(", ")),
+                rationale: None,
+                fix: Some(fix.to_string()),
+            });
+        }
+    }
+    match (&git.signing, git.signing_key_available) {
+        (Some(format), Some(false)) => findings.push(Finding {
+            severity: Severity::Error,
+            check: "git-signing".to_string(),
+            subject: "git".to_string(),
+            issue: format!("commit.gpgsign is on but the {} signing key is not available; commits will fail", format),
+            rationale: None,
+            fix: Some(match format.as_str() {
+                "ssh" => "git config --global user.signingkey ~/.ssh/id_ed25519.pub".to_string(),
+                _ => "import the key (gpg --import) or set user.signingkey to one in gpg --list-secret-keys".to_string(),
+            }),
+        }),
+        (None, _) if !git.signatures_required_by.is_empty() => findings.push(Finding {
+            severity: Severity::Warning,
+            check: "git-signing".to_string(),
+            subject: "git".to_string(),
+            issue: format!(
+                "{} verifies commit signatures but commit signing is not set up here",
+                git.signatures_required_by.join(", ")
+            ),
+            rationale: None,
+            fix: Some("set user.signingkey and git config commit.gpgsign true".to_string()),
+        }),
+        _ => {}
+    }
+    if git.autocrlf.as_deref() == Some("true") && !cfg!(windows) {
+        findings.push(Finding {
+            severity: Severity::Warning,
+            check: "git-autocrlf".to_string(),
+            subject: "git".to_string(),
+            issue: "core.autocrlf=true converts line endings to CRLF on checkout".to_string(),
+            rationale: None,
+            fix: Some("git config --global core.autocrlf input".to_string()),
+        });
+    }
+    findings
+}
+
+/// SSH remotes that will prompt or fail: unknown host keys, and no key in the
+/// agent or on disk to authenticate with
+fn check_ssh(ssh: &SshReadiness) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    if ssh.hosts.is_empty() {
+        return findings;
+    }
+    if ssh.agent_keys.is_empty() && ssh.key_files.is_empty() && ssh.hosts.iter().all(|h| h.identity_files.is_empty()) {
+        findings.push(Finding {
+            severity: Severity::Error,
+            check: "ssh-key".to_string(),
+            subject: "ssh".to_string(),
+            issue: "the project has SSH remotes but no SSH key is loaded or in ~/.ssh".to_string(),
+            rationale: None,
+            fix: Some("ssh-keygen -t ed25519 and add the public key to the git host".to_string()),
+        });
+    } else if !ssh.agent_running {
+        findings.push(Finding {
+            severity: Severity::Warning,
+            check: "ssh-agent".to_string(),
+            subject: "ssh-agent".to_string(),
+            issue: "not running; passphrase-protected keys will prompt on every fetch".to_string(),
+            rationale: None,
+            fix: Some("eval \"$(ssh-agent -s)\" && ssh-add".to_string()),
+        });
+    } else if ssh.agent_keys.is_empty() {
+        findings.push(Finding {
+            severity: Severity::Warning,
+            check: "ssh-agent".to_string(),
+            subject: "ssh-agent".to_string(),
+            issue: "running but has no keys loaded".to_string(),
+            rationale: None,
+            fix: Some("ssh-add".to_string()),
+        });
+    }
+    for host in ssh.hosts.iter().filter(|h| !h.known) {
+        let scan = if host.port == 22 { host.hostname.clone() } else { format!("-p {} {}", host.port, host.hostname) };
+        findings.push(Finding {
+            severity: Severity::Warning,
+            check: "known-hosts".to_string(),
+            subject: host.hostname.clone(),
+            issue: format!("not in known_hosts; the first fetch from {} will ask to trust its key", host.remotes.join(", ")),
+            rationale: None,
+            fix: Some(format!("ssh-keyscan {} >> ~/.ssh/known_hosts (check the fingerprint first)", scan)),
+        });
+    }
+    findings
+}
+
+/// CA bundles that don't exist or hold no certificates, and proxy variables
+/// whose upper- and lower-case spellings disagree
+fn check_proxy_env(proxy: &ProxyEnv) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for bundle in &proxy.ca_bundles {
+        let issue = match (bundle.exists, bundle.certificates) {
+            (false, _) => format!("{} does not exist; {} will fail TLS connections", bundle.path.display(), bundle.used_by),
+            (true, Some(0)) => format!("{} contains no PEM certificates", bundle.path.display()),
+            _ => continue,
+        };
+        findings.push(Finding {
+            severity: if bundle.exists { Severity::Warning } else { Severity::Error },
+            check: "ca-bundle".to_string(),
+            subject: bundle.var.clone(),
+            issue,
+            rationale: None,
+            fix: Some(format!("unset {} or point it at the corporate CA bundle", bundle.var)),
+        });
+    }
+    for (lower, upper) in crate::core::proxy::case_conflicts(proxy) {
+        findings.push(Finding {
+            severity: Severity::Warning,
+            check: "proxy-case".to_string(),
+            subject: upper.to_string(),
+            issue: format!("{} and {} are set to different proxies; tools disagree on which they read", lower, upper),
+            rationale: None,
+            fix: Some(format!("set {} and {} to the same value", lower, upper)),
+        });
+    }
+    findings
+}
+
+/// The command a toolchain-manager package name installs, e.g. nodejs -> node
+pub fn binary_name(tool: &str) -> &str {
+    BINARY_ALIASES
+        .iter()
+        .find(|(pkg, _)| *pkg == tool)
+        .map(|(_, bin)| *bin)
+        .unwrap_or(tool)
+}
+
+/// Suggested command to install a manager's pins
+fn activate_hint(manager: &str) -> Option<String> {
+    match manager {
+        "hermit" => Some(". bin/activate-hermit".to_string()),
+        "proto" => Some("proto use".to_string()),
+        _ => None,
+    }
+}
+
+fn check_pin(pin: &ToolPin, tools: &[Tool]) -> Option<Finding> {
+    let binary = binary_name(&pin.tool);
+    let found = tools.iter().find(|t| t.name == binary).or_else(|| tools.iter().find(|t| t.provides_command(binary)));
+    let Some(tool) = found else {
+        return Some(Finding {
+            severity: Severity::Error,
+            check: "pin".to_string(),
+            subject: pin.tool.clone(),
+            issue: format!("pinned to {} by {} ({}) but not installed", pin.version, pin.manager, pin.source),
+            rationale: None,
+            fix: activate_hint(&pin.manager),
+        });
+    };
+
+    let actual = tool.version.as_deref()?;
+    if version_matches(&pin.version, actual) {
+        return None;
+    }
+    Some(Finding {
+        severity: Severity::Warning,
+        check: "pin".to_string(),
+        subject: pin.tool.clone(),
+        issue: format!("pinned to {} by {} but {} is v{}", pin.version, pin.manager, tool.path.display(), actual),
+        rationale: None,
+        fix: activate_hint(&pin.manager),
+    })
+}
+
+/// Installed rustc older than the workspace's `rust-version`
+fn check_msrv(result: &ScanResult) -> Option<Finding> {
+    let rust = result.project_info.as_ref()?.ecosystems.get("Rust")?;
+    let EcosystemDetails::Rust(details) = &rust.details else {
+        return None;
+    };
+    let msrv = details.msrv.as_deref()?;
+    let installed = rust
+        .tool_version
+        .as_deref()
+        .or_else(|| result.global_tools.iter().find(|t| t.name == "rustc").and_then(|t| t.version.as_deref()))?;
+
+    compare_versions(installed, msrv).is_lt().then(|| Finding {
+        severity: Severity::Error,
+        check: "msrv".to_string(),
+        subject: "rustc".to_string(),
+        issue: format!("v{} is older than the workspace MSRV (rust-version = {})", installed, msrv),
+        rationale: None,
+        fix: Some(format!("rustup update stable  # or: rustup toolchain install {}", msrv)),
+    })
+}
+
+/// Package managers the project's lock files or `packageManager` pin expect
+/// but that are missing, at another version, or contradicted by another lock file
+fn check_package_managers(result: &ScanResult) -> Vec<Finding> {
+    let Some(project) = &result.project_info else {
+        return Vec::new();
+    };
+    let mut findings = Vec::new();
+    for manager in project.ecosystems.values().filter_map(|e| e.details.package_manager()) {
+        let fix = corepack_fix(manager);
+        match (&manager.version, &manager.pinned) {
+            (None, pinned) => findings.push(Finding {
+                severity: Severity::Warning,
+                check: "package-manager".to_string(),
+                subject: manager.name.clone(),
+                issue: match (pinned, &manager.corepack) {
+                    (Some(v), Some(corepack)) if corepack.enabled => {
+                        format!("pinned to {} by {} but corepack has not downloaded it yet", v, manager.source)
+                    }
+                    (Some(v), _) => format!("pinned to {} by {} but not installed", v, manager.source),
+                    (None, _) => format!("the project uses {} ({}) but it is not installed", manager.name, manager.source),
+                },
+                rationale: None,
+                fix,
+            }),
+            (Some(active), Some(pinned)) if !version_matches(pinned, active) => findings.push(Finding {
+                severity: Severity::Warning,
+                check: "package-manager".to_string(),
+                subject: manager.name.clone(),
+                issue: match &manager.corepack {
+                    Some(corepack) if !corepack.enabled => {
+                        format!("pinned to {} by {} but corepack is not enabled, so v{} runs", pinned, manager.source, active)
+                    }
+                    _ => format!("pinned to {} by {} but v{} is active", pinned, manager.source, active),
+                },
+                rationale: None,
+                fix,
+            }),
+            _ => {}
+        }
+        if !manager.conflicts.is_empty() {
+            findings.push(Finding {
+                severity: Severity::Warning,
+                check: "package-manager".to_string(),
+                subject: manager.name.clone(),
+                issue: format!(
+                    "the project expects {} ({}) but also has {}",
+                    manager.name,
+                    manager.source,
+                    manager.conflicts.join(", ")
+                ),
+                rationale: None,
+                fix: Some(format!("remove the stale lock file(s) and run {} install", manager.name)),
+            });
+        }
+    }
+    findings
+}
+
+/// How to get corepack to provide the pinned yarn/pnpm
+fn corepack_fix(manager: &PackageManager) -> Option<String> {
+    let corepack = manager.corepack.as_ref()?;
+    if !corepack.installed {
+        return Some("npm install -g corepack && corepack enable".to_string());
+    }
+    Some(if corepack.enabled { "corepack install" } else { "corepack enable" }.to_string())
+}
+
+/// Installed JDK older than the Java release a pom.xml or Gradle toolchain targets,
+/// or outside maven-enforcer's requireJavaVersion range; JAVA_HOME is checked too,
+/// since Maven and Gradle prefer it over the java on PATH
+fn check_java_target(result: &ScanResult) -> Vec<Finding> {
+    let Some(jvm) = result.project_info.as_ref().and_then(|p| {
+        p.ecosystems.values().find_map(|e| match &e.details {
+            EcosystemDetails::Jvm(jvm) if jvm.java_version.is_some() || jvm.java_range.is_some() => Some(jvm),
+            _ => None,
+        })
+    }) else {
+        return Vec::new();
+    };
+    let (target, range) = (jvm.java_version.as_deref(), jvm.java_range.as_deref());
+    let wanted = format!("Java {}", range.or(target).unwrap_or_default());
+    let fix = match jvm.jdks.iter().find(|c| c.satisfies) {
+        Some(candidate) => format!("export JAVA_HOME={}", candidate.jdk.home.display()),
+        None => format!("install JDK {} or newer", target.or(range).unwrap_or_default()),
+    };
+
+    let mut findings = Vec::new();
+    if let Some(java_home) = jvm.jdks.iter().find(|c| c.jdk.source == "JAVA_HOME" && !c.satisfies) {
+        findings.push(Finding {
+            severity: Severity::Error,
+            check: "java-target".to_string(),
+            subject: "JAVA_HOME".to_string(),
+            issue: format!("JDK {} cannot build for {} ({})", java_home.jdk.version, wanted, jvm.source),
+            rationale: None,
+            fix: Some(fix.clone()),
+        });
+    }
+    match result.global_tools.iter().find(|t| t.name == "javac" || t.name == "java") {
+        Some(java) => {
+            if let Some(installed) = java.version.as_deref().filter(|v| !jdk::satisfies(v, target, range)) {
+                findings.push(Finding {
+                    severity: Severity::Error,
+                    check: "java-target".to_string(),
+                    subject: java.name.clone(),
+                    issue: format!("v{} cannot build for {} ({})", installed, wanted, jvm.source),
+                    rationale: None,
+                    fix: Some(fix),
+                });
+            }
+        }
+        // Nothing on PATH to complain about unless no installed JDK would do either
+        None if !jvm.jdks.is_empty() && jvm.jdks.iter().all(|c| !c.satisfies) => findings.push(Finding {
+            severity: Severity::Error,
+            check: "java-target".to_string(),
+            subject: "java".to_string(),
+            issue: format!("no installed JDK satisfies {} ({})", wanted, jvm.source),
+            rationale: None,
+            fix: Some(fix),
+        }),
+        None => {}
+    }
+    findings
+}
+
+/// PHP outside composer.json's constraint, and required extensions `php -m` doesn't list
+fn check_php(result: &ScanResult) -> Vec<Finding> {
+    let Some(php) = result.project_info.as_ref().and_then(|p| p.ecosystems.get("PHP")) else {
+        return Vec::new();
+    };
+    let EcosystemDetails::Php(requirements) = &php.details else {
+        return Vec::new();
+    };
+
+    let mut findings = Vec::new();
+    if let (Some(false), Some(constraint), Some(installed)) =
+        (requirements.php_satisfied, &requirements.php_constraint, &php.tool_version)
+    {
+        findings.push(Finding {
+            severity: Severity::Error,
+            check: "php".to_string(),
+            subject: "php".to_string(),
+            issue: format!("v{} does not satisfy composer.json's \"php\": \"{}\"", installed, constraint),
+            rationale: None,
+            fix: None,
+        });
+    }
+    for ext in requirements.extensions.iter().filter(|e| e.loaded == Some(false)) {
+        findings.push(Finding {
+            severity: Severity::Error,
+            check: "php-extension".to_string(),
+            subject: format!("ext-{}", ext.name),
+            issue: format!("required by {} but not loaded by php", ext.required_by.join(", ")),
+            rationale: None,
+            fix: Some(format!("install the php-{} package (or pecl install {}) and enable it in php.ini", ext.name, ext.name)),
+        });
+    }
+    findings
+}
+
+/// No C compiler, cmake older than the project needs, preset generators or
+/// toolchain files that are missing, and libraries CMakeLists.txt asks for
+/// that pkg-config and the CMake package directories don't have
+fn check_cxx(result: &ScanResult) -> Vec<Finding> {
+    let Some(project) = result.project_info.as_ref() else {
+        return Vec::new();
+    };
+    let Some(cxx) = project.ecosystems.values().find_map(|e| match &e.details {
+        EcosystemDetails::Cxx(cxx) => Some(cxx),
+        _ => None,
+    }) else {
+        return Vec::new();
+    };
+
+    let mut findings = Vec::new();
+    if let Some(required) = &cxx.cmake_required {
+        let installed = project
+            .ecosystems
+            .get("C/C++ (CMake)")
+            .and_then(|e| e.tool_version.clone())
+            .or_else(|| result.global_tools.iter().find(|t| t.name == "cmake").and_then(|t| t.version.clone()));
+        let issue = match installed {
+            None => Some(format!("not installed (the project requires {} or newer)", required)),
+            Some(v) if compare_versions(&v, required).is_lt() => {
+                Some(format!("v{} is older than the {} the project requires", v, required))
+            }
+            Some(_) => None,
+        };
+        findings.extend(issue.map(|issue| Finding {
+            severity: Severity::Error,
+            check: "cmake".to_string(),
+            subject: "cmake".to_string(),
+            issue,
+            rationale: None,
+            fix: Some(format!("install cmake {} or newer (e.g. pip install cmake)", required)),
+        }));
+    }
+    let mut missing_generators: Vec<&str> = Vec::new();
+    for preset in &cxx.presets {
+        if let (Some(false), Some(tool), Some(generator)) = (preset.generator_installed, &preset.generator_tool, &preset.generator) {
+            if !missing_generators.contains(&tool.as_str()) {
+                missing_generators.push(tool);
+                findings.push(Finding {
+                    severity: Severity::Warning,
+                    check: "cmake-generator".to_string(),
+                    subject: tool.clone(),
+                    issue: format!("needed by the \"{}\" generator of CMake preset {} but not installed", generator, preset.name),
+                    rationale: None,
+                    fix: None,
+                });
+            }
+        }
+        if let (Some(false), Some(toolchain)) = (preset.toolchain_exists, &preset.toolchain_file) {
+            findings.push(Finding {
+                severity: Severity::Warning,
+                check: "cmake-toolchain".to_string(),
+                subject: "cmake".to_string(),
+                issue: format!("toolchain file {} of CMake preset {} does not exist", toolchain, preset.name),
+                rationale: None,
+                fix: None,
+            });
+        }
+    }
+    if cxx.compilers.is_empty() {
+        findings.push(Finding {
+            severity: Severity::Error,
+            check: "compiler".to_string(),
+            subject: "cc".to_string(),
+            issue: "no C/C++ compiler (gcc, clang or $CC) on PATH".to_string(),
+            rationale: None,
+            fix: Some("install gcc or clang".to_string()),
+        });
+    }
+    for library in cxx.libraries.iter().filter(|l| l.found == Some(false)) {
+        findings.push(Finding {
+            severity: Severity::Warning,
+            check: "cmake-library".to_string(),
+            subject: library.name.clone(),
+            issue: format!("{}() in CMakeLists.txt, but not found", library.via),
+            rationale: None,
+            fix: Some(if library.name == "PkgConfig" {
+                "install pkg-config".to_string()
+            } else {
+                format!("install the {} development package", library.name)
+            }),
+        });
+    }
+    findings
+}
+
+/// Ruby outside the Gemfile's requirement, and bundler missing or a major
+/// version away from Gemfile.lock's BUNDLED WITH
+fn check_ruby(result: &ScanResult) -> Vec<Finding> {
+    let Some(ruby) = result.project_info.as_ref().and_then(|p| p.ecosystems.get("Ruby")) else {
+        return Vec::new();
+    };
+    let EcosystemDetails::Ruby(requirements) = &ruby.details else {
+        return Vec::new();
+    };
+
+    let mut findings = Vec::new();
+    if let (Some(false), Some(constraint), Some(source), Some(installed)) = (
+        requirements.ruby_satisfied,
+        &requirements.ruby_constraint,
+        &requirements.ruby_source,
+        &ruby.tool_version,
+    ) {
+        findings.push(Finding {
+            severity: Severity::Error,
+            check: "ruby".to_string(),
+            subject: "ruby".to_string(),
+            issue: format!("v{} does not satisfy {} ({})", installed, constraint, source),
+            rationale: None,
+            // Only an exact version can be handed to a version manager
+            fix: constraint
+                .chars()
+                .all(|c| c.is_ascii_digit() || c == '.')
+                .then(|| format!("rbenv install {0}  # or: asdf install ruby {0}", constraint)),
+        });
+    }
+    if let Some(locked) = &requirements.bundler_locked {
+        let major = |v: &str| v.split('.').next().map(String::from);
+        let issue = match &requirements.bundler_installed {
+            None => Some(format!("Gemfile.lock was bundled with {} but bundler is not installed", locked)),
+            Some(installed) if major(installed) != major(locked) => {
+                Some(format!("v{} installed but Gemfile.lock was bundled with {}", installed, locked))
+            }
+            Some(_) => None,
+        };
+        findings.extend(issue.map(|issue| Finding {
+            severity: Severity::Warning,
+            check: "bundler".to_string(),
+            subject: "bundler".to_string(),
+            issue,
+            rationale: None,
+            fix: Some(format!("gem install bundler -v {}", locked)),
+        }));
+    }
+    findings
+}
+
+/// A version requirement from a project file that the installed tool misses
+/// or that couldn't be checked
+fn requirement_finding(check: &RequirementCheck) -> Option<Finding> {
+    let requirement = &check.requirement;
+    let wants = format!("{} asks for {}", requirement.source, requirement.constraint);
+    let (severity, issue) = match (check.status, &check.path, &check.installed) {
+        (RequirementStatus::Pass, _, _) => return None,
+        (_, None, _) => (Severity::Error, format!("{} but it is not installed", wants)),
+        (_, Some(path), None) => (Severity::Warning, format!("{} but {} didn't report a version", wants, path.display())),
+        (RequirementStatus::Fail, Some(path), Some(installed)) => {
+            (Severity::Error, format!("{} but {} is v{}", wants, path.display(), installed))
+        }
+        (RequirementStatus::Warn, Some(_), Some(installed)) => {
+            (Severity::Warning, format!("{}, which can't be checked against v{}", wants, installed))
+        }
+    };
+    let fix = match requirement.source.as_str() {
+        ".nvmrc" => Some("nvm install".to_string()),
+        ".node-version" => Some("fnm install".to_string()),
+        ".tool-versions" => Some("asdf install".to_string()),
+        "rust-toolchain.toml" | "rust-toolchain" => Some(format!("rustup toolchain install {}", requirement.constraint)),
+        "pyproject.toml" => Some(format!("uv python install '{}'", requirement.constraint)),
+        _ => None,
+    };
+    Some(Finding { severity, check: "requirement".to_string(), subject: requirement.tool.clone(), issue, rationale: None, fix })
+}
+
+/// Orchestrator missing, or installed at a different major version than the repo pins
+fn check_orchestrator(orch: &MonorepoOrchestrator) -> Option<Finding> {
+    // workspace:* and workspace:^ point at a package in the repo, not a release
+    let pinned = orch.pinned_version.as_deref().filter(|spec| !spec.starts_with("workspace:"))?;
+    let major = |v: &str| v.trim_start_matches(['~', '^', '=', 'v']).split('.').next().map(String::from);
+    let (severity, issue) = match &orch.installed_version {
+        None => (Severity::Error, format!("pinned to {} by {} but not installed", pinned, orch.config)),
+        Some(installed) if major(pinned) != major(installed) => (
+            Severity::Warning,
+            format!("pinned to {} by {} but v{} is installed", pinned, orch.config, installed),
+        ),
+        Some(_) => return None,
+    };
+    Some(Finding {
+        severity,
+        check: "orchestrator".to_string(),
+        subject: orch.name.clone(),
+        issue,
+        rationale: None,
+        fix: Some(if orch.name == "rush" { "rush install" } else { "npm install" }.to_string()),
+    })
+}
+
+/// Compare dotted numeric versions; missing components count as zero
+pub fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let parse = |v: &str| -> Vec<u64> {
+        v.trim_start_matches('v')
+            .split(['.', '-', '+'])
+            .map_while(|part| part.parse().ok())
+            .collect()
+    };
+    let (a, b) = (parse(a), parse(b));
+    (0..a.len().max(b.len()))
+        .map(|i| a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)))
+        .find(|o| o.is_ne())
+        .unwrap_or(std::cmp::Ordering::Equal)
+}
+
+/// Whether an installed version satisfies a pin. Pins may be partial ("20",
+/// "~20.11") or channels ("lts", "stable") that any version satisfies.
+pub fn version_matches(pin: &str, actual: &str) -> bool {
+    let pin = pin.trim_start_matches(['~', '^', '=', 'v']);
+    if !pin.starts_with(|c: char| c.is_ascii_digit()) {
+        return true;
+    }
+    let actual = actual.trim_start_matches('v');
+    let mut actual_parts = actual.split(['.', '-', '+']);
+    pin.split('.').all(|part| actual_parts.next() == Some(part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ToolCategory;
+
+    #[test]
+    fn test_version_matches() {
+        assert!(version_matches("20", "20.11.1"));
+        assert!(version_matches("~20.11", "20.11.1"));
+        assert!(version_matches("1.21.0", "1.21.0"));
+        assert!(!version_matches("1.21.0", "1.22.0"));
+        assert!(!version_matches("2", "20.1.0"));
+        assert!(version_matches("lts", "18.19.0"));
+
+        assert!(compare_versions("1.70.0", "1.74").is_lt());
+        assert!(compare_versions("1.74", "1.74.0").is_eq());
+        assert!(compare_versions("1.100.0", "1.95.0").is_gt());
+    }
+
+    #[test]
+    fn test_check_orchestrator() {
+        let orch = |pinned: &str, installed: Option<&str>| MonorepoOrchestrator {
+            name: "turbo".to_string(),
+            config: "turbo.json".to_string(),
+            pinned_version: Some(pinned.to_string()),
+            installed_version: installed.map(String::from),
+            packages: 2,
+        };
+        assert!(check_orchestrator(&orch("^1.13.0", Some("1.13.2"))).is_none());
+        assert_eq!(check_orchestrator(&orch("^1.13.0", Some("2.0.1"))).unwrap().severity, Severity::Warning);
+        assert!(check_orchestrator(&orch("workspace:*", Some("2.0.1"))).is_none());
+        assert!(check_orchestrator(&orch("workspace:^", None)).is_none());
+    }
+
+    #[test]
+    fn test_diagnose_pins() {
+        let mut result = ScanResult::new();
+        result.global_tools.push(Tool {
+            version: Some("18.19.0".to_string()),
+            category: ToolCategory::LanguageToolchain,
+            ..Tool::new("node", "/usr/bin/node")
+        });
+        for (tool, version) in [("node", "20.11.0"), ("go", "1.21.0")] {
+            result.pinned_tools.push(ToolPin {
+                tool: tool.to_string(),
+                version: version.to_string(),
+                manager: "proto".to_string(),
+                source: ".prototools".to_string(),
+            });
+        }
+
+        let findings = diagnose(&result);
+        assert_eq!(findings.len(), 2);
+        assert_eq!((findings[0].subject.as_str(), findings[0].severity), ("go", Severity::Error));
+        assert_eq!((findings[1].subject.as_str(), findings[1].severity), ("node", Severity::Warning));
+        assert!(fails(&findings, Severity::Error));
+        assert!(fails(&findings[1..], Severity::Warning) && !fails(&findings[1..], Severity::Error));
+        // The JSON keeps the field name doctor has always written
+        assert_eq!(serde_json::to_value(&findings[0]).unwrap()["tool"], "go");
+    }
+}
@@ -0,0 +1,153 @@
+use crate::core::{exec, paths, shell};
+use crate::types::DotfilesInfo;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Home-relative locations of bare-repo dotfiles setups
+static BARE_REPO_DIRS: &[&str] = &[".dotfiles", ".cfg", ".dotfiles.git", ".dots"];
+
+/// Home-relative directories commonly used as GNU stow packages
+static STOW_DIRS: &[&str] = &["dotfiles", ".dotfiles"];
+
+/// Detect the dotfiles manager in use and which devfetch-relevant files it manages
+pub fn detect_dotfiles() -> Option<DotfilesInfo> {
+    let home = paths::home_dir()?;
+
+    let (manager, repo, tracked) = detect_chezmoi(&home)
+        .or_else(|| detect_yadm(&home))
+        .or_else(|| detect_bare_repo(&home))
+        .or_else(|| detect_stow(&home))?;
+
+    let last_sync = last_commit_date(&repo);
+    let managed_files = relevant_files()
+        .into_iter()
+        .filter(|rel| tracked.iter().any(|t| t == rel))
+        .collect();
+
+    Some(DotfilesInfo {
+        manager: manager.to_string(),
+        repo,
+        last_sync,
+        managed_files,
+    })
+}
+
+/// Shell rc files plus version pin files that influence scan results
+fn relevant_files() -> Vec<String> {
+    let mut files: Vec<String> = shell::RC_FILES.iter().map(|f| f.to_string()).collect();
+    files.push(".tool-versions".to_string());
+    files.push(".config/mise/config.toml".to_string());
+    files
+}
+
+type Detection = (&'static str, PathBuf, Vec<String>);
+
+fn detect_chezmoi(home: &Path) -> Option<Detection> {
+    let source = exec::execute_for_output("chezmoi", &["source-path"])
+        .map(|s| PathBuf::from(s.trim()))
+        .or_else(|| Some(home.join(".local/share/chezmoi")))
+        .filter(|p| p.is_dir())?;
+
+    let tracked = exec::execute_for_output("chezmoi", &["managed", "--include=files"])
+        .map(|out| out.lines().map(|l| l.trim().to_string()).collect())
+        .unwrap_or_default();
+
+    Some(("chezmoi", source, tracked))
+}
+
+fn detect_yadm(home: &Path) -> Option<Detection> {
+    let repo = [".local/share/yadm/repo.git", ".config/yadm/repo.git"]
+        .iter()
+        .map(|rel| home.join(rel))
+        .find(|p| p.is_dir())?;
+
+    let tracked = git_tracked_files(&repo, home);
+    Some(("yadm", repo, tracked))
+}
+
+fn detect_bare_repo(home: &Path) -> Option<Detection> {
+    let repo = BARE_REPO_DIRS
+        .iter()
+        .map(|rel| home.join(rel))
+        .find(|p| is_bare_git_repo(p))?;
+
+    let tracked = git_tracked_files(&repo, home);
+    Some(("bare git repo", repo, tracked))
+}
+
+/// Stow leaves no metadata, so look for rc files symlinked into a stow directory
+fn detect_stow(home: &Path) -> Option<Detection> {
+    let stow_dir = STOW_DIRS
+        .iter()
+        .map(|rel| home.join(rel))
+        .find(|p| p.is_dir() && !is_bare_git_repo(p))?;
+    let stow_dir = fs::canonicalize(&stow_dir).ok()?;
+
+    let tracked: Vec<String> = relevant_files()
+        .into_iter()
+        .filter(|rel| {
+            let file = home.join(rel);
+            fs::symlink_metadata(&file).map(|m| m.file_type().is_symlink()).unwrap_or(false)
+                && fs::canonicalize(&file).map(|t| t.starts_with(&stow_dir)).unwrap_or(false)
+        })
+        .collect();
+
+    if tracked.is_empty() {
+        return None;
+    }
+
+    Some(("GNU stow", stow_dir, tracked))
+}
+
+/// A bare repository has HEAD and objects at its top level and no work tree
+fn is_bare_git_repo(path: &Path) -> bool {
+    path.join("HEAD").is_file() && path.join("objects").is_dir() && !path.join(".git").exists()
+}
+
+fn git_tracked_files(git_dir: &Path, work_tree: &Path) -> Vec<String> {
+    let git_dir_arg = format!("--git-dir={}", git_dir.display());
+    let work_tree_arg = format!("--work-tree={}", work_tree.display());
+
+    exec::execute_for_output("git", &[&git_dir_arg, &work_tree_arg, "ls-files"])
+        .map(|out| out.lines().map(|l| l.trim().to_string()).collect())
+        .unwrap_or_default()
+}
+
+/// Date of the newest commit in the dotfiles repo, as a proxy for last sync
+fn last_commit_date(repo: &Path) -> Option<String> {
+    let git_dir = if repo.join(".git").exists() {
+        repo.join(".git")
+    } else {
+        repo.to_path_buf()
+    };
+    let git_dir_arg = format!("--git-dir={}", git_dir.display());
+
+    exec::execute_command("git", &[&git_dir_arg, "log", "-1", "--format=%cI"])
+        .ok()
+        .filter(|out| out.status.success())
+        .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_is_bare_git_repo() {
+        let dir = TempDir::new("bare");
+        fs::create_dir_all(dir.join("objects")).unwrap();
+        assert!(!is_bare_git_repo(&dir));
+
+        fs::write(dir.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+        assert!(is_bare_git_repo(&dir));
+    }
+
+    #[test]
+    fn test_relevant_files() {
+        let files = relevant_files();
+        assert!(files.iter().any(|f| f == ".zshrc"));
+        assert!(files.iter().any(|f| f == ".tool-versions"));
+    }
+}
@@ -1,22 +1,75 @@
 use anyhow::Result;
+use std::path::Path;
 use std::process::{Command, Output, Stdio};
 use std::time::{Duration, Instant};
 
 /// Maximum time to wait for a command to execute (in milliseconds)
-const COMMAND_TIMEOUT_MS: u64 = 1500;
+pub const COMMAND_TIMEOUT_MS: u64 = 1500;
+
+/// Error for a command killed after running past its timeout
+#[derive(Debug)]
+pub struct TimedOut(pub String);
+
+impl std::fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Command timed out: {}", self.0)
+    }
+}
+
+impl std::error::Error for TimedOut {}
 
 /// Safely execute a command with timeout and error handling.
 /// Properly kills the child process if it exceeds the timeout.
 pub fn execute_command(program: &str, args: &[&str]) -> Result<Output> {
-    let mut child = Command::new(program)
+    execute_command_with_timeout(program, args, Duration::from_millis(COMMAND_TIMEOUT_MS))
+}
+
+/// Like `execute_command`, for the few commands that legitimately need longer
+/// (e.g. starting a login shell with a heavy rc file)
+pub fn execute_command_with_timeout(program: &str, args: &[&str], timeout: Duration) -> Result<Output> {
+    wait_with_timeout(Command::new(program), program, args, timeout)
+}
+
+/// Like `execute_command`, run from `dir` (for tools that read the project
+/// in their working directory, e.g. `cargo metadata`)
+pub fn execute_command_in(dir: &Path, program: &str, args: &[&str]) -> Result<Output> {
+    let mut command = Command::new(program);
+    command.current_dir(dir);
+    wait_with_timeout(command, program, args, Duration::from_millis(COMMAND_TIMEOUT_MS))
+}
+
+/// Like `execute_command_with_timeout`, feeding `input` to the command's stdin
+/// (for secrets such as tokens and webhook URLs that must stay out of the process list)
+pub fn execute_command_with_input(program: &str, args: &[&str], input: &[u8], timeout: Duration) -> Result<Output> {
+    run_with_timeout(Command::new(program), program, args, Some(input), timeout)
+}
+
+fn wait_with_timeout(command: Command, program: &str, args: &[&str], timeout: Duration) -> Result<Output> {
+    run_with_timeout(command, program, args, None, timeout)
+}
+
+fn run_with_timeout(mut command: Command, program: &str, args: &[&str], input: Option<&[u8]>, timeout: Duration) -> Result<Output> {
+    // Untranslated messages are what the version and output parsers expect
+    let mut child = command
+        .env("LC_ALL", "C")
+        .env("LANG", "C")
         .args(args)
-        .stdin(Stdio::null())
+        .stdin(if input.is_some() { Stdio::piped() } else { Stdio::null() })
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()?;
 
+    // Written from a thread so a child that fills its output pipes first can't deadlock us;
+    // dropping the handle closes stdin
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        let input = input.to_vec();
+        std::thread::spawn(move || {
+            use std::io::Write;
+            let _ = stdin.write_all(&input);
+        });
+    }
+
     let start = Instant::now();
-    let timeout = Duration::from_millis(COMMAND_TIMEOUT_MS);
 
     loop {
         match child.try_wait() {
@@ -30,7 +83,7 @@ pub fn execute_command(program: &str, args: &[&str]) -> Result<Output> {
                     // Kill the child process to avoid zombies
                     let _ = child.kill();
                     let _ = child.wait(); // Reap the process
-                    return Err(anyhow::anyhow!("Command timed out: {}", program));
+                    return Err(TimedOut(program.to_string()).into());
                 }
                 std::thread::sleep(Duration::from_millis(25));
             }
@@ -43,10 +96,65 @@ pub fn execute_command(program: &str, args: &[&str]) -> Result<Output> {
 /// Many tools (e.g. `java -version`) write version info to stderr,
 /// so we capture both streams.
 pub fn execute_for_output(program: &str, args: &[&str]) -> Option<String> {
-    match execute_command(program, args) {
+    output_text(execute_command(program, args))
+}
+
+/// `execute_for_output` with its own timeout, telling a timeout apart from a
+/// failed or silent command
+pub fn execute_for_output_or_timeout(program: &str, args: &[&str], timeout: Duration) -> std::result::Result<Option<String>, TimedOut> {
+    match execute_command_with_timeout(program, args, timeout) {
+        Err(e) => match e.downcast::<TimedOut>() {
+            Ok(timed_out) => Err(timed_out),
+            Err(e) => Ok(output_text(Err(e))),
+        },
+        ok => Ok(output_text(ok)),
+    }
+}
+
+/// `execute_for_output`, run from `dir`
+pub fn execute_for_output_in(dir: &Path, program: &str, args: &[&str]) -> Option<String> {
+    output_text(execute_command_in(dir, program, args))
+}
+
+/// `execute_for_output_in` with its own timeout
+pub fn execute_for_output_in_with_timeout(dir: &Path, program: &str, args: &[&str], timeout: Duration) -> Option<String> {
+    let mut command = Command::new(program);
+    command.current_dir(dir);
+    output_text(wait_with_timeout(command, program, args, timeout))
+}
+
+/// `timeout`, or what is left until `deadline` (--budget) when that is sooner;
+/// None once the deadline has passed
+pub fn budgeted(timeout: Duration, deadline: Option<Instant>) -> Option<Duration> {
+    match deadline {
+        None => Some(timeout),
+        Some(deadline) => deadline.checked_duration_since(Instant::now()).filter(|left| !left.is_zero()).map(|left| left.min(timeout)),
+    }
+}
+
+/// Command output as text: UTF-8, UTF-16 (with a BOM, as some Windows tools
+/// write), else a single-byte code page such as CP1252, whose ASCII digits
+/// and letters survive being read as Latin-1
+pub fn decode_output(bytes: &[u8]) -> String {
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return text.to_string();
+    }
+    let utf16 = |bytes: &[u8], from: fn([u8; 2]) -> u16| {
+        let units: Vec<u16> = bytes.chunks_exact(2).map(|pair| from([pair[0], pair[1]])).collect();
+        String::from_utf16_lossy(&units)
+    };
+    match bytes {
+        [0xFF, 0xFE, rest @ ..] => utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => utf16(rest, u16::from_be_bytes),
+        _ => bytes.iter().map(|&b| b as char).collect(),
+    }
+}
+
+fn output_text(result: Result<Output>) -> Option<String> {
+    match result {
         Ok(output) => {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = decode_output(&output.stdout);
+            let stderr = decode_output(&output.stderr);
 
             if output.status.success() {
                 // Prefer stdout if it has content, otherwise fall back to stderr
@@ -76,6 +184,21 @@ pub fn command_exists(program: &str) -> bool {
     which::which(program).is_ok()
 }
 
+/// A duration such as "2s", "500ms", "1.5s" or "1m"; bare numbers are seconds
+pub fn parse_duration(text: &str) -> std::result::Result<Duration, String> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let value: f64 = number.parse().map_err(|_| format!("invalid duration {:?}", text))?;
+    let seconds = match unit {
+        "ms" => value / 1000.0,
+        "" | "s" => value,
+        "m" => value * 60.0,
+        _ => return Err(format!("unknown unit {:?} in {:?}; use ms, s or m", unit, text)),
+    };
+    Duration::try_from_secs_f64(seconds).map_err(|_| format!("duration {:?} is out of range", text))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -91,4 +214,31 @@ mod tests {
         let result = execute_command("echo", &["test"]);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_decode_output() {
+        assert_eq!(decode_output("versión 3.11.0".as_bytes()), "versión 3.11.0");
+        // CP1252/Latin-1 "versión"
+        assert_eq!(decode_output(b"versi\xf3n 3.11.0"), "versión 3.11.0");
+        let utf16: Vec<u8> = [0xFF, 0xFE].into_iter().chain("dotnet 8.0.1".encode_utf16().flat_map(|u| u.to_le_bytes())).collect();
+        assert_eq!(decode_output(&utf16), "dotnet 8.0.1");
+    }
+
+    #[test]
+    fn test_timeout() {
+        let timed_out = execute_command_with_timeout("sleep", &["5"], Duration::from_millis(100));
+        assert!(timed_out.unwrap_err().downcast_ref::<TimedOut>().is_some());
+        assert!(matches!(execute_for_output_or_timeout("false", &[], Duration::from_millis(COMMAND_TIMEOUT_MS)), Ok(None)));
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("2s"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("1.5"), Ok(Duration::from_millis(1500)));
+        assert!(parse_duration("2h").is_err() && parse_duration("s").is_err());
+        assert!(parse_duration(&"9".repeat(400)).is_err());
+        assert_eq!(budgeted(Duration::from_secs(1), Some(Instant::now())), None);
+        assert_eq!(budgeted(Duration::from_secs(1), None), Some(Duration::from_secs(1)));
+    }
 }
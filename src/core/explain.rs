@@ -0,0 +1,54 @@
+use crate::core::cache::ProbeCache;
+use crate::core::{classify, paths, probe};
+use crate::types::{ToolCategory, ToolExplanation};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Find `name` on PATH (or take it as a path) and work out its category, scope
+/// and version the way a scan does, recording why at each step
+pub fn explain_tool(
+    name: &str,
+    project_root: Option<&Path>,
+    timeout: Duration,
+    overrides: &[(glob::Pattern, ToolCategory)],
+) -> Result<ToolExplanation> {
+    let path = if name.contains(std::path::MAIN_SEPARATOR) {
+        PathBuf::from(name)
+    } else {
+        which::which(name).with_context(|| format!("{} is not on PATH", name))?
+    };
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| name.to_string());
+    let (category, category_reason) = classify::explain_tool(&name, &path, overrides);
+    let project_root = project_root.map(classify::canonical_root);
+    let (scope, scope_reason) = classify::explain_scope(&path, project_root.as_deref(), paths::home_dir().as_deref());
+    Ok(ToolExplanation {
+        cache: ProbeCache::load().describe(&path),
+        probes: probe::explain_probe(&path.to_string_lossy(), timeout),
+        name,
+        path,
+        category,
+        category_reason,
+        scope,
+        scope_reason,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::InstallScope;
+
+    #[test]
+    fn test_explain_tool() {
+        let explanation = explain_tool("sh", None, Duration::from_millis(1500), &[]).unwrap();
+        assert_eq!(explanation.name, "sh");
+        assert!(!explanation.probes.is_empty());
+        assert!(explain_tool("devfetch-no-such-tool", None, Duration::from_millis(1500), &[]).is_err());
+
+        let (category, reason) = classify::explain_tool("pyright", Path::new("/home/u/.pyenv/shims/pyright"), &[]);
+        assert_eq!((category, reason.as_str()), (ToolCategory::LanguageToolchain, "path contains \".pyenv\""));
+        let (scope, reason) = classify::explain_scope(Path::new("/app/node_modules/.bin/tsc"), None, None);
+        assert_eq!((scope, reason.as_str()), (InstallScope::Project, "path contains \"/node_modules/.bin/\""));
+    }
+}
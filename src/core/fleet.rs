@@ -0,0 +1,217 @@
+use crate::core::snapshot;
+use crate::types::{FleetMachine, FleetOutlier, FleetReport, FleetTool, ScanResult};
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Share of machines that must have a tool before it is expected everywhere
+const COMMON_TOOL_THRESHOLD: f64 = 0.5;
+
+/// A scan loaded from disk, labeled with the machine it came from
+pub struct MachineScan {
+    pub machine: String,
+    pub team: Option<String>,
+    pub result: ScanResult,
+}
+
+/// Load scan JSON files (migrated to the current layout), naming each machine
+/// by its file: hostnames repeat across fleets and redacted scans share one.
+/// When the files come from several directories (scans/<team>/<host>.json),
+/// the directory name is used as the machine's team.
+pub fn load_scans(files: &[PathBuf]) -> Result<Vec<MachineScan>> {
+    let parents: BTreeSet<Option<&Path>> = files.iter().map(|f| f.parent()).collect();
+    let has_teams = parents.len() > 1;
+    let labels: Vec<String> = files.iter().map(|f| file_label(f)).collect();
+
+    files
+        .iter()
+        .zip(&labels)
+        .map(|(file, label)| {
+            let result = snapshot::load(file)?;
+            // The same file name in two directories is two machines
+            let machine = if labels.iter().filter(|l| *l == label).count() > 1 {
+                file.display().to_string()
+            } else {
+                label.clone()
+            };
+            let team = if has_teams { team_label(file) } else { None };
+            Ok(MachineScan { machine, team, result })
+        })
+        .collect()
+}
+
+fn team_label(file: &Path) -> Option<String> {
+    file.parent()
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().to_string())
+}
+
+/// The file name without its extensions (host.json.gz is "host")
+fn file_label(file: &Path) -> String {
+    file.file_name()
+        .map(|s| s.to_string_lossy().split('.').next().unwrap_or_default().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| file.display().to_string())
+}
+
+/// Aggregate many machine scans into per-tool version distributions.
+/// `required` tools are expected on every machine; without it, tools present
+/// on at least half the fleet are.
+pub fn merge(scans: &[MachineScan], required: &[String]) -> FleetReport {
+    let machines: Vec<String> = scans.iter().map(|s| s.machine.clone()).collect();
+    let fleet_machines = scans
+        .iter()
+        .map(|s| FleetMachine {
+            name: s.machine.clone(),
+            team: s.team.clone(),
+        })
+        .collect();
+
+    // tool -> version -> machines
+    let mut distribution: BTreeMap<String, BTreeMap<String, Vec<String>>> = BTreeMap::new();
+    for scan in scans {
+        for tool in &scan.result.global_tools {
+            let version = tool.version.clone().unwrap_or_else(|| "unknown".to_string());
+            distribution
+                .entry(tool.name.clone())
+                .or_default()
+                .entry(version)
+                .or_default()
+                .push(scan.machine.clone());
+        }
+    }
+
+    for name in required {
+        distribution.entry(name.clone()).or_default();
+    }
+
+    let tools = distribution
+        .into_iter()
+        .map(|(name, versions)| {
+            let present: BTreeSet<&String> = versions.values().flatten().collect();
+            let share = present.len() as f64 / machines.len().max(1) as f64;
+            let expected = required.contains(&name) || share >= COMMON_TOOL_THRESHOLD;
+
+            let missing_on = if expected {
+                machines.iter().filter(|m| !present.contains(m)).cloned().collect()
+            } else {
+                Vec::new()
+            };
+
+            let outliers = find_outliers(&versions);
+
+            FleetTool {
+                name,
+                versions,
+                missing_on,
+                outliers,
+            }
+        })
+        .collect();
+
+    FleetReport {
+        machines: fleet_machines,
+        tools,
+    }
+}
+
+/// Machines on a different major version than most of the fleet; machines
+/// whose version is unknown neither vote for the majority nor deviate from it
+fn find_outliers(versions: &BTreeMap<String, Vec<String>>) -> Vec<FleetOutlier> {
+    let mut by_major: BTreeMap<&str, usize> = BTreeMap::new();
+    for (version, machines) in versions.iter().filter(|(version, _)| version.as_str() != "unknown") {
+        *by_major.entry(major(version)).or_default() += machines.len();
+    }
+
+    let Some((common_major, common_count)) = by_major.iter().max_by_key(|(_, count)| **count) else {
+        return Vec::new();
+    };
+
+    // A tie means there is no clear fleet standard to deviate from
+    if by_major.values().filter(|count| *count == common_count).count() > 1 {
+        return Vec::new();
+    }
+
+    versions
+        .iter()
+        .filter(|(version, _)| major(version) != *common_major && version.as_str() != "unknown")
+        .flat_map(|(version, machines)| {
+            machines.iter().map(move |machine| FleetOutlier {
+                machine: machine.clone(),
+                version: version.clone(),
+            })
+        })
+        .collect()
+}
+
+fn major(version: &str) -> &str {
+    version.split('.').next().unwrap_or(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+    use crate::types::Tool;
+    use std::fs;
+
+    fn machine(name: &str, tools: &[(&str, &str)]) -> MachineScan {
+        let mut result = ScanResult::new();
+        result.global_tools = tools
+            .iter()
+            .map(|(tool, version)| Tool {
+                version: Some(version.to_string()),
+                ..Tool::new(*tool, format!("/usr/bin/{}", tool))
+            })
+            .collect();
+        MachineScan { machine: name.to_string(), team: None, result }
+    }
+
+    #[test]
+    fn test_merge() {
+        let scans = vec![
+            machine("a", &[("node", "20.11.0"), ("protoc", "25.1")]),
+            machine("b", &[("node", "20.10.0")]),
+            machine("c", &[("node", "18.19.0"), ("go", "1.22.0")]),
+        ];
+
+        let report = merge(&scans, &["protoc".to_string()]);
+        let node = report.tools.iter().find(|t| t.name == "node").unwrap();
+        assert_eq!(node.versions.len(), 3);
+        assert!(node.missing_on.is_empty());
+        assert_eq!(node.outliers.len(), 1);
+        assert_eq!(node.outliers[0].machine, "c");
+
+        let protoc = report.tools.iter().find(|t| t.name == "protoc").unwrap();
+        assert_eq!(protoc.missing_on, vec!["b", "c"]);
+
+        // go is on a minority of machines and not required
+        let go = report.tools.iter().find(|t| t.name == "go").unwrap();
+        assert!(go.missing_on.is_empty());
+
+        // Unknown versions don't outvote the known ones
+        let mut versions = BTreeMap::new();
+        versions.insert("unknown".to_string(), vec!["a".to_string(), "b".to_string()]);
+        versions.insert("20.11.0".to_string(), vec!["c".to_string()]);
+        versions.insert("18.19.0".to_string(), vec!["d".to_string(), "e".to_string()]);
+        let outliers = find_outliers(&versions);
+        assert_eq!(outliers.len(), 1);
+        assert_eq!(outliers[0].machine, "c");
+    }
+
+    #[test]
+    fn test_machine_names() {
+        assert_eq!(file_label(Path::new("scans/web-1.json.gz")), "web-1");
+        let dir = TempDir::new("fleet");
+        let files = [dir.join("a/dev.json"), dir.join("b/dev.json"), dir.join("b/ci.json")];
+        for file in &files {
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            // Every scan claims the same (redacted) hostname
+            let mut result = ScanResult::new();
+            result.hostname = Some("<hostname>".to_string());
+            fs::write(file, serde_json::to_string(&result).unwrap()).unwrap();
+        }
+        let machines: Vec<String> = load_scans(&files).unwrap().into_iter().map(|s| s.machine).collect();
+        assert_eq!(machines, vec![files[0].display().to_string(), files[1].display().to_string(), "ci".to_string()]);
+    }
+}
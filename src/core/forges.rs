@@ -0,0 +1,108 @@
+use crate::core::{exec, probe};
+use crate::types::{ForgeCli, ForgeLogin};
+use regex::Regex;
+use std::time::Duration;
+
+/// Git hosting CLIs: GitHub, GitLab, Gitea/Forgejo
+static FORGE_CLIS: &[&str] = &["gh", "glab", "tea"];
+
+/// Auth status commands may call the forge's API to validate tokens
+const AUTH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Detect git hosting CLIs, optionally asking each which hosts it's logged into
+pub fn detect_forge_clis(check_auth: bool) -> Vec<ForgeCli> {
+    FORGE_CLIS
+        .iter()
+        .filter_map(|name| {
+            let path = which::which(name).ok()?;
+            let path_str = path.to_string_lossy().to_string();
+            Some(ForgeCli {
+                name: name.to_string(),
+                version: probe::probe_version(&path_str).version,
+                logins: if check_auth { auth_status(name, &path_str) } else { None },
+                path,
+            })
+        })
+        .collect()
+}
+
+/// Hosts the CLI holds credentials for. None when the status command failed to run.
+fn auth_status(name: &str, path: &str) -> Option<Vec<ForgeLogin>> {
+    let args: &[&str] = match name {
+        "tea" => &["login", "list"],
+        _ => &["auth", "status"],
+    };
+    // gh/glab exit non-zero when any host's token is invalid, and older
+    // releases print the report on stderr, so read both streams regardless
+    let output = exec::execute_command_with_timeout(path, args, AUTH_TIMEOUT).ok()?;
+    let text = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+    Some(match name {
+        "tea" => parse_tea_logins(&text),
+        _ => parse_auth_status(&text),
+    })
+}
+
+/// `gh auth status` / `glab auth status`: "Logged in to <host> account <user>"
+/// (newer gh) or "Logged in to <host> as <user>"
+fn parse_auth_status(text: &str) -> Vec<ForgeLogin> {
+    let logged_in = Regex::new(r"Logged in to (\S+) (?:account|as) (\S+)").unwrap();
+    logged_in
+        .captures_iter(text)
+        .map(|caps| ForgeLogin { host: caps[1].to_string(), user: Some(caps[2].to_string()) })
+        .collect()
+}
+
+/// `tea login list` prints a table with URL and USER columns
+fn parse_tea_logins(text: &str) -> Vec<ForgeLogin> {
+    let rows: Vec<Vec<&str>> = text
+        .lines()
+        .filter(|l| l.contains('|'))
+        .map(|l| l.split('|').map(str::trim).collect())
+        .collect();
+    let Some(header) = rows.iter().find(|r| r.iter().any(|c| c.eq_ignore_ascii_case("url"))) else {
+        return Vec::new();
+    };
+    let column = |name: &str| header.iter().position(|c| c.eq_ignore_ascii_case(name));
+    let (Some(url), user) = (column("url"), column("user")) else {
+        return Vec::new();
+    };
+
+    rows.iter()
+        .filter(|r| r.get(url).is_some_and(|u| u.starts_with("http")))
+        .map(|r| {
+            let host = r[url].trim_start_matches("https://").trim_start_matches("http://").trim_end_matches('/');
+            ForgeLogin {
+                host: host.to_string(),
+                user: user.and_then(|i| r.get(i)).filter(|u| !u.is_empty()).map(|u| u.to_string()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_auth_status() {
+        let gh = "github.com\n  ✓ Logged in to github.com account octocat (keyring)\n  - Active account: true\n\
+                  ghe.corp.com\n  X Failed to log in to ghe.corp.com using token (GH_ENTERPRISE_TOKEN)\n";
+        let logins: Vec<(String, Option<String>)> = parse_auth_status(gh).into_iter().map(|l| (l.host, l.user)).collect();
+        assert_eq!(logins, vec![("github.com".to_string(), Some("octocat".to_string()))]);
+
+        let glab = "gitlab.com\n  ✓ Logged in to gitlab.com as jdoe (/home/jdoe/.config/glab-cli/config.yml)\n";
+        assert_eq!(parse_auth_status(glab)[0].user.as_deref(), Some("jdoe"));
+    }
+
+    #[test]
+    fn test_parse_tea_logins() {
+        let table = "+--------+-------------------+-----------+------+---------+\n\
+                     |  NAME  |        URL        |  SSHHOST  | USER | DEFAULT |\n\
+                     +--------+-------------------+-----------+------+---------+\n\
+                     | gitea  | https://gitea.com | gitea.com | bob  | true    |\n\
+                     +--------+-------------------+-----------+------+---------+\n";
+        let logins = parse_tea_logins(table);
+        assert_eq!(logins.len(), 1);
+        assert_eq!((logins[0].host.as_str(), logins[0].user.as_deref()), ("gitea.com", Some("bob")));
+    }
+}
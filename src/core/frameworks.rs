@@ -0,0 +1,187 @@
+use crate::types::Framework;
+use regex::Regex;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// (npm package, framework, config files that identify it on their own).
+/// Meta-frameworks come before the libraries they build on.
+static NODE_FRAMEWORKS: &[(&str, &str, &[&str])] = &[
+    ("next", "Next.js", &["next.config.js", "next.config.mjs", "next.config.ts"]),
+    ("nuxt", "Nuxt", &["nuxt.config.ts", "nuxt.config.js"]),
+    ("@sveltejs/kit", "SvelteKit", &[]),
+    ("@angular/core", "Angular", &["angular.json"]),
+    ("react", "React", &[]),
+    ("vue", "Vue", &[]),
+    ("svelte", "Svelte", &[]),
+];
+
+static PYTHON_MANIFESTS: &[&str] = &["requirements.txt", "pyproject.toml", "Pipfile"];
+
+/// (ecosystem prefix, framework, package, manifests to search, telltale files)
+type BackendRule = (&'static str, &'static str, &'static str, &'static [&'static str], &'static [&'static str]);
+
+static BACKEND_FRAMEWORKS: &[BackendRule] = &[
+    ("Python", "Django", "django", PYTHON_MANIFESTS, &["manage.py"]),
+    ("Python", "Flask", "flask", PYTHON_MANIFESTS, &[]),
+    ("Python", "FastAPI", "fastapi", PYTHON_MANIFESTS, &[]),
+    ("Ruby", "Rails", "rails", &["Gemfile.lock", "Gemfile"], &["config/application.rb"]),
+    ("PHP", "Laravel", "laravel/framework", &["composer.json"], &["artisan"]),
+    ("Rust", "Actix Web", "actix-web", &["Cargo.toml"], &[]),
+    ("Rust", "Axum", "axum", &["Cargo.toml"], &[]),
+];
+
+static SPRING_BOOT_TELLTALES: &[&str] = &[
+    "src/main/resources/application.yml",
+    "src/main/resources/application.yaml",
+    "src/main/resources/application.properties",
+];
+
+/// Frameworks used by a project in the given ecosystem
+pub fn detect_frameworks(dir: &Path, ecosystem: &str) -> Vec<Framework> {
+    if ecosystem == "Node.js" {
+        return node_frameworks(dir);
+    }
+    if ecosystem.starts_with("Java") || ecosystem.starts_with("JVM") {
+        return spring_boot(dir).into_iter().collect();
+    }
+
+    BACKEND_FRAMEWORKS
+        .iter()
+        .filter(|(prefix, ..)| ecosystem.starts_with(prefix))
+        .filter_map(|(_, name, package, manifests, telltales)| {
+            let declared = manifests
+                .iter()
+                .filter_map(|m| fs::read_to_string(dir.join(m)).ok())
+                .find_map(|content| find_dependency(&content, package));
+            if declared.is_none() && !telltales.iter().any(|t| dir.join(t).is_file()) {
+                return None;
+            }
+            Some(Framework { name: name.to_string(), version: declared.flatten() })
+        })
+        .collect()
+}
+
+/// Look for `package` at the start of a manifest line (requirements.txt,
+/// pyproject, Pipfile, Gemfile(.lock), package.json, composer.json and
+/// Cargo.toml all fit) and return the rest of that line
+pub fn declaration<'a>(content: &'a str, package: &str) -> Option<&'a str> {
+    content.lines().find_map(|line| {
+        let line = line.trim().trim_start_matches("gem ").trim_start_matches(['"', '\'', '-', ' ']);
+        let head = line.get(..package.len()).filter(|head| head.eq_ignore_ascii_case(package))?;
+        let rest = &line[head.len()..];
+        if rest.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' || c == '/') {
+            return None;
+        }
+        Some(rest)
+    })
+}
+
+/// Some(major version if one is given) when the package is declared
+fn find_dependency(content: &str, package: &str) -> Option<Option<String>> {
+    declaration(content, package).map(major_version)
+}
+
+/// Spring Boot from the Maven parent POM or the Gradle plugin, or the
+/// application config Boot apps ship with
+fn spring_boot(dir: &Path) -> Option<Framework> {
+    let patterns = [
+        ("pom.xml", r"spring-boot-starter-parent</artifactId>\s*<version>([^<]+)"),
+        ("build.gradle", r#"org\.springframework\.boot['"]\)?\s+version\s+['"]([^'"]+)"#),
+        ("build.gradle.kts", r#"org\.springframework\.boot['"]\)?\s+version\s+['"]([^'"]+)"#),
+    ];
+    let mut declared = false;
+    for (file, pattern) in patterns {
+        let Ok(content) = fs::read_to_string(dir.join(file)) else {
+            continue;
+        };
+        if let Some(caps) = Regex::new(pattern).unwrap().captures(&content) {
+            return Some(Framework { name: "Spring Boot".to_string(), version: major_version(&caps[1]) });
+        }
+        declared |= content.contains("spring-boot");
+    }
+
+    (declared || SPRING_BOOT_TELLTALES.iter().any(|t| dir.join(t).is_file()))
+        .then(|| Framework { name: "Spring Boot".to_string(), version: None })
+}
+
+fn node_frameworks(dir: &Path) -> Vec<Framework> {
+    let manifest: Option<Value> = fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok());
+
+    NODE_FRAMEWORKS
+        .iter()
+        .filter_map(|(package, name, configs)| {
+            let spec = manifest.as_ref().and_then(|m| dependency_spec(m, package));
+            if spec.is_none() && !configs.iter().any(|c| dir.join(c).is_file()) {
+                return None;
+            }
+            // Prefer the installed copy's version over the declared range
+            let installed = fs::read_to_string(dir.join("node_modules").join(package).join("package.json"))
+                .ok()
+                .and_then(|c| serde_json::from_str::<Value>(&c).ok())
+                .and_then(|m| m.get("version")?.as_str().map(String::from));
+            Some(Framework {
+                name: name.to_string(),
+                version: installed.or(spec).as_deref().and_then(major_version),
+            })
+        })
+        .collect()
+}
+
+fn dependency_spec(manifest: &Value, package: &str) -> Option<String> {
+    ["dependencies", "devDependencies", "peerDependencies"]
+        .iter()
+        .find_map(|section| manifest.get(section)?.get(package)?.as_str().map(String::from))
+}
+
+/// Major version from a version or range ("^14.1.0" -> "14", "0.7.4" -> "0.7");
+/// None for tags like "latest" or "workspace:*"
+pub fn major_version(spec: &str) -> Option<String> {
+    let caps = Regex::new(r"(\d+)(?:\.(\d+))?").unwrap().captures(spec)?;
+    match (&caps[1], caps.get(2)) {
+        ("0", Some(minor)) => Some(format!("0.{}", minor.as_str())),
+        (major, _) => Some(major.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_node_frameworks() {
+        let dir = TempDir::new("frameworks");
+        fs::create_dir_all(dir.join("node_modules/react")).unwrap();
+        fs::write(
+            dir.join("package.json"),
+            r#"{"dependencies": {"next": "^14.1.0", "react": "^18"}, "devDependencies": {"vue": "latest"}}"#,
+        )
+        .unwrap();
+        fs::write(dir.join("node_modules/react/package.json"), r#"{"version": "18.2.0"}"#).unwrap();
+        fs::write(dir.join("angular.json"), "{}").unwrap();
+
+        let found: Vec<String> = detect_frameworks(&dir, "Node.js").iter().map(Framework::label).collect();
+        assert_eq!(found, vec!["Next.js 14", "Angular", "React 18", "Vue"]);
+        assert_eq!(major_version("~3.4.2"), Some("3".to_string()));
+        assert_eq!(major_version("{ version = \"0.7\" }"), Some("0.7".to_string()));
+    }
+
+    #[test]
+    fn test_find_dependency() {
+        let requirements = "flask-cors==4.0\nDjango>=4.2,<5\n";
+        assert_eq!(find_dependency(requirements, "django"), Some(Some("4".to_string())));
+        assert_eq!(find_dependency(requirements, "flask"), None);
+
+        let gemfile_lock = "GEM\n  specs:\n    rails (7.1.2)\n      actioncable (= 7.1.2)\n";
+        assert_eq!(find_dependency(gemfile_lock, "rails"), Some(Some("7".to_string())));
+
+        let composer = "{\n  \"require\": {\n    \"laravel/framework\": \"^10.10\"\n  }\n}";
+        assert_eq!(find_dependency(composer, "laravel/framework"), Some(Some("10".to_string())));
+
+        let cargo = "[dependencies]\naxum = { version = \"0.7\", features = [\"macros\"] }\n";
+        assert_eq!(find_dependency(cargo, "axum"), Some(Some("0.7".to_string())));
+    }
+}
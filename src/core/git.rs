@@ -0,0 +1,174 @@
+use crate::core::{ci, exec, paths, probe};
+use crate::types::{GitSetup, Submodule};
+use std::fs;
+use std::path::Path;
+
+/// Programs git can sign commits with
+static SIGNING_TOOLS: &[&str] = &["gpg", "gpgsm", "ssh-keygen", "gitsign"];
+
+/// CI lines and repository settings that mean unsigned commits get rejected
+static SIGNATURE_CHECKS: &[&str] = &["required_signatures: true", "git verify-commit", "gitsign verify", "--show-signature"];
+
+/// Git settings that affect building and committing, as seen from `dir`
+/// (so the repository's own config applies), or None without git
+pub fn inspect(dir: &Path) -> Option<GitSetup> {
+    let version = exec::execute_for_output("git", &["--version"])?;
+    let config = exec::execute_for_output_in(dir, "git", &["config", "--list"]).unwrap_or_default();
+    let mut setup = from_config(&config);
+    setup.version = probe::extract_version(&version);
+    setup.lfs_installed = exec::execute_for_output("git-lfs", &["version"]).is_some();
+    setup.repo_uses_lfs = fs::read_to_string(dir.join(".gitattributes")).is_ok_and(|attributes| uses_lfs(&attributes));
+    if setup.repo_uses_lfs {
+        // rev-parse resolves core.hooksPath and worktrees
+        let hooks = exec::execute_for_output_in(dir, "git", &["rev-parse", "--path-format=absolute", "--git-path", "hooks"]);
+        setup.lfs_hooks = hooks.is_some_and(|h| {
+            fs::read_to_string(Path::new(h.trim()).join("pre-push")).is_ok_and(|hook| hook.contains("git lfs"))
+        });
+        if setup.lfs_installed {
+            let files = exec::execute_for_output_in(dir, "git", &["lfs", "ls-files"]).unwrap_or_default();
+            setup.lfs_missing = missing_lfs_objects(&files);
+        }
+    }
+    setup.signing_tools = SIGNING_TOOLS.iter().filter(|tool| exec::command_exists(tool)).map(|tool| tool.to_string()).collect();
+    if let Some(format) = setup.signing.clone() {
+        let key = config_value(&config, "user.signingkey").or_else(|| config_value(&config, "user.email"));
+        let key_command = config_value(&config, "gpg.ssh.defaultkeycommand");
+        setup.signing_key_available = Some(signing_key_available(&format, key.as_deref(), key_command.is_some()));
+    }
+    setup.signatures_required_by = signature_requirements(dir);
+    if dir.join(".gitmodules").is_file() {
+        let status = exec::execute_for_output_in(dir, "git", &["submodule", "status", "--recursive"]).unwrap_or_default();
+        setup.submodules = parse_submodule_status(&status);
+    }
+    Some(setup)
+}
+
+/// Last value of a key in `git config --list` output
+fn config_value(config: &str, key: &str) -> Option<String> {
+    config.lines().filter_map(|l| l.split_once('=')).rfind(|(k, _)| *k == key).map(|(_, v)| v.to_string())
+}
+
+/// Whether the key git would sign with exists: a secret key in the GPG (or
+/// gpgsm) keyring, or for SSH an inline `key::` or an existing key file
+fn signing_key_available(format: &str, key: Option<&str>, ssh_key_command: bool) -> bool {
+    match format {
+        "ssh" => match key {
+            Some(key) if key.starts_with("key::") || key.starts_with("ssh-") => true,
+            Some(file) => paths::expand_tilde(file).is_file(),
+            None => ssh_key_command,
+        },
+        "x509" => {
+            let args: Vec<&str> = ["--list-secret-keys"].into_iter().chain(key).collect();
+            exec::execute_command("gpgsm", &args).is_ok_and(|o| o.status.success() && !o.stdout.is_empty())
+        }
+        _ => {
+            let args: Vec<&str> = ["--list-secret-keys", "--with-colons"].into_iter().chain(key).collect();
+            exec::execute_command("gpg", &args).is_ok_and(|o| o.status.success() && !o.stdout.is_empty())
+        }
+    }
+}
+
+/// CI configs and .github/settings.yml that verify or require commit signatures
+fn signature_requirements(dir: &Path) -> Vec<String> {
+    let mut files: Vec<(String, String)> = ci::read_ci_configs(dir);
+    if let Ok(settings) = fs::read_to_string(dir.join(".github/settings.yml")) {
+        files.push((".github/settings.yml".to_string(), settings));
+    }
+    files
+        .into_iter()
+        .filter(|(_, content)| {
+            content.lines().filter(|l| !l.trim_start().starts_with('#')).any(|l| SIGNATURE_CHECKS.iter().any(|c| l.contains(c)))
+        })
+        .map(|(file, _)| file)
+        .collect()
+}
+
+/// `git lfs ls-files` marks files whose content is checked out with `*` and
+/// pointer files still waiting for `git lfs pull` with `-`
+fn missing_lfs_objects(ls_files: &str) -> usize {
+    ls_files.lines().filter(|l| l.split_whitespace().nth(1) == Some("-")).count()
+}
+
+/// `git submodule status` prefixes each line with ' ' (checked out at the
+/// recorded commit), '-' (not initialized), '+' (at another commit) or 'U' (conflicts)
+fn parse_submodule_status(status: &str) -> Vec<Submodule> {
+    status
+        .lines()
+        .filter_map(|line| {
+            let state = match line.chars().next()? {
+                ' ' => "ok",
+                '-' => "uninitialized",
+                '+' => "out of date",
+                'U' => "conflict",
+                _ => return None,
+            };
+            let path = line[1..].split_whitespace().nth(1)?;
+            Some(Submodule { path: path.to_string(), state: state.to_string() })
+        })
+        .collect()
+}
+
+/// Read `git config --list` output; later lines override earlier ones, and an
+/// empty credential.helper clears the helpers configured before it
+fn from_config(config: &str) -> GitSetup {
+    let mut setup = GitSetup::default();
+    let mut gpgsign = false;
+    let mut format = None;
+    for (key, value) in config.lines().filter_map(|l| l.split_once('=')) {
+        let value = value.to_string();
+        match key {
+            "user.name" => setup.user_name = Some(value),
+            "user.email" => setup.user_email = Some(value),
+            "core.autocrlf" => setup.autocrlf = Some(value),
+            "credential.helper" if value.is_empty() => setup.credential_helpers.clear(),
+            "credential.helper" => setup.credential_helpers.push(value),
+            "commit.gpgsign" => gpgsign = value == "true",
+            "gpg.format" => format = Some(value),
+            "filter.lfs.process" | "filter.lfs.smudge" => setup.lfs_initialized = true,
+            _ => {}
+        }
+    }
+    setup.signing = gpgsign.then(|| format.unwrap_or_else(|| "openpgp".to_string()));
+    setup
+}
+
+/// Whether .gitattributes routes any path through the LFS filter
+fn uses_lfs(attributes: &str) -> bool {
+    attributes
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.starts_with('#'))
+        .any(|l| l.split_whitespace().skip(1).any(|attr| attr == "filter=lfs"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_submodule_status() {
+        let status = " 1a2b3c4d vendor/lib (v1.2.0)\n-5e6f7a8b third_party/proto\n+9c0d1e2f tools/gen (heads/main)\n";
+        let states: Vec<(String, String)> = parse_submodule_status(status).into_iter().map(|s| (s.path, s.state)).collect();
+        let expected = [("vendor/lib", "ok"), ("third_party/proto", "uninitialized"), ("tools/gen", "out of date")];
+        assert_eq!(states, expected.map(|(p, s)| (p.to_string(), s.to_string())));
+    }
+
+    #[test]
+    fn test_from_config() {
+        let config = "user.name=Ada\ncredential.helper=cache\ncredential.helper=\ncredential.helper=osxkeychain\n\
+                      commit.gpgsign=true\ngpg.format=ssh\nfilter.lfs.process=git-lfs filter-process\ncore.autocrlf=input\n";
+        let setup = from_config(config);
+        assert_eq!(setup.user_name.as_deref(), Some("Ada"));
+        assert_eq!(setup.user_email, None);
+        assert_eq!(setup.credential_helpers, vec!["osxkeychain"]);
+        assert_eq!(setup.signing.as_deref(), Some("ssh"));
+        assert!(setup.lfs_initialized);
+        assert_eq!(config_value(config, "credential.helper").as_deref(), Some("osxkeychain"));
+        assert!(signing_key_available("ssh", Some("key::ssh-ed25519 AAAAC3Nz"), false));
+        assert!(!signing_key_available("ssh", Some("/nonexistent/id_ed25519.pub"), false));
+
+        assert!(uses_lfs("*.psd filter=lfs diff=lfs merge=lfs -text\n"));
+        assert_eq!(missing_lfs_objects("3f2a1b9c4d * art/logo.psd\n9e8d7c6b5a - art/hero.psd\n"), 1);
+        assert!(!uses_lfs("# *.psd filter=lfs\n*.sh text eol=lf\n"));
+    }
+}
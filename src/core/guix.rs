@@ -0,0 +1,105 @@
+use crate::core::nix::{parse_store_name, symlink_chain_stores};
+use crate::core::paths;
+use crate::types::{GuixInfo, GuixProfile, StoreProvenance, Tool};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const GUIX_STORE: &str = "/gnu/store";
+
+/// Well-known Guix profile links: (name, link relative to $HOME or absolute)
+static PROFILES: &[(&str, &str)] = &[
+    ("user profile", "~/.guix-profile"),
+    ("guix home", "~/.guix-home/profile"),
+    ("guix pull", "~/.config/guix/current"),
+    ("system", "/run/current-system/profile"),
+];
+
+/// Detect Guix profiles and their active generations
+pub fn detect_guix() -> Option<GuixInfo> {
+    if !Path::new(GUIX_STORE).is_dir() {
+        return None;
+    }
+
+    let profiles: Vec<GuixProfile> = PROFILES
+        .iter()
+        .filter_map(|(name, link)| {
+            let path = paths::expand_tilde(link);
+            fs::symlink_metadata(&path).ok()?;
+            Some(GuixProfile {
+                name: name.to_string(),
+                generation: active_generation(&path),
+                path,
+            })
+        })
+        .collect();
+
+    Some(GuixInfo { profiles })
+}
+
+/// Annotate tools that resolve into the Guix store with their package and profile
+pub fn annotate_tools(tools: &mut [Tool], guix: &GuixInfo) {
+    for tool in tools.iter_mut() {
+        let chain = symlink_chain_stores(&tool.path, GUIX_STORE);
+        let Some(store_path) = chain.last().cloned() else {
+            continue;
+        };
+        let dir_name = store_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let (package, version) = parse_store_name(&dir_name);
+
+        let profile = guix.profiles.iter().find(|p| tool.path.starts_with(&p.path));
+        tool.store = Some(StoreProvenance {
+            manager: "guix".to_string(),
+            store_path,
+            package,
+            version,
+            profile: profile.map(|p| p.name.clone()).unwrap_or_else(|| "guix shell".to_string()),
+            source: profile.and_then(|p| p.generation).map(|g| format!("generation {}", g)),
+        });
+    }
+}
+
+/// Generation number from the `<profile>-<N>-link` a profile symlink points at
+fn active_generation(profile: &Path) -> Option<u32> {
+    let mut current: PathBuf = profile.to_path_buf();
+    for _ in 0..8 {
+        let target = fs::read_link(&current).ok()?;
+        let name = target.file_name()?.to_string_lossy().to_string();
+        if let Some(generation) = generation_from_link(&name) {
+            return Some(generation);
+        }
+        current = match current.parent() {
+            Some(parent) if target.is_relative() => parent.join(target),
+            _ => target,
+        };
+    }
+    None
+}
+
+/// "guix-profile-42-link" -> 42
+fn generation_from_link(name: &str) -> Option<u32> {
+    let stem = name.strip_suffix("-link")?;
+    stem.rsplit('-').next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_generation_from_link() {
+        assert_eq!(generation_from_link("guix-profile-42-link"), Some(42));
+        assert_eq!(generation_from_link("system-7-link"), Some(7));
+        assert_eq!(generation_from_link("guix-profile"), None);
+    }
+
+    #[test]
+    fn test_active_generation() {
+        let dir = TempDir::new("guix");
+        fs::create_dir_all(dir.join("guix-profile-3-link")).unwrap();
+        std::os::unix::fs::symlink("guix-profile-3-link", dir.join("guix-profile")).unwrap();
+        std::os::unix::fs::symlink(dir.join("guix-profile"), dir.join(".guix-profile")).unwrap();
+
+        assert_eq!(active_generation(&dir.join(".guix-profile")), Some(3));
+    }
+}
@@ -0,0 +1,222 @@
+use crate::core::{cache, daemon};
+use crate::types::{Finding, ProjectSummary, ScanResult, Severity};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Mismatches listed before the rest are summarized as "+N more"
+const MAX_MISMATCHES: usize = 2;
+
+const ZSH_HOOK: &str = r#"_devfetch_hook() {
+  __DEVFETCH__ hook --summary 2>/dev/null
+}
+autoload -Uz add-zsh-hook
+add-zsh-hook chpwd _devfetch_hook
+"#;
+
+const BASH_HOOK: &str = r#"_devfetch_hook() {
+  if [[ "$PWD" != "${_DEVFETCH_LAST_DIR:-}" ]]; then
+    _DEVFETCH_LAST_DIR="$PWD"
+    __DEVFETCH__ hook --summary 2>/dev/null
+  fi
+}
+_DEVFETCH_LAST_DIR="$PWD"
+if [[ ";${PROMPT_COMMAND:-};" != *";_devfetch_hook;"* ]]; then
+  PROMPT_COMMAND="_devfetch_hook${PROMPT_COMMAND:+;$PROMPT_COMMAND}"
+fi
+"#;
+
+const FISH_HOOK: &str = r#"function __devfetch_hook --on-variable PWD
+    status --is-command-substitution; and return
+    __DEVFETCH__ hook --summary 2>/dev/null
+end
+"#;
+
+/// Shell code that prints a project summary whenever the directory changes;
+/// `exe` is the devfetch binary the hook calls back into
+pub fn snippet(shell: &str, exe: &Path) -> Result<String> {
+    let template = match shell {
+        "zsh" => ZSH_HOOK,
+        "bash" => BASH_HOOK,
+        "fish" => FISH_HOOK,
+        other => bail!("Unsupported shell '{}' (expected zsh, bash or fish)", other),
+    };
+    let quoted = format!("'{}'", exe.to_string_lossy().replace('\'', r"'\''"));
+    Ok(template.replace("__DEVFETCH__", &quoted))
+}
+
+/// Ecosystems and doctor errors/warnings of a project-only scan
+pub fn summarize(result: &ScanResult, findings: &[Finding]) -> Option<ProjectSummary> {
+    let project = result.project_info.as_ref()?;
+    let mut ecosystems: Vec<(String, Option<String>)> =
+        project.ecosystems.iter().map(|(name, info)| (name.clone(), info.tool_version.clone())).collect();
+    ecosystems.sort();
+    let primary = project.markers.iter().map(|m| &m.ecosystem).find(|e| project.ecosystems.contains_key(*e)).cloned();
+    let mismatches = findings
+        .iter()
+        .filter(|f| f.severity >= Severity::Warning)
+        .map(|f| format!("{}: {}", f.subject, f.issue))
+        .collect();
+    Some(ProjectSummary { path: project.path.clone(), primary, ecosystems, mismatches })
+}
+
+/// The summary as one line, e.g. "Rust 1.78.0 · Node.js 20.11.1 · ⚠ ruby: requires 3.3.0 (+1 more)"
+pub fn render(summary: &ProjectSummary) -> String {
+    let mut parts: Vec<String> = summary
+        .ecosystems
+        .iter()
+        .map(|(name, version)| match version {
+            Some(v) => format!("{} {}", name, v),
+            None => name.clone(),
+        })
+        .collect();
+    if !summary.mismatches.is_empty() {
+        let mut shown = summary.mismatches.iter().take(MAX_MISMATCHES).cloned().collect::<Vec<_>>().join("; ");
+        if summary.mismatches.len() > MAX_MISMATCHES {
+            shown.push_str(&format!(" (+{} more)", summary.mismatches.len() - MAX_MISMATCHES));
+        }
+        parts.push(format!("⚠ {}", shown));
+    }
+    parts.join(" · ")
+}
+
+/// The primary runtime and version with tmux color escapes for status-right,
+/// yellow with a warning sign when the project has mismatches
+pub fn render_tmux(summary: &ProjectSummary) -> String {
+    let Some(primary) = &summary.primary else {
+        return String::new();
+    };
+    let version = summary.ecosystems.iter().find(|(name, _)| name == primary).and_then(|(_, v)| v.as_deref());
+    let label = match version {
+        Some(v) => format!("{} {}", primary, v),
+        None => primary.clone(),
+    };
+    if summary.mismatches.is_empty() {
+        format!("#[fg=green]{}#[default]", label)
+    } else {
+        format!("#[fg=yellow]{} ⚠{}#[default]", label, summary.mismatches.len())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct HookEntry {
+    stamp: u64,
+    /// None when the directory is not a project
+    summary: Option<ProjectSummary>,
+}
+
+/// Summaries by directory, recomputed when the directory's files or PATH change
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SummaryCache {
+    entries: BTreeMap<PathBuf, HookEntry>,
+}
+
+fn summary_file() -> Option<PathBuf> {
+    cache::cache_dir().map(|d| d.join("hook.json"))
+}
+
+impl SummaryCache {
+    pub fn load() -> Self {
+        summary_file()
+            .and_then(|f| fs::read_to_string(f).ok())
+            .and_then(|c| serde_json::from_str(&c).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the cache, dropping directories that no longer exist
+    pub fn save(&mut self) -> Result<()> {
+        let Some(file) = summary_file() else {
+            bail!("No home directory for the cache");
+        };
+        self.entries.retain(|dir, _| dir.is_dir());
+        if let Some(dir) = file.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(file, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// The cached summary for `dir`, or `compute`'s result when anything changed
+    /// since. `compute` also says whether its summary is complete; one missing
+    /// checks (no daemon to check pins against) is returned but not cached
+    pub fn get_or_compute<F>(&mut self, dir: &Path, compute: F) -> Result<Option<ProjectSummary>>
+    where
+        F: FnOnce() -> Result<(Option<ProjectSummary>, bool)>,
+    {
+        let stamp = stamp(dir);
+        if let Some(entry) = self.entries.get(dir).filter(|e| e.stamp == stamp) {
+            return Ok(entry.summary.clone());
+        }
+        let (summary, complete) = compute()?;
+        if complete {
+            self.entries.insert(dir.to_path_buf(), HookEntry { stamp, summary: summary.clone() });
+        }
+        Ok(summary)
+    }
+}
+
+/// Hash of the modification times of the directory, its top-level entries
+/// (manifests, lock files, version files) and the PATH directories
+fn stamp(dir: &Path) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    fs::metadata(dir).and_then(|m| m.modified()).ok().hash(&mut hasher);
+    let mut entries: Vec<_> = fs::read_dir(dir)
+        .map(|rd| rd.flatten().map(|e| (e.file_name(), e.metadata().and_then(|m| m.modified()).ok())).collect())
+        .unwrap_or_default();
+    entries.sort();
+    entries.hash(&mut hasher);
+    daemon::path_stamps().hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_render() {
+        let mut summary = ProjectSummary {
+            path: PathBuf::from("/src/app"),
+            primary: Some("Rust".to_string()),
+            ecosystems: vec![("Node.js".to_string(), Some("20.11.1".to_string())), ("Rust".to_string(), None)],
+            mismatches: Vec::new(),
+        };
+        assert_eq!(render(&summary), "Node.js 20.11.1 · Rust");
+        assert_eq!(render_tmux(&summary), "#[fg=green]Rust#[default]");
+
+        summary.mismatches = vec!["ruby: a".to_string(), "php: b".to_string(), "node: c".to_string()];
+        assert_eq!(render(&summary), "Node.js 20.11.1 · Rust · ⚠ ruby: a; php: b (+1 more)");
+        summary.primary = Some("Node.js".to_string());
+        assert_eq!(render_tmux(&summary), "#[fg=yellow]Node.js 20.11.1 ⚠3#[default]");
+    }
+
+    #[test]
+    fn test_snippet() {
+        let zsh = snippet("zsh", Path::new("/opt/it's/devfetch")).unwrap();
+        assert!(zsh.contains(r"'/opt/it'\''s/devfetch' hook --summary"));
+        assert!(snippet("tcsh", Path::new("devfetch")).is_err());
+    }
+
+    #[test]
+    fn test_summary_cache() {
+        let dir = TempDir::new("hook");
+        let mut cache = SummaryCache::default();
+        let summary = |name: &str| ProjectSummary { path: dir.to_path_buf(), primary: None, ecosystems: vec![(name.to_string(), None)], mismatches: Vec::new() };
+
+        // Without the daemon's tool list the summary is shown but not kept
+        let degraded = cache.get_or_compute(&dir, || Ok((Some(summary("Zig")), false))).unwrap();
+        assert_eq!(degraded.unwrap().ecosystems[0].0, "Zig");
+        let first = cache.get_or_compute(&dir, || Ok((Some(summary("Rust")), true))).unwrap();
+        let cached = cache.get_or_compute(&dir, || Ok((Some(summary("Go")), true))).unwrap();
+        assert_eq!(first.unwrap().ecosystems, cached.unwrap().ecosystems);
+
+        fs::write(dir.join("go.mod"), "module example.com/app\n").unwrap();
+        let changed = cache.get_or_compute(&dir, || Ok((Some(summary("Go")), true))).unwrap();
+        assert_eq!(changed.unwrap().ecosystems[0].0, "Go");
+    }
+}
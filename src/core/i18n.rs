@@ -0,0 +1,204 @@
+use std::env;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+/// Languages the pretty output's headers and labels are translated to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    En,
+    Es,
+    De,
+    Zh,
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+/// Labels by their English text: (en, es, de, zh)
+static TRANSLATIONS: &[(&str, &str, &str, &str)] = &[
+    ("GLOBAL DEVELOPER TOOLS", "HERRAMIENTAS DE DESARROLLO GLOBALES", "GLOBALE ENTWICKLERWERKZEUGE", "全局开发工具"),
+    ("PROJECT INFORMATION", "INFORMACIÓN DEL PROYECTO", "PROJEKTINFORMATIONEN", "项目信息"),
+    ("DOCTOR", "DIAGNÓSTICO", "DIAGNOSE", "诊断"),
+    ("Path:", "Ruta:", "Pfad:", "路径："),
+    ("Detected Ecosystems:", "Ecosistemas detectados:", "Erkannte Ökosysteme:", "检测到的生态系统："),
+    ("Ecosystem Details:", "Detalles del ecosistema:", "Ökosystem-Details:", "生态系统详情："),
+    ("Warnings:", "Advertencias:", "Warnungen:", "警告："),
+    ("Dependencies:", "Dependencias:", "Abhängigkeiten:", "依赖："),
+    ("Language Toolchains", "Lenguajes y compiladores", "Sprach-Toolchains", "语言工具链"),
+    ("Package Managers", "Gestores de paquetes", "Paketmanager", "包管理器"),
+    ("Build Systems", "Sistemas de compilación", "Build-Systeme", "构建系统"),
+    ("Formatters & Linters", "Formateadores y linters", "Formatierer & Linter", "格式化与代码检查工具"),
+    ("Coverage & Profiling", "Cobertura y perfilado", "Abdeckung & Profiling", "覆盖率与性能分析"),
+    ("Developer Tools", "Herramientas de desarrollo", "Entwicklerwerkzeuge", "开发工具"),
+    ("Other Tools", "Otras herramientas", "Weitere Werkzeuge", "其他工具"),
+    ("Package Store Provenance:", "Origen en almacenes de paquetes:", "Herkunft aus Paketspeichern:", "包存储来源："),
+    ("Unix-on-Windows Environment:", "Entorno Unix en Windows:", "Unix-Umgebung unter Windows:", "Windows 上的 Unix 环境："),
+    ("Cross-compilation:", "Compilación cruzada:", "Cross-Kompilierung:", "交叉编译："),
+    ("Virtualization:", "Virtualización:", "Virtualisierung:", "虚拟化："),
+    ("Git Hosting CLIs:", "CLI de alojamiento Git:", "Git-Hosting-CLIs:", "Git 托管平台 CLI："),
+    ("Git:", "Git:", "Git:", "Git："),
+    ("Proxy & Certificates:", "Proxy y certificados:", "Proxy & Zertifikate:", "代理与证书："),
+    ("Dotfiles:", "Archivos de configuración:", "Dotfiles:", "配置文件："),
+    ("Version Managers:", "Gestores de versiones:", "Versionsmanager:", "版本管理器："),
+    ("GNU Guix:", "GNU Guix:", "GNU Guix:", "GNU Guix："),
+    ("Shell:", "Shell:", "Shell:", "Shell："),
+    ("JDKs:", "JDK:", "JDKs:", "JDK："),
+    ("Cache:", "Caché:", "Cache:", "缓存："),
+    ("Usage statistics:", "Estadísticas de uso:", "Nutzungsstatistik:", "使用统计："),
+    ("Language Servers:", "Servidores de lenguaje:", "Sprachserver:", "语言服务器："),
+    ("Formatters & Linters:", "Formateadores y linters:", "Formatierer & Linter:", "格式化与代码检查工具："),
+    ("CI Coverage & Profiling Tools:", "Cobertura y perfilado en CI:", "CI-Abdeckung & Profiling:", "CI 覆盖率与性能分析工具："),
+    ("Entry Points:", "Puntos de entrada:", "Einstiegspunkte:", "入口点："),
+    ("Monorepo:", "Monorepositorio:", "Monorepo:", "单体仓库："),
+    ("Workspace:", "Espacio de trabajo:", "Arbeitsbereich:", "工作区："),
+    ("Sub-projects:", "Subproyectos:", "Unterprojekte:", "子项目："),
+    ("API Specs:", "Especificaciones de API:", "API-Spezifikationen:", "API 规范："),
+    ("Protobuf Toolchain:", "Herramientas de Protobuf:", "Protobuf-Toolchain:", "Protobuf 工具链："),
+    ("Documentation:", "Documentación:", "Dokumentation:", "文档："),
+    ("Release Tooling:", "Herramientas de publicación:", "Release-Werkzeuge:", "发布工具："),
+    ("Container Registries:", "Registros de contenedores:", "Container-Registries:", "容器镜像仓库："),
+    ("Databases:", "Bases de datos:", "Datenbanken:", "数据库："),
+    ("Database Migrations:", "Migraciones de bases de datos:", "Datenbankmigrationen:", "数据库迁移："),
+    ("Project Environments:", "Entornos del proyecto:", "Projektumgebungen:", "项目环境："),
+    ("Pinned Toolchain:", "Versiones fijadas:", "Festgelegte Versionen:", "固定版本的工具链："),
+    ("Editor Support:", "Soporte de editores:", "Editor-Unterstützung:", "编辑器支持："),
+    ("System Requirements:", "Requisitos del sistema:", "Systemanforderungen:", "系统要求："),
+    ("Services:", "Servicios:", "Dienste:", "服务："),
+    ("Package Registries:", "Registros de paquetes:", "Paket-Registries:", "软件包仓库："),
+    ("SSH:", "SSH:", "SSH:", "SSH："),
+    ("Network:", "Red:", "Netzwerk:", "网络："),
+    ("Security Audit:", "Auditoría de seguridad:", "Sicherheitsprüfung:", "安全审计："),
+    ("All project requirements satisfied", "Se cumplen todos los requisitos del proyecto", "Alle Projektanforderungen erfüllt", "项目的所有要求均已满足"),
+    ("Partial result: the scan stopped before every probe finished", "Resultado parcial: el escaneo se detuvo antes de terminar todas las pruebas", "Unvollständiges Ergebnis: der Scan wurde vor Abschluss aller Prüfungen beendet", "部分结果：扫描在所有探测完成前已停止"),
+    ("No problems found", "No se encontraron problemas", "Keine Probleme gefunden", "未发现问题"),
+    ("error", "error", "Fehler", "错误"),
+    ("warning", "advertencia", "Warnung", "警告"),
+    ("Version requirements:", "Requisitos de versión:", "Versionsanforderungen:", "版本要求："),
+    ("pass", "ok", "ok", "通过"),
+    ("warn", "aviso", "Warnung", "警告"),
+    ("fail", "fallo", "Fehler", "失败"),
+    ("version unknown", "versión desconocida", "Version unbekannt", "版本未知"),
+    ("not installed", "no instalado", "nicht installiert", "未安装"),
+    ("none installed", "ninguno instalado", "keine installiert", "均未安装"),
+    ("on PATH", "en PATH", "im PATH", "在 PATH 中"),
+    ("active", "activa", "aktiv", "当前"),
+    ("info", "info", "Info", "提示"),
+    ("system", "sistema", "System", "系统"),
+    ("user", "usuario", "Benutzer", "用户"),
+    ("project", "proyecto", "Projekt", "项目"),
+    ("Install scope:", "Ámbito de instalación:", "Installationsbereich:", "安装范围："),
+    ("{} contains developer tools but is not on PATH", "{} contiene herramientas de desarrollo pero no está en PATH", "{} enthält Entwicklerwerkzeuge, ist aber nicht im PATH", "{} 包含开发工具，但不在 PATH 中"),
+    ("Active:", "Activo:", "Aktiv:", "当前："),
+    ("with {} v{} ({})", "con {} v{} ({})", "mit {} v{} ({})", "使用 {} v{}（{}）"),
+    ("with {} ({}, not installed)", "con {} ({}, no instalado)", "mit {} ({}, nicht installiert)", "使用 {}（{}，未安装）"),
+    ("Members:", "Miembros:", "Mitglieder:", "成员："),
+    ("Binaries:", "Binarios:", "Programme:", "可执行文件："),
+    ("MSRV:", "MSRV:", "MSRV:", "MSRV："),
+    ("Requires Python", "Requiere Python", "Benötigt Python", "需要 Python"),
+    ("Interpreters:", "Intérpretes:", "Interpreter:", "解释器："),
+    ("Locked in {}", "Bloqueado en {}", "Gesperrt in {}", "锁定于 {}"),
+    ("{} dependencies", "{} dependencias", "{} Abhängigkeiten", "{} 个依赖"),
+    ("Sample:", "Muestra:", "Auswahl:", "示例："),
+    ("Framework:", "Framework:", "Framework:", "框架："),
+    ("Plugin manager:", "Gestor de plugins:", "Plugin-Manager:", "插件管理器："),
+    ("Credential helpers:", "Asistentes de credenciales:", "Credential-Helper:", "凭据助手："),
+    ("Signing:", "Firma:", "Signierung:", "签名："),
+    ("Signing tools:", "Herramientas de firma:", "Signierwerkzeuge:", "签名工具："),
+    ("LFS:", "LFS:", "LFS:", "LFS："),
+    ("Submodules:", "Submódulos:", "Submodule:", "子模块："),
+    ("Last sync:", "Última sincronización:", "Letzte Synchronisierung:", "上次同步："),
+    ("Manages:", "Gestiona:", "Verwaltet:", "管理："),
+    ("Tools:", "Herramientas:", "Werkzeuge:", "工具："),
+    ("PATH", "PATH", "PATH", "PATH"),
+    ("FLEET ({} machines)", "FLOTA ({} máquinas)", "FLOTTE ({} Rechner)", "机群（{} 台机器）"),
+    ("DIFF: {} ↔ {}", "DIFERENCIAS: {} ↔ {}", "VERGLEICH: {} ↔ {}", "差异：{} ↔ {}"),
+    ("Tool", "Herramienta", "Werkzeug", "工具"),
+];
+
+impl Lang {
+    /// A language code or locale such as "de", "es_ES.UTF-8" or "zh-Hans"
+    pub fn from_code(code: &str) -> Option<Self> {
+        let language = code.split(['_', '-', '.', '@']).next().unwrap_or_default().to_lowercase();
+        match language.as_str() {
+            "en" | "c" | "posix" => Some(Lang::En),
+            "es" => Some(Lang::Es),
+            "de" => Some(Lang::De),
+            "zh" => Some(Lang::Zh),
+            _ => None,
+        }
+    }
+}
+
+/// Choose the output language: `--lang` if given, else the locale from
+/// LC_ALL, LC_MESSAGES or LANG, else English
+pub fn init(explicit: Option<&str>) {
+    let lang = explicit
+        .and_then(Lang::from_code)
+        .or_else(|| {
+            ["LC_ALL", "LC_MESSAGES", "LANG"]
+                .iter()
+                .find_map(|var| env::var(var).ok().filter(|v| !v.is_empty()))
+                .and_then(|locale| Lang::from_code(&locale))
+        })
+        .unwrap_or(Lang::En);
+    let _ = LANG.set(lang);
+}
+
+/// `text` in the output language; labels without a translation stay English
+pub fn t(text: &str) -> &str {
+    translate(LANG.get().copied().unwrap_or(Lang::En), text)
+}
+
+/// `t(template)` with each `{}` filled by the next of `args`, so translations
+/// can put the values where their word order needs them
+pub fn tf(template: &str, args: &[&dyn Display]) -> String {
+    fill(t(template), args)
+}
+
+fn fill(text: &str, args: &[&dyn Display]) -> String {
+    let mut args = args.iter();
+    let mut filled = String::new();
+    for (i, part) in text.split("{}").enumerate() {
+        if i > 0 {
+            if let Some(arg) = args.next() {
+                filled.push_str(&arg.to_string());
+            }
+        }
+        filled.push_str(part);
+    }
+    filled
+}
+
+fn translate(lang: Lang, text: &str) -> &str {
+    let Some(&(_, es, de, zh)) = TRANSLATIONS.iter().find(|(en, ..)| *en == text) else {
+        return text;
+    };
+    match lang {
+        Lang::En => text,
+        Lang::Es => es,
+        Lang::De => de,
+        Lang::Zh => zh,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_code() {
+        assert_eq!(Lang::from_code("de_DE.UTF-8"), Some(Lang::De));
+        assert_eq!(Lang::from_code("zh-Hans"), Some(Lang::Zh));
+        assert_eq!(Lang::from_code("C.UTF-8"), Some(Lang::En));
+        assert_eq!(Lang::from_code("fr_FR"), None);
+    }
+
+    #[test]
+    fn test_translate() {
+        assert_eq!(translate(Lang::Es, "Warnings:"), "Advertencias:");
+        assert_eq!(translate(Lang::Zh, "Package Managers"), "包管理器");
+        assert_eq!(translate(Lang::De, "Not a label"), "Not a label");
+        assert_eq!(translate(Lang::En, "DOCTOR"), "DOCTOR");
+        assert_eq!(fill(translate(Lang::Zh, "with {} v{} ({})"), &[&"uv", &"0.4.1", &"uv.lock"]), "使用 uv v0.4.1（uv.lock）");
+        assert_eq!(fill(translate(Lang::De, "FLEET ({} machines)"), &[&3]), "FLOTTE (3 Rechner)");
+    }
+}
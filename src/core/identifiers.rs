@@ -0,0 +1,173 @@
+use crate::core::exec;
+use crate::types::Tool;
+use std::fs;
+
+/// Tool base name -> (CPE vendor, CPE product), following NVD naming
+static CPE_PRODUCTS: &[(&str, &str, &str)] = &[
+    ("python", "python", "python"),
+    ("node", "nodejs", "node.js"),
+    ("npm", "npmjs", "npm"),
+    ("deno", "deno", "deno"),
+    ("ruby", "ruby-lang", "ruby"),
+    ("perl", "perl", "perl"),
+    ("php", "php", "php"),
+    ("go", "golang", "go"),
+    ("rustc", "rust-lang", "rust"),
+    ("cargo", "rust-lang", "cargo"),
+    ("gcc", "gnu", "gcc"),
+    ("g++", "gnu", "gcc"),
+    ("make", "gnu", "make"),
+    ("clang", "llvm", "clang"),
+    ("git", "git-scm", "git"),
+    ("curl", "haxx", "curl"),
+    ("openssl", "openssl", "openssl"),
+    ("cmake", "kitware", "cmake"),
+    ("docker", "docker", "docker"),
+    ("kubectl", "kubernetes", "kubernetes"),
+    ("helm", "helm", "helm"),
+    ("terraform", "hashicorp", "terraform"),
+    ("vagrant", "hashicorp", "vagrant"),
+    ("packer", "hashicorp", "packer"),
+    ("ansible", "redhat", "ansible"),
+    ("psql", "postgresql", "postgresql"),
+    ("mysql", "oracle", "mysql"),
+    ("sqlite", "sqlite", "sqlite"),
+    ("redis-server", "redis", "redis"),
+    ("protoc", "google", "protobuf"),
+    ("jq", "jqlang", "jq"),
+];
+
+/// Fill in purl and CPE identifiers for discovered tools.
+/// Tools claimed by a system package manager (see --verify-integrity) get
+/// a distro package purl; everything else falls back to pkg:generic.
+pub fn assign_identifiers(tools: &mut [Tool]) {
+    let distro = distro_id();
+    for tool in tools.iter_mut() {
+        tool.purl = tool_purl(tool, distro.as_deref());
+        tool.cpe = tool.version.as_deref().and_then(|v| cpe_name(&tool.name, v));
+    }
+}
+
+/// Distribution ID from /etc/os-release (debian, ubuntu, fedora, ...)
+fn distro_id() -> Option<String> {
+    let content = fs::read_to_string("/etc/os-release").ok()?;
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("ID="))
+        .map(|id| id.trim_matches('"').to_string())
+}
+
+fn tool_purl(tool: &Tool, distro: Option<&str>) -> Option<String> {
+    if let Some(check) = &tool.integrity {
+        match check.manager.as_str() {
+            "dpkg" => {
+                let (name, arch) = match check.package.split_once(':') {
+                    Some((name, arch)) => (name, Some(arch)),
+                    None => (check.package.as_str(), None),
+                };
+                let version = exec::execute_for_output("dpkg-query", &["-W", "-f=${Version}", &check.package])
+                    .filter(|v| !v.trim().is_empty());
+                let mut purl = format!("pkg:deb/{}/{}", distro.unwrap_or("debian"), name);
+                if let Some(v) = version {
+                    purl.push_str(&format!("@{}", encode(v.trim())));
+                }
+                if let Some(arch) = arch {
+                    purl.push_str(&format!("?arch={}", arch));
+                }
+                return Some(purl);
+            }
+            "rpm" => {
+                let query = exec::execute_for_output(
+                    "rpm",
+                    &["-q", "--qf", "%{NAME} %{VERSION}-%{RELEASE} %{ARCH}", &check.package],
+                )?;
+                let mut fields = query.split_whitespace();
+                let (name, version, arch) = (fields.next()?, fields.next()?, fields.next()?);
+                return Some(format!(
+                    "pkg:rpm/{}/{}@{}?arch={}",
+                    distro.unwrap_or("fedora"),
+                    name,
+                    encode(version),
+                    arch
+                ));
+            }
+            "homebrew" => {
+                return Some(match &tool.version {
+                    Some(v) => format!("pkg:brew/{}@{}", check.package, encode(v)),
+                    None => format!("pkg:brew/{}", check.package),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    tool.version
+        .as_deref()
+        .map(|v| format!("pkg:generic/{}@{}", encode(&tool.name), encode(v)))
+}
+
+/// purl for a project dependency in a language registry (npm, pypi, cargo)
+pub fn package_purl(purl_type: &str, name: &str, version: &str) -> String {
+    let name = match purl_type {
+        // PEP 503 normalization, as the purl spec requires
+        "pypi" => name.to_lowercase().replace('_', "-"),
+        _ => name.to_string(),
+    };
+    // Scoped npm packages put the scope in the purl namespace
+    let name = match name.strip_prefix('@').and_then(|n| n.split_once('/')) {
+        Some((scope, pkg)) => format!("%40{}/{}", encode(scope), encode(pkg)),
+        None => encode(&name),
+    };
+    format!("pkg:{}/{}@{}", purl_type, name, encode(version))
+}
+
+/// Purl with a namespace segment: the Maven groupId (pkg:maven/org.slf4j/slf4j-api@2.0.9)
+/// or the Composer vendor (pkg:composer/laravel/framework@11.0.0)
+pub fn namespaced_purl(purl_type: &str, namespace: &str, name: &str, version: &str) -> String {
+    format!("pkg:{}/{}/{}@{}", purl_type, encode(namespace), encode(name), encode(version))
+}
+
+/// Best-effort CPE 2.3 name for a tool, when it maps to a known NVD product
+pub fn cpe_name(tool_name: &str, version: &str) -> Option<String> {
+    let base = tool_name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.' || c == '-');
+    CPE_PRODUCTS
+        .iter()
+        .find(|(name, _, _)| *name == base || *name == tool_name)
+        .map(|(_, vendor, product)| format!("cpe:2.3:a:{}:{}:{}:*:*:*:*:*:*:*", vendor, product, version))
+}
+
+/// Percent-encode characters that are not allowed verbatim in a purl segment
+fn encode(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || "-._~:".contains(c) {
+                c.to_string()
+            } else {
+                let mut buf = [0u8; 4];
+                c.encode_utf8(&mut buf).bytes().map(|b| format!("%{:02X}", b)).collect()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_purl() {
+        assert_eq!(package_purl("npm", "@types/node", "20.11.0"), "pkg:npm/%40types/node@20.11.0");
+        assert_eq!(package_purl("pypi", "Typing_Extensions", "4.9.0"), "pkg:pypi/typing-extensions@4.9.0");
+        assert_eq!(package_purl("cargo", "serde", "1.0.195+build"), "pkg:cargo/serde@1.0.195%2Bbuild");
+    }
+
+    #[test]
+    fn test_cpe_name() {
+        assert_eq!(
+            cpe_name("python3.11", "3.11.4").as_deref(),
+            Some("cpe:2.3:a:python:python:3.11.4:*:*:*:*:*:*:*")
+        );
+        assert_eq!(cpe_name("gcc-12", "12.2.0").as_deref(), Some("cpe:2.3:a:gnu:gcc:12.2.0:*:*:*:*:*:*:*"));
+        assert_eq!(cpe_name("shellcheck", "0.9.0"), None);
+    }
+}
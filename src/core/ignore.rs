@@ -0,0 +1,152 @@
+use crate::core::time::today;
+use crate::types::{Finding, Severity};
+use anyhow::{bail, Context, Result};
+use glob::Pattern;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// File in the project root listing doctor findings to leave out
+pub const FILE_NAME: &str = ".devfetchignore";
+
+/// Findings to leave out by id (`check:subject`, each part a glob),
+/// optionally only until a date
+#[derive(Debug)]
+pub struct IgnoreRule {
+    /// The entry as written, for reporting it back
+    text: String,
+    /// None for a bare subject, which is not applied: write `*:subject` to
+    /// ignore a subject for every check
+    check: Option<Pattern>,
+    subject: Pattern,
+    /// Last day the rule applies, as YYYY-MM-DD
+    until: Option<String>,
+}
+
+impl IgnoreRule {
+    fn matches(&self, finding: &Finding) -> bool {
+        self.check.as_ref().is_some_and(|c| c.matches(&finding.check)) && self.subject.matches(&finding.subject)
+    }
+}
+
+/// Rules from the project's .devfetchignore; none when there isn't one
+pub fn load(dir: &Path) -> Result<Vec<IgnoreRule>> {
+    let file = dir.join(FILE_NAME);
+    match fs::read_to_string(&file) {
+        Ok(content) => parse(&content).with_context(|| format!("Invalid {}", file.display())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", file.display())),
+    }
+}
+
+/// One rule per line, `CHECK:SUBJECT [until YYYY-MM-DD]`, with `#` comments
+fn parse(content: &str) -> Result<Vec<IgnoreRule>> {
+    let mut rules = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (id, until) = match line.rsplit_once(" until ") {
+            Some((id, date)) if is_date(date.trim()) => (id.trim(), Some(date.trim().to_string())),
+            Some(_) => bail!("line {}: expected a YYYY-MM-DD date after `until`", number + 1),
+            None => (line, None),
+        };
+        let pattern = |text: &str| Pattern::new(text).with_context(|| format!("line {}: bad pattern {:?}", number + 1, text));
+        let (check, subject) = match id.split_once(':') {
+            Some((check, subject)) => (Some(pattern(check)?), pattern(subject)?),
+            None => (None, pattern(id)?),
+        };
+        rules.push(IgnoreRule { text: id.to_string(), check, subject, until });
+    }
+    Ok(rules)
+}
+
+fn is_date(text: &str) -> bool {
+    text.len() == 10
+        && text.char_indices().all(|(i, c)| if i == 4 || i == 7 { c == '-' } else { c.is_ascii_digit() })
+}
+
+/// Drop findings whose id an unexpired rule matches. Expired rules, and rules
+/// that name no check, are reported as warnings so they get revisited rather
+/// than silently lapsing or hiding more than intended.
+/// Returns the remaining findings and how many were ignored
+pub fn apply(findings: Vec<Finding>, rules: &[IgnoreRule], today: &str) -> (Vec<Finding>, usize) {
+    let (active, expired): (Vec<&IgnoreRule>, Vec<&IgnoreRule>) =
+        rules.iter().partition(|r| r.until.as_deref().is_none_or(|until| today <= until));
+    let total = findings.len();
+    let mut kept: Vec<Finding> = findings.into_iter().filter(|f| !active.iter().any(|r| r.matches(f))).collect();
+    let ignored = total - kept.len();
+    for rule in active.iter().filter(|r| r.check.is_none()) {
+        kept.push(Finding {
+            severity: Severity::Warning,
+            check: "ignore".to_string(),
+            subject: rule.text.clone(),
+            issue: format!("the {} entry names no check, so it is not applied", FILE_NAME),
+            rationale: None,
+            fix: Some(format!("use the id doctor prints, e.g. pin:{0}, or *:{0} for every check", rule.text)),
+        });
+    }
+    for rule in expired {
+        kept.push(Finding {
+            severity: Severity::Warning,
+            check: "ignore".to_string(),
+            subject: rule.text.clone(),
+            issue: format!("the {} entry expired on {}", FILE_NAME, rule.until.as_deref().unwrap_or_default()),
+            rationale: None,
+            fix: Some(format!("fix what it hid, or extend or remove the entry in {}", FILE_NAME)),
+        });
+    }
+    (kept, ignored)
+}
+
+/// Load the project's rules and apply them as of today
+pub fn filter(dir: &Path, findings: Vec<Finding>) -> Result<(Vec<Finding>, usize)> {
+    Ok(apply(findings, &load(dir)?, &today()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(check: &str, subject: &str) -> Finding {
+        Finding {
+            severity: Severity::Error,
+            check: check.to_string(),
+            subject: subject.to_string(),
+            issue: "pinned to 2.7 but not installed".to_string(),
+            rationale: None,
+            fix: None,
+        }
+    }
+
+    #[test]
+    fn test_apply() {
+        let rules = parse("# legacy tooling\npin:python2  # EOL, still needed by the build\nsystem:br_* module until 2026-06-30\npin:go until 2026-01-31\n*:ssh-agent\n").unwrap();
+        let findings = vec![
+            finding("pin", "python2"),
+            finding("requirement", "python2"),
+            finding("system", "br_netfilter module"),
+            finding("pin", "go"),
+            finding("ssh-agent", "ssh-agent"),
+            finding("pin", "node"),
+        ];
+        let (kept, ignored) = apply(findings, &rules, "2026-03-01");
+        assert_eq!(ignored, 3);
+        let ids: Vec<String> = kept.iter().map(Finding::id).collect();
+        assert_eq!(ids, ["requirement:python2", "pin:go", "pin:node", "ignore:pin:go"]);
+        assert!(kept[3].issue.contains("expired on 2026-01-31"));
+
+        assert!(parse("go until soon").is_err());
+        assert!(is_date(&today()));
+    }
+
+    #[test]
+    fn test_bare_subject_is_not_applied() {
+        let rules = parse("python2\n").unwrap();
+        let (kept, ignored) = apply(vec![finding("pin", "python2")], &rules, "2026-03-01");
+        assert_eq!(ignored, 0);
+        assert_eq!(kept.len(), 2);
+        assert!(kept[1].issue.contains("names no check"));
+    }
+}
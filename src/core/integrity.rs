@@ -0,0 +1,198 @@
+use crate::core::exec;
+use crate::types::{IntegrityCheck, Tool};
+use rayon::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Verify on-disk binaries against the records of the package manager that installed them
+pub fn verify_tools(tools: &mut [Tool]) {
+    tools.par_iter_mut().for_each(|tool| {
+        tool.integrity = verify_binary(&tool.path);
+    });
+}
+
+/// Directories where binaries are commonly dropped by hand (make install, curl | tar)
+static MANUAL_INSTALL_ROOTS: &[&str] = &["/usr/local", "/opt"];
+
+/// Flag tools under /usr/local or /opt that no package manager claims
+pub fn flag_manual_installs(tools: &mut [Tool]) {
+    tools
+        .par_iter_mut()
+        .filter(|tool| MANUAL_INSTALL_ROOTS.iter().any(|root| tool.path.starts_with(root)))
+        .for_each(|tool| {
+            let claimed = tool.integrity.is_some()
+                || tool.store.is_some()
+                || installed_by_language_manager(&tool.path)
+                || owning_package(&tool.path).is_some();
+            tool.manual_install = !claimed;
+        });
+}
+
+/// npm/pip/Homebrew cask installs that dpkg and rpm know nothing about
+fn installed_by_language_manager(path: &Path) -> bool {
+    let resolved = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let resolved = resolved.to_string_lossy();
+    if ["/node_modules/", "/Caskroom/", "/site-packages/"].iter().any(|m| resolved.contains(m)) {
+        return true;
+    }
+
+    // pip/setuptools console scripts start with a recognizable stub
+    let mut head = [0u8; 512];
+    let read = fs::File::open(path)
+        .and_then(|mut f| std::io::Read::read(&mut f, &mut head))
+        .unwrap_or(0);
+    String::from_utf8_lossy(&head[..read]).contains("(-script\\.pyw|\\.exe)?$")
+}
+
+/// Find which system package manager owns a file: (manager, package)
+pub fn owning_package(path: &Path) -> Option<(String, String)> {
+    let candidates = candidate_paths(path);
+
+    if exec::command_exists("dpkg") {
+        for candidate in &candidates {
+            let candidate = candidate.to_string_lossy();
+            if let Some(package) = exec::execute_command("dpkg", &["-S", &candidate])
+                .ok()
+                .filter(|out| out.status.success())
+                .and_then(|out| parse_dpkg_search(&String::from_utf8_lossy(&out.stdout)))
+            {
+                return Some(("dpkg".to_string(), package));
+            }
+        }
+    }
+
+    if exec::command_exists("rpm") {
+        for candidate in &candidates {
+            let candidate = candidate.to_string_lossy();
+            if let Some(package) = exec::execute_command("rpm", &["-qf", &candidate])
+                .ok()
+                .filter(|out| out.status.success())
+                .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+                .filter(|s| !s.is_empty())
+            {
+                return Some(("rpm".to_string(), package));
+            }
+        }
+    }
+
+    candidates
+        .iter()
+        .find_map(|c| homebrew_formula(c))
+        .map(|formula| ("homebrew".to_string(), formula))
+}
+
+/// The path as given plus its symlink-resolved target, since package databases
+/// record the real file (and usr-merged systems may record either form)
+fn candidate_paths(path: &Path) -> Vec<PathBuf> {
+    let mut candidates = vec![path.to_path_buf()];
+    if let Ok(resolved) = fs::canonicalize(path) {
+        if resolved != path {
+            candidates.push(resolved);
+        }
+    }
+    candidates
+}
+
+/// Verify a single binary. None when no package manager claims it.
+fn verify_binary(path: &Path) -> Option<IntegrityCheck> {
+    let (manager, package) = owning_package(path)?;
+    let resolved = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let resolved_str = resolved.to_string_lossy().to_string();
+
+    let modified = match manager.as_str() {
+        "dpkg" => exec::execute_command("dpkg", &["--verify", &package])
+            .ok()
+            .map(|out| digest_mismatch(&String::from_utf8_lossy(&out.stdout), &candidate_strings(path, &resolved))),
+        "rpm" => exec::execute_command("rpm", &["-Vf", &resolved_str])
+            .ok()
+            .map(|out| digest_mismatch(&String::from_utf8_lossy(&out.stdout), &candidate_strings(path, &resolved))),
+        // Homebrew keeps no per-file hashes, but its bottles are code-signed,
+        // so a failed signature check means the file changed after install
+        "homebrew" => exec::execute_command("codesign", &["--verify", &resolved_str])
+            .ok()
+            .map(|out| !out.status.success()),
+        _ => None,
+    };
+
+    Some(IntegrityCheck {
+        manager,
+        package,
+        verified: modified.map(|m| !m),
+    })
+}
+
+fn candidate_strings(path: &Path, resolved: &Path) -> Vec<String> {
+    vec![path.to_string_lossy().to_string(), resolved.to_string_lossy().to_string()]
+}
+
+/// `dpkg -S` prints `package[:arch]: /path`
+fn parse_dpkg_search(output: &str) -> Option<String> {
+    let line = output.lines().next()?;
+    let (package, _) = line.split_once(": ")?;
+    // Diversions and multiple owners are comma-separated; take the first
+    let package = package.split(',').next()?.trim();
+    Some(package.split(':').next()?.to_string())
+}
+
+/// dpkg --verify and rpm -V print a flag column per changed file, where `5`
+/// in the third position marks a checksum mismatch
+fn digest_mismatch(output: &str, paths: &[String]) -> bool {
+    output.lines().any(|line| {
+        let flags = line.split_whitespace().next().unwrap_or("");
+        let file = line.split_whitespace().last().unwrap_or("");
+        flags.chars().nth(2) == Some('5') && paths.iter().any(|p| p == file)
+    })
+}
+
+/// Map a path inside Homebrew's Cellar to its formula name
+fn homebrew_formula(path: &Path) -> Option<String> {
+    let components: Vec<String> = path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    let idx = components.iter().position(|c| c == "Cellar")?;
+    components.get(idx + 1).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_parse_dpkg_search() {
+        assert_eq!(parse_dpkg_search("jq: /usr/bin/jq\n"), Some("jq".to_string()));
+        assert_eq!(parse_dpkg_search("python3.11-minimal:amd64: /usr/bin/python3.11\n"), Some("python3.11-minimal".to_string()));
+        assert_eq!(parse_dpkg_search("garbage"), None);
+    }
+
+    #[test]
+    fn test_digest_mismatch() {
+        let paths = vec!["/usr/bin/git".to_string()];
+        assert!(digest_mismatch("??5?????? /usr/bin/git\n", &paths));
+        assert!(!digest_mismatch("??5?????? c /etc/gitconfig\n", &paths));
+        assert!(digest_mismatch("S.5....T.    /usr/bin/git\n", &paths));
+        assert!(!digest_mismatch(".M.......    /usr/bin/git\n", &paths));
+    }
+
+    #[test]
+    fn test_pip_console_script() {
+        let dir = TempDir::new("integrity");
+        let script = dir.join("black");
+        fs::write(&script, "#!/usr/bin/python3\nimport re\nimport sys\nfrom black import patched_main\nif __name__ == \"__main__\":\n    sys.argv[0] = re.sub(r'(-script\\.pyw|\\.exe)?$', '', sys.argv[0])\n").unwrap();
+        let binary = dir.join("protoc");
+        fs::write(&binary, [0x7f, b'E', b'L', b'F']).unwrap();
+
+        assert!(installed_by_language_manager(&script));
+        assert!(!installed_by_language_manager(&binary));
+    }
+
+    #[test]
+    fn test_homebrew_formula() {
+        assert_eq!(
+            homebrew_formula(Path::new("/opt/homebrew/Cellar/node/21.1.0/bin/node")),
+            Some("node".to_string())
+        );
+        assert_eq!(homebrew_formula(Path::new("/usr/bin/node")), None);
+    }
+}
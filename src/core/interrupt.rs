@@ -0,0 +1,18 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Turn the first Ctrl-C into a request to stop probing and print what was
+/// found so far; a second one exits at once
+pub fn install() {
+    let _ = ctrlc::set_handler(|| {
+        if INTERRUPTED.swap(true, Ordering::SeqCst) {
+            std::process::exit(130);
+        }
+    });
+}
+
+/// Whether Ctrl-C was pressed during the scan
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
@@ -0,0 +1,204 @@
+use crate::core::{doctor, paths, probe};
+use crate::types::Jdk;
+use regex::Regex;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Distributions by the IMPLEMENTOR in a JDK's `release` file, or a word in its directory name
+static VENDORS: &[(&str, &str)] = &[
+    ("graalvm", "GraalVM"),
+    ("adoptium", "Temurin"),
+    ("temurin", "Temurin"),
+    ("adoptopenjdk", "AdoptOpenJDK"),
+    ("amazon", "Corretto"),
+    ("corretto", "Corretto"),
+    ("azul", "Zulu"),
+    ("zulu", "Zulu"),
+    ("bellsoft", "Liberica"),
+    ("liberica", "Liberica"),
+    ("microsoft", "Microsoft"),
+    ("sap", "SapMachine"),
+    ("red hat", "Red Hat"),
+    ("jetbrains", "JetBrains Runtime"),
+    ("oracle", "Oracle"),
+];
+
+/// Directories holding one JDK per subdirectory, with the label reported for them
+fn search_roots() -> Vec<(PathBuf, &'static str)> {
+    let home = paths::home_dir();
+    let under_home = |rel: &str| home.as_ref().map(|h| h.join(rel));
+    let sdkman = env::var_os("SDKMAN_DIR").map(PathBuf::from).or_else(|| under_home(".sdkman"));
+    let asdf = env::var_os("ASDF_DATA_DIR").map(PathBuf::from).or_else(|| under_home(".asdf"));
+    let jabba = env::var_os("JABBA_HOME").map(PathBuf::from).or_else(|| under_home(".jabba"));
+
+    let mut roots = vec![
+        (PathBuf::from("/usr/lib/jvm"), "system"),
+        (PathBuf::from("/usr/java"), "system"),
+        (PathBuf::from("/Library/Java/JavaVirtualMachines"), "system"),
+    ];
+    roots.extend(sdkman.map(|d| (d.join("candidates/java"), "sdkman")));
+    roots.extend(asdf.map(|d| (d.join("installs/java"), "asdf")));
+    roots.extend(under_home(".local/share/mise/installs/java").map(|d| (d, "mise")));
+    roots.extend(jabba.map(|d| (d.join("jdk"), "jabba")));
+    roots
+}
+
+/// JDKs in JAVA_HOME, on PATH and in the usual install locations, one entry per installation
+pub fn installed_jdks() -> Vec<Jdk> {
+    let mut candidates: Vec<(PathBuf, &str)> = Vec::new();
+    if let Some(java_home) = env::var_os("JAVA_HOME").filter(|h| !h.is_empty()) {
+        candidates.push((PathBuf::from(java_home), "JAVA_HOME"));
+    }
+    // bin/java resolved through alternatives and shims to its JDK
+    let path_home = which::which("java")
+        .ok()
+        .and_then(|java| fs::canonicalize(java).ok())
+        .and_then(|java| Some(java.parent()?.parent()?.to_path_buf()));
+    candidates.extend(path_home.clone().map(|h| (h, "PATH")));
+    for (root, source) in search_roots() {
+        let Ok(entries) = fs::read_dir(&root) else {
+            continue;
+        };
+        let mut dirs: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+        dirs.sort();
+        candidates.extend(dirs.into_iter().map(|d| (d, source)));
+    }
+
+    let mut seen: Vec<PathBuf> = Vec::new();
+    let mut jdks = Vec::new();
+    for (dir, source) in candidates {
+        // macOS bundles keep the JDK under Contents/Home
+        let home = if dir.join("Contents/Home").is_dir() { dir.join("Contents/Home") } else { dir };
+        if !home.join("bin/java").is_file() {
+            continue;
+        }
+        let real = fs::canonicalize(&home).unwrap_or_else(|_| home.clone());
+        if seen.contains(&real) {
+            continue;
+        }
+        let on_path = path_home.as_ref() == Some(&real);
+        seen.push(real);
+        if let Some(jdk) = read_jdk(home, source, on_path) {
+            jdks.push(jdk);
+        }
+    }
+    jdks
+}
+
+/// A JDK described by its `release` file, else by its directory name
+fn read_jdk(home: PathBuf, source: &str, on_path: bool) -> Option<Jdk> {
+    let release = fs::read_to_string(home.join("release")).unwrap_or_default();
+    let field = |key: &str| {
+        release
+            .lines()
+            .find_map(|l| l.strip_prefix(key)?.strip_prefix('='))
+            .map(|v| v.trim().trim_matches('"').to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let dir_name = home
+        .components()
+        .rev()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .find(|c| c != "Home" && c != "Contents")
+        .unwrap_or_default();
+    let version = field("JAVA_VERSION").or_else(|| probe::extract_version(&dir_name))?;
+    let vendor = if field("GRAALVM_VERSION").is_some() {
+        Some("GraalVM".to_string())
+    } else {
+        // Distribution builds (e.g. Debian's) keep their own IMPLEMENTOR
+        let implementor = field("IMPLEMENTOR");
+        implementor.as_deref().and_then(vendor_name).or_else(|| vendor_name(&dir_name)).or(implementor)
+    };
+    Some(Jdk { home, version, source: source.to_string(), vendor, arch: field("OS_ARCH"), on_path })
+}
+
+fn vendor_name(text: &str) -> Option<String> {
+    let text = text.to_lowercase();
+    VENDORS.iter().find(|(key, _)| text.contains(key)).map(|(_, name)| name.to_string())
+}
+
+/// "1.8.0_392" -> "8.0.392", so pre-9 and modern versions compare
+fn normalize(version: &str) -> String {
+    let version = version.replace('_', ".");
+    match version.strip_prefix("1.") {
+        Some(rest) if rest.starts_with(|c: char| c.is_ascii_digit()) => rest.to_string(),
+        _ => version,
+    }
+}
+
+/// Whether a JDK can build a project targeting `release` (e.g. "17", "1.8") and
+/// allowed by a maven-enforcer `requireJavaVersion` range (e.g. "[17,21)"; a
+/// bare version means that version or newer)
+pub fn satisfies(version: &str, release: Option<&str>, range: Option<&str>) -> bool {
+    let version = normalize(version);
+    let feature = |v: &str| normalize(v).split('.').next().and_then(|f| f.parse::<u32>().ok());
+    let release_ok = release.is_none_or(|r| match (feature(&version), feature(r)) {
+        (Some(have), Some(want)) => have >= want,
+        _ => true,
+    });
+    release_ok && range.is_none_or(|r| in_range(&version, r))
+}
+
+fn in_range(version: &str, range: &str) -> bool {
+    let range = range.trim();
+    if !range.starts_with(['[', '(']) {
+        return doctor::compare_versions(version, &normalize(range)).is_ge();
+    }
+    // Several ranges ("[1.8,9),[11,)") allow any of them
+    let bounds = Regex::new(r"([\[(])\s*([^,\])]*?)\s*(?:,\s*([^\])]*?)\s*)?([\])])").unwrap();
+    let allowed = bounds.captures_iter(range).any(|caps| {
+        let lower = &caps[2];
+        let Some(upper) = caps.get(3).map(|m| m.as_str()) else {
+            // [17] pins one version
+            return doctor::compare_versions(version, &normalize(lower)).is_eq();
+        };
+        let above = lower.is_empty() || {
+            let order = doctor::compare_versions(version, &normalize(lower));
+            if &caps[1] == "[" { order.is_ge() } else { order.is_gt() }
+        };
+        let below = upper.is_empty() || {
+            let order = doctor::compare_versions(version, &normalize(upper));
+            if &caps[4] == "]" { order.is_le() } else { order.is_lt() }
+        };
+        above && below
+    });
+    allowed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_satisfies() {
+        assert!(satisfies("17.0.9", Some("17"), None));
+        assert!(!satisfies("11.0.21", Some("17"), None));
+        assert!(satisfies("1.8.0_392", Some("1.8"), None));
+        assert!(satisfies("21.0.1", None, Some("[17,)")));
+        assert!(!satisfies("21.0.1", None, Some("[17,21)")));
+        assert!(satisfies("11.0.2", None, Some("[1.8,9),[11,)")));
+        assert!(!satisfies("10", None, Some("[1.8,9),[11,)")));
+        assert!(satisfies("17.0.1", None, Some("17")));
+        assert!(!satisfies("17.0.1", Some("11"), Some("(,17.0.1)")));
+    }
+
+    #[test]
+    fn test_read_jdk() {
+        let dir = TempDir::new("jdk");
+        let home = dir.join("zulu-21.0.2");
+        fs::create_dir_all(&home).unwrap();
+        let jdk = read_jdk(home.clone(), "sdkman", false).unwrap();
+        assert_eq!((jdk.version.as_str(), jdk.vendor.as_deref()), ("21.0.2", Some("Zulu")));
+
+        fs::write(home.join("release"), "IMPLEMENTOR=\"Eclipse Adoptium\"\nJAVA_VERSION=\"21.0.3\"\nOS_ARCH=\"aarch64\"\n").unwrap();
+        let jdk = read_jdk(home.clone(), "sdkman", false).unwrap();
+        assert_eq!(jdk.version, "21.0.3");
+        assert_eq!(jdk.vendor.as_deref(), Some("Temurin"));
+        assert_eq!(jdk.arch.as_deref(), Some("aarch64"));
+
+        fs::write(home.join("release"), "IMPLEMENTOR=\"Oracle Corporation\"\nJAVA_VERSION=\"21.0.2\"\nGRAALVM_VERSION=\"23.1.2\"\n").unwrap();
+        assert_eq!(read_jdk(home, "sdkman", false).unwrap().vendor.as_deref(), Some("GraalVM"));
+    }
+}
@@ -0,0 +1,347 @@
+use crate::core::{identifiers, jdk};
+use crate::types::{DependencyInfo, JdkCandidate, JvmBuild, PinnedPlugin};
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// A declared dependency: group, artifact and version when pinned
+type Coordinate = (String, String, Option<String>);
+
+/// Read dependencies and pinned plugin/toolchain versions from pom.xml or the
+/// Gradle build and version catalog, without running mvn or gradle
+pub fn inspect(dir: &Path, ecosystem: &str) -> Option<(Option<DependencyInfo>, JvmBuild)> {
+    let (dependencies, mut build) = read_build(dir, ecosystem)?;
+    if build.java_version.is_some() || build.java_range.is_some() {
+        build.jdks = jdk::installed_jdks()
+            .into_iter()
+            .map(|jdk| JdkCandidate {
+                satisfies: jdk::satisfies(&jdk.version, build.java_version.as_deref(), build.java_range.as_deref()),
+                jdk,
+            })
+            .collect();
+    }
+    Some((dependencies, build))
+}
+
+fn read_build(dir: &Path, ecosystem: &str) -> Option<(Option<DependencyInfo>, JvmBuild)> {
+    if ecosystem.contains("Maven") {
+        let content = fs::read_to_string(dir.join("pom.xml")).ok()?;
+        let (dependencies, build) = parse_pom(&content);
+        return Some((dependency_info(dependencies), build));
+    }
+
+    let (script, content) = ["build.gradle.kts", "build.gradle"]
+        .iter()
+        .find_map(|f| fs::read_to_string(dir.join(f)).ok().map(|c| (*f, c)))?;
+    let mut build = parse_gradle_script(&content);
+    build.source = script.to_string();
+    let mut dependencies = Vec::new();
+    if let Ok(catalog) = fs::read_to_string(dir.join("gradle/libs.versions.toml")) {
+        let (libraries, catalog_build) = parse_version_catalog(&catalog);
+        dependencies = libraries;
+        build.source = "gradle/libs.versions.toml".to_string();
+        for plugin in catalog_build.plugins {
+            if !build.plugins.iter().any(|p| p.id == plugin.id) {
+                build.plugins.push(plugin);
+            }
+        }
+        build.java_version = build.java_version.or(catalog_build.java_version);
+        build.kotlin_version = build.kotlin_version.or(catalog_build.kotlin_version);
+    }
+
+    let empty = build.plugins.is_empty() && build.java_version.is_none() && build.kotlin_version.is_none();
+    if empty && dependencies.is_empty() {
+        return None;
+    }
+    Some((dependency_info(dependencies), build))
+}
+
+fn dependency_info(dependencies: Vec<Coordinate>) -> Option<DependencyInfo> {
+    if dependencies.is_empty() {
+        return None;
+    }
+    Some(DependencyInfo {
+        count: dependencies.len(),
+        sample: dependencies.iter().take(5).map(|(g, a, _)| format!("{}:{}", g, a)).collect(),
+        purls: dependencies
+            .iter()
+            .filter_map(|(g, a, v)| v.as_deref().map(|v| identifiers::namespaced_purl("maven", g, a, v)))
+            .collect(),
+    })
+}
+
+/// Direct dependencies, build plugins and compiler/Kotlin versions from a POM.
+/// `${property}` references are resolved against `<properties>`.
+fn parse_pom(content: &str) -> (Vec<Coordinate>, JvmBuild) {
+    let strip = |text: &str, pattern: &str| Regex::new(pattern).unwrap().replace_all(text, "").to_string();
+    let content = strip(content, r"(?s)<!--.*?-->");
+
+    let mut properties: HashMap<String, String> = HashMap::new();
+    let element = Regex::new(r"<([\w.\-]+)>\s*([^<]*?)\s*</([\w.\-]+)>").unwrap();
+    if let Some(section) = Regex::new(r"(?s)<properties>(.*?)</properties>").unwrap().captures(&content) {
+        for caps in element.captures_iter(&section[1]) {
+            if caps[1] == caps[3] {
+                properties.insert(caps[1].to_string(), caps[2].to_string());
+            }
+        }
+    }
+    let resolve = |value: &str| -> Option<String> {
+        match value.strip_prefix("${").and_then(|v| v.strip_suffix('}')) {
+            Some(name) => properties.get(name).cloned(),
+            None => Some(value.to_string()),
+        }
+    };
+    let field = |body: &str, name: &str| -> Option<String> {
+        let re = Regex::new(&format!(r"<{0}>\s*([^<]+?)\s*</{0}>", name)).unwrap();
+        re.captures(body).map(|c| c[1].to_string())
+    };
+
+    // Plugin bodies carry their own <dependencies> and <configuration>
+    let plugin_re = Regex::new(r"(?s)<plugin>(.*?)</plugin>").unwrap();
+    let plugins = plugin_re
+        .captures_iter(&content)
+        .map(|caps| {
+            let body = strip(&caps[1], r"(?s)<(dependencies|configuration|executions)>.*?</(dependencies|configuration|executions)>");
+            let group = field(&body, "groupId").unwrap_or_else(|| "org.apache.maven.plugins".to_string());
+            let artifact = field(&body, "artifactId").unwrap_or_default();
+            PinnedPlugin {
+                id: format!("{}:{}", group, artifact),
+                version: field(&body, "version").and_then(|v| resolve(&v)),
+            }
+        })
+        .collect();
+
+    // Managed versions are not dependencies of this module, nor are plugin dependencies
+    let body = strip(&content, r"(?s)<dependencyManagement>.*?</dependencyManagement>");
+    let body = plugin_re.replace_all(&body, "").to_string();
+    let dependencies = Regex::new(r"(?s)<dependency>(.*?)</dependency>")
+        .unwrap()
+        .captures_iter(&body)
+        .filter_map(|caps| {
+            let group = field(&caps[1], "groupId")?;
+            let artifact = field(&caps[1], "artifactId")?;
+            let version = field(&caps[1], "version").and_then(|v| resolve(&v));
+            Some((group, artifact, version))
+        })
+        .collect();
+
+    // maven-compiler-plugin's own <release>/<source> win over the properties
+    let compiler = plugin_re
+        .captures_iter(&content)
+        .find(|caps| caps[1].contains("<artifactId>maven-compiler-plugin</artifactId>"))
+        .and_then(|caps| Regex::new(r"(?s)<configuration>(.*?)</configuration>").unwrap().captures(&caps[1]).map(|c| c[1].to_string()));
+    let java_version = compiler
+        .as_deref()
+        .and_then(|config| field(config, "release").or_else(|| field(config, "source")))
+        .and_then(|v| resolve(&v))
+        .or_else(|| {
+            ["maven.compiler.release", "maven.compiler.source", "java.version"]
+                .iter()
+                .find_map(|p| properties.get(*p).cloned())
+        });
+    let java_range = Regex::new(r"(?s)<requireJavaVersion>.*?<version>\s*([^<]+?)\s*</version>")
+        .unwrap()
+        .captures(&content)
+        .and_then(|c| resolve(&c[1]));
+    let build = JvmBuild {
+        source: "pom.xml".to_string(),
+        plugins,
+        java_version,
+        kotlin_version: properties.get("kotlin.version").cloned(),
+        java_range,
+        jdks: Vec::new(),
+    };
+    (dependencies, build)
+}
+
+/// Libraries, plugins and the java/kotlin versions from a Gradle version catalog
+fn parse_version_catalog(content: &str) -> (Vec<Coordinate>, JvmBuild) {
+    let mut build = JvmBuild {
+        source: "gradle/libs.versions.toml".to_string(),
+        plugins: Vec::new(),
+        java_version: None,
+        kotlin_version: None,
+        java_range: None,
+        jdks: Vec::new(),
+    };
+    let Ok(catalog) = content.parse::<toml::Table>() else {
+        return (Vec::new(), build);
+    };
+
+    let versions = catalog.get("versions").and_then(|v| v.as_table());
+    // version = "1.2", version.ref = "name" or version = { ref = "name" }
+    let version_of = |entry: &toml::Value| -> Option<String> {
+        match entry.get("version")? {
+            toml::Value::String(v) => Some(v.clone()),
+            toml::Value::Table(t) => {
+                let name = t.get("ref")?.as_str()?;
+                versions?.get(name)?.as_str().map(String::from)
+            }
+            _ => None,
+        }
+    };
+
+    let mut libraries = Vec::new();
+    for entry in catalog.get("libraries").and_then(|l| l.as_table()).into_iter().flat_map(|t| t.values()) {
+        let coordinate = match entry {
+            toml::Value::String(notation) => {
+                let mut parts = notation.splitn(3, ':');
+                match (parts.next(), parts.next()) {
+                    (Some(g), Some(a)) => Some((g.to_string(), a.to_string(), parts.next().map(String::from))),
+                    _ => None,
+                }
+            }
+            _ => {
+                let module = entry.get("module").and_then(|m| m.as_str()).and_then(|m| m.split_once(':'));
+                let group = entry.get("group").and_then(|g| g.as_str());
+                let name = entry.get("name").and_then(|n| n.as_str());
+                module
+                    .or(group.zip(name))
+                    .map(|(g, a)| (g.to_string(), a.to_string(), version_of(entry)))
+            }
+        };
+        libraries.extend(coordinate);
+    }
+
+    for entry in catalog.get("plugins").and_then(|p| p.as_table()).into_iter().flat_map(|t| t.values()) {
+        let plugin = match entry {
+            toml::Value::String(notation) => {
+                let (id, version) = notation.split_once(':').unwrap_or((notation, ""));
+                Some(PinnedPlugin { id: id.to_string(), version: Some(version.to_string()).filter(|v| !v.is_empty()) })
+            }
+            _ => entry
+                .get("id")
+                .and_then(|i| i.as_str())
+                .map(|id| PinnedPlugin { id: id.to_string(), version: version_of(entry) }),
+        };
+        build.plugins.extend(plugin);
+    }
+
+    let pinned = |keys: &[&str]| keys.iter().find_map(|k| versions?.get(*k)?.as_str().map(String::from));
+    build.java_version = pinned(&["java", "jdk", "jvmTarget"]);
+    build.kotlin_version = pinned(&["kotlin"]);
+    (libraries, build)
+}
+
+/// Plugins with versions in the `plugins {}` block and the Java toolchain version
+fn parse_gradle_script(content: &str) -> JvmBuild {
+    let plugin = Regex::new(r#"id\s*\(?\s*["']([\w.\-]+)["']\s*\)?\s+version\s+\(?\s*["']([^"']+)["']"#).unwrap();
+    let kotlin = Regex::new(r#"kotlin\s*\(\s*"([\w.\-]+)"\s*\)\s+version\s+"([^"]+)""#).unwrap();
+    // Toolchains first, then the release/compatibility the compiler is given
+    let java_patterns = [
+        r"JavaLanguageVersion\.of\(\s*(\d+)\s*\)",
+        r"jvmToolchain\(\s*(\d+)\s*\)",
+        r"options\.release(?:\.set\(|\s*=)\s*(\d+)",
+        r"sourceCompatibility\s*=\s*JavaVersion\.VERSION_(\d+(?:_\d+)?)",
+        r#"sourceCompatibility\s*=\s*['"]?(\d+(?:\.\d+)?)"#,
+    ];
+    let java_version = java_patterns
+        .iter()
+        .find_map(|p| Regex::new(p).unwrap().captures(content).map(|c| c[1].replace('_', ".")));
+
+    let mut plugins: Vec<PinnedPlugin> = plugin
+        .captures_iter(content)
+        .map(|c| PinnedPlugin { id: c[1].to_string(), version: Some(c[2].to_string()) })
+        .collect();
+    let mut kotlin_version = None;
+    for caps in kotlin.captures_iter(content) {
+        kotlin_version.get_or_insert_with(|| caps[2].to_string());
+        plugins.push(PinnedPlugin { id: format!("org.jetbrains.kotlin.{}", &caps[1]), version: Some(caps[2].to_string()) });
+    }
+
+    JvmBuild {
+        source: String::new(),
+        plugins,
+        java_version,
+        kotlin_version,
+        java_range: None,
+        jdks: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pom() {
+        let pom = r#"<project>
+  <properties>
+    <maven.compiler.release>21</maven.compiler.release>
+    <jackson.version>2.17.0</jackson.version>
+  </properties>
+  <dependencyManagement>
+    <dependencies>
+      <dependency><groupId>org.junit</groupId><artifactId>junit-bom</artifactId><version>5.10.2</version></dependency>
+    </dependencies>
+  </dependencyManagement>
+  <dependencies>
+    <dependency>
+      <groupId>com.fasterxml.jackson.core</groupId>
+      <artifactId>jackson-databind</artifactId>
+      <version>${jackson.version}</version>
+    </dependency>
+    <!-- <dependency><groupId>x</groupId><artifactId>y</artifactId></dependency> -->
+    <dependency>
+      <groupId>org.junit.jupiter</groupId>
+      <artifactId>junit-jupiter</artifactId>
+      <scope>test</scope>
+    </dependency>
+  </dependencies>
+  <build>
+    <plugins>
+      <plugin>
+        <artifactId>maven-surefire-plugin</artifactId>
+        <version>3.2.5</version>
+      </plugin>
+      <plugin>
+        <artifactId>maven-compiler-plugin</artifactId>
+        <configuration><release>17</release></configuration>
+      </plugin>
+      <plugin>
+        <artifactId>maven-enforcer-plugin</artifactId>
+        <configuration><rules><requireJavaVersion><version>[17,22)</version></requireJavaVersion></rules></configuration>
+      </plugin>
+    </plugins>
+  </build>
+</project>"#;
+        let (deps, build) = parse_pom(pom);
+        assert_eq!(deps.len(), 2);
+        assert_eq!(deps[0].2.as_deref(), Some("2.17.0"));
+        assert_eq!(build.java_version.as_deref(), Some("17"));
+        assert_eq!(build.java_range.as_deref(), Some("[17,22)"));
+        assert_eq!(build.plugins[0].id, "org.apache.maven.plugins:maven-surefire-plugin");
+        assert_eq!(build.plugins[0].version.as_deref(), Some("3.2.5"));
+    }
+
+    #[test]
+    fn test_parse_version_catalog() {
+        let catalog = r#"
+[versions]
+kotlin = "1.9.23"
+ktor = "2.3.9"
+
+[libraries]
+ktor-server = { module = "io.ktor:ktor-server-core", version.ref = "ktor" }
+logback = "ch.qos.logback:logback-classic:1.5.3"
+junit = { group = "org.junit.jupiter", name = "junit-jupiter" }
+
+[plugins]
+kotlin-jvm = { id = "org.jetbrains.kotlin.jvm", version.ref = "kotlin" }
+"#;
+        let (libraries, build) = parse_version_catalog(catalog);
+        assert_eq!(libraries.len(), 3);
+        assert!(libraries.contains(&("io.ktor".to_string(), "ktor-server-core".to_string(), Some("2.3.9".to_string()))));
+        assert_eq!(build.kotlin_version.as_deref(), Some("1.9.23"));
+        assert_eq!(build.plugins[0].version.as_deref(), Some("1.9.23"));
+
+        let script = "plugins {\n    id(\"org.springframework.boot\") version \"3.2.4\"\n    kotlin(\"jvm\") version \"1.9.23\"\n}\njava { toolchain { languageVersion = JavaLanguageVersion.of(17) } }\n";
+        let build = parse_gradle_script(script);
+        assert_eq!(build.plugins.len(), 2);
+        assert_eq!(build.java_version.as_deref(), Some("17"));
+        assert_eq!(parse_gradle_script("kotlin { jvmToolchain(21) }").java_version.as_deref(), Some("21"));
+        let legacy = parse_gradle_script("sourceCompatibility = JavaVersion.VERSION_1_8\n");
+        assert_eq!(legacy.java_version.as_deref(), Some("1.8"));
+    }
+}
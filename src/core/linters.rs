@@ -0,0 +1,287 @@
+use crate::types::{LinterStatus, ProjectInfo};
+use std::fs;
+use std::path::Path;
+
+/// Where a formatter/linter can be configured within a project
+enum ConfigSource {
+    /// A dedicated config file
+    File(&'static str),
+    /// A section header inside a shared file, e.g. `[tool.ruff` in pyproject.toml
+    Section(&'static str, &'static str),
+    /// A top-level key in package.json
+    PackageJsonKey(&'static str),
+}
+
+use ConfigSource::*;
+
+/// A formatter or linter, its binary, config locations and relevant ecosystems
+struct LinterDef {
+    name: &'static str,
+    binary: &'static str,
+    configs: &'static [ConfigSource],
+    ecosystems: &'static [&'static str],
+}
+
+static LINTERS: &[LinterDef] = &[
+    LinterDef {
+        name: "eslint",
+        binary: "eslint",
+        configs: &[
+            File(".eslintrc"), File(".eslintrc.js"), File(".eslintrc.cjs"), File(".eslintrc.json"),
+            File(".eslintrc.yml"), File(".eslintrc.yaml"), File("eslint.config.js"),
+            File("eslint.config.mjs"), File("eslint.config.cjs"), File("eslint.config.ts"),
+            PackageJsonKey("eslintConfig"),
+        ],
+        ecosystems: &["Node.js"],
+    },
+    LinterDef {
+        name: "prettier",
+        binary: "prettier",
+        configs: &[
+            File(".prettierrc"), File(".prettierrc.json"), File(".prettierrc.yml"),
+            File(".prettierrc.yaml"), File(".prettierrc.js"), File(".prettierrc.cjs"),
+            File(".prettierrc.toml"), File("prettier.config.js"), File("prettier.config.cjs"),
+            File("prettier.config.mjs"), PackageJsonKey("prettier"),
+        ],
+        ecosystems: &["Node.js"],
+    },
+    LinterDef {
+        name: "biome",
+        binary: "biome",
+        configs: &[File("biome.json"), File("biome.jsonc")],
+        ecosystems: &["Node.js"],
+    },
+    LinterDef {
+        name: "stylelint",
+        binary: "stylelint",
+        configs: &[
+            File(".stylelintrc"), File(".stylelintrc.json"), File(".stylelintrc.yml"),
+            File("stylelint.config.js"), PackageJsonKey("stylelint"),
+        ],
+        ecosystems: &[],
+    },
+    LinterDef {
+        name: "ruff",
+        binary: "ruff",
+        configs: &[File("ruff.toml"), File(".ruff.toml"), Section("pyproject.toml", "[tool.ruff")],
+        ecosystems: &["Python"],
+    },
+    LinterDef {
+        name: "black",
+        binary: "black",
+        configs: &[Section("pyproject.toml", "[tool.black")],
+        ecosystems: &["Python"],
+    },
+    LinterDef {
+        name: "flake8",
+        binary: "flake8",
+        configs: &[File(".flake8"), Section("setup.cfg", "[flake8]"), Section("tox.ini", "[flake8]")],
+        ecosystems: &["Python"],
+    },
+    LinterDef {
+        name: "pylint",
+        binary: "pylint",
+        configs: &[File(".pylintrc"), File("pylintrc"), Section("pyproject.toml", "[tool.pylint")],
+        ecosystems: &["Python"],
+    },
+    LinterDef {
+        name: "isort",
+        binary: "isort",
+        configs: &[File(".isort.cfg"), Section("pyproject.toml", "[tool.isort")],
+        ecosystems: &["Python"],
+    },
+    LinterDef {
+        name: "mypy",
+        binary: "mypy",
+        configs: &[File("mypy.ini"), File(".mypy.ini"), Section("pyproject.toml", "[tool.mypy")],
+        ecosystems: &["Python"],
+    },
+    LinterDef {
+        name: "rubocop",
+        binary: "rubocop",
+        configs: &[File(".rubocop.yml")],
+        ecosystems: &["Ruby"],
+    },
+    LinterDef {
+        name: "rustfmt",
+        binary: "rustfmt",
+        configs: &[File("rustfmt.toml"), File(".rustfmt.toml")],
+        ecosystems: &["Rust"],
+    },
+    LinterDef {
+        name: "clippy",
+        binary: "cargo-clippy",
+        configs: &[File("clippy.toml"), File(".clippy.toml")],
+        ecosystems: &["Rust"],
+    },
+    LinterDef {
+        name: "golangci-lint",
+        binary: "golangci-lint",
+        configs: &[File(".golangci.yml"), File(".golangci.yaml"), File(".golangci.toml"), File(".golangci.json")],
+        ecosystems: &["Go"],
+    },
+    LinterDef {
+        name: "clang-format",
+        binary: "clang-format",
+        configs: &[File(".clang-format"), File("_clang-format")],
+        ecosystems: &["C/C++"],
+    },
+    LinterDef {
+        name: "clang-tidy",
+        binary: "clang-tidy",
+        configs: &[File(".clang-tidy")],
+        ecosystems: &["C/C++"],
+    },
+    LinterDef {
+        name: "swiftlint",
+        binary: "swiftlint",
+        configs: &[File(".swiftlint.yml")],
+        ecosystems: &["Swift"],
+    },
+    LinterDef {
+        name: "swiftformat",
+        binary: "swiftformat",
+        configs: &[File(".swiftformat")],
+        ecosystems: &["Swift"],
+    },
+    LinterDef {
+        name: "shellcheck",
+        binary: "shellcheck",
+        configs: &[File(".shellcheckrc")],
+        ecosystems: &[],
+    },
+    LinterDef {
+        name: "hadolint",
+        binary: "hadolint",
+        configs: &[File(".hadolint.yaml"), File(".hadolint.yml")],
+        ecosystems: &[],
+    },
+    LinterDef {
+        name: "yamllint",
+        binary: "yamllint",
+        configs: &[File(".yamllint"), File(".yamllint.yml"), File(".yamllint.yaml")],
+        ecosystems: &[],
+    },
+    LinterDef {
+        name: "markdownlint",
+        binary: "markdownlint",
+        configs: &[
+            File(".markdownlint.json"), File(".markdownlint.jsonc"), File(".markdownlint.yaml"),
+            File(".markdownlint.yml"), File(".markdownlint-cli2.jsonc"),
+        ],
+        ecosystems: &[],
+    },
+    LinterDef {
+        name: "dprint",
+        binary: "dprint",
+        configs: &[File("dprint.json"), File(".dprint.json")],
+        ecosystems: &[],
+    },
+];
+
+/// Cross-reference installed formatters/linters with the project's configs.
+/// Reports tools with a config (installed or not) and tools installed for one
+/// of the project's ecosystems but left unconfigured.
+pub fn cross_reference(project: &ProjectInfo) -> Vec<LinterStatus> {
+    let package_json = read_package_json(&project.path);
+
+    LINTERS
+        .iter()
+        .filter_map(|linter| {
+            let config = find_config(&project.path, linter.configs, package_json.as_ref());
+            let installed = which::which(linter.binary).is_ok();
+
+            let relevant = linter
+                .ecosystems
+                .iter()
+                .any(|e| project.markers.iter().any(|m| m.ecosystem.starts_with(e)));
+
+            if config.is_some() || (installed && relevant) {
+                Some(LinterStatus {
+                    name: linter.name.to_string(),
+                    installed,
+                    config,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// The command a linter runs as (clippy is `cargo-clippy`)
+pub fn binary_of(name: &str) -> Option<&'static str> {
+    LINTERS.iter().find(|l| l.name == name).map(|l| l.binary)
+}
+
+fn read_package_json(dir: &Path) -> Option<serde_json::Value> {
+    let content = fs::read_to_string(dir.join("package.json")).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Return a description of the first config source present in the project
+fn find_config(
+    dir: &Path,
+    sources: &[ConfigSource],
+    package_json: Option<&serde_json::Value>,
+) -> Option<String> {
+    for source in sources {
+        match source {
+            File(name) => {
+                if dir.join(name).exists() {
+                    return Some(name.to_string());
+                }
+            }
+            Section(file, header) => {
+                let has_section = fs::read_to_string(dir.join(file))
+                    .map(|content| content.lines().any(|l| l.trim_start().starts_with(header)))
+                    .unwrap_or(false);
+                if has_section {
+                    return Some(format!("{} {}]", file, header.trim_end_matches(']')));
+                }
+            }
+            PackageJsonKey(key) => {
+                if package_json.and_then(|p| p.get(key)).is_some() {
+                    return Some(format!("package.json \"{}\"", key));
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_find_config() {
+        let dir = TempDir::new("linters");
+        fs::write(dir.join("pyproject.toml"), "[project]\nname = \"x\"\n\n[tool.ruff.lint]\nselect = [\"E\"]\n").unwrap();
+        fs::write(dir.join(".golangci.yml"), "linters: {}\n").unwrap();
+
+        let ruff = LINTERS.iter().find(|l| l.name == "ruff").unwrap();
+        assert_eq!(find_config(&dir, ruff.configs, None), Some("pyproject.toml [tool.ruff]".to_string()));
+
+        let golangci = LINTERS.iter().find(|l| l.name == "golangci-lint").unwrap();
+        assert_eq!(find_config(&dir, golangci.configs, None), Some(".golangci.yml".to_string()));
+
+        let black = LINTERS.iter().find(|l| l.name == "black").unwrap();
+        assert_eq!(find_config(&dir, black.configs, None), None);
+    }
+
+    #[test]
+    fn test_package_json_key() {
+        let pkg: serde_json::Value = serde_json::from_str(r#"{"prettier": {"semi": false}}"#).unwrap();
+        let prettier = LINTERS.iter().find(|l| l.name == "prettier").unwrap();
+        let dir = TempDir::new("linters-package-json");
+        assert_eq!(
+            find_config(&dir, prettier.configs, Some(&pkg)),
+            Some("package.json \"prettier\"".to_string())
+        );
+        // Tools are matched on the binary, so eslint_d is not eslint
+        assert_eq!(binary_of("clippy"), Some("cargo-clippy"));
+        assert_eq!(binary_of("eslint_d"), None);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::core::probe;
+use crate::types::{LspCoverage, LspServer, ProjectInfo};
+
+/// Known language servers and the ecosystem name prefixes they serve
+static LSP_SERVERS: &[(&str, &[&str])] = &[
+    ("rust-analyzer", &["Rust"]),
+    ("gopls", &["Go"]),
+    ("pyright", &["Python"]),
+    ("basedpyright", &["Python"]),
+    ("pylsp", &["Python"]),
+    ("jedi-language-server", &["Python"]),
+    ("ruff-lsp", &["Python"]),
+    ("typescript-language-server", &["Node.js"]),
+    ("vtsls", &["Node.js"]),
+    ("clangd", &["C/C++"]),
+    ("ccls", &["C/C++"]),
+    ("jdtls", &["Java", "JVM"]),
+    ("kotlin-language-server", &["JVM"]),
+    ("solargraph", &["Ruby"]),
+    ("ruby-lsp", &["Ruby"]),
+    ("intelephense", &["PHP"]),
+    ("phpactor", &["PHP"]),
+    ("sourcekit-lsp", &["Swift"]),
+    ("elixir-ls", &["Elixir"]),
+    ("lexical", &["Elixir"]),
+    ("omnisharp", &[".NET"]),
+    ("csharp-ls", &[".NET"]),
+    ("lua-language-server", &[]),
+    ("zls", &[]),
+    ("haskell-language-server-wrapper", &[]),
+    ("bash-language-server", &[]),
+    ("yaml-language-server", &[]),
+    ("taplo", &[]),
+    ("marksman", &[]),
+    ("terraform-ls", &[]),
+];
+
+/// Find installed language servers and probe their versions
+pub fn detect_lsp_servers() -> Vec<LspServer> {
+    LSP_SERVERS
+        .iter()
+        .filter_map(|(name, ecosystems)| {
+            let path = which::which(name).ok()?;
+            let version = probe::probe_version(&path.to_string_lossy()).version;
+            Some(LspServer {
+                name: name.to_string(),
+                path,
+                version,
+                ecosystems: ecosystems.iter().map(|e| e.to_string()).collect(),
+            })
+        })
+        .collect()
+}
+
+/// Match detected project ecosystems against installed language servers
+pub fn match_project(servers: &[LspServer], project: &ProjectInfo) -> Vec<LspCoverage> {
+    let mut ecosystems: Vec<&str> = project.markers.iter().map(|m| m.ecosystem.as_str()).collect();
+    ecosystems.sort();
+    ecosystems.dedup();
+
+    ecosystems
+        .into_iter()
+        .filter_map(|ecosystem| {
+            let candidates: Vec<&str> = LSP_SERVERS
+                .iter()
+                .filter(|(_, prefixes)| prefixes.iter().any(|p| ecosystem.starts_with(p)))
+                .map(|(name, _)| *name)
+                .collect();
+
+            // No known language server for this ecosystem
+            if candidates.is_empty() {
+                return None;
+            }
+
+            let installed: Vec<String> = servers
+                .iter()
+                .filter(|s| candidates.contains(&s.name.as_str()))
+                .map(|s| s.name.clone())
+                .collect();
+
+            Some(LspCoverage {
+                ecosystem: ecosystem.to_string(),
+                servers: installed,
+                suggested: candidates.first().map(|c| c.to_string()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DetectedMarker;
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    fn project_with(ecosystems: &[&str]) -> ProjectInfo {
+        ProjectInfo {
+            path: PathBuf::from("."),
+            markers: ecosystems
+                .iter()
+                .map(|e| DetectedMarker { file: "marker".to_string(), ecosystem: e.to_string() })
+                .collect(),
+            ecosystems: BTreeMap::new(),
+            scripts: Vec::new(),
+            workspace: None,
+            subprojects: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_match_project() {
+        let servers = vec![LspServer {
+            name: "rust-analyzer".to_string(),
+            path: PathBuf::from("/usr/bin/rust-analyzer"),
+            version: Some("1.75.0".to_string()),
+            ecosystems: vec!["Rust".to_string()],
+        }];
+
+        let coverage = match_project(&servers, &project_with(&["Rust", "Go", "Elixir"]));
+        let rust = coverage.iter().find(|c| c.ecosystem == "Rust").unwrap();
+        assert_eq!(rust.servers, vec!["rust-analyzer"]);
+
+        let go = coverage.iter().find(|c| c.ecosystem == "Go").unwrap();
+        assert!(go.servers.is_empty());
+        assert_eq!(go.suggested.as_deref(), Some("gopls"));
+    }
+
+    #[test]
+    fn test_match_project_unknown_ecosystem() {
+        assert!(match_project(&[], &project_with(&["Cobol"])).is_empty());
+    }
+}
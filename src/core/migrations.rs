@@ -0,0 +1,220 @@
+use crate::core::exec;
+use crate::types::MigrationTool;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Detect database migration frameworks and count their migration files
+pub fn detect_migrations(dir: &Path) -> Vec<MigrationTool> {
+    [alembic(dir), flyway(dir), liquibase(dir), diesel(dir), sqlx(dir), prisma(dir), knex(dir)]
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+fn tool(dir: &Path, name: &str, config: &str, migrations_dir: &str, migrations: usize, cli: &str) -> MigrationTool {
+    // JS tools are usually run from node_modules/.bin via npx
+    let local_bin = dir.join("node_modules/.bin").join(cli);
+    MigrationTool {
+        tool: name.to_string(),
+        config: config.to_string(),
+        directory: migrations_dir.to_string(),
+        migrations,
+        cli: cli.to_string(),
+        cli_installed: local_bin.exists() || exec::command_exists(cli),
+    }
+}
+
+/// `key = value` from an ini/properties file
+fn read_setting(file: &Path, key: &str) -> Option<String> {
+    let content = fs::read_to_string(file).ok()?;
+    let re = Regex::new(&format!(r"(?m)^\s*{}\s*[=:]\s*(.+?)\s*$", regex::escape(key))).ok()?;
+    re.captures(&content).map(|caps| caps[1].trim_matches(['"', '\'']).to_string())
+}
+
+/// Files under `dir` (to `depth` levels) accepted by `keep`
+fn count_files(dir: &Path, depth: usize, keep: impl Fn(&str) -> bool) -> usize {
+    WalkDir::new(dir)
+        .max_depth(depth)
+        .into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_file() && e.file_name().to_str().is_some_and(&keep))
+        .count()
+}
+
+fn alembic(dir: &Path) -> Option<MigrationTool> {
+    let ini = dir.join("alembic.ini");
+    if !ini.is_file() {
+        return None;
+    }
+    let location = read_setting(&ini, "script_location")
+        .map(|l| l.trim_start_matches("%(here)s/").to_string())
+        .unwrap_or_else(|| "alembic".to_string());
+    let versions = format!("{}/versions", location);
+    let count = count_files(&dir.join(&versions), 1, |name| name.ends_with(".py") && name != "__init__.py");
+    Some(tool(dir, "alembic", "alembic.ini", &versions, count, "alembic"))
+}
+
+fn flyway(dir: &Path) -> Option<MigrationTool> {
+    let config = ["flyway.conf", "flyway.toml"].into_iter().find(|c| dir.join(c).is_file())?;
+    let location = read_setting(&dir.join(config), "flyway.locations")
+        .or_else(|| read_setting(&dir.join(config), "locations"))
+        .and_then(|l| l.split(',').next().map(|l| l.trim_matches(['[', ']', '"']).to_string()))
+        .and_then(|l| l.strip_prefix("filesystem:").map(String::from))
+        .unwrap_or_else(|| "sql".to_string());
+    // Versioned (V1__init.sql) and repeatable (R__views.sql) migrations
+    let count = count_files(&dir.join(&location), usize::MAX, |name| {
+        (name.starts_with('V') || name.starts_with('R')) && name.contains("__") && name.ends_with(".sql")
+    });
+    Some(tool(dir, "flyway", config, &location, count, "flyway"))
+}
+
+fn liquibase(dir: &Path) -> Option<MigrationTool> {
+    let props = dir.join("liquibase.properties");
+    if !props.is_file() {
+        return None;
+    }
+    let changelog = read_setting(&props, "changeLogFile").or_else(|| read_setting(&props, "changelog-file"))?;
+    let mut visited = HashSet::new();
+    let count = count_changesets(dir, &dir.join(&changelog), &mut visited);
+    Some(tool(dir, "liquibase", "liquibase.properties", &changelog, count, "liquibase"))
+}
+
+/// Changesets in a changelog and the changelogs it includes; paths resolve
+/// against the project (the default search path) or the including file
+fn count_changesets(dir: &Path, changelog: &Path, visited: &mut HashSet<PathBuf>) -> usize {
+    let Ok(content) = fs::read_to_string(changelog) else {
+        return 0;
+    };
+    if !visited.insert(changelog.to_path_buf()) {
+        return 0;
+    }
+    let (changesets, includes) = match changelog.extension().and_then(|e| e.to_str()) {
+        Some("sql") => (Regex::new(r"(?mi)^--\s*changeset\s").unwrap(), None),
+        Some("xml") => (
+            Regex::new(r"<(?:\w+:)?changeSet[\s/>]").unwrap(),
+            Some(Regex::new(r#"<(?:\w+:)?(include|includeAll)\s[^>]*?\b(?:file|path)\s*=\s*"([^"]+)""#).unwrap()),
+        ),
+        // YAML and JSON changelogs: `- changeSet:` entries and `include: {file: ...}`
+        _ => (
+            Regex::new(r#"(?m)^\s*-?\s*"?changeSet"?\s*:"#).unwrap(),
+            Some(Regex::new(r#""?(include|includeAll)"?\s*:\s*\{?\s*"?(?:file|path)"?\s*:\s*"?([^"\s,}]+)"#).unwrap()),
+        ),
+    };
+    // Commented-out XML is not part of the changelog
+    let content = Regex::new(r"(?s)<!--.*?-->").unwrap().replace_all(&content, "");
+    let mut count = changesets.find_iter(&content).count();
+    for caps in includes.iter().flat_map(|re| re.captures_iter(&content)) {
+        let parent = changelog.parent().unwrap_or(dir);
+        let target = [dir.join(&caps[2]), parent.join(&caps[2])].into_iter().find(|p| p.exists());
+        let Some(target) = target else { continue };
+        if &caps[1] == "includeAll" {
+            let mut files: Vec<PathBuf> = WalkDir::new(&target)
+                .into_iter()
+                .flatten()
+                .filter(|e| e.file_type().is_file())
+                .map(|e| e.into_path())
+                .collect();
+            files.sort();
+            count += files.iter().map(|file| count_changesets(dir, file, visited)).sum::<usize>();
+        } else {
+            count += count_changesets(dir, &target, visited);
+        }
+    }
+    count
+}
+
+fn diesel(dir: &Path) -> Option<MigrationTool> {
+    let config = dir.join("diesel.toml");
+    let content = fs::read_to_string(&config).ok()?;
+    let migrations = content
+        .parse::<toml::Table>()
+        .ok()
+        .and_then(|t| t.get("migrations_directory")?.get("dir")?.as_str().map(String::from))
+        .unwrap_or_else(|| "migrations".to_string());
+    // Each migration is a directory holding up.sql/down.sql
+    let count = count_files(&dir.join(&migrations), 2, |name| name == "up.sql");
+    Some(tool(dir, "diesel", "diesel.toml", &migrations, count, "diesel"))
+}
+
+fn sqlx(dir: &Path) -> Option<MigrationTool> {
+    let manifest = fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+    let migrations = dir.join("migrations");
+    if !manifest.lines().any(|l| l.trim_start().starts_with("sqlx")) || !migrations.is_dir() || dir.join("diesel.toml").exists() {
+        return None;
+    }
+    // Reversible migrations come in .up.sql/.down.sql pairs
+    let count = count_files(&migrations, 1, |name| name.ends_with(".sql") && !name.ends_with(".down.sql"));
+    Some(tool(dir, "sqlx", "Cargo.toml", "migrations", count, "sqlx"))
+}
+
+fn prisma(dir: &Path) -> Option<MigrationTool> {
+    if !dir.join("prisma/schema.prisma").is_file() {
+        return None;
+    }
+    let count = count_files(&dir.join("prisma/migrations"), 2, |name| name == "migration.sql");
+    Some(tool(dir, "prisma", "prisma/schema.prisma", "prisma/migrations", count, "prisma"))
+}
+
+fn knex(dir: &Path) -> Option<MigrationTool> {
+    let config = ["knexfile.js", "knexfile.ts", "knexfile.cjs", "knexfile.mjs"]
+        .into_iter()
+        .find(|c| dir.join(c).is_file())?;
+    let migrations = fs::read_to_string(dir.join(config))
+        .ok()
+        .and_then(|c| {
+            let re = Regex::new(r#"directory\s*:\s*['"]([^'"]+)['"]"#).unwrap();
+            re.captures(&c).map(|caps| caps[1].trim_start_matches("./").to_string())
+        })
+        .unwrap_or_else(|| "migrations".to_string());
+    let count = count_files(&dir.join(&migrations), 1, |name| {
+        [".js", ".ts", ".cjs", ".mjs"].iter().any(|ext| name.ends_with(ext))
+    });
+    Some(tool(dir, "knex", config, &migrations, count, "knex"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_detect_migrations() {
+        let dir = TempDir::new("migrations");
+        fs::create_dir_all(dir.join("db/alembic/versions")).unwrap();
+        fs::write(dir.join("alembic.ini"), "[alembic]\nscript_location = db/alembic\n").unwrap();
+        for file in ["1a2b_init.py", "3c4d_users.py", "__init__.py"] {
+            fs::write(dir.join("db/alembic/versions").join(file), "").unwrap();
+        }
+        for migration in ["2024-01-01-000000_create_users", "2024-02-01-000000_add_email"] {
+            fs::create_dir_all(dir.join("migrations").join(migration)).unwrap();
+            fs::write(dir.join("migrations").join(migration).join("up.sql"), "").unwrap();
+        }
+        fs::write(dir.join("diesel.toml"), "[print_schema]\nfile = \"src/schema.rs\"\n").unwrap();
+        // Only changesets reachable from the changelog count, not comments or stray files
+        fs::create_dir_all(dir.join("db/changelog/changes")).unwrap();
+        fs::write(dir.join("liquibase.properties"), "changeLogFile: db/changelog/master.xml\n").unwrap();
+        fs::write(
+            dir.join("db/changelog/master.xml"),
+            "<databaseChangeLog>\n<!-- <changeSet id=\"old\"/> -->\n<changeSet id=\"1\" author=\"a\">\n</changeSet>\n\
+             <include file=\"db/changelog/users.yaml\"/>\n<includeAll path=\"db/changelog/changes\"/>\n</databaseChangeLog>\n",
+        )
+        .unwrap();
+        fs::write(dir.join("db/changelog/users.yaml"), "databaseChangeLog:\n  - changeSet:\n      id: 2\n").unwrap();
+        fs::write(dir.join("db/changelog/changes/003.sql"), "--liquibase formatted sql\n--changeset a:3\nSELECT 1;\n").unwrap();
+        fs::write(dir.join("db/changelog/notes.md"), "Every changeSet needs an author\n").unwrap();
+
+        let found: Vec<(String, String, usize)> =
+            detect_migrations(&dir).into_iter().map(|m| (m.tool, m.directory, m.migrations)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("alembic".to_string(), "db/alembic/versions".to_string(), 2),
+                ("liquibase".to_string(), "db/changelog/master.xml".to_string(), 3),
+                ("diesel".to_string(), "migrations".to_string(), 2),
+            ]
+        );
+    }
+}
@@ -1,6 +1,76 @@
+pub mod aliases;
+pub mod apis;
+pub mod cache;
+pub mod check;
+pub mod ci;
 pub mod classify;
+pub mod compat_env;
+pub mod coverage;
+pub mod cross;
+pub mod cxx;
+pub mod daemon;
+pub mod dashboard;
+pub mod databases;
+pub mod deterministic;
+pub mod devenv;
+pub mod diff;
+pub mod docs;
+pub mod doctor;
+pub mod dotfiles;
 pub mod exec;
+pub mod explain;
+pub mod fleet;
+pub mod forges;
+pub mod frameworks;
+pub mod git;
+pub mod guix;
+pub mod hook;
+pub mod i18n;
+pub mod ignore;
+pub mod interrupt;
+pub mod identifiers;
+pub mod integrity;
+pub mod jdk;
+pub mod jvm;
+pub mod linters;
+pub mod lsp;
+pub mod migrations;
+pub mod monorepo;
+pub mod network;
+pub mod nix;
+pub mod node;
+pub mod notify;
+pub mod osquery;
 pub mod output;
+pub mod package_registries;
+pub mod path_audit;
 pub mod path_scan;
+pub mod paths;
+pub mod php;
+pub mod pins;
 pub mod probe;
 pub mod project_detect;
+pub mod protobuf;
+pub mod proxy;
+pub mod push;
+pub mod python;
+pub mod redact;
+pub mod registries;
+pub mod release;
+pub mod requirements;
+pub mod rpc;
+pub mod ruby;
+pub mod security;
+pub mod services;
+pub mod shell;
+pub mod snapshot;
+pub mod ssh;
+pub mod stats;
+pub mod system;
+pub mod tasks;
+#[cfg(test)]
+pub mod test_util;
+pub mod time;
+pub mod version_managers;
+pub mod virt;
+pub mod wrappers;
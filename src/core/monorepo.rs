@@ -0,0 +1,248 @@
+use crate::core::{exec, probe};
+use crate::types::{MonorepoOrchestrator, WorkspaceRoot};
+use regex::Regex;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// (config file, orchestrator, npm package, binary)
+static ORCHESTRATORS: &[(&str, &str, &str, &str)] = &[
+    ("nx.json", "nx", "nx", "nx"),
+    ("turbo.json", "turborepo", "turbo", "turbo"),
+    ("lerna.json", "lerna", "lerna", "lerna"),
+    ("rush.json", "rush", "@microsoft/rush", "rush"),
+];
+
+/// Detect monorepo orchestrators configured at the project root
+pub fn detect_orchestrators(dir: &Path) -> Vec<MonorepoOrchestrator> {
+    let manifest = read_json(&dir.join("package.json"));
+
+    ORCHESTRATORS
+        .iter()
+        .filter_map(|(config, name, package, binary)| {
+            let config_json = read_json(&dir.join(config))?;
+            let pinned = if *name == "rush" {
+                config_json.get("rushVersion").and_then(|v| v.as_str()).map(String::from)
+            } else {
+                manifest.as_ref().and_then(|m| dependency_spec(m, package))
+            };
+
+            Some(MonorepoOrchestrator {
+                name: name.to_string(),
+                config: config.to_string(),
+                pinned_version: pinned,
+                installed_version: installed_version(dir, package, binary),
+                packages: count_packages(dir, name, &config_json, manifest.as_ref()),
+            })
+        })
+        .collect()
+}
+
+/// The workspace this directory's manifest declares, if it is a workspace root
+pub fn workspace_root(dir: &Path) -> Option<WorkspaceRoot> {
+    let root = |kind: &str, manifest: &str, members: Vec<String>| {
+        Some(WorkspaceRoot { kind: kind.to_string(), manifest: manifest.to_string(), members })
+    };
+
+    if dir.join("pnpm-workspace.yaml").is_file() {
+        return root("pnpm", "pnpm-workspace.yaml", workspace_patterns(dir, None));
+    }
+    let manifest = read_json(&dir.join("package.json"));
+    if manifest.as_ref().is_some_and(|m| m.get("workspaces").is_some()) {
+        let kind = if dir.join("yarn.lock").is_file() { "yarn" } else { "npm" };
+        return root(kind, "package.json", workspace_patterns(dir, manifest.as_ref()));
+    }
+
+    let cargo = fs::read_to_string(dir.join("Cargo.toml")).ok().and_then(|c| c.parse::<toml::Table>().ok());
+    if let Some(workspace) = cargo.as_ref().and_then(|c| c.get("workspace")) {
+        let members = workspace.get("members").and_then(|m| m.as_array()).map_or_else(Vec::new, |m| {
+            m.iter().filter_map(|v| v.as_str().map(String::from)).collect()
+        });
+        return root("cargo", "Cargo.toml", members);
+    }
+
+    let go_work = fs::read_to_string(dir.join("go.work")).ok()?;
+    root("go", "go.work", go_work_uses(&go_work))
+}
+
+/// Directories named by `use` lines or a `use ( ... )` block
+fn go_work_uses(content: &str) -> Vec<String> {
+    let mut in_block = false;
+    let mut members = Vec::new();
+    for line in content.lines().map(|l| l.split("//").next().unwrap_or("").trim()) {
+        let item = if in_block {
+            in_block = line != ")";
+            line.trim_end_matches(')')
+        } else if let Some(rest) = line.strip_prefix("use") {
+            let rest = rest.trim();
+            in_block = rest == "(";
+            rest.trim_start_matches('(')
+        } else {
+            continue;
+        };
+        let item = item.trim().trim_matches('"');
+        if !item.is_empty() {
+            members.push(item.to_string());
+        }
+    }
+    members
+}
+
+/// Parse JSON, tolerating the comments rush.json and friends allow
+fn read_json(file: &Path) -> Option<Value> {
+    let content = fs::read_to_string(file).ok()?;
+    serde_json::from_str(&content).ok().or_else(|| {
+        let block = Regex::new(r"(?s)/\*.*?\*/").unwrap();
+        let line = Regex::new(r"(?m)^\s*//.*$").unwrap();
+        serde_json::from_str(&line.replace_all(&block.replace_all(&content, ""), "")).ok()
+    })
+}
+
+/// Version spec of a package in dependencies or devDependencies
+fn dependency_spec(manifest: &Value, package: &str) -> Option<String> {
+    ["devDependencies", "dependencies"]
+        .iter()
+        .find_map(|section| manifest.get(section)?.get(package)?.as_str().map(String::from))
+}
+
+/// The copy in node_modules wins, since that's what `npx`/scripts run;
+/// otherwise fall back to the global binary
+fn installed_version(dir: &Path, package: &str, binary: &str) -> Option<String> {
+    let local = dir.join("node_modules").join(package).join("package.json");
+    if let Some(version) = read_json(&local).and_then(|m| m.get("version")?.as_str().map(String::from)) {
+        return Some(version);
+    }
+    if !exec::command_exists(binary) {
+        return None;
+    }
+    exec::execute_for_output_in(dir, binary, &["--version"]).and_then(|out| probe::extract_version(&out))
+}
+
+/// Number of packages the orchestrator manages
+fn count_packages(dir: &Path, name: &str, config: &Value, manifest: Option<&Value>) -> usize {
+    if name == "rush" {
+        return config.get("projects").and_then(|p| p.as_array()).map_or(0, Vec::len);
+    }
+
+    let mut patterns = workspace_patterns(dir, manifest);
+    if name == "lerna" {
+        if let Some(packages) = config.get("packages").and_then(|p| p.as_array()) {
+            patterns = packages.iter().filter_map(|p| p.as_str().map(String::from)).collect();
+        }
+    }
+    if patterns.is_empty() && name == "nx" {
+        // Integrated nx repos describe each project with a project.json
+        return WalkDir::new(dir)
+            .max_depth(4)
+            .into_iter()
+            .filter_entry(|e| e.file_name() != "node_modules" && e.file_name() != ".git")
+            .flatten()
+            .filter(|e| e.file_name() == "project.json")
+            .count();
+    }
+    expand_patterns(dir, &patterns)
+}
+
+/// npm/yarn `workspaces` (array or `{packages: [...]}`), or pnpm-workspace.yaml
+fn workspace_patterns(dir: &Path, manifest: Option<&Value>) -> Vec<String> {
+    let workspaces = manifest.and_then(|m| m.get("workspaces"));
+    let list = workspaces.and_then(|w| w.as_array().or_else(|| w.get("packages")?.as_array()));
+    if let Some(list) = list {
+        return list.iter().filter_map(|p| p.as_str().map(String::from)).collect();
+    }
+
+    let Ok(content) = fs::read_to_string(dir.join("pnpm-workspace.yaml")) else {
+        return Vec::new();
+    };
+    let mut in_packages = false;
+    let mut patterns = Vec::new();
+    for line in content.lines() {
+        if !line.starts_with(char::is_whitespace) && !line.starts_with('-') {
+            in_packages = line.trim() == "packages:";
+            continue;
+        }
+        if let Some(item) = line.trim().strip_prefix('-').filter(|_| in_packages) {
+            patterns.push(item.trim().trim_matches(['"', '\'']).to_string());
+        }
+    }
+    patterns
+}
+
+/// Count directories with a package.json matched by workspace globs
+/// (`packages/*`, `apps/**`, or literal paths; `!` exclusions are ignored)
+fn expand_patterns(dir: &Path, patterns: &[String]) -> usize {
+    let mut found = std::collections::BTreeSet::new();
+    for pattern in patterns.iter().filter(|p| !p.starts_with('!')) {
+        let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+        let (base, depth) = if let Some(base) = pattern.strip_suffix("/**") {
+            (base, usize::MAX)
+        } else if let Some(base) = pattern.strip_suffix("/*") {
+            (base, 1)
+        } else {
+            (pattern, 0)
+        };
+
+        for entry in WalkDir::new(dir.join(base))
+            .max_depth(depth)
+            .into_iter()
+            .filter_entry(|e| e.file_name() != "node_modules")
+            .flatten()
+        {
+            if entry.file_type().is_dir() && entry.path().join("package.json").is_file() {
+                found.insert(entry.path().to_path_buf());
+            }
+        }
+    }
+    found.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_detect_orchestrators() {
+        let dir = TempDir::new("monorepo");
+        for pkg in ["packages/a", "packages/b", "apps/web"] {
+            fs::create_dir_all(dir.join(pkg)).unwrap();
+            fs::write(dir.join(pkg).join("package.json"), "{}").unwrap();
+        }
+        fs::create_dir_all(dir.join("node_modules/turbo")).unwrap();
+        fs::write(dir.join("node_modules/turbo/package.json"), r#"{"version": "1.13.2"}"#).unwrap();
+        fs::write(
+            dir.join("package.json"),
+            r#"{"workspaces": ["packages/*", "apps/*"], "devDependencies": {"turbo": "^1.13.0"}}"#,
+        )
+        .unwrap();
+        fs::write(dir.join("turbo.json"), "{}").unwrap();
+        fs::write(
+            dir.join("rush.json"),
+            "/* rush config */\n{\n  // pinned by rush\n  \"rushVersion\": \"5.112.0\",\n  \"projects\": [{\"packageName\": \"a\"}]\n}\n",
+        )
+        .unwrap();
+
+        let found = detect_orchestrators(&dir);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].name, "turborepo");
+        assert_eq!(found[0].pinned_version.as_deref(), Some("^1.13.0"));
+        assert_eq!(found[0].installed_version.as_deref(), Some("1.13.2"));
+        assert_eq!(found[0].packages, 3);
+        assert_eq!((found[1].pinned_version.as_deref(), found[1].packages), (Some("5.112.0"), 1));
+    }
+
+    #[test]
+    fn test_workspace_root() {
+        let dir = TempDir::new("workspace");
+        assert!(workspace_root(&dir).is_none());
+
+        fs::write(dir.join("Cargo.toml"), "[workspace]\nmembers = [\"crates/*\", \"cli\"]\n").unwrap();
+        let cargo = workspace_root(&dir).unwrap();
+        assert_eq!((cargo.kind.as_str(), cargo.members), ("cargo", vec!["crates/*".to_string(), "cli".to_string()]));
+
+        fs::remove_file(dir.join("Cargo.toml")).unwrap();
+        fs::write(dir.join("go.work"), "go 1.22\n\nuse ./tools // local\nuse (\n\t./api\n\t./web\n)\n").unwrap();
+        assert_eq!(workspace_root(&dir).unwrap().members, ["./tools", "./api", "./web"]);
+    }
+}
@@ -0,0 +1,147 @@
+use crate::types::{NetworkCheck, PackageRegistry};
+use rayon::prelude::*;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fs;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::time::Duration;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Public hosts everyone depends on; their outages aren't what this check is for
+static PUBLIC_HOSTS: &[&str] = &[
+    "registry.npmjs.org",
+    "registry.yarnpkg.com",
+    "registry.npmmirror.com",
+    "pypi.org",
+    "pypi.python.org",
+    "files.pythonhosted.org",
+    "crates.io",
+    "index.crates.io",
+    "static.crates.io",
+    "rubygems.org",
+    "repo.packagist.org",
+    "packagist.org",
+    "repo.maven.apache.org",
+    "repo1.maven.org",
+    "plugins.gradle.org",
+    "github.com",
+    "codeload.github.com",
+    "objects.githubusercontent.com",
+    "gitlab.com",
+    "bitbucket.org",
+    "proxy.golang.org",
+    "www.w3.org",
+    "json-schema.org",
+    "maven.apache.org",
+    "docs.npmjs.com",
+    "pip.pypa.io",
+];
+
+/// Check that the custom registries, indexes and mirrors configured for npm,
+/// pip, Cargo and Maven (`registries`, from package_registries) and the
+/// project's git submodule remotes accept a connection, to catch
+/// "dependencies won't download because the VPN is down"
+pub fn check_hosts(dir: &Path, registries: &[PackageRegistry]) -> Vec<NetworkCheck> {
+    let mut hosts: BTreeMap<(String, u16), Vec<String>> = BTreeMap::new();
+    let mut add = |endpoint: (String, u16), source: &str| {
+        let sources = hosts.entry(endpoint).or_default();
+        if !sources.iter().any(|s| s == source) {
+            sources.push(source.to_string());
+        }
+    };
+    for registry in registries {
+        if let (Some(host), Some(port)) = (&registry.host, registry.port) {
+            let host = host.rsplit_once(':').map_or(host.as_str(), |(host, _)| host);
+            add((host.to_string(), port), &registry.source);
+        }
+    }
+    if let Ok(content) = fs::read_to_string(dir.join(".gitmodules")) {
+        for endpoint in submodule_remotes(&content) {
+            add(endpoint, ".gitmodules");
+        }
+    }
+
+    hosts
+        .into_iter()
+        .filter(|((host, _), _)| is_private(host))
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|((host, port), sources)| probe(host, port, sources))
+        .collect()
+}
+
+/// (host, port) of the `url =` of each submodule, as a URL or scp-style remote
+fn submodule_remotes(content: &str) -> Vec<(String, u16)> {
+    let url = Regex::new(r"^([a-z][a-z0-9+]*)://(?:[^@/\s]+@)?([A-Za-z0-9.-]+)(?::(\d+))?").unwrap();
+    let scp = Regex::new(r"^[\w.-]+@([A-Za-z0-9.-]+):").unwrap();
+
+    let mut found: Vec<(String, u16)> = Vec::new();
+    for line in content.lines() {
+        let Some(("url", value)) = line.split_once('=').map(|(k, v)| (k.trim(), v.trim())) else {
+            continue;
+        };
+        if let Some(caps) = url.captures(value) {
+            let default_port = match caps[1].rsplit('+').next().unwrap_or_default() {
+                "https" => 443,
+                "http" => 80,
+                "ssh" => 22,
+                "git" => 9418,
+                _ => continue,
+            };
+            let port = caps.get(3).and_then(|p| p.as_str().parse().ok()).unwrap_or(default_port);
+            found.push((caps[2].to_lowercase(), port));
+        } else if let Some(caps) = scp.captures(value) {
+            found.push((caps[1].to_lowercase(), 22));
+        }
+    }
+    found.sort();
+    found.dedup();
+    found
+}
+
+/// Hosts worth probing: not a public service, not local
+fn is_private(host: &str) -> bool {
+    host.contains('.') && !PUBLIC_HOSTS.contains(&host) && host != "localhost" && !host.ends_with(".local")
+}
+
+fn probe(host: String, port: u16, sources: Vec<String>) -> NetworkCheck {
+    let addresses: Vec<_> = match (host.as_str(), port).to_socket_addrs() {
+        Ok(addresses) => addresses.collect(),
+        Err(_) => Vec::new(),
+    };
+    let (reachable, error) = if addresses.is_empty() {
+        (false, Some("does not resolve".to_string()))
+    } else {
+        let mut last_error = None;
+        let connected = addresses.iter().any(|addr| match TcpStream::connect_timeout(addr, CONNECT_TIMEOUT) {
+            Ok(_) => true,
+            Err(e) => {
+                last_error = Some(e.to_string());
+                false
+            }
+        });
+        (connected, if connected { None } else { last_error })
+    };
+    NetworkCheck { host, port, sources, resolved: !addresses.is_empty(), reachable, error }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_submodule_remotes() {
+        let gitmodules = "[submodule \"ui\"]\n\tpath = ui\n\turl = ssh://git@git.internal.acme.io:2222/web/ui-kit.git\n\
+                          [submodule \"gems\"]\n\tpath = gems\n\turl = git@gitlab.acme.io:platform/gems.git\n\
+                          [submodule \"docs\"]\n\tpath = docs\n\turl = https://github.com/acme/docs\n";
+        let remotes = submodule_remotes(gitmodules);
+        assert_eq!(
+            remotes,
+            vec![("git.internal.acme.io".to_string(), 2222), ("github.com".to_string(), 443), ("gitlab.acme.io".to_string(), 22)]
+        );
+        let private: Vec<_> = remotes.iter().filter(|(host, _)| is_private(host)).collect();
+        assert_eq!(private.len(), 2);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::core::paths;
+use crate::types::{StoreProvenance, Tool};
+use serde_json::Value;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+const NIX_STORE: &str = "/nix/store";
+
+/// Annotate tools that resolve into the Nix store with their package and profile
+pub fn annotate_tools(tools: &mut [Tool]) {
+    if !Path::new(NIX_STORE).is_dir() {
+        return;
+    }
+    let manifest = user_manifest();
+    for tool in tools.iter_mut() {
+        if let Some(store) = provenance(&tool.path, manifest.as_ref()) {
+            tool.store = Some(store);
+        }
+    }
+}
+
+/// Split a store directory name "<hash>-<name>-<version>" into (name, version).
+/// The version starts at the first dash followed by a digit, as in Nix's own parseDrvName.
+pub fn parse_store_name(dir_name: &str) -> (String, Option<String>) {
+    let name = match dir_name.split_once('-') {
+        Some((hash, rest)) if hash.len() == 32 => rest,
+        _ => dir_name,
+    };
+    let split = name
+        .char_indices()
+        .find(|&(i, c)| c == '-' && name[i + 1..].starts_with(|d: char| d.is_ascii_digit()))
+        .map(|(i, _)| i);
+    match split {
+        Some(i) => (name[..i].to_string(), Some(name[i + 1..].to_string())),
+        None => (name.to_string(), None),
+    }
+}
+
+/// The top-level store directory containing a path, e.g. /nix/store/<hash>-git-2.43.0
+pub fn store_dir(path: &Path, store: &str) -> Option<PathBuf> {
+    let rest = path.strip_prefix(store).ok()?;
+    match rest.components().next() {
+        Some(Component::Normal(dir)) => Some(Path::new(store).join(dir)),
+        _ => None,
+    }
+}
+
+/// Every store directory a symlink chain passes through, ending with the real binary's
+pub fn symlink_chain_stores(path: &Path, store: &str) -> Vec<PathBuf> {
+    let mut stores = Vec::new();
+    let mut current = path.to_path_buf();
+    // Profiles are a handful of hops deep; the cap guards against loops
+    for _ in 0..16 {
+        if let Some(dir) = store_dir(&current, store) {
+            if !stores.contains(&dir) {
+                stores.push(dir);
+            }
+        }
+        match fs::read_link(&current) {
+            Ok(target) => {
+                current = match current.parent() {
+                    Some(parent) if target.is_relative() => parent.join(target),
+                    _ => target,
+                };
+            }
+            Err(_) => break,
+        }
+    }
+    if let Some(dir) = fs::canonicalize(path).ok().and_then(|real| store_dir(&real, store)) {
+        stores.retain(|d| d != &dir);
+        stores.push(dir);
+    }
+    stores
+}
+
+fn provenance(path: &Path, manifest: Option<&Value>) -> Option<StoreProvenance> {
+    let chain = symlink_chain_stores(path, NIX_STORE);
+    let store_path = chain.last()?.clone();
+    let dir_name = store_path.file_name()?.to_string_lossy().to_string();
+    let (package, version) = parse_store_name(&dir_name);
+
+    let via_home_manager = chain.iter().any(|d| d.to_string_lossy().ends_with("-home-manager-path"));
+    let home = paths::home_dir().unwrap_or_default();
+
+    let (profile, source) = if path.starts_with("/run/current-system") {
+        ("NixOS system".to_string(), Some("NixOS configuration".to_string()))
+    } else if via_home_manager || path.starts_with("/etc/profiles/per-user") {
+        ("home-manager".to_string(), Some("home-manager configuration".to_string()))
+    } else if path.starts_with(home.join(".nix-profile")) || path.starts_with(home.join(".local/state/nix/profiles")) {
+        ("user profile".to_string(), manifest.and_then(|m| manifest_source(m, &chain)).or_else(channel_source))
+    } else if path.starts_with("/nix/var/nix/profiles/default") {
+        ("default profile".to_string(), None)
+    } else if path.starts_with(NIX_STORE) {
+        // Direct store paths on PATH come from nix-shell, nix develop or direnv
+        ("nix shell".to_string(), None)
+    } else {
+        ("symlink".to_string(), None)
+    };
+
+    Some(StoreProvenance {
+        manager: "nix".to_string(),
+        store_path,
+        package,
+        version,
+        profile,
+        source,
+    })
+}
+
+/// manifest.json of the user's `nix profile`, if it uses the new CLI
+fn user_manifest() -> Option<Value> {
+    let profile = paths::home_dir()?.join(".nix-profile");
+    let content = fs::read_to_string(profile.join("manifest.json")).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Flake reference of the profile element providing one of the given store paths
+fn manifest_source(manifest: &Value, stores: &[PathBuf]) -> Option<String> {
+    // Version 2 stores elements as an array, version 3 as an object keyed by name
+    let elements: Vec<&Value> = match manifest.get("elements")? {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(map) => map.values().collect(),
+        _ => return None,
+    };
+
+    elements.into_iter().find_map(|element| {
+        let provides = element
+            .get("storePaths")?
+            .as_array()?
+            .iter()
+            .filter_map(|p| p.as_str())
+            .any(|p| stores.iter().any(|s| s == Path::new(p)));
+        if !provides {
+            return None;
+        }
+        let url = element.get("originalUrl").or_else(|| element.get("url"))?.as_str()?;
+        let attr = element.get("attrPath").and_then(|a| a.as_str()).map(short_attr_path);
+        Some(match attr {
+            Some(attr) => format!("{}#{}", url, attr),
+            None => url.to_string(),
+        })
+    })
+}
+
+/// "legacyPackages.x86_64-linux.nodejs_20" -> "nodejs_20"
+fn short_attr_path(attr: &str) -> &str {
+    for prefix in ["legacyPackages.", "packages."] {
+        if let Some(rest) = attr.strip_prefix(prefix) {
+            return rest.split_once('.').map(|(_, a)| a).unwrap_or(rest);
+        }
+    }
+    attr
+}
+
+/// Channel for nix-env installs, from ~/.nix-channels ("<url> <name>" lines)
+fn channel_source() -> Option<String> {
+    let content = fs::read_to_string(paths::home_dir()?.join(".nix-channels")).ok()?;
+    let name = content.lines().find_map(|l| l.split_whitespace().nth(1))?;
+    Some(format!("channel {}", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_store_name() {
+        assert_eq!(
+            parse_store_name("0c4kd3p7mv5rcmgq9mfjchpr7kl9gqyv-nodejs-20.11.1"),
+            ("nodejs".to_string(), Some("20.11.1".to_string()))
+        );
+        assert_eq!(
+            parse_store_name("0c4kd3p7mv5rcmgq9mfjchpr7kl9gqyv-python3-3.11.7-env"),
+            ("python3".to_string(), Some("3.11.7-env".to_string()))
+        );
+        assert_eq!(
+            parse_store_name("0c4kd3p7mv5rcmgq9mfjchpr7kl9gqyv-home-manager-path"),
+            ("home-manager-path".to_string(), None)
+        );
+        assert_eq!(
+            store_dir(Path::new("/nix/store/abc-git-2.43.0/bin/git"), NIX_STORE),
+            Some(PathBuf::from("/nix/store/abc-git-2.43.0"))
+        );
+    }
+
+    #[test]
+    fn test_manifest_source() {
+        let manifest: Value = serde_json::from_str(
+            r#"{"version":2,"elements":[{"active":true,"attrPath":"legacyPackages.x86_64-linux.nodejs_20",
+                "originalUrl":"flake:nixpkgs","storePaths":["/nix/store/abc-nodejs-20.11.1"]}]}"#,
+        )
+        .unwrap();
+        let stores = vec![PathBuf::from("/nix/store/abc-nodejs-20.11.1")];
+        assert_eq!(manifest_source(&manifest, &stores).as_deref(), Some("flake:nixpkgs#nodejs_20"));
+        assert_eq!(manifest_source(&manifest, &[PathBuf::from("/nix/store/x-go-1.22")]), None);
+    }
+}
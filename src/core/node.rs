@@ -0,0 +1,132 @@
+use crate::core::{exec, paths, probe};
+use crate::types::{CorepackStatus, PackageManager};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Package managers `packageManager` may name
+static MANAGERS: &[&str] = &["npm", "yarn", "pnpm", "bun"];
+
+/// Managers corepack provides shims for by default
+static COREPACK_MANAGERS: &[&str] = &["yarn", "pnpm"];
+
+/// Apply package.json's `packageManager` pin ("pnpm@8.15.4+sha512...") on top
+/// of the manager the lock files picked. The pin wins; a lock file for a
+/// different manager is reported as a conflict
+pub fn package_manager(dir: &Path, from_lock: Option<PackageManager>) -> Option<PackageManager> {
+    let Some((name, pinned)) = read_pin(dir) else {
+        return from_lock;
+    };
+
+    let corepack = COREPACK_MANAGERS.contains(&name.as_str()).then(|| corepack_status(&name, &pinned));
+    let mut conflicts = Vec::new();
+    let mut lock_version = None;
+    match from_lock {
+        Some(lock) if lock.name == name => {
+            conflicts = lock.conflicts;
+            lock_version = lock.version;
+        }
+        Some(lock) => {
+            conflicts.push(lock.source);
+            conflicts.extend(lock.conflicts);
+        }
+        None => {}
+    }
+    let version = match &corepack {
+        // Corepack's shim runs the pinned version, but probing it would start
+        // a download when that version isn't cached yet
+        Some(status) if status.enabled => status.cached.then(|| pinned.clone()),
+        _ => lock_version.or_else(|| installed_version(dir, &name)),
+    };
+    Some(PackageManager { name, version, source: "package.json".to_string(), pinned: Some(pinned), conflicts, corepack })
+}
+
+fn corepack_status(name: &str, pinned: &str) -> CorepackStatus {
+    let enabled = which::which(name)
+        .ok()
+        .and_then(|p| fs::canonicalize(p).ok())
+        .is_some_and(|target| target.components().any(|c| c.as_os_str() == "corepack"));
+    CorepackStatus {
+        installed: exec::command_exists("corepack"),
+        enabled,
+        cached: corepack_home().is_some_and(|home| is_cached(&home, name, pinned)),
+    }
+}
+
+/// $COREPACK_HOME, else corepack's default under the XDG cache directory
+fn corepack_home() -> Option<PathBuf> {
+    env::var_os("COREPACK_HOME").filter(|h| !h.is_empty()).map(PathBuf::from).or_else(|| {
+        env::var_os("XDG_CACHE_HOME")
+            .filter(|d| !d.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| paths::home_dir().map(|h| h.join(".cache")))
+            .map(|cache| cache.join("node").join("corepack"))
+    })
+}
+
+/// Corepack 0.20+ stores versions under v1/, older releases at the top level
+fn is_cached(home: &Path, name: &str, version: &str) -> bool {
+    [home.join("v1"), home.to_path_buf()].iter().any(|base| base.join(name).join(version).is_dir())
+}
+
+/// Version of a manager as run from the project (yarn berry reads .yarnrc.yml there)
+fn installed_version(dir: &Path, name: &str) -> Option<String> {
+    if !exec::command_exists(name) {
+        return None;
+    }
+    exec::execute_for_output_in(dir, name, &["--version"]).and_then(|out| probe::extract_version(&out))
+}
+
+/// `packageManager` split into name and version, without the integrity hash
+fn read_pin(dir: &Path) -> Option<(String, String)> {
+    let content = fs::read_to_string(dir.join("package.json")).ok()?;
+    let manifest: serde_json::Value = serde_json::from_str(&content).ok()?;
+    parse_pin(manifest.get("packageManager")?.as_str()?)
+}
+
+fn parse_pin(pin: &str) -> Option<(String, String)> {
+    let (name, version) = pin.split_once('@')?;
+    let version = version.split('+').next().unwrap_or(version);
+    (MANAGERS.contains(&name) && !version.is_empty()).then(|| (name.to_string(), version.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_parse_pin() {
+        assert_eq!(parse_pin("pnpm@8.15.4+sha512.abc"), Some(("pnpm".to_string(), "8.15.4".to_string())));
+        assert_eq!(parse_pin("yarn@4.1.0"), Some(("yarn".to_string(), "4.1.0".to_string())));
+        assert_eq!(parse_pin("deno@1.40.0"), None);
+        assert_eq!(parse_pin("npm"), None);
+    }
+
+    #[test]
+    fn test_pin_overrides_lock() {
+        let dir = TempDir::new("node");
+        fs::write(dir.join("package.json"), r#"{"name": "app", "packageManager": "definitely-not-a-pm@1.0.0"}"#).unwrap();
+        let lock = PackageManager {
+            name: "npm".to_string(),
+            version: Some("10.2.4".to_string()),
+            source: "package-lock.json".to_string(),
+            pinned: None,
+            conflicts: Vec::new(),
+            corepack: None,
+        };
+        // Unknown managers are ignored
+        assert_eq!(package_manager(&dir, Some(lock.clone())).unwrap().source, "package-lock.json");
+
+        fs::write(dir.join("package.json"), r#"{"name": "app", "packageManager": "yarn@4.1.0"}"#).unwrap();
+        let manager = package_manager(&dir, Some(lock)).unwrap();
+        assert_eq!(manager.name, "yarn");
+        assert_eq!(manager.pinned.as_deref(), Some("4.1.0"));
+        assert_eq!(manager.conflicts, vec!["package-lock.json"]);
+        assert!(manager.corepack.is_some());
+
+        fs::create_dir_all(dir.join("v1/pnpm/8.15.4")).unwrap();
+        assert!(is_cached(&dir, "pnpm", "8.15.4"));
+        assert!(!is_cached(&dir, "yarn", "4.1.0"));
+    }
+}
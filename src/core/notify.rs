@@ -0,0 +1,254 @@
+use crate::core::{exec, push};
+use crate::types::{Finding, FleetReport, ScanResult, Severity};
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+const POST_TIMEOUT: Duration = Duration::from_secs(15);
+/// Discord rejects messages longer than this
+const DISCORD_LIMIT: usize = 2000;
+/// Keep chat messages compact; the rest is summarized as "...and N more"
+const MAX_LINES: usize = 15;
+
+/// Chat service a webhook URL belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    Slack,
+    Discord,
+    Teams,
+}
+
+impl Provider {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "slack" => Some(Provider::Slack),
+            "discord" => Some(Provider::Discord),
+            "teams" => Some(Provider::Teams),
+            _ => None,
+        }
+    }
+
+    /// Guess the provider from the webhook host
+    pub fn detect(url: &str) -> Option<Self> {
+        if url.contains("hooks.slack.com") {
+            Some(Provider::Slack)
+        } else if url.contains("discord.com/api/webhooks") || url.contains("discordapp.com/api/webhooks") {
+            Some(Provider::Discord)
+        } else if url.contains(".webhook.office.com") || url.contains(".logic.azure.com") {
+            Some(Provider::Teams)
+        } else {
+            None
+        }
+    }
+}
+
+/// A short title plus one line per notable finding
+pub struct Summary {
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+/// Summarize a single machine's scan, leading with doctor's errors and warnings
+pub fn summarize_scan(result: &ScanResult, findings: &[Finding]) -> Summary {
+    let host = result.hostname.as_deref().unwrap_or("unknown host");
+    let mut lines = Vec::new();
+
+    let count = |severity| findings.iter().filter(|f| f.severity == severity).count();
+    let (errors, warnings) = (count(Severity::Error), count(Severity::Warning));
+    if errors + warnings == 0 {
+        lines.push("Doctor: no problems found".to_string());
+    } else {
+        lines.push(format!("Doctor: {} errors, {} warnings", errors, warnings));
+    }
+    let mut serious: Vec<&Finding> = findings.iter().filter(|f| f.severity >= Severity::Warning).collect();
+    serious.sort_by_key(|f| std::cmp::Reverse(f.severity));
+    for finding in serious {
+        lines.push(format!("{}: {} — {}", finding.severity.display_name(), finding.subject, finding.issue));
+    }
+
+    if !result.global_tools.is_empty() {
+        lines.push(format!("{} developer tools found", result.global_tools.len()));
+    }
+    if let Some(project) = &result.project_info {
+        let mut ecosystems: Vec<&String> = project.ecosystems.keys().collect();
+        ecosystems.sort();
+        if !ecosystems.is_empty() {
+            let names: Vec<&str> = ecosystems.iter().map(|e| e.as_str()).collect();
+            lines.push(format!("Project ecosystems: {}", names.join(", ")));
+        }
+    }
+
+    let flagged = result.global_tools.iter().filter(|t| !t.security.is_empty()).count();
+    if flagged > 0 {
+        lines.push(format!("{} tools with security flags", flagged));
+    }
+    let tampered = result
+        .global_tools
+        .iter()
+        .filter(|t| t.integrity.as_ref().and_then(|i| i.verified) == Some(false))
+        .count();
+    if tampered > 0 {
+        lines.push(format!("{} tools fail integrity verification", tampered));
+    }
+    let manual = result.global_tools.iter().filter(|t| t.manual_install).count();
+    if manual > 0 {
+        lines.push(format!("{} manually installed tools in /usr/local or /opt", manual));
+    }
+    for req in result.ci_tools.iter().filter(|r| !r.installed) {
+        lines.push(format!("Missing {} (required by {})", req.tool, req.ci_file));
+    }
+    for linter in result.linters.iter().filter(|l| !l.installed) {
+        if let Some(config) = &linter.config {
+            lines.push(format!("Missing {} (configured in {})", linter.name, config));
+        }
+    }
+
+    Summary { title: format!("devfetch scan of {}", host), lines }
+}
+
+/// Summarize a fleet merge, leading with gaps and version outliers
+pub fn summarize_fleet(report: &FleetReport) -> Summary {
+    let mut lines = Vec::new();
+    for tool in &report.tools {
+        if !tool.missing_on.is_empty() {
+            lines.push(format!("{} machines missing {}", tool.missing_on.len(), tool.name));
+        }
+    }
+    for tool in &report.tools {
+        if !tool.outliers.is_empty() {
+            lines.push(format!("{} machines on outlier versions of {}", tool.outliers.len(), tool.name));
+        }
+    }
+    if lines.is_empty() {
+        lines.push("All machines consistent".to_string());
+    }
+
+    Summary {
+        title: format!("devfetch fleet report: {} machines, {} tools", report.machines.len(), report.tools.len()),
+        lines,
+    }
+}
+
+/// Lines to show, with overflow collapsed into a final count
+fn visible_lines(summary: &Summary) -> Vec<String> {
+    let mut lines: Vec<String> = summary.lines.iter().take(MAX_LINES).cloned().collect();
+    if summary.lines.len() > MAX_LINES {
+        lines.push(format!("...and {} more", summary.lines.len() - MAX_LINES));
+    }
+    lines
+}
+
+/// Build the webhook payload in the provider's message format
+pub fn render_payload(provider: Provider, summary: &Summary) -> Value {
+    let lines = visible_lines(summary);
+    match provider {
+        Provider::Slack => {
+            let body: Vec<String> = lines.iter().map(|l| format!("• {}", l)).collect();
+            json!({
+                "text": summary.title,
+                "blocks": [{
+                    "type": "section",
+                    "text": { "type": "mrkdwn", "text": format!("*{}*\n{}", summary.title, body.join("\n")) }
+                }]
+            })
+        }
+        Provider::Discord => {
+            let body: Vec<String> = lines.iter().map(|l| format!("- {}", l)).collect();
+            let mut content = format!("**{}**\n{}", summary.title, body.join("\n"));
+            if content.chars().count() > DISCORD_LIMIT {
+                content = content.chars().take(DISCORD_LIMIT - 1).collect::<String>() + "…";
+            }
+            json!({ "content": content })
+        }
+        Provider::Teams => {
+            let body: Vec<String> = lines.iter().map(|l| format!("- {}", l)).collect();
+            json!({
+                "@type": "MessageCard",
+                "@context": "https://schema.org/extensions",
+                "summary": summary.title,
+                "title": summary.title,
+                "text": body.join("\n"),
+            })
+        }
+    }
+}
+
+/// Post a summary to a chat webhook
+pub fn notify(url: &str, provider: Option<&str>, summary: &Summary) -> Result<()> {
+    let provider = match provider {
+        Some(name) => match Provider::from_name(name) {
+            Some(p) => p,
+            None => bail!("Unknown webhook format {:?}; use slack, discord or teams", name),
+        },
+        None => match Provider::detect(url) {
+            Some(p) => p,
+            // The URL itself is the webhook's secret, so it isn't echoed
+            None => bail!("Cannot tell the webhook provider from its URL; pass --notify-format"),
+        },
+    };
+
+    // The URL is the credential for Slack and Discord webhooks; it goes through
+    // stdin so other users can't read it from the process list
+    let payload = render_payload(provider, summary).to_string();
+    let config = push::curl_config(&[("url", url), ("data-binary", &payload)]);
+    let output = exec::execute_command_with_input(
+        "curl",
+        &["-sS", "-f", "-X", "POST", "-H", "Content-Type: application/json", "--config", "-"],
+        config.as_bytes(),
+        POST_TIMEOUT,
+    )?;
+    if !output.status.success() {
+        bail!("Webhook post failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_provider() {
+        assert_eq!(Provider::detect("https://hooks.slack.com/services/T0/B0/x"), Some(Provider::Slack));
+        assert_eq!(Provider::detect("https://discord.com/api/webhooks/1/abc"), Some(Provider::Discord));
+        assert_eq!(Provider::detect("https://acme.webhook.office.com/webhookb2/x"), Some(Provider::Teams));
+        assert_eq!(Provider::detect("https://example.com/hook"), None);
+    }
+
+    #[test]
+    fn test_render_payload() {
+        let summary = Summary {
+            title: "devfetch fleet report".to_string(),
+            lines: (0..20).map(|i| format!("line {}", i)).collect(),
+        };
+
+        let slack = render_payload(Provider::Slack, &summary);
+        let text = slack["blocks"][0]["text"]["text"].as_str().unwrap();
+        assert!(text.starts_with("*devfetch fleet report*\n• line 0"));
+        assert!(text.ends_with("• ...and 5 more"));
+
+        let discord = render_payload(Provider::Discord, &summary);
+        assert!(discord["content"].as_str().unwrap().starts_with("**devfetch fleet report**"));
+
+        let teams = render_payload(Provider::Teams, &summary);
+        assert_eq!(teams["@type"], "MessageCard");
+    }
+
+    #[test]
+    fn test_summarize_scan_doctor() {
+        let finding = |severity, subject: &str| Finding {
+            severity,
+            check: "pin".to_string(),
+            subject: subject.to_string(),
+            issue: "pinned 20, found 18".to_string(),
+            rationale: None,
+            fix: None,
+        };
+        let findings = [finding(Severity::Info, "go"), finding(Severity::Warning, "python"), finding(Severity::Error, "node")];
+        let summary = summarize_scan(&ScanResult::new(), &findings);
+        assert_eq!(summary.lines[0], "Doctor: 1 errors, 1 warnings");
+        assert_eq!(summary.lines[1], "error: node — pinned 20, found 18");
+        assert_eq!(summary.lines.len(), 3);
+        assert_eq!(summarize_scan(&ScanResult::new(), &[]).lines, ["Doctor: no problems found"]);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::types::ScanResult;
+use std::collections::BTreeMap;
+
+/// A row as osquery represents it: every column is a string
+pub type Row = BTreeMap<String, String>;
+
+/// Table name -> (column, osquery type)
+pub static TABLES: &[(&str, &[(&str, &str)])] = &[
+    (
+        "devfetch_tools",
+        &[
+            ("name", "TEXT"),
+            ("path", "TEXT"),
+            ("version", "TEXT"),
+            ("category", "TEXT"),
+            ("scope", "TEXT"),
+            ("on_path", "INTEGER"),
+            ("purl", "TEXT"),
+            ("cpe", "TEXT"),
+        ],
+    ),
+    (
+        "devfetch_projects",
+        &[
+            ("path", "TEXT"),
+            ("ecosystem", "TEXT"),
+            ("markers", "TEXT"),
+            ("tool_version", "TEXT"),
+            ("dependency_count", "INTEGER"),
+        ],
+    ),
+];
+
+/// CREATE TABLE statements describing the exported tables
+pub fn schema_sql() -> String {
+    TABLES
+        .iter()
+        .map(|(table, columns)| {
+            let cols: Vec<String> = columns.iter().map(|(name, ty)| format!("`{}` {}", name, ty)).collect();
+            format!("CREATE TABLE {}({});\n", table, cols.join(", "))
+        })
+        .collect()
+}
+
+fn row(pairs: &[(&str, String)]) -> Row {
+    pairs.iter().map(|(k, v)| (k.to_string(), v.clone())).collect()
+}
+
+/// Rows for the devfetch_tools table
+pub fn tool_rows(result: &ScanResult) -> Vec<Row> {
+    result
+        .global_tools
+        .iter()
+        .map(|tool| {
+            row(&[
+                ("name", tool.name.clone()),
+                ("path", tool.path.display().to_string()),
+                ("version", tool.version.clone().unwrap_or_default()),
+                ("category", tool.category.display_name().to_string()),
+                ("scope", tool.scope.display_name().to_string()),
+                ("on_path", if tool.on_path { "1" } else { "0" }.to_string()),
+                ("purl", tool.purl.clone().unwrap_or_default()),
+                ("cpe", tool.cpe.clone().unwrap_or_default()),
+            ])
+        })
+        .collect()
+}
+
+/// Rows for the devfetch_projects table, one per detected ecosystem
+pub fn project_rows(result: &ScanResult) -> Vec<Row> {
+    let Some(project) = &result.project_info else {
+        return Vec::new();
+    };
+
+    let mut markers: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for marker in &project.markers {
+        markers.entry(marker.ecosystem.as_str()).or_default().push(marker.file.as_str());
+    }
+
+    markers
+        .into_iter()
+        .map(|(ecosystem, files)| {
+            let info = project.ecosystems.get(ecosystem);
+            row(&[
+                ("path", project.path.display().to_string()),
+                ("ecosystem", ecosystem.to_string()),
+                ("markers", files.join(",")),
+                ("tool_version", info.and_then(|i| i.tool_version.clone()).unwrap_or_default()),
+                (
+                    "dependency_count",
+                    info.and_then(|i| i.dependencies.as_ref())
+                        .map(|d| d.count.to_string())
+                        .unwrap_or_else(|| "0".to_string()),
+                ),
+            ])
+        })
+        .collect()
+}
+
+/// Rows for a table by name
+pub fn table_rows(table: &str, result: &ScanResult) -> Vec<Row> {
+    match table {
+        "devfetch_tools" => tool_rows(result),
+        "devfetch_projects" => project_rows(result),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DetectedMarker, ProjectInfo};
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_project_rows() {
+        let mut result = ScanResult::new();
+        result.project_info = Some(ProjectInfo {
+            path: PathBuf::from("/src/app"),
+            markers: vec![
+                DetectedMarker { file: "package.json".to_string(), ecosystem: "Node.js".to_string() },
+                DetectedMarker { file: "package-lock.json".to_string(), ecosystem: "Node.js".to_string() },
+            ],
+            ecosystems: BTreeMap::new(),
+            scripts: Vec::new(),
+            workspace: None,
+            subprojects: Vec::new(),
+        });
+
+        let rows = project_rows(&result);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["markers"], "package.json,package-lock.json");
+        assert_eq!(rows[0]["dependency_count"], "0");
+        // Every declared column is present
+        let columns = TABLES.iter().find(|(t, _)| *t == "devfetch_projects").unwrap().1;
+        assert!(columns.iter().all(|(c, _)| rows[0].contains_key(*c)));
+    }
+}
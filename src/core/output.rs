@@ -1,28 +1,99 @@
-use crate::types::{ScanResult, Tool, ToolCategory};
+use crate::types::{
+    ApiInfo, CacheEntry, CacheStats, CiToolRequirement, CompatEnvInfo, ConfiguredTool, ConstraintCheck, CrossCompileInfo, DatabaseInfo, DevEnvironment, DiffStatus, EcosystemDetails, Finding, DotfilesInfo, FleetReport, ForgeCli, GitSetup, GuixInfo, InstallScope, Jdk, LinterStatus,
+    LspCoverage, LspServer, MigrationTool, MonorepoOrchestrator, NetworkCheck, PackageRegistry, PathAudit, ProjectInfo, ProjectScript, ProjectSummary, ProtoRequirement, ProxyEnv, RegistryAuth, RequirementCheck, RequirementStatus, ScanDiff, ScanProgress, ScanResult, ScanWarning, ServiceCheck, Severity, ShellInfo, SshReadiness, SystemCheck, Tool, ToolPin,
+    ToolCategory, ToolExplanation, UsageStats, VersionManager, VirtTool,
+};
+use crate::core::i18n::{t, tf};
+use crate::core::snapshot::Codec;
+use crate::core::linters;
 use colored::*;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
 
 /// Output scan results in pretty terminal format
 pub fn print_pretty(result: &ScanResult) {
     // Print global tools
     if !result.global_tools.is_empty() {
         println!("\n{}", "═══════════════════════════════════════════════════════".bright_blue().bold());
-        println!("{}", "  GLOBAL DEVELOPER TOOLS".bright_blue().bold());
+        println!("{}", format!("  {}", t("GLOBAL DEVELOPER TOOLS")).bright_blue().bold());
         println!("{}", "═══════════════════════════════════════════════════════".bright_blue().bold());
         
-        print_tools_by_category(&result.global_tools);
+        print_tools_by_category(&result.global_tools, &result.linters);
+        print_store_provenance(&result.global_tools);
+        print_scope_totals(&result.global_tools);
+        print_off_path_hint(&result.global_tools);
+    }
+
+    // Print Unix-on-Windows environment details
+    if let Some(compat) = &result.compat_env {
+        print_compat_env(compat);
+    }
+
+    // Print Guix profile generations
+    if let Some(guix) = &result.guix {
+        print_guix(guix);
+    }
+
+    // Print cross-compilation targets
+    if let Some(cross) = &result.cross_compile {
+        print_cross_compile(cross);
+    }
+
+    // Print emulator and virtualization tooling
+    if !result.virtualization.is_empty() {
+        print_virtualization(&result.virtualization);
+    }
+
+    // Print git hosting CLIs
+    if !result.forge_clis.is_empty() {
+        print_forge_clis(&result.forge_clis);
+    }
+
+    // Print shell environment
+    if let Some(shell) = &result.shell {
+        print_shell(shell);
+    }
+
+    // Print git identity, signing, credential helpers and LFS
+    if let Some(git) = &result.git {
+        print_git(git);
+    }
+
+    // Print proxy variables and custom CA bundles
+    if let Some(proxy) = &result.proxy {
+        print_proxy_env(proxy);
+    }
+
+    // Print dotfiles manager
+    if let Some(dotfiles) = &result.dotfiles {
+        print_dotfiles(dotfiles);
+    }
+
+    // Print installed JDKs
+    if !result.jdks.is_empty() {
+        print_jdks(&result.jdks);
+    }
+
+    // Print version managers and the versions they select
+    if !result.version_managers.is_empty() {
+        print_version_managers(&result.version_managers);
+    }
+
+    // Print installed language servers
+    if !result.lsp_servers.is_empty() {
+        print_lsp_servers(&result.lsp_servers);
     }
 
     // Print project information
     if let Some(project) = &result.project_info {
         println!("\n{}", "═══════════════════════════════════════════════════════".bright_green().bold());
-        println!("{}", "  PROJECT INFORMATION".bright_green().bold());
+        println!("{}", format!("  {}", t("PROJECT INFORMATION")).bright_green().bold());
         println!("{}", "═══════════════════════════════════════════════════════".bright_green().bold());
         
-        println!("\n{} {}", "📁 Path:".bold(), project.path.display().to_string().cyan());
+        println!("\n{} {}", format!("📁 {}", t("Path:")).bold(), project.path.display().to_string().cyan());
         
         if !project.markers.is_empty() {
-            println!("\n{}", "Detected Ecosystems:".bold().yellow());
+            println!("\n{}", t("Detected Ecosystems:").bold().yellow());
             for marker in &project.markers {
                 println!("  {} {} ({})", 
                     "▸".green(),
@@ -33,19 +104,238 @@ pub fn print_pretty(result: &ScanResult) {
         }
 
         if !project.ecosystems.is_empty() {
-            println!("\n{}", "Ecosystem Details:".bold().yellow());
+            println!("\n{}", t("Ecosystem Details:").bold().yellow());
             for (name, info) in &project.ecosystems {
                 print!("  {} {}", "◆".cyan(), name.bright_white());
                 
                 if let Some(version) = &info.tool_version {
                     print!(" {}", format!("v{}", version).green());
                 }
+                if !info.frameworks.is_empty() {
+                    let labels: Vec<String> = info.frameworks.iter().map(|f| f.label()).collect();
+                    print!(" {}", labels.join(", ").cyan());
+                }
+                if let Some(manager) = info.details.package_manager() {
+                    let label = match &manager.version {
+                        Some(v) => tf("with {} v{} ({})", &[&manager.name, v, &manager.source]),
+                        None => tf("with {} ({}, not installed)", &[&manager.name, &manager.source]),
+                    };
+                    print!(" {}", label.dimmed());
+                    match (&manager.pinned, &manager.version) {
+                        (Some(pinned), Some(active)) if !crate::core::doctor::version_matches(pinned, active) => {
+                            print!(" {}", format!("[pinned {}]", pinned).yellow())
+                        }
+                        _ => {}
+                    }
+                    if !manager.conflicts.is_empty() {
+                        print!(" {}", format!("[also {}]", manager.conflicts.join(", ")).yellow());
+                    }
+                    match &manager.corepack {
+                        Some(corepack) if corepack.enabled && corepack.cached => print!(" {}", "[corepack]".dimmed()),
+                        Some(corepack) if corepack.enabled => print!(" {}", "[corepack: not downloaded]".yellow()),
+                        Some(_) => print!(" {}", "[corepack not enabled]".yellow()),
+                        None => {}
+                    }
+                }
+                if let Some(wrapper) = &info.wrapper {
+                    print!(" {}", format!("(via {}, {})", wrapper.wrapper, wrapper.source).dimmed());
+                    if let Some(global) = &wrapper.global_version {
+                        print!(" {}", format!("[global {} is v{}]", wrapper.tool, global).yellow());
+                    }
+                }
                 println!();
 
+                if let EcosystemDetails::Rust(rust) = &info.details {
+                    let mut details = Vec::new();
+                    if !rust.members.is_empty() {
+                        details.push(format!("{} {}", t("Members:"), rust.members.join(", ")));
+                    }
+                    if !rust.binaries.is_empty() {
+                        details.push(format!("{} {}", t("Binaries:"), rust.binaries.join(", ")));
+                    }
+                    if let Some(msrv) = &rust.msrv {
+                        details.push(format!("{} {}", t("MSRV:"), msrv));
+                    }
+                    print_tree(&details);
+                }
+
+                if let EcosystemDetails::Jvm(jvm) = &info.details {
+                    let mut details = Vec::new();
+                    if let Some(java) = &jvm.java_version {
+                        details.push(format!("Java: {}", java));
+                    }
+                    if let Some(range) = &jvm.java_range {
+                        details.push(format!("Requires Java {}", range));
+                    }
+                    if !jvm.jdks.is_empty() {
+                        let jdks: Vec<String> = jvm
+                            .jdks
+                            .iter()
+                            .map(|c| {
+                                let mark = if c.satisfies { "✓" } else { "✗" };
+                                let name = match &c.jdk.vendor {
+                                    Some(vendor) => format!("{} {}", vendor, c.jdk.version),
+                                    None => c.jdk.version.clone(),
+                                };
+                                format!("{} {} ({}, {})", mark, name, c.jdk.source, c.jdk.home.display())
+                            })
+                            .collect();
+                        details.push(format!("JDKs: {}", jdks.join(", ")));
+                    }
+                    if let Some(kotlin) = &jvm.kotlin_version {
+                        details.push(format!("Kotlin: {}", kotlin));
+                    }
+                    let plugins: Vec<String> = jvm
+                        .plugins
+                        .iter()
+                        .map(|p| match &p.version {
+                            Some(v) => format!("{} {}", p.id, v),
+                            None => p.id.clone(),
+                        })
+                        .collect();
+                    if !plugins.is_empty() {
+                        details.push(format!("Plugins: {}", plugins.join(", ")));
+                    }
+                    if !details.is_empty() {
+                        details.push(format!("From: {}", jvm.source));
+                        print_tree(&details);
+                    }
+                }
+
+                if let EcosystemDetails::Php(php) = &info.details {
+                    let mut details = Vec::new();
+                    if let Some(constraint) = &php.php_constraint {
+                        let status = match php.php_satisfied {
+                            Some(true) => " ✓".green().to_string(),
+                            Some(false) => " ✗ not satisfied".red().to_string(),
+                            None => String::new(),
+                        };
+                        details.push(format!("Requires PHP {}{}", constraint, status));
+                    }
+                    if !php.extensions.is_empty() {
+                        let extensions: Vec<String> = php
+                            .extensions
+                            .iter()
+                            .map(|e| match e.loaded {
+                                Some(false) => format!("{} (missing)", e.name).red().to_string(),
+                                _ => e.name.clone(),
+                            })
+                            .collect();
+                        details.push(format!("Extensions: {}", extensions.join(", ")));
+                    }
+                    print_tree(&details);
+                }
+
+                if let EcosystemDetails::Cxx(cxx) = &info.details {
+                    let mut details: Vec<String> = cxx
+                        .compilers
+                        .iter()
+                        .map(|c| {
+                            let mut line = format!("{} {}", c.name, c.version);
+                            if let Some(target) = &c.target {
+                                line.push_str(&format!(" → {}", target));
+                            }
+                            let standards: Vec<&str> = [&c.c_std, &c.cxx_std].into_iter().flatten().map(|s| s.as_str()).collect();
+                            if !standards.is_empty() {
+                                line.push_str(&format!(", default {}", standards.join("/")));
+                            }
+                            if let Some(sysroot) = &c.sysroot {
+                                line.push_str(&format!(", sysroot {}", sysroot));
+                            }
+                            line
+                        })
+                        .collect();
+                    if cxx.compilers.is_empty() {
+                        details.push("No C compiler on PATH".red().to_string());
+                    }
+                    details.push(match &cxx.pkg_config {
+                        Some(v) if !v.is_empty() => format!("pkg-config {}", v),
+                        Some(_) => "pkg-config".to_string(),
+                        None => "pkg-config not installed".dimmed().to_string(),
+                    });
+                    if let Some(required) = &cxx.cmake_required {
+                        details.push(format!("Requires CMake {}", required));
+                    }
+                    if !cxx.presets.is_empty() {
+                        let presets: Vec<String> = cxx
+                            .presets
+                            .iter()
+                            .map(|p| {
+                                let generator = p.generator.as_deref().unwrap_or("default generator");
+                                match (p.generator_installed, &p.generator_tool) {
+                                    (Some(false), Some(tool)) => format!("{} ({}, {} missing)", p.name, generator, tool).red().to_string(),
+                                    _ if p.toolchain_exists == Some(false) => {
+                                        format!("{} ({}, toolchain file missing)", p.name, generator).red().to_string()
+                                    }
+                                    _ => format!("{} ({})", p.name, generator),
+                                }
+                            })
+                            .collect();
+                        details.push(format!("Presets: {}", presets.join(", ")));
+                    }
+                    if !cxx.libraries.is_empty() {
+                        let libraries: Vec<String> = cxx
+                            .libraries
+                            .iter()
+                            .map(|l| match (l.found, &l.version) {
+                                (Some(true), Some(v)) => format!("{} {}", l.name, v),
+                                (Some(true), None) => l.name.clone(),
+                                (Some(false), _) => format!("{} (missing)", l.name).red().to_string(),
+                                (None, _) => format!("{} (checked by cmake)", l.name).dimmed().to_string(),
+                            })
+                            .collect();
+                        details.push(format!("Libraries: {}", libraries.join(", ")));
+                    }
+                    print_tree(&details);
+                }
+
+                if let EcosystemDetails::Ruby(ruby) = &info.details {
+                    let mut details = Vec::new();
+                    if let (Some(constraint), Some(source)) = (&ruby.ruby_constraint, &ruby.ruby_source) {
+                        let status = match ruby.ruby_satisfied {
+                            Some(true) => " ✓".green().to_string(),
+                            Some(false) => " ✗ not satisfied".red().to_string(),
+                            None => String::new(),
+                        };
+                        details.push(format!("Requires Ruby {} ({}){}", constraint, source, status));
+                    }
+                    if let Some(locked) = &ruby.bundler_locked {
+                        let installed = match &ruby.bundler_installed {
+                            Some(v) if v == locked => " ✓".green().to_string(),
+                            Some(v) => format!(" (installed v{})", v).yellow().to_string(),
+                            None => " (bundler not installed)".red().to_string(),
+                        };
+                        details.push(format!("Bundled with {}{}", locked, installed));
+                    }
+                    print_tree(&details);
+                }
+
+                if let EcosystemDetails::Python(python) = &info.details {
+                    let mut details = Vec::new();
+                    if let Some(requires) = &python.requires_python {
+                        details.push(format!("{} {}", t("Requires Python"), requires));
+                    }
+                    if !python.interpreters.is_empty() {
+                        let interpreters: Vec<String> = python
+                            .interpreters
+                            .iter()
+                            .map(|i| {
+                                let label = format!("{} {}", i.name, i.version);
+                                if i.satisfies { format!("{} ✓", label).green().to_string() } else { label.dimmed().to_string() }
+                            })
+                            .collect();
+                        details.push(format!("{} {}", t("Interpreters:"), interpreters.join(", ")));
+                    }
+                    if let Some(lock) = &python.lock_file {
+                        details.push(tf("Locked in {}", &[lock]));
+                    }
+                    print_tree(&details);
+                }
+
                 if let Some(deps) = &info.dependencies {
-                    println!("    {} {} dependencies", "├─".dimmed(), deps.count.to_string().yellow());
+                    println!("    {} {}", "├─".dimmed(), tf("{} dependencies", &[&deps.count.to_string().yellow()]));
                     if !deps.sample.is_empty() {
-                        println!("    {} {}", "└─".dimmed(), "Sample:".dimmed());
+                        println!("    {} {}", "└─".dimmed(), t("Sample:").dimmed());
                         for dep in &deps.sample {
                             println!("       {} {}", "•".dimmed(), dep.bright_white());
                         }
@@ -53,13 +343,141 @@ pub fn print_pretty(result: &ScanResult) {
                 }
             }
         }
+
+        if !project.scripts.is_empty() {
+            print_scripts(&project.scripts);
+        }
+
+        if let Some(workspace) = &project.workspace {
+            println!("\n{}", t("Workspace:").bold().yellow());
+            println!("  {} {} {}", "▸".green(), workspace.kind.bright_white(), format!("({})", workspace.manifest).dimmed());
+            if !workspace.members.is_empty() {
+                print_tree(&[format!("{} {}", t("Members:"), workspace.members.join(", "))]);
+            }
+        }
+
+        if !project.subprojects.is_empty() {
+            println!("\n{}", t("Sub-projects:").bold().yellow());
+            print_subprojects(&project.path, &project.subprojects, "  ");
+        }
+
+        let unconfigured_or_missing: Vec<&LinterStatus> = result
+            .linters
+            .iter()
+            .filter(|l| !l.installed || l.config.is_none())
+            .collect();
+        if !unconfigured_or_missing.is_empty() {
+            print_linter_gaps(&unconfigured_or_missing);
+        }
+
+        if !result.ci_tools.is_empty() {
+            print_ci_tools(&result.ci_tools);
+        }
+
+        if !result.lsp_coverage.is_empty() {
+            print_lsp_coverage(&result.lsp_coverage);
+        }
+    }
+
+    // Print nx/turborepo/lerna/rush setups
+    if !result.orchestrators.is_empty() {
+        print_orchestrators(&result.orchestrators);
+    }
+
+    // Print OpenAPI/GraphQL/Protobuf specs and their tooling
+    if let Some(api) = &result.api_specs {
+        print_api_specs(api);
+    }
+
+    // Print protoc/buf/plugins the codegen setup needs
+    if !result.proto_toolchain.is_empty() {
+        print_proto_toolchain(&result.proto_toolchain);
+    }
+
+    // Print documentation generators
+    if !result.docs_tools.is_empty() {
+        print_configured_tools("Documentation:", &result.docs_tools);
+    }
+
+    // Print release automation
+    if !result.release_tools.is_empty() {
+        print_configured_tools("Release Tooling:", &result.release_tools);
+    }
+
+    // Print ORMs/drivers and the database engines they need
+    if let Some(databases) = &result.databases {
+        print_databases(databases);
+    }
+
+    // Print database migration tooling
+    if !result.migrations.is_empty() {
+        print_migrations(&result.migrations);
+    }
+
+    // Print Devbox/Flox environments
+    if !result.dev_environments.is_empty() {
+        print_dev_environments(&result.dev_environments);
+    }
+
+    // Print container registry logins (--registry-auth)
+    if let Some(auth) = &result.registry_auth {
+        print_registry_auth(auth);
+    }
+
+    // Print versions pinned by project toolchain managers
+    if !result.pinned_tools.is_empty() {
+        print_pinned_tools(&result.pinned_tools);
+    }
+
+    // Print devfetch.toml's disk, memory, OS and kernel requirements
+    if !result.system_checks.is_empty() {
+        print_system_checks(&result.system_checks);
+    }
+
+    // Print services devfetch.toml needs running
+    if !result.services.is_empty() {
+        print_services(&result.services);
+    }
+
+    // Print custom npm/pip/Cargo/Maven registries and mirrors
+    if !result.package_registries.is_empty() {
+        print_package_registries(&result.package_registries);
+    }
+
+    // Print SSH readiness for git remotes (--ssh)
+    if let Some(ssh) = &result.ssh {
+        print_ssh(ssh);
+    }
+
+    // Print package hosts checked with --network
+    if !result.network.is_empty() {
+        print_network(&result.network);
+    }
+
+    // Print what the scan couldn't look at
+    if !result.warnings.is_empty() {
+        print_warnings(&result.warnings);
+    }
+
+    if result.partial {
+        println!("\n{} {}", "⚠".yellow(), t("Partial result: the scan stopped before every probe finished").yellow());
     }
 
     println!();
 }
 
-/// Print tools grouped by category
-fn print_tools_by_category(tools: &[Tool]) {
+/// Print directories, tools and files the scan had to skip
+fn print_warnings(warnings: &[ScanWarning]) {
+    println!("\n{}", t("Warnings:").bold().yellow());
+
+    for warning in warnings {
+        println!("  {} {} {}", "⚠".yellow(), warning.path.display().to_string().bright_white(), warning.message.dimmed());
+    }
+}
+
+/// Print tools grouped by category. Formatters and linters are annotated
+/// with whether the current project configures them.
+fn print_tools_by_category(tools: &[Tool], linters: &[LinterStatus]) {
     let mut by_category: HashMap<ToolCategory, Vec<&Tool>> = HashMap::new();
     
     for tool in tools {
@@ -71,6 +489,8 @@ fn print_tools_by_category(tools: &[Tool]) {
         ToolCategory::LanguageToolchain,
         ToolCategory::PackageManager,
         ToolCategory::BuildSystem,
+        ToolCategory::FormatterLinter,
+        ToolCategory::CoverageProfiler,
         ToolCategory::DeveloperTool,
         ToolCategory::Unknown,
     ];
@@ -85,11 +505,13 @@ fn print_tools_by_category(tools: &[Tool]) {
                 ToolCategory::LanguageToolchain => "",
                 ToolCategory::PackageManager => "",
                 ToolCategory::BuildSystem => " ",
+                ToolCategory::FormatterLinter => "",
+                ToolCategory::CoverageProfiler => "",
                 ToolCategory::DeveloperTool => " ",
                 ToolCategory::Unknown => "",
             };
 
-            println!("\n{} {}", icon, category.display_name().bold().yellow());
+            println!("\n{} {}", icon, t(category.display_name()).bold().yellow());
             
             let mut sorted_tools = tools_in_cat.clone();
             sorted_tools.sort_by(|a, b| a.name.cmp(&b.name));
@@ -101,34 +523,1246 @@ fn print_tools_by_category(tools: &[Tool]) {
                     print!(" {}", format!("v{}", version).green());
                 }
                 
-                println!(" {}", format!("({})", tool.path.display()).dimmed());
+                print!(" {}", format!("({})", tool.path.display()).dimmed());
+
+                if let Some(error) = &tool.probe_error {
+                    print!(" {}", format!("[version unknown: {}]", error).yellow());
+                }
+
+                if tool.scope != InstallScope::System {
+                    print!(" {}", format!("[{}]", tool.scope.display_name()).cyan());
+                }
+
+                if !tool.security.is_empty() {
+                    let flags: Vec<String> = tool.security.iter().map(|f| f.describe()).collect();
+                    print!(" {}", format!("[⚠ {}]", flags.join(", ")).red());
+                }
+
+                if let Some(check) = &tool.integrity {
+                    match check.verified {
+                        Some(true) => print!(" {}", format!("[✓ {}:{}]", check.manager, check.package).green()),
+                        Some(false) => print!(" {}", format!("[✗ modified since {} install of {}]", check.manager, check.package).red().bold()),
+                        None => print!(" {}", format!("[? {}:{} unverifiable]", check.manager, check.package).dimmed()),
+                    }
+                }
+
+                if let Some(store) = &tool.store {
+                    let package = match &store.version {
+                        Some(v) => format!("{}-{}", store.package, v),
+                        None => store.package.clone(),
+                    };
+                    print!(" {}", format!("[{}: {}]", store.manager, package).cyan());
+                }
+
+                if !tool.aliases.is_empty() {
+                    print!(" {}", format!("(also {})", tool.aliases.join(", ")).dimmed());
+                }
+
+                if !tool.shadows.is_empty() {
+                    let shadowed: Vec<String> = tool.shadows.iter().map(|p| p.display().to_string()).collect();
+                    print!(" {}", format!("[shadows {}]", shadowed.join(", ")).yellow());
+                }
+
+                if tool.manual_install {
+                    print!(" {}", "[manually installed]".yellow());
+                }
+
+                if !tool.on_path {
+                    print!(" {}", "[not on PATH]".yellow());
+                }
+
+                if let Some(status) = linters
+                    .iter()
+                    .find(|l| linters::binary_of(&l.name).is_some_and(|binary| tool.provides_command(binary))) {
+                    match &status.config {
+                        Some(config) => print!(" {}", format!("[project config: {}]", config).cyan()),
+                        None => print!(" {}", "[no project config]".dimmed()),
+                    }
+                }
+                println!();
+            }
+        }
+    }
+}
+
+/// Print Guix profiles with their active generations
+fn print_guix(guix: &GuixInfo) {
+    println!("\n{}", t("GNU Guix:").bold().yellow());
+    if guix.profiles.is_empty() {
+        println!("  {} {}", "▸".green(), "store present, no profiles found".dimmed());
+    }
+    for profile in &guix.profiles {
+        print!("  {} {}", "▸".green(), profile.name.bright_white());
+        if let Some(generation) = profile.generation {
+            print!(" {}", format!("generation {}", generation).green());
+        }
+        println!(" {}", format!("({})", profile.path.display()).dimmed());
+    }
+}
+
+/// Print tools from Nix/Guix stores grouped by the profile that provides them
+fn print_store_provenance(tools: &[Tool]) {
+    let mut groups: BTreeMap<(&str, &str, Option<&str>), Vec<&Tool>> = BTreeMap::new();
+    for tool in tools {
+        if let Some(store) = &tool.store {
+            groups
+                .entry((store.manager.as_str(), store.profile.as_str(), store.source.as_deref()))
+                .or_default()
+                .push(tool);
+        }
+    }
+    if groups.is_empty() {
+        return;
+    }
+
+    println!("\n{}", t("Package Store Provenance:").bold().yellow());
+    for ((manager, profile, source), tools) in groups {
+        print!("  {} {} {}", "▸".green(), manager.bright_white(), profile.cyan());
+        if let Some(source) = source {
+            print!(" {}", format!("({})", source).dimmed());
+        }
+        println!();
+
+        let details: Vec<String> = tools
+            .iter()
+            .filter_map(|t| t.store.as_ref().map(|s| (t, s)))
+            .map(|(t, s)| format!("{} {}", t.name, s.store_path.display().to_string().dimmed()))
+            .collect();
+        print_tree(&details);
+    }
+}
+
+/// Print how many tools are system-provisioned vs user-managed vs project-local
+fn print_scope_totals(tools: &[Tool]) {
+    let totals: Vec<String> = [InstallScope::System, InstallScope::User, InstallScope::Project]
+        .iter()
+        .map(|scope| {
+            let count = tools.iter().filter(|t| t.scope == *scope).count();
+            format!("{} {}", count.to_string().yellow(), t(scope.display_name()))
+        })
+        .collect();
+
+    println!("\n{} {}", t("Install scope:").bold(), totals.join(&" · ".dimmed().to_string()));
+}
+
+/// Warn about directories holding tools that are not on PATH
+fn print_off_path_hint(tools: &[Tool]) {
+    let mut dirs: Vec<String> = tools
+        .iter()
+        .filter(|t| !t.on_path)
+        .filter_map(|t| t.path.parent().map(|p| p.display().to_string()))
+        .collect();
+    dirs.sort();
+    dirs.dedup();
+
+    for dir in dirs {
+        println!("\n{} {}", "⚠".yellow(), tf("{} contains developer tools but is not on PATH", &[&dir]).yellow());
+    }
+}
+
+/// Print MSYS2 / MinGW / Cygwin environment details
+fn print_compat_env(compat: &CompatEnvInfo) {
+    println!("\n{}", t("Unix-on-Windows Environment:").bold().yellow());
+
+    if let Some(active) = &compat.active {
+        println!("  {} {} {}", "▸".green(), t("Active:").bold(), active.bright_white());
+    }
+
+    for provider in &compat.providers {
+        println!("  {} {} {}",
+            "◆".cyan(),
+            provider.environment.bright_white(),
+            format!("({})", provider.tools.join(", ")).dimmed()
+        );
+    }
+
+    for warning in &compat.warnings {
+        println!("  {} {}", "⚠".yellow(), warning.yellow());
+    }
+}
+
+/// Print installed cross-compilation targets
+fn print_cross_compile(cross: &CrossCompileInfo) {
+    println!("\n{}", t("Cross-compilation:").bold().yellow());
+
+    let lists = [
+        ("Rust (rustup)", &cross.rust_targets),
+        ("GCC cross compilers", &cross.gcc_cross_prefixes),
+    ];
+    for (label, targets) in lists {
+        if !targets.is_empty() {
+            println!("  {} {} {}", "▸".green(), label.bright_white(), targets.join(", ").dimmed());
+        }
+    }
+
+    // Zig and Go support dozens of targets; a count is more useful than the list
+    let counts = [
+        ("Zig (bundled libc)", cross.zig_targets.len()),
+        ("Go (GOOS/GOARCH)", cross.go_targets.len()),
+    ];
+    for (label, count) in counts {
+        if count > 0 {
+            println!("  {} {} {}", "▸".green(), label.bright_white(), format!("{} targets", count).dimmed());
+        }
+    }
+}
+
+/// Print emulator and virtualization tooling
+fn print_virtualization(tools: &[VirtTool]) {
+    println!("\n{}", t("Virtualization:").bold().yellow());
+
+    for tool in tools {
+        print!("  {} {}", "▸".green(), tool.name.bright_white());
+
+        if let Some(version) = &tool.version {
+            print!(" {}", format!("v{}", version).green());
+        }
+
+        match &tool.running {
+            Some(running) if running.is_empty() => print!(" {}", "[no running instances]".dimmed()),
+            Some(running) => print!(" {}", format!("[running: {}]", running.join(", ")).cyan()),
+            None => {}
+        }
+
+        println!(" {}", format!("({})", tool.path.display()).dimmed());
+    }
+}
+
+/// Print git hosting CLIs and, with --git-auth, the hosts they're logged into
+fn print_forge_clis(clis: &[ForgeCli]) {
+    println!("\n{}", t("Git Hosting CLIs:").bold().yellow());
+
+    for cli in clis {
+        print!("  {} {}", "▸".green(), cli.name.bright_white());
+        if let Some(version) = &cli.version {
+            print!(" {}", format!("v{}", version).green());
+        }
+        match &cli.logins {
+            Some(logins) if logins.is_empty() => print!(" {}", "[not logged in]".yellow()),
+            Some(logins) => {
+                let hosts: Vec<String> = logins
+                    .iter()
+                    .map(|l| match &l.user {
+                        Some(user) => format!("{}@{}", user, l.host),
+                        None => l.host.clone(),
+                    })
+                    .collect();
+                print!(" {}", format!("[logged in: {}]", hosts.join(", ")).cyan());
             }
+            None => {}
+        }
+        println!(" {}", format!("({})", cli.path.display()).dimmed());
+    }
+}
+
+/// Print login shell, framework and version-manager init hooks
+fn print_shell(shell: &ShellInfo) {
+    println!("\n{}", t("Shell:").bold().yellow());
+
+    print!("  {} {}", "▸".green(), shell.name.bright_white());
+    if let Some(version) = &shell.version {
+        print!(" {}", format!("v{}", version).green());
+    }
+    println!(" {}", format!("({})", shell.path.display()).dimmed());
+
+    let mut details = Vec::new();
+    if !shell.frameworks.is_empty() {
+        details.push(format!("{} {}", t("Framework:").dimmed(), shell.frameworks.join(", ")));
+    }
+    if !shell.plugin_managers.is_empty() {
+        details.push(format!("{} {}", t("Plugin manager:").dimmed(), shell.plugin_managers.join(", ")));
+    }
+    for hook in &shell.init_hooks {
+        details.push(format!("{} {}",
+            hook.manager.cyan(),
+            format!("initialized in {}", hook.file.display()).dimmed()
+        ));
+    }
+    print_tree(&details);
+}
+
+fn print_git(git: &GitSetup) {
+    println!("\n{}", t("Git:").bold().yellow());
+    print!("  {} {}", "▸".green(), "git".bright_white());
+    if let Some(version) = &git.version {
+        print!(" {}", format!("v{}", version).green());
+    }
+    println!();
+
+    let unset = || "not set".yellow().to_string();
+    let mut details = vec![
+        format!("{} {}", "user.name:".dimmed(), git.user_name.clone().unwrap_or_else(unset)),
+        format!("{} {}", "user.email:".dimmed(), git.user_email.clone().unwrap_or_else(unset)),
+    ];
+    if let Some(autocrlf) = &git.autocrlf {
+        details.push(format!("{} {}", "core.autocrlf:".dimmed(), autocrlf));
+    }
+    if !git.credential_helpers.is_empty() {
+        details.push(format!("{} {}", t("Credential helpers:").dimmed(), git.credential_helpers.join(", ")));
+    }
+    match &git.signing {
+        Some(format) => {
+            let key = match git.signing_key_available {
+                Some(false) => "signing key not found".red().to_string(),
+                _ => "key available".to_string(),
+            };
+            details.push(format!("{} {} ({})", t("Signing:").dimmed(), format, key));
         }
+        None if !git.signatures_required_by.is_empty() => details.push(format!(
+            "{} {}",
+            t("Signing:").dimmed(),
+            format!("off, but {} verifies signatures", git.signatures_required_by.join(", ")).yellow()
+        )),
+        None => {}
+    }
+    if !git.signing_tools.is_empty() {
+        details.push(format!("{} {}", t("Signing tools:").dimmed(), git.signing_tools.join(", ")));
+    }
+    let lfs = match (git.lfs_installed, git.lfs_initialized) {
+        (false, _) if git.repo_uses_lfs => "not installed, but the project uses it".red().to_string(),
+        (false, _) => "not installed".dimmed().to_string(),
+        (true, false) if git.repo_uses_lfs => "installed, not initialized (run git lfs install)".red().to_string(),
+        (true, false) => "installed, not initialized".to_string(),
+        (true, true) if git.repo_uses_lfs && !git.lfs_hooks => "installed, repository hooks missing".yellow().to_string(),
+        (true, true) if git.lfs_missing > 0 => format!("{} files not pulled", git.lfs_missing).yellow().to_string(),
+        (true, true) => "installed and initialized".to_string(),
+    };
+    details.push(format!("{} {}", t("LFS:").dimmed(), lfs));
+    if !git.submodules.is_empty() {
+        let pending: Vec<String> =
+            git.submodules.iter().filter(|s| s.state != "ok").map(|s| format!("{} ({})", s.path, s.state)).collect();
+        let summary = if pending.is_empty() {
+            format!("{} checked out", git.submodules.len())
+        } else {
+            format!("{} of {} not ready: {}", pending.len(), git.submodules.len(), pending.join(", ")).yellow().to_string()
+        };
+        details.push(format!("{} {}", t("Submodules:").dimmed(), summary));
+    }
+    print_tree(&details);
+}
+
+/// Print proxy settings and CA bundles, marking bundles that are missing or empty
+fn print_proxy_env(proxy: &ProxyEnv) {
+    println!("\n{}", t("Proxy & Certificates:").bold().yellow());
+    for setting in &proxy.proxies {
+        println!("  {} {}={}", "▸".green(), setting.var.bright_white(), setting.value.cyan());
+    }
+    for conflict in crate::core::proxy::case_conflicts(proxy) {
+        println!("    {} {}", "⚠".yellow(), format!("{} and {} differ; tools pick different ones", conflict.0, conflict.1).yellow());
+    }
+    for bundle in &proxy.ca_bundles {
+        let status = match (bundle.exists, bundle.certificates) {
+            (false, _) => "missing".red(),
+            (true, Some(0)) => "no PEM certificates".yellow(),
+            (true, Some(n)) => format!("{} certificates", n).green(),
+            (true, None) => "directory".green(),
+        };
+        println!(
+            "  {} {}={} {} {}",
+            "▸".green(),
+            bundle.var.bright_white(),
+            bundle.path.display().to_string().cyan(),
+            status,
+            format!("({})", bundle.used_by).dimmed()
+        );
     }
 }
 
-/// Output scan results in JSON format
-pub fn print_json(result: &ScanResult) -> anyhow::Result<()> {
+/// Print dotfiles manager and managed files
+fn print_dotfiles(dotfiles: &DotfilesInfo) {
+    println!("\n{}", t("Dotfiles:").bold().yellow());
+    println!("  {} {} {}",
+        "▸".green(),
+        dotfiles.manager.bright_white(),
+        format!("({})", dotfiles.repo.display()).dimmed()
+    );
+
+    let mut details = Vec::new();
+    if let Some(last_sync) = &dotfiles.last_sync {
+        details.push(format!("{} {}", t("Last sync:").dimmed(), last_sync));
+    }
+    if !dotfiles.managed_files.is_empty() {
+        details.push(format!("{} {}", t("Manages:").dimmed(), dotfiles.managed_files.join(", ").cyan()));
+    }
+    print_tree(&details);
+}
+
+/// Print installed JDKs, marking the one on PATH
+fn print_jdks(jdks: &[Jdk]) {
+    println!("\n{}", t("JDKs:").bold().yellow());
+
+    for jdk in jdks {
+        let marker = if jdk.on_path { "▸".green() } else { "•".dimmed() };
+        print!("  {} {}", marker, jdk.vendor.as_deref().unwrap_or("JDK").bright_white());
+        print!(" {}", format!("v{}", jdk.version).green());
+        if let Some(arch) = &jdk.arch {
+            print!(" {}", arch.cyan());
+        }
+        let mut origin = jdk.source.clone();
+        if jdk.on_path && jdk.source != "PATH" {
+            origin.push_str(", on PATH");
+        }
+        println!(" {}", format!("({}: {})", origin, jdk.home.display()).dimmed());
+    }
+}
+
+/// Print version managers with their installed versions, highlighting the active one
+fn print_version_managers(managers: &[VersionManager]) {
+    println!("\n{}", t("Version Managers:").bold().yellow());
+
+    for manager in managers {
+        let root = if manager.root.as_os_str().is_empty() { String::new() } else { format!("({})", manager.root.display()) };
+        println!("  {} {} {}", "▸".green(), manager.name.bright_white(), root.dimmed());
+        let lines: Vec<String> = manager
+            .tools
+            .iter()
+            .map(|tool| {
+                let installed: Vec<String> = tool
+                    .installed
+                    .iter()
+                    .map(|v| if tool.active.as_ref() == Some(v) { v.green().bold().to_string() } else { v.clone() })
+                    .collect();
+                let mut line = format!("{}: {}", tool.tool, if installed.is_empty() { t("none installed").dimmed().to_string() } else { installed.join(", ") });
+                if let Some(active) = &tool.active {
+                    let mut why = tool.reason.clone().unwrap_or_default();
+                    if tool.on_path {
+                        why = if why.is_empty() { t("on PATH").to_string() } else { format!("{}, {}", why, t("on PATH")) };
+                    }
+                    line.push_str(&format!("  {}", format!("{} {}", t("active"), active).green()));
+                    if !why.is_empty() {
+                        line.push_str(&format!(" {}", format!("({})", why).dimmed()));
+                    }
+                    if !tool.installed.is_empty() && !tool.installed.contains(active) && active != "system" {
+                        line.push_str(&format!(" {}", t("not installed").red()));
+                    }
+                }
+                line
+            })
+            .collect();
+        print_tree(&lines);
+    }
+}
+
+/// Print installed language servers
+fn print_lsp_servers(servers: &[LspServer]) {
+    println!("\n{}", t("Language Servers:").bold().yellow());
+
+    for server in servers {
+        print!("  {} {}", "▸".green(), server.name.bright_white());
+        if let Some(version) = &server.version {
+            print!(" {}", format!("v{}", version).green());
+        }
+        println!(" {}", format!("({})", server.path.display()).dimmed());
+    }
+}
+
+/// Print formatters/linters that are configured but missing, or installed but unconfigured
+fn print_linter_gaps(linters: &[&LinterStatus]) {
+    println!("\n{}", t("Formatters & Linters:").bold().yellow());
+
+    for linter in linters {
+        match (&linter.config, linter.installed) {
+            (Some(config), false) => println!("  {} {} {}",
+                "✗".red(),
+                config.bright_white(),
+                format!("present but {} is not installed", linter.name).red()
+            ),
+            _ => println!("  {} {} {}",
+                "•".dimmed(),
+                linter.name.bright_white(),
+                "installed but the project has no config for it".dimmed()
+            ),
+        }
+    }
+}
+
+/// Print coverage/profiling tools expected by CI
+fn print_ci_tools(requirements: &[CiToolRequirement]) {
+    println!("\n{}", t("CI Coverage & Profiling Tools:").bold().yellow());
+
+    for req in requirements {
+        let (mark, status) = if req.installed {
+            ("✓".green(), "installed".green())
+        } else {
+            ("✗".red(), "missing locally".red())
+        };
+        println!("  {} {} {} {}", mark, req.tool.bright_white(), status, format!("({})", req.ci_file).dimmed());
+    }
+}
+
+/// Print the project's entry points and how to run them
+fn print_scripts(scripts: &[ProjectScript]) {
+    println!("\n{}", t("Entry Points:").bold().yellow());
+    let width = scripts.iter().map(|s| s.run.len()).max().unwrap_or(0);
+    for script in scripts {
+        let detail = script.description.as_deref().or(script.command.as_deref()).unwrap_or("");
+        print!("  {} {}  {}",
+            "▸".green(),
+            format!("{:width$}", script.run).bright_white(),
+            detail.dimmed()
+        );
+        if script.runner_missing {
+            let runner = script.run.split(' ').next().unwrap_or_default();
+            print!(" {}", format!("[{} not installed]", runner).red());
+        }
+        println!();
+    }
+}
+
+/// Print monorepo orchestrators with pinned vs installed versions
+fn print_orchestrators(orchestrators: &[MonorepoOrchestrator]) {
+    println!("\n{}", t("Monorepo:").bold().yellow());
+    for orch in orchestrators {
+        print!("  {} {}", "▸".green(), orch.name.bright_white());
+        match &orch.installed_version {
+            Some(v) => print!(" {}", format!("v{}", v).green()),
+            None => print!(" {}", "[not installed]".red()),
+        }
+        if let Some(pinned) = &orch.pinned_version {
+            print!(" {}", format!("(pinned: {})", pinned).dimmed());
+        }
+        println!(" {}", format!("{} packages, {}", orch.packages, orch.config).dimmed());
+    }
+}
+
+/// Print spec files grouped by kind and version, then the tools for them
+fn print_api_specs(info: &ApiInfo) {
+    println!("\n{}", t("API Specs:").bold().yellow());
+    for spec in &info.specs {
+        print!("  {} {}", "▸".green(), spec.version.as_deref().unwrap_or(&spec.kind).bright_white());
+        println!(" {}", format!("({} files)", spec.files.len()).dimmed());
+        let shown: Vec<String> = spec.files.iter().take(3).cloned().collect();
+        print_tree(&shown);
+    }
+    let tools: Vec<String> = info
+        .tools
+        .iter()
+        .map(|t| if t.installed { t.name.green().to_string() } else { t.name.red().to_string() })
+        .collect();
+    println!("    {} {}", t("Tools:").dimmed(), tools.join(", "));
+}
+
+/// Print the protobuf codegen tools and which are missing
+fn print_proto_toolchain(requirements: &[ProtoRequirement]) {
+    println!("\n{}", t("Protobuf Toolchain:").bold().yellow());
+    for req in requirements {
+        print!("  {} {} {}", "▸".green(), req.tool.bright_white(), format!("({})", req.source).dimmed());
+        if !req.installed {
+            print!(" {}", "[not installed]".red());
+        }
+        println!();
+    }
+}
+
+/// Print configured workflow tools with their pins, CLI status and run command
+fn print_configured_tools(title: &str, tools: &[ConfiguredTool]) {
+    println!("\n{}", t(title).bold().yellow());
+    for tool in tools {
+        print!("  {} {}", "▸".green(), tool.tool.bright_white());
+        if let Some(pinned) = &tool.pinned_version {
+            print!(" {}", pinned.green());
+        }
+        print!(" {}", format!("({})", tool.config).dimmed());
+        if !tool.cli_installed {
+            print!(" {}", format!("[{} not installed]", tool.cli).red());
+        }
+        println!();
+        if let Some(command) = &tool.command {
+            println!("    {} {}", "└─".dimmed(), command.cyan());
+        }
+    }
+}
+
+/// Print registries the user is logged into, then those the project references
+fn print_registry_auth(auth: &RegistryAuth) {
+    println!("\n{}", t("Container Registries:").bold().yellow());
+    if auth.logins.is_empty() {
+        println!("  {}", "Not logged into any registry".dimmed());
+    }
+    for login in &auth.logins {
+        let via = match &login.helper {
+            Some(helper) => format!("({}, via {})", login.source, helper),
+            None => format!("({})", login.source),
+        };
+        println!("  {} {} {}", "✓".green(), login.registry.bright_white(), via.dimmed());
+    }
+    for reference in &auth.referenced {
+        let mark = if reference.logged_in { "▸".green() } else { "▸".yellow() };
+        print!("  {} {} {}", mark, reference.registry.bright_white(), format!("(used by {})", reference.files.join(", ")).dimmed());
+        if !reference.logged_in {
+            print!(" {}", "[not logged in]".yellow());
+        }
+        println!();
+    }
+}
+
+/// Print ORMs and drivers, then each implied engine and whether it's available locally
+fn print_databases(info: &DatabaseInfo) {
+    println!("\n{}", t("Databases:").bold().yellow());
+    for library in &info.libraries {
+        println!("  {} {} {}",
+            "▸".green(),
+            library.name.bright_white(),
+            format!("({}, {})", library.kind, library.source).dimmed()
+        );
+    }
+    for engine in &info.engines {
+        print!("  {} {} {}",
+            "◆".cyan(),
+            engine.engine.bright_white(),
+            format!("(used by {})", engine.required_by.join(", ")).dimmed()
+        );
+        if engine.embedded {
+            print!(" {}", "[embedded]".green());
+        } else if let Some(server) = &engine.server {
+            print!(" {}", format!("[server: {}]", server).green());
+        } else if let Some(client) = &engine.client {
+            print!(" {}", format!("[no local server, client: {}]", client).yellow());
+        } else {
+            print!(" {}", "[no local server or client]".red());
+        }
+        println!();
+    }
+}
+
+/// Print migration frameworks with their migration counts and CLI status
+fn print_migrations(tools: &[MigrationTool]) {
+    println!("\n{}", t("Database Migrations:").bold().yellow());
+    for tool in tools {
+        print!("  {} {} {} {}",
+            "▸".green(),
+            tool.tool.bright_white(),
+            format!("{} migrations", tool.migrations).cyan(),
+            format!("({}, {})", tool.directory, tool.config).dimmed()
+        );
+        if !tool.cli_installed {
+            print!(" {}", format!("[{} CLI not installed]", tool.cli).red());
+        }
+        println!();
+    }
+}
+
+/// Print Devbox/Flox environments with their packages and build state
+fn print_dev_environments(envs: &[DevEnvironment]) {
+    println!("\n{}", t("Project Environments:").bold().yellow());
+    for env in envs {
+        print!("  {} {}", "▸".green(), env.tool.bright_white());
+        if let Some(name) = &env.name {
+            print!(" {}", name.cyan());
+        }
+        print!(" {}", format!("({})", env.manifest).dimmed());
+        if let Some(python) = &env.python {
+            print!(" {}", format!("python {}", python).green());
+        }
+        if let Some(count) = env.locked_packages {
+            print!(" {}", format!("{} locked packages", count).dimmed());
+        }
+        if !env.cli_installed {
+            print!(" {}", format!("[{} CLI not installed]", env.tool).red());
+        } else if !env.built {
+            print!(" {}", "[not built yet]".yellow());
+        } else {
+            print!(" {}", "[built]".green());
+        }
+        println!();
+        print_tree(&env.packages);
+    }
+}
+
+/// Print tool versions pinned by Hermit, proto and similar managers
+fn print_pinned_tools(pins: &[ToolPin]) {
+    println!("\n{}", t("Pinned Toolchain:").bold().yellow());
+    for pin in pins {
+        println!("  {} {} {} {}",
+            "▸".green(),
+            pin.tool.bright_white(),
+            pin.version.green(),
+            format!("({}: {})", pin.manager, pin.source).dimmed()
+        );
+    }
+}
+
+fn print_system_checks(checks: &[SystemCheck]) {
+    println!("\n{}", t("System Requirements:").bold().yellow());
+    for check in checks {
+        let mark = if check.satisfied { "✓".green() } else { "✗".red() };
+        let actual = match &check.actual {
+            Some(actual) => format!("(have {})", actual),
+            None => "(could not check)".to_string(),
+        };
+        println!("  {} {} {} {}", mark, check.kind.display_name().bright_white(), check.required, actual.dimmed());
+    }
+}
+
+fn print_services(services: &[ServiceCheck]) {
+    println!("\n{}", t("Services:").bold().yellow());
+    for service in services {
+        match &service.via {
+            Some(via) => println!("  {} {} {}", "✓".green(), service.name.bright_white(), format!("(running, {})", via).dimmed()),
+            None => {
+                print!("  {} {} {}", "✗".red(), service.name.bright_white(), "not running".red());
+                if let Some(hint) = &service.start_hint {
+                    print!(" {}", format!("(start with: {})", hint).dimmed());
+                }
+                println!();
+            }
+        }
+    }
+}
+
+fn print_package_registries(registries: &[PackageRegistry]) {
+    println!("\n{}", t("Package Registries:").bold().yellow());
+    for registry in registries {
+        let label = match &registry.name {
+            Some(name) => format!("{} {} {}", registry.ecosystem, registry.kind, name),
+            None => format!("{} {}", registry.ecosystem, registry.kind),
+        };
+        print!("  {} {}", "▸".green(), label.bright_white());
+        if let Some(host) = &registry.host {
+            print!(" → {}", host.cyan());
+        }
+        print!(" {}", format!("({})", registry.source).dimmed());
+        if let Some(problem) = &registry.problem {
+            print!(" {}", format!("[{}]", problem).yellow());
+        }
+        println!();
+    }
+}
+
+fn print_ssh(ssh: &SshReadiness) {
+    println!("\n{}", t("SSH:").bold().yellow());
+    let agent = match (ssh.agent_running, ssh.agent_keys.is_empty()) {
+        (false, _) => "not running".yellow(),
+        (true, true) => "running, no keys loaded".yellow(),
+        (true, false) => format!("running, {} keys ({})", ssh.agent_keys.len(), ssh.agent_keys.join(", ")).green(),
+    };
+    println!("  {} {} {}", "▸".green(), "ssh-agent".bright_white(), agent);
+    if !ssh.key_files.is_empty() {
+        println!("  {} {} {}", "▸".green(), "~/.ssh".bright_white(), ssh.key_files.join(", ").dimmed());
+    }
+    for host in &ssh.hosts {
+        let mark = if host.known { "✓".green() } else { "✗".red() };
+        let mut name = host.hostname.clone();
+        if host.port != 22 {
+            name = format!("{}:{}", name, host.port);
+        }
+        if host.alias != host.hostname {
+            name = format!("{} → {}", host.alias, name);
+        }
+        let mut notes = vec![format!("remotes: {}", host.remotes.join(", "))];
+        if !host.known {
+            notes.push("not in known_hosts".to_string());
+        }
+        if host.configured {
+            notes.push("in ~/.ssh/config".to_string());
+        }
+        if !host.identity_files.is_empty() {
+            notes.push(format!("identity {}", host.identity_files.join(", ")));
+        }
+        println!("  {} {} {}", mark, name.bright_white(), format!("({})", notes.join("; ")).dimmed());
+    }
+}
+
+fn print_network(checks: &[NetworkCheck]) {
+    println!("\n{}", t("Network:").bold().yellow());
+    for check in checks {
+        let endpoint = format!("{}:{}", check.host, check.port);
+        let status = match (check.reachable, check.resolved, &check.error) {
+            (true, _, _) => "reachable".green(),
+            (false, false, _) => "does not resolve".red(),
+            (false, true, Some(error)) => format!("unreachable: {}", error).red(),
+            (false, true, None) => "unreachable".red(),
+        };
+        let mark = if check.reachable { "✓".green() } else { "✗".red() };
+        println!("  {} {} {} {}", mark, endpoint.bright_white(), status, format!("({})", check.sources.join(", ")).dimmed());
+    }
+}
+
+/// Print one line per `devfetch check` constraint
+pub fn print_checks(checks: &[ConstraintCheck]) {
+    for check in checks {
+        let mark = if check.satisfied { "✓".green() } else { "✗".red() };
+        print!("  {} {}", mark, check.tool.bright_white());
+        match (&check.path, &check.version) {
+            (None, _) => print!(" {}", "not found".red()),
+            (Some(_), Some(version)) => print!(" {}", format!("v{}", version).green()),
+            (Some(_), None) => print!(" {}", "version unknown".yellow()),
+        }
+        if check.constraint != check.tool {
+            let verdict = if check.satisfied { "satisfies" } else { "does not satisfy" };
+            print!(" {}", format!("{} {}", verdict, check.constraint).dimmed());
+        }
+        println!();
+    }
+}
+
+/// Print `devfetch tool`, with the reasoning behind each answer for --explain
+pub fn print_tool_explanation(tool: &ToolExplanation, explain: bool) {
+    let version = tool.probes.iter().find_map(|p| p.version.as_deref());
+    print!("  {} {}", "▸".green(), tool.name.bright_white());
+    match version {
+        Some(v) => print!(" {}", format!("v{}", v).green()),
+        None => print!(" {}", "version unknown".yellow()),
+    }
+    println!(" {}", format!("({})", tool.path.display()).dimmed());
+    let because = |reason: &str| if explain { format!(" — {}", reason).dimmed().to_string() } else { String::new() };
+    let mut details = vec![
+        format!("Category: {}{}", tool.category.display_name(), because(&tool.category_reason)),
+        format!("Scope: {}{}", tool.scope.display_name(), because(&tool.scope_reason)),
+    ];
+    if explain {
+        details.extend(tool.probes.iter().map(|p| format!("Probe {}: {}", p.flag, p.outcome)));
+        details.push(format!("Cache: {}", tool.cache));
+    }
+    print_tree(&details);
+}
+
+/// Print `devfetch cache stats`
+pub fn print_cache_stats(stats: &CacheStats) {
+    println!("{} {}", t("Cache:").bold().yellow(), stats.path.display().to_string().dimmed());
+    println!("  {} {} entries ({} KiB)", "▸".green(), stats.entries.to_string().bright_white(), stats.size_bytes / 1024);
+    if stats.stale > 0 {
+        println!("  {} {}", "▸".green(), format!("{} stale (binary changed or removed)", stats.stale).yellow());
+    }
+    match &stats.last_scan {
+        Some(usage) => println!(
+            "  {} last scan: {} hits, {} misses ({:.0}% hit rate)",
+            "▸".green(),
+            usage.hits.to_string().green(),
+            usage.misses.to_string().yellow(),
+            usage.hit_rate()
+        ),
+        None => println!("  {} {}", "▸".green(), "no scan recorded yet".dimmed()),
+    }
+    let ages: Vec<String> = stats.ages.iter().map(|(label, count)| format!("{}: {}", label, count)).collect();
+    print_tree(&ages);
+}
+
+/// Print `devfetch stats show`
+pub fn print_usage_stats(stats: &UsageStats, file: &std::path::Path, enabled: bool) {
+    println!("{} {}", t("Usage statistics:").bold().yellow(), file.display().to_string().dimmed());
+    if !enabled {
+        println!("  {} {}", "▸".green(), "recording is off; set `enabled = true` under [stats] in the config".dimmed());
+    }
+    if stats.scans == 0 {
+        println!("  {} {}", "▸".green(), "no scan recorded yet".dimmed());
+        return;
+    }
+    println!(
+        "  {} {} scans ({} in a project)",
+        "▸".green(),
+        stats.scans.to_string().bright_white(),
+        stats.project_scans
+    );
+    println!(
+        "  {} duration: {} ms average, {} ms max",
+        "▸".green(),
+        stats.total_duration_ms / stats.scans,
+        stats.max_duration_ms
+    );
+    println!("  {} tools: {} average, {} max", "▸".green(), stats.total_tools / stats.scans, stats.max_tools);
+}
+
+/// Print `devfetch cache show` entries
+pub fn print_cache_entries(entries: &[(&std::path::PathBuf, &CacheEntry, bool)]) {
+    for (path, entry, fresh) in entries {
+        print!("  {} {}", "▸".green(), path.display().to_string().bright_white());
+        if *fresh {
+            println!(" {}", "[fresh]".green());
+        } else {
+            println!(" {}", "[stale: binary changed since probing]".yellow());
+        }
+        let version = match (&entry.version, entry.success) {
+            (Some(v), _) => format!("Version: v{}", v),
+            (None, false) => "Version: probe failed".to_string(),
+            (None, true) => "Version: unknown".to_string(),
+        };
+        let mut details = vec![
+            version,
+            format!("Target: {} ({} bytes, mtime {})", entry.target.display(), entry.size, entry.mtime / 1_000_000_000),
+            format!("Probed at: {} (unix time)", entry.probed_at),
+        ];
+        if let Some(first) = entry.output.lines().next() {
+            details.push(format!("Output: {}", first));
+        }
+        print_tree(&details);
+    }
+}
+
+/// Print the shell hook's one-line project summary
+pub fn print_project_summary(summary: &ProjectSummary) {
+    let line = crate::core::hook::render(summary);
+    if summary.mismatches.is_empty() {
+        println!("{} {}", "devfetch:".dimmed(), line);
+    } else {
+        println!("{} {}", "devfetch:".dimmed(), line.yellow());
+    }
+}
+
+/// Print `devfetch doctor` findings, most severe first
+pub fn print_doctor(checks: &[RequirementCheck], findings: &[Finding], ignored: usize) {
+    println!("\n{}", "═══════════════════════════════════════════════════════".bright_blue().bold());
+    println!("{}", format!("  {}", t("DOCTOR")).bright_blue().bold());
+    println!("{}", "═══════════════════════════════════════════════════════".bright_blue().bold());
+    println!();
+
+    if !checks.is_empty() {
+        print_requirement_checks(checks);
+        println!();
+    }
+
+    if findings.is_empty() {
+        println!("  {} {}", "✓".green(), t("All project requirements satisfied").green());
+    }
+
+    for finding in findings {
+        let label = format!("[{}]", t(finding.severity.display_name()));
+        let label = match finding.severity {
+            Severity::Error => label.red().bold(),
+            Severity::Warning => label.yellow(),
+            Severity::Info => label.dimmed(),
+        };
+        println!("  {} {} {} {}", label, finding.subject.bright_white(), finding.issue, format!("({})", finding.id()).dimmed());
+        if let Some(fix) = &finding.fix {
+            println!("    {} {}", "└─ fix:".dimmed(), fix.cyan());
+        }
+    }
+    if ignored > 0 {
+        println!("  {}", format!("({} ignored by {})", ignored, crate::core::ignore::FILE_NAME).dimmed());
+    }
+
+    println!();
+}
+
+/// Pass/warn/fail table of the project's version requirements
+fn print_requirement_checks(checks: &[RequirementCheck]) {
+    println!("{}", t("Version requirements:").bold().yellow());
+    let tool_width = checks.iter().map(|c| c.requirement.tool.len()).max().unwrap_or(0);
+    let constraint_width = checks.iter().map(|c| c.requirement.constraint.len()).max().unwrap_or(0);
+    let source_width = checks.iter().map(|c| c.requirement.source.len()).max().unwrap_or(0);
+    for check in checks {
+        let label = match check.status {
+            RequirementStatus::Pass => format!("[{}]", t("pass")).green(),
+            RequirementStatus::Warn => format!("[{}]", t("warn")).yellow(),
+            RequirementStatus::Fail => format!("[{}]", t("fail")).red().bold(),
+        };
+        let installed = match (&check.path, &check.installed) {
+            (None, _) => t("not installed").dimmed(),
+            (Some(_), Some(version)) => format!("v{}", version).normal(),
+            (Some(_), None) => t("version unknown").dimmed(),
+        };
+        println!(
+            "  {} {:tool_width$}  {:constraint_width$}  {:source_width$}  {}",
+            label,
+            check.requirement.tool.bright_white(),
+            check.requirement.constraint,
+            check.requirement.source.dimmed(),
+            installed,
+        );
+    }
+}
+
+/// Print which project ecosystems have a language server installed
+fn print_lsp_coverage(coverage: &[LspCoverage]) {
+    println!("\n{}", t("Editor Support:").bold().yellow());
+
+    for entry in coverage {
+        if entry.servers.is_empty() {
+            let hint = entry
+                .suggested
+                .as_ref()
+                .map(|s| format!(" (e.g. install {})", s))
+                .unwrap_or_default();
+            println!("  {} {} {}{}",
+                "✗".red(),
+                entry.ecosystem.bright_white(),
+                "no language server installed".red(),
+                hint.dimmed()
+            );
+        } else {
+            println!("  {} {} {}", "✓".green(), entry.ecosystem.bright_white(), entry.servers.join(", ").dimmed());
+        }
+    }
+}
+
+/// Print nested projects as a tree of paths relative to `root`, each with
+/// its ecosystems and workspace kind
+fn print_subprojects(root: &Path, projects: &[ProjectInfo], prefix: &str) {
+    for (i, project) in projects.iter().enumerate() {
+        let last = i + 1 == projects.len();
+        let relative = project.path.strip_prefix(root).unwrap_or(&project.path);
+        let mut ecosystems: Vec<String> = project
+            .ecosystems
+            .iter()
+            .map(|(name, info)| match &info.tool_version {
+                Some(version) => format!("{} v{}", name, version),
+                None => name.clone(),
+            })
+            .collect();
+        for marker in &project.markers {
+            if !project.ecosystems.contains_key(&marker.ecosystem) && !ecosystems.contains(&marker.ecosystem) {
+                ecosystems.push(marker.ecosystem.clone());
+            }
+        }
+        print!("{}{} {}", prefix, (if last { "└─" } else { "├─" }).dimmed(), relative.display().to_string().bright_white());
+        if !ecosystems.is_empty() {
+            print!("  {}", ecosystems.join(", ").cyan());
+        }
+        if let Some(workspace) = &project.workspace {
+            print!(" {}", format!("[{} workspace]", workspace.kind).dimmed());
+        }
+        println!();
+        let child_prefix = format!("{}{}  ", prefix, if last { " " } else { "│" }.dimmed());
+        print_subprojects(&project.path, &project.subprojects, &child_prefix);
+    }
+}
+
+/// Print detail lines as a tree under the preceding entry
+fn print_tree(lines: &[String]) {
+    for (i, line) in lines.iter().enumerate() {
+        let branch = if i + 1 == lines.len() { "└─" } else { "├─" };
+        println!("    {} {}", branch.dimmed(), line);
+    }
+}
+
+/// Print PATH entries and security audit findings
+pub fn print_path_audit(audit: &PathAudit) {
+    println!("\n{}", "═══════════════════════════════════════════════════════".bright_blue().bold());
+    println!("{}", format!("  {}", t("PATH")).bright_blue().bold());
+    println!("{}", "═══════════════════════════════════════════════════════".bright_blue().bold());
+    println!();
+
+    for entry in &audit.entries {
+        let dir = entry.dir.display().to_string();
+        let dir = if entry.exists { dir.bright_white() } else { dir.dimmed() };
+        println!("  {} {}", format!("{:>3}.", entry.index + 1).dimmed(), dir);
+    }
+
+    println!("\n{}", t("Security Audit:").bold().yellow());
+    if audit.findings.is_empty() {
+        println!("  {} {}", "✓".green(), t("No problems found").green());
+    }
+
+    let mut findings: Vec<&Finding> = audit.findings.iter().collect();
+    findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+    for finding in findings {
+        let label = format!("[{}]", t(finding.severity.display_name()));
+        let label = match finding.severity {
+            Severity::Error => label.red().bold(),
+            Severity::Warning => label.yellow(),
+            Severity::Info => label.dimmed(),
+        };
+        let entry = if finding.subject.is_empty() { "(empty)" } else { &finding.subject };
+        println!("  {} {} {}", label, finding.issue.bright_white(), entry.cyan());
+        if let Some(rationale) = &finding.rationale {
+            println!("    {} {}", "└─".dimmed(), rationale.dimmed());
+        }
+    }
+
+    println!();
+}
+
+/// Print merged fleet data: version spread, missing tools and outliers
+pub fn print_fleet_report(report: &FleetReport) {
+    println!("\n{}", "═══════════════════════════════════════════════════════".bright_blue().bold());
+    println!("{}", format!("  {}", tf("FLEET ({} machines)", &[&report.machines.len()])).bright_blue().bold());
+    let total = report.machines.len();
+    println!("{}", "═══════════════════════════════════════════════════════".bright_blue().bold());
+
+    for tool in &report.tools {
+        let installed: usize = tool.versions.values().map(|m| m.len()).sum();
+        println!("\n  {} {} {}",
+            "◆".cyan(),
+            tool.name.bright_white().bold(),
+            format!("({}/{} machines)", installed, total).dimmed()
+        );
+
+        let mut details: Vec<String> = tool
+            .versions
+            .iter()
+            .map(|(version, machines)| format!("{} {}", version.green(), format!("× {}", machines.len()).dimmed()))
+            .collect();
+        if !tool.missing_on.is_empty() {
+            details.push(format!("{} {}", "missing on:".red(), tool.missing_on.join(", ")));
+        }
+        for outlier in &tool.outliers {
+            details.push(format!("{} {} runs {}", "outlier:".yellow(), outlier.machine, outlier.version));
+        }
+        print_tree(&details);
+    }
+
+    println!();
+}
+
+/// Print two scans side by side, highlighting differences
+pub fn print_scan_diff(diff: &ScanDiff, mismatched_only: bool) {
+    let rows: Vec<_> = diff
+        .tools
+        .iter()
+        .filter(|t| !mismatched_only || t.status != DiffStatus::Same)
+        .collect();
+
+    let name_width = rows.iter().map(|t| t.name.len()).chain(std::iter::once(t("Tool").chars().count())).max().unwrap_or(0);
+    let left_width = rows
+        .iter()
+        .filter_map(|t| t.left.as_ref().map(|v| v.len()))
+        .chain(std::iter::once(diff.left.len()))
+        .max()
+        .unwrap_or(0);
+
+    println!("\n{}", "═══════════════════════════════════════════════════════".bright_blue().bold());
+    println!("{}", format!("  {}", tf("DIFF: {} ↔ {}", &[&diff.left, &diff.right])).bright_blue().bold());
+    println!("{}", "═══════════════════════════════════════════════════════".bright_blue().bold());
+
+    println!("\n  {:name_width$}   {:left_width$}   {}", t("Tool").bold(), diff.left.bold(), diff.right.bold());
+    for tool in &rows {
+        // Pad before coloring so ANSI codes don't break alignment
+        let left = format!("{:left_width$}", tool.left.as_deref().unwrap_or("—"));
+        let right = tool.right.as_deref().unwrap_or("—").to_string();
+        let name = format!("{:name_width$}", tool.name);
+        match tool.status {
+            DiffStatus::Same => println!("  {}   {}   {}", name.dimmed(), left.dimmed(), right.dimmed()),
+            DiffStatus::Changed => println!("  {}   {}   {}", name.bright_white(), left.yellow(), right.yellow()),
+            DiffStatus::Upgraded => println!("  {}   {}   {} {}", name.bright_white(), left.yellow(), right.green(), "↑".green()),
+            DiffStatus::Downgraded => println!("  {}   {}   {} {}", name.bright_white(), left.yellow(), right.red(), "↓".red()),
+            DiffStatus::OnlyLeft => println!("  {}   {}   {}", name.bright_white(), left.green(), right.red()),
+            DiffStatus::OnlyRight => println!("  {}   {}   {}", name.bright_white(), left.red(), right.green()),
+        }
+    }
+
+    if !diff.dependencies.is_empty() {
+        println!("\n{}", t("Dependencies:").bold().yellow());
+        for dep in &diff.dependencies {
+            let count = |c: Option<usize>| c.map_or("—".to_string(), |c| c.to_string());
+            println!("  {} {}: {} → {}", "▸".green(), dep.ecosystem.bright_white(), count(dep.left), count(dep.right));
+        }
+    }
+
+    let count = |status| diff.tools.iter().filter(|t| t.status == status).count();
+    println!(
+        "\n  {} same, {} upgraded, {} downgraded, {} otherwise different, {} only on {}, {} only on {}\n",
+        count(DiffStatus::Same),
+        count(DiffStatus::Upgraded),
+        count(DiffStatus::Downgraded),
+        count(DiffStatus::Changed),
+        count(DiffStatus::OnlyLeft),
+        diff.left,
+        count(DiffStatus::OnlyRight),
+        diff.right
+    );
+}
+
+/// Draw the PATH scan's progress as a single stderr line
+pub fn print_progress(event: &ScanProgress) {
+    use std::io::Write;
+    match event {
+        ScanProgress::Started { .. } | ScanProgress::Message(_) => return,
+        ScanProgress::Probed { done, total, .. } => eprint!("\r  Probing tools... {}/{}", done, total),
+        // Clear the progress line
+        ScanProgress::Finished { .. } => eprint!("\r                                      \r"),
+    }
+    let _ = std::io::stderr().flush();
+}
+
+/// Output any serializable result in JSON format
+pub fn print_json<T: serde::Serialize>(result: &T) -> anyhow::Result<()> {
     let json = serde_json::to_string_pretty(result)?;
     println!("{}", json);
     Ok(())
 }
 
+/// A destination for a finished scan; one scan can go to several at once
+pub trait OutputSink {
+    fn emit(&self, result: &ScanResult) -> anyhow::Result<()>;
+}
+
+/// Pretty or JSON output on stdout
+pub struct TerminalSink {
+    pub json: bool,
+}
+
+impl OutputSink for TerminalSink {
+    fn emit(&self, result: &ScanResult) -> anyhow::Result<()> {
+        if self.json {
+            print_json(result)
+        } else {
+            print_pretty(result);
+            Ok(())
+        }
+    }
+}
+
+/// File formats for --output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    /// Pretty-printed JSON, replacing the file
+    Json,
+    /// One compact JSON object per line, appended (for scan logs)
+    JsonLines,
+}
+
+impl FileFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "json" => Some(FileFormat::Json),
+            "jsonl" => Some(FileFormat::JsonLines),
+            _ => None,
+        }
+    }
+}
+
+/// The scan written to a file, gzip- or zstd-compressed for .gz and .zst names
+pub struct FileSink {
+    pub path: std::path::PathBuf,
+    pub format: FileFormat,
+}
+
+impl OutputSink for FileSink {
+    fn emit(&self, result: &ScanResult) -> anyhow::Result<()> {
+        use anyhow::Context;
+        use std::io::Write;
+
+        let codec = Codec::for_path(&self.path);
+        match self.format {
+            FileFormat::Json => std::fs::write(&self.path, codec.compress((serde_json::to_string_pretty(result)? + "\n").as_bytes())?),
+            FileFormat::JsonLines => {
+                let line = codec.compress((serde_json::to_string(result)? + "\n").as_bytes())?;
+                std::fs::OpenOptions::new().create(true).append(true).open(&self.path).and_then(|mut file| file.write_all(&line))
+            }
+        }
+        .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
+    use crate::core::test_util::TempDir;
 
     #[test]
     fn test_print_json() {
         let mut result = ScanResult::new();
         result.global_tools.push(Tool {
-            name: "python3".to_string(),
-            path: PathBuf::from("/usr/bin/python3"),
             version: Some("3.11.0".to_string()),
             category: ToolCategory::LanguageToolchain,
+            ..Tool::new("python3", "/usr/bin/python3")
         });
 
         assert!(print_json(&result).is_ok());
     }
+
+    #[test]
+    fn test_file_sink() {
+        let dir = TempDir::new("sink");
+        let path = dir.join("scans.jsonl");
+        let sink = FileSink { path: path.clone(), format: FileFormat::JsonLines };
+        let mut result = ScanResult::new();
+        result.hostname = Some("ci-runner".to_string());
+
+        sink.emit(&result).unwrap();
+        sink.emit(&result).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().count(), 2);
+        assert!(content.lines().all(|l| serde_json::from_str::<ScanResult>(l).unwrap().hostname.as_deref() == Some("ci-runner")));
+    }
 }
@@ -0,0 +1,226 @@
+use crate::core::security;
+use crate::types::{PathAudit, PathEntry, Finding, Severity};
+use std::collections::HashSet;
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::Path;
+
+/// Sticky bit: only owners may delete/rename entries (as on /tmp)
+const STICKY_BIT: u32 = 0o1000;
+
+/// List PATH entries in order and audit them for security problems
+pub fn audit_path() -> PathAudit {
+    let path_var = env::var_os("PATH").unwrap_or_default();
+    audit_path_value(&path_var)
+}
+
+fn audit_path_value(path_var: &OsStr) -> PathAudit {
+    let mut entries = Vec::new();
+    let mut findings = Vec::new();
+    let mut seen = HashSet::new();
+    let current_uid = security::current_uid();
+
+    for (index, dir) in env::split_paths(path_var).enumerate() {
+        let display = dir.to_string_lossy().to_string();
+        let exists = dir.is_dir();
+
+        entries.push(PathEntry {
+            index,
+            dir: dir.clone(),
+            exists,
+        });
+
+        if display.is_empty() {
+            findings.push(finding(
+                Severity::Error,
+                &display,
+                "Empty PATH entry",
+                "An empty entry is treated as the current directory, so any executable in \
+                 whatever directory you cd into can shadow real tools.",
+            ));
+            continue;
+        }
+
+        if display == "." {
+            findings.push(finding(
+                Severity::Error,
+                &display,
+                "Current directory in PATH",
+                "Running a command inside an untrusted checkout can execute a binary planted in it.",
+            ));
+            continue;
+        }
+
+        if dir.is_relative() {
+            findings.push(finding(
+                Severity::Warning,
+                &display,
+                "Relative PATH entry",
+                "Relative entries resolve against the working directory, so the tools found \
+                 change with every cd.",
+            ));
+            continue;
+        }
+
+        if !seen.insert(dir.clone()) {
+            findings.push(finding(
+                Severity::Info,
+                &display,
+                "Duplicate PATH entry",
+                "Harmless, but usually a sign of shell rc files appending PATH more than once.",
+            ));
+            continue;
+        }
+
+        if !exists {
+            findings.push(finding(
+                Severity::Info,
+                &display,
+                "Directory does not exist",
+                "If anyone can later create it, binaries placed there will be picked up.",
+            ));
+            continue;
+        }
+
+        findings.extend(audit_directory(&dir, &display, current_uid));
+    }
+
+    PathAudit { entries, findings }
+}
+
+/// Check a PATH directory and its files for unsafe permissions and owners
+fn audit_directory(dir: &Path, display: &str, current_uid: Option<u32>) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    if let Ok(metadata) = fs::metadata(dir) {
+        let mode = metadata.permissions().mode();
+        if mode & 0o002 != 0 {
+            let severity = if mode & STICKY_BIT != 0 { Severity::Warning } else { Severity::Error };
+            findings.push(finding(
+                severity,
+                display,
+                "World-writable directory in PATH",
+                "Any local user can drop an executable here that shadows a tool later in PATH.",
+            ));
+        } else if mode & 0o020 != 0 {
+            findings.push(finding(
+                Severity::Warning,
+                display,
+                "Group-writable directory in PATH",
+                "Every member of the directory's group can add or replace executables.",
+            ));
+        }
+        if security::is_other_user(metadata.uid(), current_uid) {
+            findings.push(finding(
+                Severity::Warning,
+                display,
+                "PATH directory owned by another user",
+                "Its owner can add or replace the executables you run.",
+            ));
+        }
+    }
+
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let Ok(metadata) = entry.metadata() else { continue };
+            let mode = metadata.permissions().mode();
+            if !metadata.is_file() || mode & 0o111 == 0 {
+                continue;
+            }
+            let path = entry.path().to_string_lossy().to_string();
+            if mode & 0o002 != 0 {
+                findings.push(finding(
+                    Severity::Error,
+                    &path,
+                    "World-writable executable",
+                    "Any local user can replace this binary with code that runs as whoever invokes it.",
+                ));
+            } else if mode & 0o020 != 0 {
+                findings.push(finding(
+                    Severity::Warning,
+                    &path,
+                    "Group-writable executable",
+                    "Every member of the file's group can replace this binary.",
+                ));
+            }
+            if security::is_other_user(metadata.uid(), current_uid) {
+                findings.push(finding(
+                    Severity::Warning,
+                    &path,
+                    "Executable owned by another user",
+                    "Its owner can replace it with code that runs as you.",
+                ));
+            }
+        }
+    }
+
+    findings
+}
+
+fn finding(severity: Severity, entry: &str, issue: &str, rationale: &str) -> Finding {
+    Finding {
+        severity,
+        check: "path".to_string(),
+        subject: entry.to_string(),
+        issue: issue.to_string(),
+        rationale: Some(rationale.to_string()),
+        fix: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    fn issues(audit: &PathAudit) -> Vec<&str> {
+        audit.findings.iter().map(|f| f.issue.as_str()).collect()
+    }
+
+    #[test]
+    fn test_audit_entries() {
+        let audit = audit_path_value(OsStr::new("/usr/bin::.:bin:/usr/bin:/devfetch/does/not/exist"));
+        assert_eq!(audit.entries.len(), 6);
+        assert_eq!(
+            issues(&audit),
+            vec![
+                "Empty PATH entry",
+                "Current directory in PATH",
+                "Relative PATH entry",
+                "Duplicate PATH entry",
+                "Directory does not exist",
+            ]
+        );
+        // `devfetch path --json` keeps the field name it has always written
+        let json = serde_json::to_value(&audit).unwrap();
+        assert_eq!(json["findings"][2]["entry"], "bin");
+        assert!(json["findings"][2].get("tool").is_none());
+    }
+
+    #[test]
+    fn test_world_writable_directory() {
+        let dir = TempDir::new("audit");
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o777)).unwrap();
+        let exe = dir.join("tool");
+        fs::write(&exe, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(&exe, fs::Permissions::from_mode(0o777)).unwrap();
+
+        let group = dir.join("group-tool");
+        fs::write(&group, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(&group, fs::Permissions::from_mode(0o775)).unwrap();
+
+        let owner = fs::metadata(&exe).unwrap().uid();
+        let findings = audit_directory(&dir, &dir.to_string_lossy(), Some(owner));
+        assert!(findings.iter().any(|f| f.issue == "World-writable directory in PATH" && f.severity == Severity::Error));
+        assert!(findings.iter().any(|f| f.issue == "World-writable executable"));
+        assert!(findings.iter().any(|f| f.issue == "Group-writable executable" && f.subject.ends_with("group-tool")));
+        assert!(!findings.iter().any(|f| f.issue.contains("owned by another user")));
+        // Handing the binary to another regular user is only possible as root
+        if std::os::unix::fs::chown(&exe, Some(4242), None).is_ok() {
+            let findings = audit_directory(&dir, &dir.to_string_lossy(), Some(owner));
+            assert!(findings.iter().any(|f| f.issue == "Executable owned by another user" && f.subject.ends_with("/tool")));
+        }
+    }
+}
@@ -1,12 +1,17 @@
-use crate::core::probe;
-use crate::types::Tool;
+use crate::core::cache::ProbeCache;
+use crate::core::{exec, interrupt, paths, probe, security};
+use crate::types::{ProbeResult, ScanProgress, ScanWarning, Tool, WarningKind};
 use rayon::prelude::*;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::env;
+use std::ffi::OsString;
 use std::fs;
-use std::io::{self, Write};
-use std::os::unix::fs::PermissionsExt;
+use std::io;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, UNIX_EPOCH};
 
 /// Known developer tool prefixes/patterns to include
 static DEVELOPER_TOOL_PATTERNS: &[&str] = &[
@@ -36,10 +41,125 @@ static DEVELOPER_TOOL_PATTERNS: &[&str] = &[
     "jest", "mocha", "pytest", "rspec", "junit",
     // Linters/formatters
     "eslint", "prettier", "black", "flake8", "pylint", "rubocop", "rustfmt", "clippy",
+    "ruff", "biome", "stylelint", "isort", "mypy", "shellcheck", "shfmt", "hadolint",
+    "yamllint", "markdownlint", "dprint", "ktlint",
+    // Coverage/profiling
+    "gcov", "lcov", "llvm-cov", "grcov", "coverage", "nyc", "c8", "kcov", "perf", "valgrind",
+    "samply", "heaptrack", "flamegraph", "py-spy",
     // Other dev tools
     "jq", "yq", "protoc", "thrift",
 ];
 
+/// Toolchains and package managers probed first, so a --budget scan has them
+static PRIORITY_TOOLS: &[&str] = &[
+    "python", "python3", "pip", "pip3", "node", "npm", "yarn", "pnpm", "deno", "bun",
+    "go", "rustc", "cargo", "java", "javac", "mvn", "gradle", "ruby", "gem", "bundle",
+    "php", "composer", "dotnet", "gcc", "g++", "clang", "make", "cmake", "git", "docker",
+];
+
+/// Well-known install locations that are often missing from PATH
+static DEFAULT_EXTRA_DIRS: &[&str] = &[
+    "~/.local/bin", "~/bin", "~/go/bin", "~/.cargo/bin", "~/.dotnet/tools",
+    "~/.bun/bin", "~/.deno/bin", "~/.volta/bin", "~/.npm-global/bin", "~/.yarn/bin",
+    "~/.pub-cache/bin", "~/.config/composer/vendor/bin", "~/.composer/vendor/bin",
+    "~/.ghcup/bin", "~/.nimble/bin", "/opt/homebrew/bin", "/usr/local/go/bin", "/snap/bin",
+];
+
+/// Directories listed at once, so slow (network-mounted) ones aren't hit by
+/// every core together
+const MAX_LIST_THREADS: usize = 8;
+
+/// Options controlling which directories the PATH scan visits
+#[derive(Debug, Clone, Default)]
+pub struct PathScanOptions {
+    /// PATH to scan instead of the environment's
+    pub path: Option<OsString>,
+    /// Extra directories to scan for tools that are not on PATH
+    pub extra_dirs: Vec<String>,
+    /// PATH entries to exclude, including everything beneath them
+    pub skip_dirs: Vec<PathBuf>,
+    /// Probes not started by this time are skipped and ones still running are
+    /// cut short (--budget)
+    pub deadline: Option<Instant>,
+    /// How long each version flag may take; the default when unset
+    pub probe_timeout: Option<Duration>,
+    /// Tool names (globs) left out of the results
+    pub ignore_tools: Vec<glob::Pattern>,
+    /// Tool names (globs) scanned besides the built-in developer tool list
+    pub tool_patterns: Vec<glob::Pattern>,
+}
+
+impl PathScanOptions {
+    pub fn is_skipped(&self, dir: &Path) -> bool {
+        self.skip_dirs.iter().any(|skip| dir.starts_with(skip))
+    }
+
+    pub fn is_ignored(&self, name: &str) -> bool {
+        self.ignore_tools.iter().any(|pattern| pattern.matches(name))
+    }
+
+    fn is_dev_tool(&self, name: &str) -> bool {
+        is_likely_dev_tool(name) || self.tool_patterns.iter().any(|pattern| pattern.matches(name))
+    }
+
+    /// Probe within the probe timeout and what is left of the budget; None
+    /// when the budget ran out first, which says nothing about the tool
+    fn probe(&self, path: &str) -> Option<ProbeResult> {
+        let timeout = self.probe_timeout.unwrap_or(Duration::from_millis(exec::COMMAND_TIMEOUT_MS));
+        let result = probe::probe_version_before(path, timeout, self.deadline);
+        let out_of_budget = self.deadline.is_some_and(|deadline| Instant::now() >= deadline);
+        (!(result.timed_out && out_of_budget)).then_some(result)
+    }
+
+    /// All PATH entries, before exclusions
+    pub fn all_path_dirs(&self) -> Vec<PathBuf> {
+        self.path
+            .clone()
+            .or_else(|| env::var_os("PATH"))
+            .map(|p| env::split_paths(&p).collect())
+            .unwrap_or_default()
+    }
+
+    /// PATH entries that will actually be scanned
+    pub fn path_dirs(&self) -> Vec<PathBuf> {
+        self.all_path_dirs()
+            .into_iter()
+            .filter(|dir| !self.is_skipped(dir))
+            .collect()
+    }
+
+    /// The directories a scan lists, each once: PATH entries in order, with
+    /// relative ones (".", "bin") resolved against the current directory, then
+    /// the well-known and configured install dirs that are not on PATH
+    pub fn scan_dirs(&self) -> ScanDirs {
+        let cwd = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let all_path_dirs = self.all_path_dirs();
+        let mut seen = HashSet::new();
+        let path = self
+            .path_dirs()
+            .into_iter()
+            .map(|dir| if dir.is_relative() { cwd.join(dir) } else { dir })
+            .filter(|dir| seen.insert(dir.clone()))
+            .collect();
+        let extra = DEFAULT_EXTRA_DIRS
+            .iter()
+            .copied()
+            .chain(self.extra_dirs.iter().map(|d| d.as_str()))
+            .map(paths::expand_tilde)
+            .filter(|dir| !all_path_dirs.contains(dir) && !self.is_skipped(dir) && seen.insert(dir.clone()))
+            .collect();
+        ScanDirs { path, extra }
+    }
+}
+
+/// Directories a scan lists, from `PathScanOptions::scan_dirs`
+#[derive(Debug, Clone)]
+pub struct ScanDirs {
+    pub path: Vec<PathBuf>,
+    /// Install dirs outside PATH
+    pub extra: Vec<PathBuf>,
+}
+
 /// Check if a tool name matches developer tool patterns
 fn is_likely_dev_tool(name: &str) -> bool {
     let name_lower = name.to_lowercase();
@@ -69,117 +189,526 @@ fn is_executable(metadata: &fs::Metadata) -> bool {
     metadata.permissions().mode() & 0o111 != 0
 }
 
-/// Scan PATH directories for developer tools
-pub fn scan_path() -> Vec<String> {
-    let path_var = match env::var("PATH") {
-        Ok(p) => p,
-        Err(_) => return Vec::new(),
+/// Executables in `dir` that look like developer tools, with their metadata.
+/// Names are filtered before anything is stat'ed; the stat follows symlinks,
+/// as the shell does when it runs them. Directories that exist but can't be
+/// listed are reported in `warnings`; missing ones are left to `devfetch path`
+fn list_executables(
+    dir: &Path,
+    options: &PathScanOptions,
+    warnings: &mut Vec<ScanWarning>,
+) -> Vec<(String, PathBuf, fs::Metadata)> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            if e.kind() != io::ErrorKind::NotFound {
+                warnings.push(ScanWarning {
+                    kind: WarningKind::UnreadableDir,
+                    path: dir.to_path_buf(),
+                    message: format!("could not list directory: {}", e),
+                });
+            }
+            return Vec::new();
+        }
     };
-
-    let mut executables = HashSet::new();
-    
-    for dir in env::split_paths(&path_var) {
-        if let Ok(entries) = fs::read_dir(&dir) {
-            for entry in entries.flatten() {
-                if let Ok(metadata) = entry.metadata() {
-                    if metadata.is_file() && is_executable(&metadata) {
-                        if let Some(name) = entry.file_name().to_str() {
-                            // Only include likely developer tools
-                            if is_likely_dev_tool(name) {
-                                executables.insert(name.to_string());
-                            }
-                        }
-                    }
+    entries
+        .filter_map(|entry| {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    warnings.push(ScanWarning {
+                        kind: WarningKind::UnreadableDir,
+                        path: dir.to_path_buf(),
+                        message: format!("listing stopped early: {}", e),
+                    });
+                    return None;
+                }
+            };
+            let name = entry.file_name().into_string().ok().filter(|n| options.is_dev_tool(n))?;
+            let path = entry.path();
+            let metadata = match fs::metadata(&path) {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    let message = if e.kind() == io::ErrorKind::NotFound {
+                        "broken symlink".to_string()
+                    } else {
+                        format!("could not inspect: {}", e)
+                    };
+                    warnings.push(ScanWarning { kind: WarningKind::SkippedTool, path, message });
+                    return None;
                 }
+            };
+            (metadata.is_file() && is_executable(&metadata)).then_some((name, path, metadata))
+        })
+        .collect()
+}
+
+/// `list_executables` for each of `dirs`, in order; the listings (a stat per
+/// entry) run in parallel so one slow directory doesn't hold up the rest
+fn list_dirs(dirs: &[PathBuf], options: &PathScanOptions, warnings: &mut Vec<ScanWarning>) -> Vec<Vec<(String, PathBuf, fs::Metadata)>> {
+    let list = || -> Vec<_> {
+        dirs.par_iter()
+            .map(|dir| {
+                let mut dir_warnings = Vec::new();
+                (list_executables(dir, options, &mut dir_warnings), dir_warnings)
+            })
+            .collect()
+    };
+    let listings = match rayon::ThreadPoolBuilder::new().num_threads(MAX_LIST_THREADS).build() {
+        Ok(pool) => pool.install(list),
+        Err(_) => list(),
+    };
+    listings
+        .into_iter()
+        .map(|(found, dir_warnings)| {
+            warnings.extend(dir_warnings);
+            found
+        })
+        .collect()
+}
+
+/// Scan PATH directories for developer tools in one pass: every copy of each
+/// name in PATH order, so the first is the one that runs and later ones are shadowed
+pub fn scan_path(options: &PathScanOptions, dirs: &ScanDirs, warnings: &mut Vec<ScanWarning>) -> Vec<(String, PathBuf, fs::Metadata)> {
+    let mut executables = Vec::new();
+    for mut found in list_dirs(&dirs.path, options, warnings) {
+        found.retain(|(name, _, _)| !options.is_ignored(name));
+        found.sort_by(|a, b| a.0.cmp(&b.0));
+        executables.extend(found);
+    }
+    executables
+}
+
+/// Scan well-known directories that are not on PATH for developer tools.
+/// Tools whose name is already provided by PATH are skipped, since the PATH
+/// copy shadows them anyway.
+pub fn scan_extra_dirs(
+    options: &PathScanOptions,
+    dirs: &ScanDirs,
+    on_path: &HashSet<String>,
+    warnings: &mut Vec<ScanWarning>,
+) -> Vec<(String, PathBuf, fs::Metadata)> {
+    let mut found = Vec::new();
+    for listing in list_dirs(&dirs.extra, options, warnings) {
+        found.extend(
+            listing
+                .into_iter()
+                .filter(|(name, _, _)| !on_path.contains(name) && !options.is_ignored(name)),
+        );
+    }
+
+    found.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+    found.dedup_by(|a, b| a.0 == b.0);
+    found
+}
+
+/// A tool that won't say its version is still installed; keep it with the reason
+fn version_of(probe_result: ProbeResult) -> (Option<String>, Option<String>) {
+    if probe_result.success {
+        (probe_result.version, None)
+    } else {
+        (None, probe_result.error)
+    }
+}
+
+/// Every directory a scan lists, in order, with its mtime in nanoseconds
+/// (0 when missing, so one appearing later is noticed)
+fn dir_mtimes(dirs: &ScanDirs) -> Vec<(PathBuf, u64)> {
+    dirs.path
+        .iter()
+        .chain(&dirs.extra)
+        .map(|dir| {
+            let mtime = fs::metadata(dir)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_nanos() as u64);
+            (dir.clone(), mtime)
+        })
+        .collect()
+}
+
+/// `discover_tools` for repeat scans: while no scanned directory has changed,
+/// the last scan's tools are reused and only binaries that changed in place or
+/// are never cached (version manager shims) are probed again
+pub fn rescan_tools(
+    options: &PathScanOptions,
+    cache: &mut ProbeCache,
+    warnings: &mut Vec<ScanWarning>,
+    progress: &(dyn Fn(ScanProgress) + Sync),
+) -> Vec<Tool> {
+    let scan_dirs = options.scan_dirs();
+    let dirs = dir_mtimes(&scan_dirs);
+    let ignored: Vec<String> = options.ignore_tools.iter().map(|p| p.as_str().to_string()).collect();
+    let tool_patterns: Vec<String> = options.tool_patterns.iter().map(|p| p.to_string()).collect();
+    let Some((previous, previous_warnings)) = cache.previous_tools(&dirs, &ignored, &tool_patterns) else {
+        progress(ScanProgress::Message("Tool directories changed since the last scan; scanning them all".to_string()));
+        let before = warnings.len();
+        let tools = discover_in(options, &scan_dirs, cache, warnings, progress);
+        let found = &warnings[before..];
+        if !found.iter().any(|w| matches!(w.kind, WarningKind::BudgetExhausted | WarningKind::Interrupted)) {
+            cache.remember_tools(dirs, ignored, tool_patterns, &tools, found);
+        }
+        return tools;
+    };
+
+    let current_uid = security::current_uid();
+    let (mut tools, changed): (Vec<Tool>, Vec<Tool>) = previous.into_iter().partition(|t| cache.get(&t.path).is_some());
+    let hits = tools.len();
+    let total = changed.len();
+    let probed = AtomicUsize::new(0);
+    let skipped = AtomicUsize::new(0);
+    progress(ScanProgress::Started { total });
+    // Binaries gone without their directory changing can't be probed; they're dropped.
+    // Ones the budget or Ctrl-C stopped keep the last scan's details, uncached
+    let reprobed: Vec<(Tool, Option<ProbeResult>)> = changed
+        .into_par_iter()
+        .filter_map(|mut tool| {
+            let metadata = fs::metadata(&tool.path).ok()?;
+            let Some(probe_result) = options.probe(&tool.path.to_string_lossy()).filter(|_| !interrupt::interrupted()) else {
+                skipped.fetch_add(1, Ordering::Relaxed);
+                return Some((tool, None));
+            };
+            let done = probed.fetch_add(1, Ordering::Relaxed) + 1;
+            progress(ScanProgress::Probed { name: tool.name.clone(), done, total });
+            progress(ScanProgress::Message(format!("Re-probed: {}", tool.path.display())));
+            (tool.version, tool.probe_error) = version_of(probe_result.clone());
+            tool.security = security::tool_security_flags(&tool.path, &metadata, current_uid)
+                .into_iter()
+                .chain(security::gatekeeper_flags(&tool.path))
+                .collect();
+            Some((tool, Some(probe_result)))
+        })
+        .collect();
+    let skipped = skipped.into_inner();
+    let misses = reprobed.len() - skipped;
+    // The last scan's warnings still hold, except timeouts of binaries probed again now
+    let mut found: Vec<ScanWarning> = previous_warnings
+        .into_iter()
+        .filter(|w| w.kind != WarningKind::ProbeTimeout || !reprobed.iter().any(|(t, p)| p.is_some() && t.path == w.path))
+        .collect();
+    for (tool, probe_result) in reprobed {
+        if let Some(probe_result) = probe_result {
+            cache.insert(&tool.path, &probe_result);
+            if probe_result.timed_out {
+                found.push(ScanWarning {
+                    kind: WarningKind::ProbeTimeout,
+                    path: tool.path.clone(),
+                    message: "version probe timed out".to_string(),
+                });
             }
         }
+        tools.push(tool);
+    }
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+    progress(ScanProgress::Finished { tools: tools.len() });
+    cache.record_usage(hits, misses);
+    if skipped == 0 {
+        cache.remember_tools(dirs, ignored, tool_patterns, &tools, &found);
+    }
+    warnings.extend(found);
+    if skipped > 0 {
+        warnings.push(skipped_warning(skipped, total));
     }
+    tools
+}
 
-    let mut sorted: Vec<String> = executables.into_iter().collect();
-    sorted.sort();
-    sorted
+/// Why `skipped` of `total` tools went unprobed: Ctrl-C or the --budget
+fn skipped_warning(skipped: usize, total: usize) -> ScanWarning {
+    let (kind, reason) = if interrupt::interrupted() {
+        (WarningKind::Interrupted, "the scan was interrupted")
+    } else {
+        (WarningKind::BudgetExhausted, "the scan budget ran out")
+    };
+    ScanWarning {
+        kind,
+        path: PathBuf::from("PATH"),
+        message: format!("{} of {} tools not probed before {}", skipped, total, reason),
+    }
 }
 
-/// Discover developer tools from PATH using parallel version probing
-pub fn discover_tools(verbose: bool) -> Vec<Tool> {
-    let executables = scan_path();
+/// Whether a tool is probed in the first batch; versioned names such as
+/// python3.12 or gcc-13 count as their base tool
+fn is_priority(name: &str) -> bool {
+    let base = name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.' || c == '-');
+    PRIORITY_TOOLS.contains(&name) || PRIORITY_TOOLS.contains(&base)
+}
+
+/// Discover developer tools from PATH (plus well-known extra directories)
+/// using parallel version probing; unchanged binaries are served from `cache`.
+/// Directories that couldn't be read are added to `warnings`; details for
+/// verbose output go to `progress` as messages
+pub fn discover_tools(
+    options: &PathScanOptions,
+    cache: &mut ProbeCache,
+    warnings: &mut Vec<ScanWarning>,
+    progress: &(dyn Fn(ScanProgress) + Sync),
+) -> Vec<Tool> {
+    discover_in(options, &options.scan_dirs(), cache, warnings, progress)
+}
 
-    if verbose {
-        eprintln!("Found {} potential executables", executables.len());
+/// `discover_tools` over directories already listed
+fn discover_in(
+    options: &PathScanOptions,
+    dirs: &ScanDirs,
+    cache: &mut ProbeCache,
+    warnings: &mut Vec<ScanWarning>,
+    progress: &(dyn Fn(ScanProgress) + Sync),
+) -> Vec<Tool> {
+    for dir in options.all_path_dirs().iter().filter(|d| options.is_skipped(d)) {
+        progress(ScanProgress::Message(format!("Skipping PATH entry: {}", dir.display())));
     }
 
-    let total = executables.len();
-    let probed = AtomicUsize::new(0);
+    // The first copy of a name runs; later copies of other files are shadowed by it
+    let mut executables: Vec<(String, PathBuf, fs::Metadata, bool)> = Vec::new();
+    // (/bin and /usr/bin are often one directory, so copies are compared by inode)
+    let mut shadows: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    let mut first: HashMap<String, usize> = HashMap::new();
+    let mut files: HashSet<(String, u64, u64)> = HashSet::new();
+    for (name, path, metadata) in scan_path(options, dirs, warnings) {
+        if !files.insert((name.clone(), metadata.dev(), metadata.ino())) {
+            continue;
+        }
+        if first.contains_key(&name) {
+            shadows.entry(name).or_default().push(path);
+        } else {
+            first.insert(name.clone(), executables.len());
+            executables.push((name, path, metadata, true));
+        }
+    }
 
-    // Resolve which paths upfront (cheap, serial)
-    let candidates: Vec<(String, std::path::PathBuf)> = executables
+    progress(ScanProgress::Message(format!("Found {} potential executables", executables.len())));
+
+    let on_path: HashSet<String> = first.into_keys().collect();
+    let off_path = scan_extra_dirs(options, dirs, &on_path, warnings);
+
+    if !off_path.is_empty() {
+        progress(ScanProgress::Message(format!("Found {} potential executables outside PATH", off_path.len())));
+    }
+
+    let total = executables.len() + off_path.len();
+    let current_uid = security::current_uid();
+    let probed = AtomicUsize::new(0);
+    let candidates: Vec<(String, PathBuf, fs::Metadata, bool)> = executables
         .into_iter()
-        .filter_map(|name| {
-            which::which(&name).ok().map(|path| (name, path))
-        })
+        .chain(off_path.into_iter().map(|(name, path, metadata)| (name, path, metadata, false)))
         .collect();
+    progress(ScanProgress::Started { total });
+    // Common toolchains are probed as a first batch, everything else after
+    let (priority, rest): (Vec<_>, Vec<_>) = candidates.iter().partition(|(name, ..)| is_priority(name));
 
-    // Parallel version probing with rayon
-    let mut tools: Vec<Tool> = candidates
-        .par_iter()
-        .filter_map(|(exe_name, exe_path)| {
-            let probe_result = probe::probe_version(exe_path.to_str().unwrap_or(exe_name));
-
-            let done = probed.fetch_add(1, Ordering::Relaxed) + 1;
-            if !verbose {
-                // Show a compact progress indicator
-                eprint!("\r  Probing tools... {}/{}", done, total);
-                let _ = io::stderr().flush();
+    // Parallel version probing with rayon; fresh probes are collected for the cache
+    let shared_cache: &ProbeCache = cache;
+    let fresh_probes = Mutex::new(Vec::new());
+    let skipped = AtomicUsize::new(0);
+    let probe_tool = |candidate: &&(String, PathBuf, fs::Metadata, bool)| {
+        let (exe_name, exe_path, metadata, on_path) = candidate;
+        let cached = shared_cache.get(exe_path);
+        if cached.is_none() && (interrupt::interrupted() || options.deadline.is_some_and(|deadline| Instant::now() >= deadline)) {
+            skipped.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        let probe_result = match cached {
+            Some(result) => result,
+            None => {
+                // Ctrl-C reaches the probe's process too, so a probe in flight
+                // says nothing about the tool, nor does one the budget cut
+                // short; count them as skipped, uncached
+                let Some(result) = options.probe(exe_path.to_str().unwrap_or(exe_name)).filter(|_| !interrupt::interrupted()) else {
+                    skipped.fetch_add(1, Ordering::Relaxed);
+                    return None;
+                };
+                fresh_probes.lock().unwrap().push((exe_path.clone(), result.clone()));
+                result
             }
+        };
 
-            if probe_result.success && probe::looks_like_version(&probe_result.output) {
-                if verbose {
-                    eprintln!("Discovered: {} {:?}", exe_name, probe_result.version);
-                }
+        let done = probed.fetch_add(1, Ordering::Relaxed) + 1;
+        progress(ScanProgress::Probed { name: exe_name.clone(), done, total });
 
-                Some(Tool {
-                    name: exe_name.clone(),
-                    path: exe_path.clone(),
-                    version: probe_result.version,
-                    category: crate::types::ToolCategory::Unknown,
-                })
-            } else {
-                None
-            }
+        let (version, probe_error) = version_of(probe_result);
+        progress(ScanProgress::Message(match &probe_error {
+            None => format!("Discovered: {} {:?}", exe_name, version),
+            Some(error) => format!("Discovered: {} (no version: {})", exe_name, error),
+        }));
+
+        Some(Tool {
+            version,
+            on_path: *on_path,
+            security: security::tool_security_flags(exe_path, metadata, current_uid)
+                .into_iter()
+                .chain(security::gatekeeper_flags(exe_path))
+                .collect(),
+            shadows: shadows.get(exe_name).cloned().unwrap_or_default(),
+            probe_error,
+            ..Tool::new(exe_name.clone(), exe_path.clone())
         })
-        .collect();
+    };
+    let mut tools: Vec<Tool> = priority.par_iter().filter_map(&probe_tool).collect();
+    tools.extend(rest.par_iter().filter_map(&probe_tool).collect::<Vec<_>>());
 
-    if !verbose {
-        // Clear the progress line
-        eprint!("\r                                      \r");
-        let _ = io::stderr().flush();
-    }
+    progress(ScanProgress::Finished { tools: tools.len() });
 
     // Sort tools alphabetically for consistent output
     tools.sort_by(|a, b| a.name.cmp(&b.name));
 
-    if verbose {
-        eprintln!("Found {} developer tools", tools.len());
+    let skipped = skipped.into_inner();
+    if skipped > 0 {
+        warnings.push(skipped_warning(skipped, total));
     }
 
+    let fresh_probes = fresh_probes.into_inner().unwrap();
+    cache.record_usage(candidates.len() - skipped - fresh_probes.len(), fresh_probes.len());
+    for (path, result) in &fresh_probes {
+        cache.insert(path, result);
+        if result.timed_out {
+            warnings.push(ScanWarning {
+                kind: WarningKind::ProbeTimeout,
+                path: path.clone(),
+                message: "version probe timed out".to_string(),
+            });
+        }
+    }
+
+    for warning in warnings.iter() {
+        progress(ScanProgress::Message(format!("Warning: {}: {}", warning.path.display(), warning.message)));
+    }
+    progress(ScanProgress::Message(format!("Found {} developer tools", tools.len())));
+
     tools
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::test_util::TempDir;
 
     #[test]
     fn test_scan_path() {
-        let executables = scan_path();
+        let options = PathScanOptions::default();
+        let executables = scan_path(&options, &options.scan_dirs(), &mut Vec::new());
         // PATH should have at least some executables
         assert!(!executables.is_empty());
     }
 
+    #[test]
+    fn test_list_executables() {
+        let dir = TempDir::new("path");
+        fs::write(dir.join("node"), "").unwrap();
+        fs::set_permissions(dir.join("node"), fs::Permissions::from_mode(0o755)).unwrap();
+        fs::write(dir.join("npm"), "").unwrap();
+        fs::write(dir.join("notes"), "").unwrap();
+        fs::set_permissions(dir.join("notes"), fs::Permissions::from_mode(0o755)).unwrap();
+        std::os::unix::fs::symlink(dir.join("node"), dir.join("nodejs")).unwrap();
+
+        let mut warnings = Vec::new();
+        let options = PathScanOptions::default();
+        let mut names: Vec<String> = list_executables(&dir, &options, &mut warnings).into_iter().map(|(name, _, _)| name).collect();
+        names.sort();
+        // Not executable (npm) and not a dev tool (notes) are left out; symlinks are followed
+        assert_eq!(names, vec!["node", "nodejs"]);
+
+        // Configured tool patterns add to the built-in list
+        let options = PathScanOptions { tool_patterns: vec![glob::Pattern::new("note*").unwrap()], ..Default::default() };
+        assert_eq!(list_executables(&dir, &options, &mut warnings).len(), 3);
+
+        // Missing directories are not worth a warning, unreadable ones are
+        assert!(list_executables(&dir.join("missing"), &options, &mut warnings).is_empty());
+        assert!(list_executables(&dir.join("node"), &options, &mut warnings).is_empty());
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].path, dir.join("node"));
+    }
+
+    #[test]
+    fn test_skip_dirs() {
+        let options = PathScanOptions {
+            skip_dirs: vec![PathBuf::from("/mnt/c")],
+            ignore_tools: vec![glob::Pattern::new("gcc-*").unwrap()],
+            ..Default::default()
+        };
+        assert!(options.is_skipped(Path::new("/mnt/c/Windows/System32")));
+        assert!(!options.is_skipped(Path::new("/mnt/cdrom")));
+        assert!(!options.is_skipped(Path::new("/usr/bin")));
+        assert!(options.is_ignored("gcc-13") && !options.is_ignored("gcc"));
+    }
+
     #[test]
     fn test_discover_tools() {
-        let tools = discover_tools(false);
+        let events = Mutex::new(Vec::new());
+        let progress = |event| events.lock().unwrap().push(event);
+        let tools = discover_tools(&PathScanOptions::default(), &mut ProbeCache::default(), &mut Vec::new(), &progress);
         // Should find at least some developer tools
         assert!(!tools.is_empty());
+        // Details for verbose output come as messages between the progress events
+        let (messages, events): (Vec<ScanProgress>, Vec<ScanProgress>) =
+            events.into_inner().unwrap().into_iter().partition(|e| matches!(e, ScanProgress::Message(_)));
+        assert!(messages.contains(&ScanProgress::Message(format!("Found {} developer tools", tools.len()))));
+        assert!(matches!(events[0], ScanProgress::Started { total } if total == tools.len()));
+        assert_eq!(events.iter().filter(|e| matches!(e, ScanProgress::Probed { .. })).count(), tools.len());
+        assert_eq!(events.last(), Some(&ScanProgress::Finished { tools: tools.len() }));
+    }
+
+    #[test]
+    fn test_rescan_tools() {
+        let dir = TempDir::new("rescan");
+        fs::write(dir.join("node"), "#!/bin/sh\necho v20.11.0\n").unwrap();
+        fs::write(dir.join("npm"), "#!/bin/sh\nexec sleep 5\n").unwrap();
+        for name in ["node", "npm"] {
+            fs::set_permissions(dir.join(name), fs::Permissions::from_mode(0o755)).unwrap();
+        }
+
+        let options = PathScanOptions { probe_timeout: Some(Duration::from_millis(200)), ..fixture_options(&dir) };
+        let mut cache = ProbeCache::default();
+        let (mut first_warnings, mut second_warnings) = (Vec::new(), Vec::new());
+        let first = rescan_tools(&options, &mut cache, &mut first_warnings, &|_| {});
+        let second = rescan_tools(&options, &mut cache, &mut second_warnings, &|_| {});
+        let names = |tools: &[Tool]| tools.iter().map(|t| t.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&first), vec!["node", "npm"]);
+        assert_eq!(names(&first), names(&second));
+        // The reused scan still reports what the first one warned about
+        assert!(first_warnings.iter().any(|w| w.kind == WarningKind::ProbeTimeout));
+        let paths = |warnings: &[ScanWarning]| warnings.iter().map(|w| w.path.clone()).collect::<Vec<_>>();
+        assert_eq!(paths(&first_warnings), paths(&second_warnings));
+
+        // A directory that changed invalidates the previous tools
+        let mut dirs = dir_mtimes(&options.scan_dirs());
+        assert_eq!(dirs.len(), 1);
+        assert!(cache.previous_tools(&dirs, &[], &[]).is_some());
+        assert!(cache.previous_tools(&dirs, &["node*".to_string()], &[]).is_none());
+        assert!(cache.previous_tools(&dirs, &[], &["node*".to_string()]).is_none());
+        dirs[0].1 += 1;
+        assert!(cache.previous_tools(&dirs, &[], &[]).is_none());
+    }
+
+    /// Options that scan only `dir`: it is the PATH and the well-known install dirs are skipped
+    fn fixture_options(dir: &Path) -> PathScanOptions {
+        PathScanOptions {
+            path: Some(dir.as_os_str().to_owned()),
+            skip_dirs: DEFAULT_EXTRA_DIRS.iter().map(|d| paths::expand_tilde(d)).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_budget() {
+        assert!(is_priority("python3.12") && is_priority("gcc-13") && !is_priority("pylint"));
+        let dir = TempDir::new("budget");
+        for name in ["node", "npm"] {
+            fs::write(dir.join(name), "#!/bin/sh\nexec sleep 5\n").unwrap();
+            fs::set_permissions(dir.join(name), fs::Permissions::from_mode(0o755)).unwrap();
+        }
+
+        let options = PathScanOptions { deadline: Some(Instant::now()), ..fixture_options(&dir) };
+        let mut warnings = Vec::new();
+        assert!(discover_tools(&options, &mut ProbeCache::default(), &mut warnings, &|_| {}).is_empty());
+        assert!(warnings.iter().any(|w| w.kind == WarningKind::BudgetExhausted && w.message.starts_with("2 of 2")));
+
+        // Probes already running are cut short at the deadline and not cached
+        let start = Instant::now();
+        let options = PathScanOptions { deadline: Some(start + Duration::from_millis(300)), ..fixture_options(&dir) };
+        let mut cache = ProbeCache::default();
+        assert!(discover_tools(&options, &mut cache, &mut Vec::new(), &|_| {}).is_empty());
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert!(cache.get(&dir.join("node")).is_none());
     }
 }
@@ -0,0 +1,46 @@
+use std::env;
+use std::path::PathBuf;
+
+/// The current user's home directory, if known
+pub fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .filter(|h| !h.is_empty())
+        .map(PathBuf::from)
+}
+
+/// $XDG_DATA_HOME/devfetch, else ~/.local/share/devfetch
+pub fn data_dir() -> Option<PathBuf> {
+    env::var_os("XDG_DATA_HOME")
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|h| h.join(".local").join("share")))
+        .map(|d| d.join("devfetch"))
+}
+
+/// Expand a leading `~` to the home directory
+pub fn expand_tilde(path: &str) -> PathBuf {
+    if path == "~" {
+        return home_dir().unwrap_or_else(|| PathBuf::from(path));
+    }
+
+    match (path.strip_prefix("~/"), home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_tilde() {
+        if let Some(home) = home_dir() {
+            assert_eq!(expand_tilde("~/go/bin"), home.join("go/bin"));
+            assert_eq!(expand_tilde("~"), home);
+        }
+        assert_eq!(expand_tilde("/opt/bin"), PathBuf::from("/opt/bin"));
+        assert_eq!(expand_tilde("a/~/b"), PathBuf::from("a/~/b"));
+    }
+}
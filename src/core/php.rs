@@ -0,0 +1,186 @@
+use crate::core::check::satisfies;
+use crate::core::{exec, identifiers};
+use crate::types::{DependencyInfo, PhpExtension, PhpRequirements};
+use regex::Regex;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// Read composer.json/composer.lock for dependencies, the PHP constraint and
+/// required extensions, and check the extensions against `php -m`
+pub fn inspect(dir: &Path, php_version: Option<&str>) -> Option<(Option<DependencyInfo>, PhpRequirements)> {
+    let manifest: Value = serde_json::from_str(&fs::read_to_string(dir.join("composer.json")).ok()?).ok()?;
+    let lock: Option<Value> =
+        fs::read_to_string(dir.join("composer.lock")).ok().and_then(|c| serde_json::from_str(&c).ok());
+
+    let require = |value: &Value| -> Vec<(String, String)> {
+        ["require", "require-dev"]
+            .iter()
+            .filter_map(|k| value.get(*k).and_then(|r| r.as_object()))
+            .flatten()
+            .map(|(name, constraint)| (name.clone(), constraint.as_str().unwrap_or_default().to_string()))
+            .collect()
+    };
+    let is_platform = |name: &str| name == "php" || name.starts_with("ext-") || name.starts_with("lib-");
+
+    // Extensions the project asks for, then those its locked packages ask for
+    let mut extensions: Vec<PhpExtension> = Vec::new();
+    let mut add_extension = |name: &str, required_by: &str| {
+        let Some(ext) = name.strip_prefix("ext-") else {
+            return;
+        };
+        let ext = ext.to_lowercase();
+        match extensions.iter_mut().find(|e| e.name == ext) {
+            Some(e) if !e.required_by.iter().any(|r| r == required_by) => e.required_by.push(required_by.to_string()),
+            Some(_) => {}
+            None => extensions.push(PhpExtension { name: ext, required_by: vec![required_by.to_string()], loaded: None }),
+        }
+    };
+    for (name, _) in require(&manifest) {
+        add_extension(&name, "composer.json");
+    }
+
+    let packages: Vec<&Value> = lock
+        .iter()
+        .flat_map(|l| ["packages", "packages-dev"].map(|k| l.get(k).and_then(|p| p.as_array())))
+        .flatten()
+        .flatten()
+        .collect();
+    for package in &packages {
+        let name = package.get("name").and_then(|n| n.as_str()).unwrap_or_default();
+        for (requirement, _) in require(package) {
+            add_extension(&requirement, name);
+        }
+    }
+
+    let dependencies: Vec<(String, Option<String>)> = if lock.is_some() {
+        packages
+            .iter()
+            .filter_map(|p| {
+                let name = p.get("name")?.as_str()?.to_string();
+                let version = p.get("version").and_then(|v| v.as_str()).map(|v| v.trim_start_matches('v').to_string());
+                Some((name, version))
+            })
+            .collect()
+    } else {
+        require(&manifest).into_iter().filter(|(n, _)| !is_platform(n)).map(|(n, _)| (n, None)).collect()
+    };
+
+    if !extensions.is_empty() {
+        if let Some(loaded) = loaded_extensions() {
+            for ext in &mut extensions {
+                ext.loaded = Some(loaded.contains(&ext.name));
+            }
+        }
+    }
+
+    let php_constraint = require(&manifest).into_iter().find(|(n, _)| n == "php").map(|(_, c)| c);
+    let requirements = PhpRequirements {
+        php_satisfied: php_constraint.as_deref().zip(php_version).and_then(|(c, v)| constraint_satisfied(c, v)),
+        php_constraint,
+        extensions,
+    };
+    Some((dependency_info(dependencies), requirements))
+}
+
+fn dependency_info(dependencies: Vec<(String, Option<String>)>) -> Option<DependencyInfo> {
+    if dependencies.is_empty() {
+        return None;
+    }
+    let purls = dependencies
+        .iter()
+        .filter_map(|(name, version)| {
+            let (vendor, package) = name.split_once('/')?;
+            version.as_deref().map(|v| identifiers::namespaced_purl("composer", vendor, package, v))
+        })
+        .collect();
+    Some(DependencyInfo {
+        count: dependencies.len(),
+        sample: dependencies.iter().take(5).map(|(name, _)| name.clone()).collect(),
+        purls,
+    })
+}
+
+/// Lowercased module names from `php -m` (Zend extensions listed as "Zend OPcache")
+fn loaded_extensions() -> Option<Vec<String>> {
+    if !exec::command_exists("php") {
+        return None;
+    }
+    let output = exec::execute_for_output("php", &["-m"])?;
+    Some(
+        output
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty() && !l.starts_with('['))
+            .map(|l| l.to_lowercase().trim_start_matches("zend ").replace(' ', "_"))
+            .collect(),
+    )
+}
+
+/// Evaluate a Composer constraint such as "^8.1", ">=8.1 <8.4" or "^7.4 || ^8.0".
+/// None when the constraint uses syntax we don't understand.
+fn constraint_satisfied(constraint: &str, version: &str) -> Option<bool> {
+    let clause = Regex::new(r"^(>=|<=|>|<|!=|==|=|\^|~)?\s*v?(\d+(?:\.\d+)*)(\.\*)?$").unwrap();
+    let mut any = false;
+    for alternative in constraint.split('|').map(str::trim).filter(|a| !a.is_empty()) {
+        let mut all = true;
+        for part in alternative.split([' ', ',']).filter(|p| !p.is_empty()) {
+            if part == "*" {
+                continue;
+            }
+            let caps = clause.captures(part)?;
+            let wanted = &caps[2];
+            let op = match caps.get(1).map(|m| m.as_str()) {
+                // ~8.2 allows 8.x, ~8.2.1 allows 8.2.x
+                Some("~") if wanted.split('.').count() == 2 => "^",
+                Some(op) => op,
+                None => "=",
+            };
+            all &= satisfies(op, wanted, version);
+        }
+        any |= all;
+    }
+    Some(any)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_constraint_satisfied() {
+        assert_eq!(constraint_satisfied("^8.1", "8.3.6"), Some(true));
+        assert_eq!(constraint_satisfied("^8.2", "8.1.27"), Some(false));
+        assert_eq!(constraint_satisfied(">=8.1 <8.3", "8.3.0"), Some(false));
+        assert_eq!(constraint_satisfied("^7.4 || ^8.0", "8.2.0"), Some(true));
+        assert_eq!(constraint_satisfied("~8.2", "8.4.1"), Some(true));
+        assert_eq!(constraint_satisfied("8.2.*", "8.2.9"), Some(true));
+        assert_eq!(constraint_satisfied("8.1 - 8.3", "8.2.0"), None);
+    }
+
+    #[test]
+    fn test_inspect() {
+        let dir = TempDir::new("php");
+        fs::write(
+            dir.join("composer.json"),
+            r#"{"require": {"php": "^8.2", "ext-intl": "*", "laravel/framework": "^11.0"}, "require-dev": {"phpunit/phpunit": "^11.0"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("composer.lock"),
+            r#"{"packages": [{"name": "laravel/framework", "version": "v11.4.0", "require": {"ext-mbstring": "*", "ext-intl": "*"}}],
+                "packages-dev": [{"name": "phpunit/phpunit", "version": "11.1.2"}]}"#,
+        )
+        .unwrap();
+
+        let (deps, requirements) = inspect(&dir, Some("8.1.2")).unwrap();
+        let deps = deps.unwrap();
+        assert_eq!(deps.count, 2);
+        assert_eq!(deps.purls[0], "pkg:composer/laravel/framework@11.4.0");
+        assert_eq!(requirements.php_satisfied, Some(false));
+        let extensions: Vec<(&str, usize)> =
+            requirements.extensions.iter().map(|e| (e.name.as_str(), e.required_by.len())).collect();
+        assert_eq!(extensions, vec![("intl", 2), ("mbstring", 1)]);
+    }
+}
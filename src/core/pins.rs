@@ -0,0 +1,111 @@
+use crate::core::nix::parse_store_name;
+use crate::types::ToolPin;
+use std::fs;
+use std::path::Path;
+
+/// Collect tool versions pinned by project toolchain managers
+pub fn detect_pins(dir: &Path) -> Vec<ToolPin> {
+    let mut pins = hermit_pins(dir);
+    pins.extend(proto_pins(dir));
+    pins
+}
+
+/// Hermit records each installed package as `bin/.<name>-<version>.pkg`
+/// (or `bin/.<name>@<channel>.pkg`) next to the `bin/hermit` launcher
+fn hermit_pins(dir: &Path) -> Vec<ToolPin> {
+    let bin = dir.join("bin");
+    if !bin.join("hermit").is_file() && !bin.join("hermit.hcl").is_file() {
+        return Vec::new();
+    }
+
+    let mut pins: Vec<ToolPin> = fs::read_dir(&bin)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let package = file_name.strip_prefix('.')?.strip_suffix(".pkg")?;
+            let (tool, version) = split_package(package)?;
+            Some(ToolPin {
+                tool,
+                version,
+                manager: "hermit".to_string(),
+                source: format!("bin/{}", file_name),
+            })
+        })
+        .collect();
+    pins.sort_by(|a, b| a.tool.cmp(&b.tool));
+    pins
+}
+
+/// "go-1.21.0" -> (go, 1.21.0); "node@lts" -> (node, lts)
+fn split_package(package: &str) -> Option<(String, String)> {
+    if let Some((name, channel)) = package.split_once('@') {
+        return Some((name.to_string(), channel.to_string()));
+    }
+    match parse_store_name(package) {
+        (name, Some(version)) => Some((name, version)),
+        _ => None,
+    }
+}
+
+/// proto pins tools as top-level `tool = "version"` entries in `.prototools`;
+/// tables such as [plugins] and [settings] are configuration, not pins
+fn proto_pins(dir: &Path) -> Vec<ToolPin> {
+    let Ok(content) = fs::read_to_string(dir.join(".prototools")) else {
+        return Vec::new();
+    };
+    let Ok(table) = content.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+
+    let mut pins: Vec<ToolPin> = table
+        .iter()
+        .filter_map(|(tool, value)| {
+            Some(ToolPin {
+                tool: tool.clone(),
+                version: value.as_str()?.to_string(),
+                manager: "proto".to_string(),
+                source: ".prototools".to_string(),
+            })
+        })
+        .collect();
+    pins.sort_by(|a, b| a.tool.cmp(&b.tool));
+    pins
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_detect_pins() {
+        let dir = TempDir::new("pins");
+        fs::create_dir_all(dir.join("bin")).unwrap();
+        fs::write(dir.join("bin/hermit"), "#!/bin/bash\n").unwrap();
+        fs::write(dir.join("bin/.go-1.21.0.pkg"), "").unwrap();
+        fs::write(dir.join("bin/.node@lts.pkg"), "").unwrap();
+        fs::write(dir.join("bin/go"), "").unwrap();
+        fs::write(
+            dir.join(".prototools"),
+            "node = \"20.11.0\"\npnpm = \"8\"\n\n[plugins]\nmy-tool = \"source:https://example.com/plugin.toml\"\n",
+        )
+        .unwrap();
+
+        let pins: Vec<(String, String, String)> = detect_pins(&dir)
+            .into_iter()
+            .map(|p| (p.manager, p.tool, p.version))
+            .collect();
+        let expected = [
+            ("hermit", "go", "1.21.0"),
+            ("hermit", "node", "lts"),
+            ("proto", "node", "20.11.0"),
+            ("proto", "pnpm", "8"),
+        ];
+        assert_eq!(
+            pins,
+            expected.iter().map(|(m, t, v)| (m.to_string(), t.to_string(), v.to_string())).collect::<Vec<_>>()
+        );
+    }
+}
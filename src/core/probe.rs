@@ -1,7 +1,8 @@
-use crate::types::ProbeResult;
+use crate::types::{ProbeAttempt, ProbeResult, NO_VERSION_ERROR};
 use crate::core::exec;
 use regex::Regex;
 use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 
 /// Version patterns to extract version numbers from command output
 static VERSION_REGEX: OnceLock<Regex> = OnceLock::new();
@@ -13,44 +14,119 @@ fn get_version_regex() -> &'static Regex {
     })
 }
 
+/// Version flags, in order of likelihood
+static VERSION_FLAGS: &[&str] = &["--version", "-v", "version", "-V"];
+
 /// Probe a binary for version information
 pub fn probe_version(binary_path: &str) -> ProbeResult {
-    // Try different version flags in order of likelihood
-    let strategies = [
-        vec!["--version"],
-        vec!["-v"],
-        vec!["version"],
-        vec!["-V"],
-    ];
-
-    for args in &strategies {
-        if let Some(output) = exec::execute_for_output(binary_path, args) {
-            // Check if output looks like version info
-            if let Some(version) = extract_version(&output) {
-                return ProbeResult {
-                    success: true,
-                    output: output.trim().to_string(),
-                    version: Some(version),
-                };
+    probe_version_with_timeout(binary_path, Duration::from_millis(exec::COMMAND_TIMEOUT_MS))
+}
+
+/// `probe_version`, allowing each version flag `timeout` to answer
+pub fn probe_version_with_timeout(binary_path: &str, timeout: Duration) -> ProbeResult {
+    probe_version_before(binary_path, timeout, None)
+}
+
+/// `probe_version_with_timeout` that gives up at `deadline` (--budget), cutting
+/// short a flag still running then; that counts as timing out
+pub fn probe_version_before(binary_path: &str, timeout: Duration, deadline: Option<Instant>) -> ProbeResult {
+    probe_attempts(binary_path, timeout, deadline).0
+}
+
+/// Each version flag `probe_version` tries and what it got, up to the first
+/// that yields a version
+pub fn explain_probe(binary_path: &str, timeout: Duration) -> Vec<ProbeAttempt> {
+    probe_attempts(binary_path, timeout, None).1
+}
+
+/// Try the version flags in turn, recording each attempt; a version only
+/// counts when the output also looks like version information
+fn probe_attempts(binary_path: &str, timeout: Duration, deadline: Option<Instant>) -> (ProbeResult, Vec<ProbeAttempt>) {
+    let mut attempts = Vec::new();
+    let mut timed_out = false;
+    for flag in VERSION_FLAGS {
+        let Some(flag_timeout) = exec::budgeted(timeout, deadline) else {
+            timed_out = true;
+            attempts.push(ProbeAttempt { flag: flag.to_string(), outcome: "skipped: the scan budget ran out".to_string(), version: None });
+            break;
+        };
+        let outcome = match exec::execute_for_output_or_timeout(binary_path, &[flag], flag_timeout) {
+            Ok(Some(output)) => match extract_version(&output).filter(|_| looks_like_version(&output)) {
+                Some(version) => {
+                    attempts.push(ProbeAttempt {
+                        flag: flag.to_string(),
+                        outcome: format!("found version {}", version),
+                        version: Some(version.clone()),
+                    });
+                    let result = ProbeResult {
+                        success: true,
+                        output: output.trim().to_string(),
+                        version: Some(version),
+                        timed_out: false,
+                        error: None,
+                    };
+                    return (result, attempts);
+                }
+                None => {
+                    let first_line: String = output.lines().find(|l| !l.trim().is_empty()).unwrap_or_default().chars().take(60).collect();
+                    format!("no version in \"{}\"", first_line.trim())
+                }
+            },
+            Ok(None) => "no output, or the command failed".to_string(),
+            Err(_) => {
+                timed_out = true;
+                format!("timed out after {}ms", flag_timeout.as_millis())
             }
-        }
+        };
+        attempts.push(ProbeAttempt { flag: flag.to_string(), outcome, version: None });
     }
 
-    ProbeResult {
+    let error = if timed_out {
+        format!("timed out after {}ms", timeout.as_millis())
+    } else {
+        NO_VERSION_ERROR.to_string()
+    };
+    let result = ProbeResult {
         success: false,
         output: String::new(),
         version: None,
-    }
+        timed_out,
+        error: Some(error),
+    };
+    (result, attempts)
+}
+
+/// "version" in the languages tools are commonly translated to
+static VERSION_WORDS: &[&str] = &[
+    "version", "versión", "versão", "versione", "wersja", "verze", "versiyon", "версия", "версія", "版本", "バージョン", "버전",
+];
+
+/// Fold digits from other scripts (fullwidth ３．１１, Arabic-Indic, Devanagari)
+/// into ASCII so version patterns match localized output
+fn ascii_digits(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            let zero = match c {
+                '０'..='９' => '０',
+                '٠'..='٩' => '٠',
+                '۰'..='۹' => '۰',
+                '०'..='९' => '०',
+                '．' | '٫' => return '.',
+                _ => return c,
+            };
+            char::from(b'0' + (c as u32 - zero as u32) as u8)
+        })
+        .collect()
 }
 
 /// Extract version number from output text
 pub fn extract_version(text: &str) -> Option<String> {
     let re = get_version_regex();
     
-    // Take first line for more reliable parsing
-    let first_line = text.lines().next()?;
+    // Take the first non-blank line for more reliable parsing (perl starts with a blank one)
+    let first_line = ascii_digits(text.lines().find(|l| !l.trim().is_empty())?);
     
-    re.captures(first_line)
+    re.captures(&first_line)
         .and_then(|cap| cap.get(1))
         .map(|m| m.as_str().to_string())
 }
@@ -65,11 +141,11 @@ pub fn looks_like_version(output: &str) -> bool {
     }
     
     // Must have version-like content
-    let has_version_keyword = output_lower.contains("version")
+    let has_version_keyword = VERSION_WORDS.iter().any(|w| output_lower.contains(w))
         || output_lower.contains("copyright")
         || output_lower.contains("release");
     
-    let has_version_pattern = get_version_regex().is_match(output);
+    let has_version_pattern = get_version_regex().is_match(&ascii_digits(output));
     
     has_version_keyword || has_version_pattern
 }
@@ -77,6 +153,7 @@ pub fn looks_like_version(output: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::test_util::TempDir;
 
     #[test]
     fn test_extract_version() {
@@ -86,10 +163,51 @@ mod tests {
         assert_eq!(extract_version("rustc 1.75.0"), Some("1.75.0".to_string()));
     }
 
+    #[test]
+    fn test_localized_output() {
+        let fixtures = [
+            ("Python versión 3.11.0", "3.11.0"),
+            ("git Version 2.39.2", "2.39.2"),
+            ("\nThis is perl 5, version 36, subversion 0 (v5.36.0) built for x86_64-linux-gnu-thread-multi", "5.36.0"),
+            ("gcc (Debian 12.2.0-14) 12.2.0\nCopyright (C) 2022 Free Software Foundation, Inc.", "12.2.0-14"),
+            ("node バージョン ２０．１１．１", "20.11.1"),
+            ("أداة الإصدار ٣٫١١٫٠", "3.11.0"),
+            ("make версия 4.3", "4.3"),
+        ];
+        for (output, version) in fixtures {
+            assert_eq!(extract_version(output).as_deref(), Some(version), "{}", output);
+            assert!(looks_like_version(output), "{}", output);
+        }
+    }
+
     #[test]
     fn test_looks_like_version() {
         assert!(looks_like_version("Python 3.11.0"));
         assert!(looks_like_version("version 1.2.3"));
         assert!(!looks_like_version("This is a long help text that goes on and on..."));
     }
+
+    #[test]
+    fn test_probe_failure() {
+        let probe = probe_version("/nonexistent/devfetch-probe");
+        assert!(!probe.success && !probe.timed_out);
+        assert!(probe.error.is_some());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_explain_matches_probe() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = TempDir::new("probe");
+        // Help text that happens to start with a number is not a version
+        let script = dir.join("tool");
+        std::fs::write(&script, format!("#!/bin/sh\necho '1.0 {}'\n", "usage ".repeat(100))).unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let path = script.to_string_lossy();
+        assert!(!probe_version(&path).success);
+        let attempts = explain_probe(&path, Duration::from_millis(1500));
+        assert_eq!(attempts.len(), VERSION_FLAGS.len());
+        assert!(attempts.iter().all(|a| a.version.is_none()));
+    }
 }
@@ -1,16 +1,63 @@
-use crate::core::exec;
+use crate::core::{cxx, exec, frameworks, jvm, monorepo, node, php, python, ruby, tasks, wrappers};
 use crate::types::*;
-use std::collections::HashMap;
-use std::fs;
-use std::path::Path;
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Dependency, build and environment directories never searched for sub-projects
+static SKIPPED_DIRS: &[&str] = &[
+    "node_modules",
+    "bower_components",
+    "target",
+    "vendor",
+    "dist",
+    "build",
+    "out",
+    "venv",
+    "__pycache__",
+];
+
+/// Built-in markers with the config's command overrides applied, plus
+/// markers the config adds
+fn get_project_markers(overrides: &BTreeMap<String, MarkerOverride>, progress: &(dyn Fn(ScanProgress) + Sync)) -> Vec<ProjectMarker> {
+    let mut markers = builtin_markers();
+    for (file_name, change) in overrides {
+        let mut matched = false;
+        for marker in markers.iter_mut().filter(|m| &m.file_name == file_name) {
+            if let Some(commands) = &change.commands {
+                marker.commands = commands.clone();
+            }
+            marker.commands.extend(change.extra_commands.iter().cloned());
+            if let Some(ecosystem) = &change.ecosystem {
+                marker.ecosystem = ecosystem.clone();
+            }
+            matched = true;
+        }
+        if matched {
+            continue;
+        }
+        match &change.ecosystem {
+            Some(ecosystem) => markers.push(ProjectMarker {
+                file_name: file_name.clone(),
+                ecosystem: ecosystem.clone(),
+                package_manager: None,
+                commands: change.commands.iter().flatten().chain(&change.extra_commands).cloned().collect(),
+            }),
+            None => progress(ScanProgress::Message(format!("Config marker {} needs an ecosystem", file_name))),
+        }
+    }
+    markers
+}
 
 /// Project markers and their associated ecosystems
-fn get_project_markers() -> Vec<ProjectMarker> {
+fn builtin_markers() -> Vec<ProjectMarker> {
     vec![
         // JavaScript/Node
         ProjectMarker {
             file_name: "package.json".to_string(),
             ecosystem: "Node.js".to_string(),
+            package_manager: None,
             commands: vec![
                 EcosystemCommand {
                     tool: "node".to_string(),
@@ -24,10 +71,71 @@ fn get_project_markers() -> Vec<ProjectMarker> {
                 },
             ],
         },
+        ProjectMarker {
+            file_name: "pnpm-lock.yaml".to_string(),
+            ecosystem: "Node.js".to_string(),
+            package_manager: Some("pnpm".to_string()),
+            commands: vec![
+                EcosystemCommand {
+                    tool: "pnpm".to_string(),
+                    args: vec!["--version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+            ],
+        },
+        ProjectMarker {
+            file_name: "yarn.lock".to_string(),
+            ecosystem: "Node.js".to_string(),
+            package_manager: Some("yarn".to_string()),
+            commands: vec![
+                EcosystemCommand {
+                    tool: "yarn".to_string(),
+                    args: vec!["--version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+            ],
+        },
+        ProjectMarker {
+            file_name: "bun.lockb".to_string(),
+            ecosystem: "Node.js".to_string(),
+            package_manager: Some("bun".to_string()),
+            commands: vec![
+                EcosystemCommand {
+                    tool: "bun".to_string(),
+                    args: vec!["--version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+            ],
+        },
+        ProjectMarker {
+            file_name: "bun.lock".to_string(),
+            ecosystem: "Node.js".to_string(),
+            package_manager: Some("bun".to_string()),
+            commands: vec![
+                EcosystemCommand {
+                    tool: "bun".to_string(),
+                    args: vec!["--version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+            ],
+        },
+        ProjectMarker {
+            file_name: "package-lock.json".to_string(),
+            ecosystem: "Node.js".to_string(),
+            package_manager: Some("npm".to_string()),
+            commands: vec![
+                EcosystemCommand {
+                    tool: "npm".to_string(),
+                    args: vec!["--version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+            ],
+        },
         // Python
         ProjectMarker {
             file_name: "pyproject.toml".to_string(),
             ecosystem: "Python".to_string(),
+            package_manager: None,
             commands: vec![
                 EcosystemCommand {
                     tool: "python3".to_string(),
@@ -44,6 +152,7 @@ fn get_project_markers() -> Vec<ProjectMarker> {
         ProjectMarker {
             file_name: "requirements.txt".to_string(),
             ecosystem: "Python".to_string(),
+            package_manager: None,
             commands: vec![
                 EcosystemCommand {
                     tool: "python3".to_string(),
@@ -53,11 +162,24 @@ fn get_project_markers() -> Vec<ProjectMarker> {
             ],
         },
         ProjectMarker {
-            file_name: "Pipfile".to_string(),
-            ecosystem: "Python (Pipenv)".to_string(),
+            file_name: "uv.lock".to_string(),
+            ecosystem: "Python".to_string(),
+            package_manager: Some("uv".to_string()),
             commands: vec![
                 EcosystemCommand {
-                    tool: "pipenv".to_string(),
+                    tool: "uv".to_string(),
+                    args: vec!["--version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+            ],
+        },
+        ProjectMarker {
+            file_name: "pdm.lock".to_string(),
+            ecosystem: "Python".to_string(),
+            package_manager: Some("pdm".to_string()),
+            commands: vec![
+                EcosystemCommand {
+                    tool: "pdm".to_string(),
                     args: vec!["--version".to_string()],
                     parser: CommandParser::PlainText,
                 },
@@ -65,7 +187,8 @@ fn get_project_markers() -> Vec<ProjectMarker> {
         },
         ProjectMarker {
             file_name: "poetry.lock".to_string(),
-            ecosystem: "Python (Poetry)".to_string(),
+            ecosystem: "Python".to_string(),
+            package_manager: Some("poetry".to_string()),
             commands: vec![
                 EcosystemCommand {
                     tool: "poetry".to_string(),
@@ -74,10 +197,23 @@ fn get_project_markers() -> Vec<ProjectMarker> {
                 },
             ],
         },
+        ProjectMarker {
+            file_name: "Pipfile".to_string(),
+            ecosystem: "Python".to_string(),
+            package_manager: Some("pipenv".to_string()),
+            commands: vec![
+                EcosystemCommand {
+                    tool: "pipenv".to_string(),
+                    args: vec!["--version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+            ],
+        },
         // Rust
         ProjectMarker {
             file_name: "Cargo.toml".to_string(),
             ecosystem: "Rust".to_string(),
+            package_manager: None,
             commands: vec![
                 EcosystemCommand {
                     tool: "rustc".to_string(),
@@ -95,6 +231,7 @@ fn get_project_markers() -> Vec<ProjectMarker> {
         ProjectMarker {
             file_name: "go.mod".to_string(),
             ecosystem: "Go".to_string(),
+            package_manager: None,
             commands: vec![
                 EcosystemCommand {
                     tool: "go".to_string(),
@@ -107,6 +244,7 @@ fn get_project_markers() -> Vec<ProjectMarker> {
         ProjectMarker {
             file_name: "pom.xml".to_string(),
             ecosystem: "Java (Maven)".to_string(),
+            package_manager: None,
             commands: vec![
                 EcosystemCommand {
                     tool: "mvn".to_string(),
@@ -119,6 +257,7 @@ fn get_project_markers() -> Vec<ProjectMarker> {
         ProjectMarker {
             file_name: "build.gradle".to_string(),
             ecosystem: "JVM (Gradle)".to_string(),
+            package_manager: None,
             commands: vec![
                 EcosystemCommand {
                     tool: "gradle".to_string(),
@@ -130,6 +269,7 @@ fn get_project_markers() -> Vec<ProjectMarker> {
         ProjectMarker {
             file_name: "build.gradle.kts".to_string(),
             ecosystem: "JVM (Gradle/Kotlin)".to_string(),
+            package_manager: None,
             commands: vec![
                 EcosystemCommand {
                     tool: "gradle".to_string(),
@@ -138,18 +278,52 @@ fn get_project_markers() -> Vec<ProjectMarker> {
                 },
             ],
         },
-        // Ruby
+        // Bazel
         ProjectMarker {
-            file_name: "Gemfile".to_string(),
-            ecosystem: "Ruby".to_string(),
+            file_name: "MODULE.bazel".to_string(),
+            ecosystem: "Bazel".to_string(),
+            package_manager: None,
             commands: vec![
                 EcosystemCommand {
-                    tool: "ruby".to_string(),
+                    tool: "bazel".to_string(),
                     args: vec!["--version".to_string()],
                     parser: CommandParser::PlainText,
                 },
+            ],
+        },
+        ProjectMarker {
+            file_name: "WORKSPACE".to_string(),
+            ecosystem: "Bazel".to_string(),
+            package_manager: None,
+            commands: vec![
                 EcosystemCommand {
-                    tool: "bundle".to_string(),
+                    tool: "bazel".to_string(),
+                    args: vec!["--version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+            ],
+        },
+        // Batect
+        ProjectMarker {
+            file_name: "batect.yml".to_string(),
+            ecosystem: "Batect".to_string(),
+            package_manager: None,
+            commands: vec![
+                EcosystemCommand {
+                    tool: "batect".to_string(),
+                    args: vec!["--version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+            ],
+        },
+        // Ruby
+        ProjectMarker {
+            file_name: "Gemfile".to_string(),
+            ecosystem: "Ruby".to_string(),
+            package_manager: None,
+            commands: vec![
+                EcosystemCommand {
+                    tool: "ruby".to_string(),
                     args: vec!["--version".to_string()],
                     parser: CommandParser::PlainText,
                 },
@@ -159,6 +333,7 @@ fn get_project_markers() -> Vec<ProjectMarker> {
         ProjectMarker {
             file_name: "composer.json".to_string(),
             ecosystem: "PHP".to_string(),
+            package_manager: None,
             commands: vec![
                 EcosystemCommand {
                     tool: "php".to_string(),
@@ -176,6 +351,7 @@ fn get_project_markers() -> Vec<ProjectMarker> {
         ProjectMarker {
             file_name: "pubspec.yaml".to_string(),
             ecosystem: "Dart/Flutter".to_string(),
+            package_manager: None,
             commands: vec![
                 EcosystemCommand {
                     tool: "dart".to_string(),
@@ -188,6 +364,7 @@ fn get_project_markers() -> Vec<ProjectMarker> {
         ProjectMarker {
             file_name: "Package.swift".to_string(),
             ecosystem: "Swift".to_string(),
+            package_manager: None,
             commands: vec![
                 EcosystemCommand {
                     tool: "swift".to_string(),
@@ -200,6 +377,7 @@ fn get_project_markers() -> Vec<ProjectMarker> {
         ProjectMarker {
             file_name: "mix.exs".to_string(),
             ecosystem: "Elixir".to_string(),
+            package_manager: None,
             commands: vec![
                 EcosystemCommand {
                     tool: "elixir".to_string(),
@@ -212,6 +390,7 @@ fn get_project_markers() -> Vec<ProjectMarker> {
         ProjectMarker {
             file_name: "CMakeLists.txt".to_string(),
             ecosystem: "C/C++ (CMake)".to_string(),
+            package_manager: None,
             commands: vec![
                 EcosystemCommand {
                     tool: "cmake".to_string(),
@@ -223,6 +402,7 @@ fn get_project_markers() -> Vec<ProjectMarker> {
         ProjectMarker {
             file_name: "Makefile".to_string(),
             ecosystem: "C/C++ (Make)".to_string(),
+            package_manager: None,
             commands: vec![
                 EcosystemCommand {
                     tool: "make".to_string(),
@@ -234,6 +414,7 @@ fn get_project_markers() -> Vec<ProjectMarker> {
         ProjectMarker {
             file_name: "meson.build".to_string(),
             ecosystem: "C/C++ (Meson)".to_string(),
+            package_manager: None,
             commands: vec![
                 EcosystemCommand {
                     tool: "meson".to_string(),
@@ -242,10 +423,85 @@ fn get_project_markers() -> Vec<ProjectMarker> {
                 },
             ],
         },
+        // Task runners
+        ProjectMarker {
+            file_name: "justfile".to_string(),
+            ecosystem: "just".to_string(),
+            package_manager: None,
+            commands: vec![
+                EcosystemCommand {
+                    tool: "just".to_string(),
+                    args: vec!["--version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+            ],
+        },
+        ProjectMarker {
+            file_name: "Justfile".to_string(),
+            ecosystem: "just".to_string(),
+            package_manager: None,
+            commands: vec![
+                EcosystemCommand {
+                    tool: "just".to_string(),
+                    args: vec!["--version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+            ],
+        },
+        ProjectMarker {
+            file_name: "Taskfile.yml".to_string(),
+            ecosystem: "Task".to_string(),
+            package_manager: None,
+            commands: vec![
+                EcosystemCommand {
+                    tool: "task".to_string(),
+                    args: vec!["--version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+            ],
+        },
+        ProjectMarker {
+            file_name: "Taskfile.yaml".to_string(),
+            ecosystem: "Task".to_string(),
+            package_manager: None,
+            commands: vec![
+                EcosystemCommand {
+                    tool: "task".to_string(),
+                    args: vec!["--version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+            ],
+        },
+        // Nix-backed project environments
+        ProjectMarker {
+            file_name: "devbox.json".to_string(),
+            ecosystem: "Devbox".to_string(),
+            package_manager: None,
+            commands: vec![
+                EcosystemCommand {
+                    tool: "devbox".to_string(),
+                    args: vec!["version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+            ],
+        },
+        ProjectMarker {
+            file_name: ".flox/env/manifest.toml".to_string(),
+            ecosystem: "Flox".to_string(),
+            package_manager: None,
+            commands: vec![
+                EcosystemCommand {
+                    tool: "flox".to_string(),
+                    args: vec!["--version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+            ],
+        },
         // .NET
         ProjectMarker {
             file_name: "*.csproj".to_string(),
             ecosystem: ".NET/C#".to_string(),
+            package_manager: None,
             commands: vec![
                 EcosystemCommand {
                     tool: "dotnet".to_string(),
@@ -257,6 +513,7 @@ fn get_project_markers() -> Vec<ProjectMarker> {
         ProjectMarker {
             file_name: "*.fsproj".to_string(),
             ecosystem: ".NET/F#".to_string(),
+            package_manager: None,
             commands: vec![
                 EcosystemCommand {
                     tool: "dotnet".to_string(),
@@ -265,43 +522,134 @@ fn get_project_markers() -> Vec<ProjectMarker> {
                 },
             ],
         },
+        ProjectMarker {
+            file_name: "*.sln".to_string(),
+            ecosystem: ".NET".to_string(),
+            package_manager: None,
+            commands: vec![
+                EcosystemCommand {
+                    tool: "dotnet".to_string(),
+                    args: vec!["--version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+            ],
+        },
+        // Ruby gems without a Gemfile
+        ProjectMarker {
+            file_name: "*.gemspec".to_string(),
+            ecosystem: "Ruby".to_string(),
+            package_manager: None,
+            commands: vec![
+                EcosystemCommand {
+                    tool: "ruby".to_string(),
+                    args: vec!["--version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+            ],
+        },
+        // Haskell
+        ProjectMarker {
+            file_name: "*.cabal".to_string(),
+            ecosystem: "Haskell (Cabal)".to_string(),
+            package_manager: None,
+            commands: vec![
+                EcosystemCommand {
+                    tool: "ghc".to_string(),
+                    args: vec!["--numeric-version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+                EcosystemCommand {
+                    tool: "cabal".to_string(),
+                    args: vec!["--numeric-version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+            ],
+        },
+        // Erlang
+        ProjectMarker {
+            file_name: "rebar.config".to_string(),
+            ecosystem: "Erlang (rebar3)".to_string(),
+            package_manager: None,
+            commands: vec![
+                EcosystemCommand {
+                    tool: "rebar3".to_string(),
+                    args: vec!["version".to_string()],
+                    parser: CommandParser::PlainText,
+                },
+            ],
+        },
     ]
 }
 
-/// Detect project markers and ecosystem information
-pub fn detect_project(path: &Path, verbose: bool) -> Option<ProjectInfo> {
+/// Detect project markers and ecosystem information; details for verbose
+/// output go to `progress` as messages
+pub fn detect_project(
+    path: &Path,
+    overrides: &BTreeMap<String, MarkerOverride>,
+    deadline: Option<Instant>,
+    progress: &(dyn Fn(ScanProgress) + Sync),
+) -> Option<ProjectInfo> {
     if !path.is_dir() {
         return None;
     }
 
-    let markers = get_project_markers();
+    let markers = get_project_markers(overrides, progress);
     let mut detected_markers = Vec::new();
-    let mut ecosystems = HashMap::new();
+    let mut ecosystems = BTreeMap::new();
+    let build_wrappers = wrappers::detect_wrappers(path);
 
     // Scan for marker files
     for marker in &markers {
-        let marker_path = path.join(&marker.file_name);
-        
-        // Handle glob patterns for .NET projects
-        let exists = if marker.file_name.contains('*') {
-            check_glob_pattern(path, &marker.file_name)
-        } else {
-            marker_path.exists()
-        };
-
-        if exists {
-            if verbose {
-                eprintln!("Found marker: {}", marker.file_name);
-            }
+        if marker_exists(path, &marker.file_name) {
+            progress(ScanProgress::Message(format!("Found marker: {}", marker.file_name)));
 
             detected_markers.push(DetectedMarker {
                 file: marker.file_name.clone(),
                 ecosystem: marker.ecosystem.clone(),
             });
 
-            // Try to get ecosystem info
-            if let Some(eco_info) = probe_ecosystem(marker, verbose) {
-                ecosystems.insert(marker.ecosystem.clone(), eco_info);
+            if let Some(manager) = &marker.package_manager {
+                // The first lock file in marker order picks the manager
+                let info = ecosystems
+                    .entry(marker.ecosystem.clone())
+                    .or_insert_with(|| bare_ecosystem(&marker.ecosystem));
+                match info.details.package_manager_mut() {
+                    Some(Some(chosen)) if &chosen.name != manager => chosen.conflicts.push(marker.file_name.clone()),
+                    Some(Some(_)) | None => {}
+                    Some(slot @ None) => {
+                        let probed = probe_ecosystem(path, marker, deadline, progress);
+                        *slot = Some(PackageManager {
+                            name: manager.clone(),
+                            version: probed.as_ref().and_then(|p| p.tool_version.clone()),
+                            source: marker.file_name.clone(),
+                            pinned: None,
+                            conflicts: Vec::new(),
+                            corepack: None,
+                        });
+                        info.dependencies = probed.and_then(|p| p.dependencies).or(info.dependencies.take());
+                    }
+                }
+                continue;
+            }
+
+            // Several markers can share an ecosystem (pyproject.toml, requirements.txt);
+            // the first to report a version wins and later ones only fill gaps
+            if ecosystems.get(&marker.ecosystem).is_some_and(|e| e.tool_version.is_some()) {
+                continue;
+            }
+
+            // Try to get ecosystem info, preferring a project wrapper's pinned version
+            let wrapper = marker
+                .commands
+                .first()
+                .and_then(|cmd| build_wrappers.iter().find(|w| w.tool == cmd.tool));
+            if let Some(eco_info) = apply_wrapper(marker, probe_ecosystem(path, marker, deadline, progress), wrapper) {
+                match ecosystems.get_mut(&marker.ecosystem) {
+                    Some(existing) => merge_ecosystem(existing, eco_info),
+                    None => {
+                        ecosystems.insert(marker.ecosystem.clone(), eco_info);
+                    }
+                }
             }
         }
     }
@@ -310,46 +658,244 @@ pub fn detect_project(path: &Path, verbose: bool) -> Option<ProjectInfo> {
         return None;
     }
 
+    // Attach frameworks once, to the first ecosystem that finds them
+    // (requirements.txt and poetry.lock both point at the same Django)
+    let mut seen_frameworks: Vec<String> = Vec::new();
+    for marker in &detected_markers {
+        let mut found = frameworks::detect_frameworks(path, &marker.ecosystem);
+        found.retain(|f| !seen_frameworks.contains(&f.name));
+        seen_frameworks.extend(found.iter().map(|f| f.name.clone()));
+        if !found.is_empty() {
+            let info = ecosystems
+                .entry(marker.ecosystem.clone())
+                .or_insert_with(|| bare_ecosystem(&marker.ecosystem));
+            info.frameworks = found;
+        }
+    }
+
+    // JVM builds are read offline; mvn/gradle only report their own version
+    for marker in detected_markers.iter().filter(|m| m.file == "pom.xml" || m.file.starts_with("build.gradle")) {
+        if let Some((dependencies, build)) = jvm::inspect(path, &marker.ecosystem) {
+            let info = ecosystems
+                .entry(marker.ecosystem.clone())
+                .or_insert_with(|| bare_ecosystem(&marker.ecosystem));
+            info.dependencies = info.dependencies.take().or(dependencies);
+            info.details = EcosystemDetails::Jvm(build);
+        }
+    }
+
+    // packageManager in package.json overrides what the lock files suggest
+    if detected_markers.iter().any(|m| m.file == "package.json") {
+        let from_lock = ecosystems.get_mut("Node.js").and_then(|e| e.details.package_manager_mut()?.take());
+        if let Some(manager) = node::package_manager(path, from_lock) {
+            let info = ecosystems.entry("Node.js".to_string()).or_insert_with(|| bare_ecosystem("Node.js"));
+            info.details = EcosystemDetails::Node(NodeEcosystem { package_manager: Some(manager) });
+        }
+    }
+
+    if detected_markers.iter().any(|m| m.file == "composer.json") {
+        let php_version = ecosystems.get("PHP").and_then(|e| e.tool_version.clone());
+        if let Some((dependencies, requirements)) = php::inspect(path, php_version.as_deref()) {
+            let info = ecosystems.entry("PHP".to_string()).or_insert_with(|| bare_ecosystem("PHP"));
+            info.dependencies = info.dependencies.take().or(dependencies);
+            info.details = EcosystemDetails::Php(requirements);
+        }
+    }
+
+    if detected_markers.iter().any(|m| m.file == "Gemfile") {
+        let ruby_version = ecosystems.get("Ruby").and_then(|e| e.tool_version.clone());
+        if let Some((dependencies, requirements)) = ruby::inspect(path, ruby_version.as_deref()) {
+            let info = ecosystems.entry("Ruby".to_string()).or_insert_with(|| bare_ecosystem("Ruby"));
+            info.dependencies = info.dependencies.take().or(dependencies);
+            info.details = EcosystemDetails::Ruby(requirements);
+        }
+    }
+
+    // Locked counts from uv.lock/pdm.lock replace what pip reports
+    if detected_markers.iter().any(|m| m.ecosystem == "Python") {
+        if let Some((dependencies, mut python)) = python::inspect(path) {
+            let info = ecosystems.entry("Python".to_string()).or_insert_with(|| bare_ecosystem("Python"));
+            if python.lock_file.is_some() {
+                info.dependencies = dependencies.or(info.dependencies.take());
+            }
+            python.package_manager = info.details.package_manager_mut().and_then(Option::take);
+            info.details = EcosystemDetails::Python(python);
+        }
+    }
+
+    // Compilers and libraries are shared by every C/C++ build system found
+    if let Some(marker) = detected_markers.iter().find(|m| m.ecosystem.starts_with("C/C++")) {
+        let info = ecosystems.entry(marker.ecosystem.clone()).or_insert_with(|| bare_ecosystem(&marker.ecosystem));
+        info.details = EcosystemDetails::Cxx(cxx::inspect(path));
+    }
+
     Some(ProjectInfo {
         path: path.to_path_buf(),
         markers: detected_markers,
         ecosystems,
+        scripts: tasks::detect_entry_points(path),
+        workspace: None,
+        subprojects: Vec::new(),
     })
 }
 
-/// Check if files matching a glob pattern exist
-fn check_glob_pattern(path: &Path, pattern: &str) -> bool {
-    if let Ok(entries) = fs::read_dir(path) {
-        for entry in entries.flatten() {
-            if let Some(name) = entry.file_name().to_str() {
-                if pattern.contains("*.csproj") && name.ends_with(".csproj") {
-                    return true;
-                }
-                if pattern.contains("*.fsproj") && name.ends_with(".fsproj") {
-                    return true;
-                }
-            }
-        }
+/// Detect the project at `path` plus projects up to `depth` directory levels
+/// below it. A directory with no markers still counts when it is a workspace
+/// root or holds sub-projects; plain intermediate directories (`packages/`)
+/// are flattened away.
+pub fn detect_project_tree(
+    path: &Path,
+    overrides: &BTreeMap<String, MarkerOverride>,
+    depth: usize,
+    deadline: Option<Instant>,
+    progress: &(dyn Fn(ScanProgress) + Sync),
+) -> Option<ProjectInfo> {
+    let subprojects = if depth == 0 { Vec::new() } else { detect_subprojects(path, overrides, depth, deadline, progress) };
+    let workspace = monorepo::workspace_root(path);
+    let mut info = match detect_project(path, overrides, deadline, progress) {
+        Some(info) => info,
+        None if subprojects.is_empty() && workspace.is_none() => return None,
+        None => ProjectInfo {
+            path: path.to_path_buf(),
+            markers: Vec::new(),
+            ecosystems: BTreeMap::new(),
+            scripts: tasks::detect_entry_points(path),
+            workspace: None,
+            subprojects: Vec::new(),
+        },
+    };
+    info.workspace = workspace;
+    info.subprojects = subprojects;
+    Some(info)
+}
+
+fn detect_subprojects(
+    path: &Path,
+    overrides: &BTreeMap<String, MarkerOverride>,
+    depth: usize,
+    deadline: Option<Instant>,
+    progress: &(dyn Fn(ScanProgress) + Sync),
+) -> Vec<ProjectInfo> {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return Vec::new();
+    };
+    // DirEntry::file_type doesn't follow symlinks, so linked trees aren't revisited
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .filter(|e| {
+            let name = e.file_name();
+            let name = name.to_string_lossy();
+            !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_ref())
+        })
+        .map(|e| e.path())
+        .collect();
+    dirs.sort();
+
+    dirs.par_iter()
+        .flat_map_iter(|dir| match detect_project_tree(dir, overrides, depth - 1, deadline, progress) {
+            Some(info) if info.markers.is_empty() && info.workspace.is_none() => info.subprojects,
+            Some(info) => vec![info],
+            None => Vec::new(),
+        })
+        .collect()
+}
+
+/// Detected JSON and TOML manifests that don't parse; the inspections that
+/// read them skip such files silently
+pub fn manifest_warnings(project: &ProjectInfo) -> Vec<ScanWarning> {
+    let files = project.markers.iter().filter(|m| !m.file.contains(['*', '?', '[']));
+    files
+        .filter_map(|marker| {
+            let file = project.path.join(&marker.file);
+            let content = std::fs::read_to_string(&file).ok()?;
+            let error = match file.extension().and_then(|e| e.to_str()) {
+                Some("json") => serde_json::from_str::<serde_json::Value>(&content).err().map(|e| e.to_string()),
+                Some("toml") => toml::from_str::<toml::Value>(&content).err().map(|e| e.message().to_string()),
+                _ => None,
+            }?;
+            Some(ScanWarning { kind: WarningKind::ParseFailure, path: file, message: format!("could not parse: {}", error) })
+        })
+        .collect()
+}
+
+/// Report a wrapper's pinned version in place of the global tool's,
+/// keeping the global version when it differs
+fn apply_wrapper(
+    marker: &ProjectMarker,
+    info: Option<EcosystemInfo>,
+    wrapper: Option<&BuildWrapper>,
+) -> Option<EcosystemInfo> {
+    let Some(wrapper) = wrapper.filter(|w| w.version.is_some()) else {
+        return info;
+    };
+
+    let mut info = info.unwrap_or_else(|| bare_ecosystem(&marker.ecosystem));
+    let mut wrapper = wrapper.clone();
+    wrapper.global_version = info.tool_version.take().filter(|global| Some(global) != wrapper.version.as_ref());
+    info.tool_version = wrapper.version.clone();
+    info.wrapper = Some(wrapper);
+    Some(info)
+}
+
+/// Fill what an earlier marker of the same ecosystem left empty
+fn merge_ecosystem(existing: &mut EcosystemInfo, probed: EcosystemInfo) {
+    existing.tool_version = existing.tool_version.take().or(probed.tool_version);
+    existing.dependencies = existing.dependencies.take().or(probed.dependencies);
+    existing.wrapper = existing.wrapper.take().or(probed.wrapper);
+    // Probing only fills details from `cargo metadata`
+    if matches!(&existing.details, EcosystemDetails::Rust(rust) if rust.members.is_empty()) {
+        existing.details = probed.details;
+    }
+}
+
+/// Ecosystem entry with nothing probed yet
+fn bare_ecosystem(name: &str) -> EcosystemInfo {
+    EcosystemInfo {
+        name: name.to_string(),
+        tool_version: None,
+        dependencies: None,
+        wrapper: None,
+        details: EcosystemDetails::for_ecosystem(name),
+        frameworks: Vec::new(),
     }
-    false
 }
 
-/// Probe ecosystem for version and dependency information
-fn probe_ecosystem(marker: &ProjectMarker, verbose: bool) -> Option<EcosystemInfo> {
+/// Whether a marker file exists; `file_name` may be a glob such as `*.sln`
+/// or `ci/*.{yml,yaml}`, relative to the project directory
+fn marker_exists(path: &Path, file_name: &str) -> bool {
+    if !file_name.contains(['*', '?', '[']) {
+        return path.join(file_name).exists();
+    }
+    let pattern = format!("{}/{}", glob::Pattern::escape(&path.to_string_lossy()), file_name);
+    glob::glob(&pattern).is_ok_and(|mut matches| matches.any(|m| m.is_ok()))
+}
+
+/// Probe ecosystem for version and dependency information, running the
+/// commands from the project directory; commands stop at `deadline` (--budget)
+fn probe_ecosystem(
+    path: &Path,
+    marker: &ProjectMarker,
+    deadline: Option<Instant>,
+    progress: &(dyn Fn(ScanProgress) + Sync),
+) -> Option<EcosystemInfo> {
     let mut tool_version = None;
     let mut dependencies = None;
+    let mut cargo = None;
 
     for cmd in &marker.commands {
         if !exec::command_exists(&cmd.tool) {
-            if verbose {
-                eprintln!("Tool not found: {}", cmd.tool);
-            }
+            progress(ScanProgress::Message(format!("Tool not found: {}", cmd.tool)));
             continue;
         }
 
+        let Some(timeout) = exec::budgeted(Duration::from_millis(exec::COMMAND_TIMEOUT_MS), deadline) else {
+            progress(ScanProgress::Message(format!("Scan budget ran out before {} {}", cmd.tool, cmd.args.join(" "))));
+            break;
+        };
         let args: Vec<&str> = cmd.args.iter().map(|s| s.as_str()).collect();
-        
-        if let Some(output) = exec::execute_for_output(&cmd.tool, &args) {
+
+        if let Some(output) = exec::execute_for_output_in_with_timeout(path, &cmd.tool, &args, timeout) {
             match cmd.parser {
                 CommandParser::PlainText => {
                     // Extract version from first command (usually --version)
@@ -360,6 +906,9 @@ fn probe_ecosystem(marker: &ProjectMarker, verbose: bool) -> Option<EcosystemInf
                 CommandParser::Json => {
                     // Try to parse dependency information
                     dependencies = parse_dependencies_json(&output, &marker.ecosystem);
+                    if marker.ecosystem == "Rust" {
+                        cargo = parse_cargo_workspace(&output);
+                    }
                 }
             }
         }
@@ -370,53 +919,108 @@ fn probe_ecosystem(marker: &ProjectMarker, verbose: bool) -> Option<EcosystemInf
             name: marker.ecosystem.clone(),
             tool_version,
             dependencies,
+            wrapper: None,
+            details: match cargo {
+                Some(rust) => EcosystemDetails::Rust(rust),
+                None => EcosystemDetails::for_ecosystem(&marker.ecosystem),
+            },
+            frameworks: Vec::new(),
         })
     } else {
         None
     }
 }
 
+/// Workspace members, binary targets and the highest MSRV from `cargo metadata`
+fn parse_cargo_workspace(json_str: &str) -> Option<RustEcosystem> {
+    let parsed: serde_json::Value = serde_json::from_str(json_str).ok()?;
+    let packages = parsed.get("packages")?.as_array()?;
+
+    let mut members = Vec::new();
+    let mut binaries = Vec::new();
+    let mut msrv: Option<String> = None;
+    for package in packages {
+        let Some(name) = package.get("name").and_then(|n| n.as_str()) else {
+            continue;
+        };
+        members.push(name.to_string());
+
+        let targets = package.get("targets").and_then(|t| t.as_array()).into_iter().flatten();
+        for target in targets {
+            let is_bin = target
+                .get("kind")
+                .and_then(|k| k.as_array())
+                .is_some_and(|kinds| kinds.iter().any(|k| k == "bin"));
+            if let (true, Some(bin)) = (is_bin, target.get("name").and_then(|n| n.as_str())) {
+                binaries.push(bin.to_string());
+            }
+        }
+
+        if let Some(declared) = package.get("rust_version").and_then(|v| v.as_str()) {
+            let higher = msrv
+                .as_deref()
+                .is_none_or(|current| crate::core::doctor::compare_versions(declared, current).is_gt());
+            if higher {
+                msrv = Some(declared.to_string());
+            }
+        }
+    }
+
+    Some(RustEcosystem { msrv, members, binaries })
+}
+
 /// Parse dependency information from JSON output
 fn parse_dependencies_json(json_str: &str, ecosystem: &str) -> Option<DependencyInfo> {
     let parsed: serde_json::Value = serde_json::from_str(json_str).ok()?;
+    let version_of = |v: &serde_json::Value| v.get("version").and_then(|n| n.as_str()).map(String::from);
 
-    let deps = if ecosystem.contains("Node") {
+    let (purl_type, deps) = if ecosystem.contains("Node") {
         // npm list format
-        parsed.get("dependencies")
+        let deps = parsed.get("dependencies")
             .and_then(|d| d.as_object())
-            .map(|obj| obj.keys().map(|k| k.to_string()).collect::<Vec<_>>())
+            .map(|obj| obj.iter().map(|(k, v)| (k.to_string(), version_of(v))).collect::<Vec<_>>());
+        ("npm", deps)
     } else if ecosystem.contains("Python") {
         // pip list format
-        parsed.as_array()
+        let deps = parsed.as_array()
             .map(|arr| {
                 arr.iter()
-                    .filter_map(|v| v.get("name").and_then(|n| n.as_str()).map(String::from))
+                    .filter_map(|v| v.get("name").and_then(|n| n.as_str()).map(|n| (n.to_string(), version_of(v))))
                     .collect::<Vec<_>>()
-            })
+            });
+        ("pypi", deps)
     } else if ecosystem.contains("Rust") {
         // cargo metadata format
-        parsed.get("packages")
+        let deps = parsed.get("packages")
             .and_then(|p| p.as_array())
             .map(|arr| {
                 arr.iter()
-                    .filter_map(|v| v.get("name").and_then(|n| n.as_str()).map(String::from))
+                    .filter_map(|v| v.get("name").and_then(|n| n.as_str()).map(|n| (n.to_string(), version_of(v))))
                     .collect::<Vec<_>>()
-            })
+            });
+        ("cargo", deps)
     } else {
-        None
+        ("", None)
     };
 
-    deps.and_then(|mut dep_list| {
+    deps.and_then(|dep_list| {
         if dep_list.is_empty() {
             return None;
         }
 
+        let purls = dep_list
+            .iter()
+            .filter_map(|(name, version)| {
+                version.as_deref().map(|v| crate::core::identifiers::package_purl(purl_type, name, v))
+            })
+            .collect();
         let count = dep_list.len();
-        dep_list.truncate(5); // Sample first 5
+        let sample = dep_list.into_iter().take(5).map(|(name, _)| name).collect(); // Sample first 5
 
         Some(DependencyInfo {
             count,
-            sample: dep_list,
+            sample,
+            purls,
         })
     })
 }
@@ -424,12 +1028,95 @@ fn parse_dependencies_json(json_str: &str, ecosystem: &str) -> Option<Dependency
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::test_util::TempDir;
+    use std::fs;
+
+    #[test]
+    fn test_parse_cargo_workspace() {
+        let metadata = r#"{"packages": [
+            {"name": "core", "rust_version": "1.70", "targets": [{"kind": ["lib"], "name": "core"}]},
+            {"name": "cli", "rust_version": "1.74.1", "targets": [{"kind": ["bin"], "name": "tool"}]},
+            {"name": "xtask", "rust_version": null, "targets": [{"kind": ["bin"], "name": "xtask"}]}
+        ]}"#;
+        let workspace = parse_cargo_workspace(metadata).unwrap();
+        assert_eq!(workspace.members, vec!["core", "cli", "xtask"]);
+        assert_eq!(workspace.binaries, vec!["tool", "xtask"]);
+        assert_eq!(workspace.msrv.as_deref(), Some("1.74.1"));
+    }
 
     #[test]
     fn test_get_project_markers() {
-        let markers = get_project_markers();
+        let markers = get_project_markers(&BTreeMap::new(), &|_| {});
         assert!(!markers.is_empty());
         assert!(markers.iter().any(|m| m.file_name == "package.json"));
         assert!(markers.iter().any(|m| m.file_name == "Cargo.toml"));
+
+        // Skip `npm list`, and add a command to requirements.txt
+        let overrides: BTreeMap<String, MarkerOverride> = toml::from_str(
+            "[\"package.json\"]\ncommands = [{ tool = \"node\", args = [\"--version\"] }]\n\n[\"requirements.txt\"]\nextra_commands = [{ tool = \"uv\", args = [\"pip\", \"list\", \"--format=json\"], parser = \"json\" }]\n",
+        )
+        .unwrap();
+        let markers = get_project_markers(&overrides, &|_| {});
+        let node = markers.iter().find(|m| m.file_name == "package.json").unwrap();
+        assert_eq!(node.commands.len(), 1);
+        let requirements = markers.iter().find(|m| m.file_name == "requirements.txt").unwrap();
+        assert_eq!(requirements.commands.last().unwrap().tool, "uv");
+    }
+
+    #[test]
+    fn test_marker_exists() {
+        let dir = TempDir::new("markers");
+        fs::create_dir_all(dir.join("ci")).unwrap();
+        fs::write(dir.join("App.sln"), "").unwrap();
+        fs::write(dir.join("ci/build.yaml"), "").unwrap();
+        fs::write(dir.join("rebar.config"), "").unwrap();
+
+        assert!(marker_exists(&dir, "*.sln"));
+        assert!(!marker_exists(&dir, "*.cabal"));
+        assert!(marker_exists(&dir, "ci/*.yaml"));
+        assert!(marker_exists(&dir, "rebar.config"));
+
+        // Markers contributed by the config may be patterns too
+        let overrides: BTreeMap<String, MarkerOverride> =
+            toml::from_str("[\"ci/*.yaml\"]\necosystem = \"Custom CI\"\n").unwrap();
+        let info = detect_project(&dir, &overrides, None, &|_| {}).unwrap();
+        assert!(info.markers.iter().any(|m| m.ecosystem == "Custom CI"));
+        assert!(info.markers.iter().any(|m| m.ecosystem == "Erlang (rebar3)"));
+    }
+
+    #[test]
+    fn test_python_markers_merge() {
+        let dir = TempDir::new("python-markers");
+        fs::write(dir.join("requirements.txt"), "requests\n").unwrap();
+        fs::write(dir.join("poetry.lock"), "").unwrap();
+        fs::write(dir.join("Pipfile"), "").unwrap();
+
+        let info = detect_project(&dir, &BTreeMap::new(), None, &|_| {}).unwrap();
+        assert_eq!(info.markers.len(), 3);
+        assert_eq!(info.ecosystems.len(), 1);
+        let manager = info.ecosystems["Python"].details.package_manager().unwrap();
+        assert_eq!((manager.name.as_str(), manager.source.as_str()), ("poetry", "poetry.lock"));
+        let json = serde_json::to_value(&info.ecosystems["Python"]).unwrap();
+        assert_eq!(json["details"]["kind"], "python");
+        assert_eq!(json["details"]["package_manager"]["name"], "poetry");
+    }
+
+    #[test]
+    fn test_detect_project_tree() {
+        let dir = TempDir::new("project-tree");
+        for sub in ["packages/web", "packages/web/node_modules/dep", "services/api", "docs"] {
+            fs::create_dir_all(dir.join(sub)).unwrap();
+        }
+        fs::write(dir.join("pnpm-workspace.yaml"), "packages:\n  - 'packages/*'\n").unwrap();
+        fs::write(dir.join("packages/web/package.json"), "{}").unwrap();
+        fs::write(dir.join("packages/web/node_modules/dep/package.json"), "{}").unwrap();
+        fs::write(dir.join("services/api/Makefile"), "all:\n").unwrap();
+
+        assert!(detect_project_tree(&dir, &BTreeMap::new(), 0, None, &|_| {}).unwrap().subprojects.is_empty());
+        let info = detect_project_tree(&dir, &BTreeMap::new(), 3, None, &|_| {}).unwrap();
+        assert_eq!(info.workspace.as_ref().unwrap().members, ["packages/*"]);
+        let paths: Vec<_> = info.subprojects.iter().map(|p| p.path.strip_prefix(&dir).unwrap()).collect();
+        assert_eq!(paths, [Path::new("packages/web"), Path::new("services/api")]);
+        assert!(info.subprojects[0].subprojects.is_empty());
     }
 }
@@ -0,0 +1,142 @@
+use crate::core::exec;
+use crate::types::ProtoRequirement;
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+/// Languages protoc generates itself, so `--<lang>_out` needs no plugin
+static PROTOC_BUILTINS: &[&str] = &["cpp", "csharp", "java", "kotlin", "objc", "php", "pyi", "python", "ruby", "rust", "upb"];
+
+/// Scripts that commonly drive protoc
+static SCRIPTS: &[&str] = &["Makefile", "makefile", "GNUmakefile", "justfile", "Taskfile.yml", "gen.sh", "scripts/gen.sh", "scripts/protoc.sh"];
+
+/// Install commands for the usual plugins
+static INSTALL_HINTS: &[(&str, &str)] = &[
+    ("buf", "go install github.com/bufbuild/buf/cmd/buf@latest"),
+    ("protoc-gen-go", "go install google.golang.org/protobuf/cmd/protoc-gen-go@latest"),
+    ("protoc-gen-go-grpc", "go install google.golang.org/grpc/cmd/protoc-gen-go-grpc@latest"),
+    ("protoc-gen-grpc-gateway", "go install github.com/grpc-ecosystem/grpc-gateway/v2/protoc-gen-grpc-gateway@latest"),
+    ("protoc-gen-ts", "npm install -D protoc-gen-ts"),
+];
+
+/// Work out which protobuf tools the project's codegen setup needs
+/// (buf config, protoc invocations in scripts, tonic-build/prost-build)
+/// and whether each is installed
+pub fn check_toolchain(dir: &Path, has_proto_files: bool) -> Vec<ProtoRequirement> {
+    let mut wanted: Vec<(String, String)> = Vec::new();
+    let mut require = |tool: &str, source: &str| {
+        if !wanted.iter().any(|(t, _)| t == tool) {
+            wanted.push((tool.to_string(), source.to_string()));
+        }
+    };
+
+    for config in ["buf.yaml", "buf.gen.yaml", "buf.work.yaml"] {
+        if dir.join(config).is_file() {
+            require("buf", config);
+            break;
+        }
+    }
+    if let Ok(content) = fs::read_to_string(dir.join("buf.gen.yaml")) {
+        for plugin in buf_gen_plugins(&content) {
+            require(&plugin, "buf.gen.yaml");
+        }
+    }
+
+    for script in SCRIPTS {
+        if let Ok(content) = fs::read_to_string(dir.join(script)) {
+            for tool in protoc_invocation_tools(&content) {
+                require(&tool, script);
+            }
+        }
+    }
+
+    // tonic-build/prost-build shell out to protoc unless a vendored copy is pulled in
+    if let Ok(manifest) = fs::read_to_string(dir.join("Cargo.toml")) {
+        let uses_build = ["tonic-build", "prost-build"].iter().find(|dep| manifest.contains(*dep));
+        let vendored = manifest.contains("protoc-bin-vendored") || manifest.contains("protobuf-src");
+        if let (Some(dep), false) = (uses_build, vendored) {
+            require("protoc", &format!("Cargo.toml ({})", dep));
+        }
+    }
+
+    if wanted.is_empty() && has_proto_files {
+        wanted.push(("protoc".to_string(), "*.proto files".to_string()));
+    }
+    wanted
+        .into_iter()
+        .map(|(tool, source)| ProtoRequirement {
+            installed: dir.join("node_modules/.bin").join(&tool).exists() || exec::command_exists(&tool),
+            tool,
+            source,
+        })
+        .collect()
+}
+
+/// Suggested install command for a missing tool
+pub fn install_hint(tool: &str) -> Option<String> {
+    INSTALL_HINTS.iter().find(|(name, _)| *name == tool).map(|(_, hint)| hint.to_string())
+}
+
+/// Local plugins from buf.gen.yaml, v1 (`plugin:`/`name:`) or v2 (`local:`,
+/// `protoc_builtin:`). Remote plugins run on the BSR and need nothing locally.
+fn buf_gen_plugins(content: &str) -> Vec<String> {
+    let entry = Regex::new(r#"(?m)^\s*-?\s*(plugin|name|local|protoc_builtin|remote)\s*:\s*["']?([^"'\s#]+)"#).unwrap();
+    let mut plugins = Vec::new();
+    for caps in entry.captures_iter(content) {
+        let value = &caps[2];
+        let tool = match &caps[1] {
+            "remote" => continue,
+            // v1 plugins with a path are BSR remote references
+            "plugin" | "name" if value.contains('/') => continue,
+            "protoc_builtin" => "protoc".to_string(),
+            "local" => value.to_string(),
+            _ if PROTOC_BUILTINS.contains(&value) => "protoc".to_string(),
+            _ => format!("protoc-gen-{}", value),
+        };
+        if !plugins.contains(&tool) {
+            plugins.push(tool);
+        }
+    }
+    plugins
+}
+
+/// protoc and the plugins implied by `--<name>_out` flags in a script
+fn protoc_invocation_tools(content: &str) -> Vec<String> {
+    let out_flag = Regex::new(r"--([A-Za-z0-9_-]+?)_out[= ]").unwrap();
+    let mut tools = Vec::new();
+    for line in content.lines().filter(|l| l.contains("protoc ") && !l.contains("buf ")) {
+        if !tools.iter().any(|t| t == "protoc") {
+            tools.push("protoc".to_string());
+        }
+        for caps in out_flag.captures_iter(line) {
+            let lang = caps[1].to_string();
+            if !PROTOC_BUILTINS.contains(&lang.as_str()) {
+                let plugin = format!("protoc-gen-{}", lang.replace('_', "-"));
+                if !tools.contains(&plugin) {
+                    tools.push(plugin);
+                }
+            }
+        }
+    }
+    tools
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buf_gen_plugins() {
+        let v1 = "version: v1\nplugins:\n  - plugin: go\n    out: gen\n  - name: java\n    out: gen\n  - plugin: buf.build/grpc/go\n    out: gen\n";
+        assert_eq!(buf_gen_plugins(v1), vec!["protoc-gen-go", "protoc"]);
+
+        let v2 = "version: v2\nplugins:\n  - local: protoc-gen-go-grpc\n    out: gen\n  - remote: buf.build/protocolbuffers/go\n    out: gen\n  - protoc_builtin: python\n";
+        assert_eq!(buf_gen_plugins(v2), vec!["protoc-gen-go-grpc", "protoc"]);
+    }
+
+    #[test]
+    fn test_protoc_invocation_tools() {
+        let makefile = "proto:\n\tprotoc -I proto --go_out=gen --go-grpc_out=gen --python_out=py proto/*.proto\n";
+        assert_eq!(protoc_invocation_tools(makefile), vec!["protoc", "protoc-gen-go", "protoc-gen-go-grpc"]);
+    }
+}
@@ -0,0 +1,226 @@
+use crate::core::exec;
+use crate::core::snapshot::Codec;
+use crate::types::ScanResult;
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use std::fs;
+use std::io::Write;
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Compress the JSON form of a scan for upload
+pub fn gzip_json(result: &ScanResult) -> Result<Vec<u8>> {
+    Codec::Gzip.compress(&serde_json::to_vec(result)?)
+}
+
+/// Parse a "Name: value" header, expanding `$VAR` / `${VAR}` from the environment
+/// so tokens can stay out of config files and shell history
+pub fn parse_header(header: &str) -> Result<(String, String)> {
+    let (name, value) = header
+        .split_once(':')
+        .with_context(|| format!("Invalid header {:?}, expected \"Name: value\"", header))?;
+    let name = name.trim();
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        bail!("Invalid header name in {:?}", header);
+    }
+
+    let var = Regex::new(r"\$\{(\w+)\}|\$(\w+)").unwrap();
+    let value = var.replace_all(value.trim(), |caps: &regex::Captures| {
+        let key = caps.get(1).or_else(|| caps.get(2)).unwrap().as_str();
+        std::env::var(key).unwrap_or_default()
+    });
+    Ok((name.to_string(), value.into_owned()))
+}
+
+/// Hide everything but the start of a header value for display
+fn mask(value: &str) -> String {
+    let visible: String = value.chars().take(4).collect();
+    if value.chars().count() <= 8 {
+        "****".to_string()
+    } else {
+        format!("{}****", visible)
+    }
+}
+
+/// A fresh directory only the current user can enter. Creation fails if
+/// anything (including a planted symlink) already has the name.
+fn private_temp_dir() -> Result<PathBuf> {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.subsec_nanos());
+    let dir = std::env::temp_dir().join(format!("devfetch-push-{}-{}", std::process::id(), nanos));
+    fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&dir)
+        .with_context(|| format!("Failed to create {}", dir.display()))?;
+    Ok(dir)
+}
+
+/// Write a new file readable only by the current user, never following or
+/// reusing an existing one
+fn write_private(path: &Path, data: &[u8]) -> Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    file.write_all(data)?;
+    Ok(())
+}
+
+/// A curl config file (`curl --config -`) setting each option, quoted so
+/// values may hold spaces, quotes and backslashes
+pub fn curl_config(options: &[(&str, &str)]) -> String {
+    options
+        .iter()
+        .map(|(name, value)| {
+            let quoted = value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\r', "\\r");
+            format!("{} = \"{}\"\n", name, quoted)
+        })
+        .collect()
+}
+
+/// Run one upload attempt, returning the error output on failure
+fn upload(target: &str, body: &Path, headers: &[(String, String)]) -> Result<()> {
+    let output = if let Some(dest) = target.strip_prefix("s3://") {
+        let dest = format!("s3://{}", dest);
+        exec::execute_command_with_timeout(
+            "aws",
+            &[
+                "s3", "cp", &body.to_string_lossy(), &dest,
+                "--content-type", "application/json",
+                "--content-encoding", "gzip",
+                "--only-show-errors",
+            ],
+            UPLOAD_TIMEOUT,
+        )?
+    } else {
+        // The URL and headers go through stdin so auth tokens never appear in the process list
+        let header_lines: Vec<String> = headers.iter().map(|(n, v)| format!("{}: {}", n, v)).collect();
+        let mut options = vec![("url", target)];
+        options.extend(header_lines.iter().map(|h| ("header", h.as_str())));
+        exec::execute_command_with_input(
+            "curl",
+            &[
+                "-sS", "-f", "-X", "POST",
+                "-H", "Content-Type: application/json",
+                "-H", "Content-Encoding: gzip",
+                "--data-binary", &format!("@{}", body.display()),
+                "--config", "-",
+            ],
+            curl_config(&options).as_bytes(),
+            UPLOAD_TIMEOUT,
+        )?
+    };
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim())
+    }
+}
+
+/// Upload a scan to an HTTP(S) endpoint or an s3:// URL, retrying with backoff.
+/// With `dry_run`, only describe what would be sent.
+pub fn push_result(result: &ScanResult, target: &str, headers: &[String], dry_run: bool, verbose: bool) -> Result<()> {
+    let is_s3 = target.starts_with("s3://");
+    if !is_s3 && !target.starts_with("http://") && !target.starts_with("https://") {
+        bail!("Unsupported push target {:?}; use http(s):// or s3://", target);
+    }
+
+    let headers = headers.iter().map(|h| parse_header(h)).collect::<Result<Vec<_>>>()?;
+    let body = gzip_json(result)?;
+
+    if dry_run {
+        eprintln!("Dry run: would push {} bytes of gzipped JSON to {}", body.len(), target);
+        if is_s3 {
+            if !headers.is_empty() {
+                eprintln!("  (headers are ignored for s3:// targets)");
+            }
+        } else {
+            for (name, value) in &headers {
+                eprintln!("  {}: {}", name, mask(value));
+            }
+        }
+        return Ok(());
+    }
+
+    let dir = private_temp_dir()?;
+    let body_file = dir.join("scan.json.gz");
+    if let Err(e) = write_private(&body_file, &body) {
+        let _ = fs::remove_dir_all(&dir);
+        return Err(e);
+    }
+
+    let mut outcome = Ok(());
+    for attempt in 1..=MAX_ATTEMPTS {
+        outcome = upload(target, &body_file, &headers);
+        match &outcome {
+            Ok(()) => break,
+            Err(e) if attempt < MAX_ATTEMPTS => {
+                let delay = Duration::from_secs(1 << attempt);
+                if verbose {
+                    eprintln!("Push attempt {} failed ({}); retrying in {}s", attempt, e, delay.as_secs());
+                }
+                std::thread::sleep(delay);
+            }
+            Err(_) => {}
+        }
+    }
+
+    let _ = fs::remove_dir_all(&dir);
+
+    outcome.with_context(|| format!("Failed to push scan to {} after {} attempts", target, MAX_ATTEMPTS))?;
+    if verbose {
+        eprintln!("Pushed {} bytes to {}", body.len(), target);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn test_gzip_roundtrip() {
+        let mut result = ScanResult::new();
+        result.hostname = Some("build-01".to_string());
+
+        let body = gzip_json(&result).unwrap();
+        let mut json = String::new();
+        GzDecoder::new(&body[..]).read_to_string(&mut json).unwrap();
+        let decoded: ScanResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.hostname.as_deref(), Some("build-01"));
+    }
+
+    #[test]
+    fn test_parse_header() {
+        std::env::set_var("DEVFETCH_TEST_PUSH_TOKEN", "s3cr3t");
+        let (name, value) = parse_header("Authorization: Bearer ${DEVFETCH_TEST_PUSH_TOKEN}").unwrap();
+        assert_eq!(name, "Authorization");
+        assert_eq!(value, "Bearer s3cr3t");
+
+        assert!(parse_header("no colon").is_err());
+        assert!(parse_header("Bad Name: x").is_err());
+        assert_eq!(mask("Bearer s3cr3t"), "Bear****");
+        assert_eq!(mask("short"), "****");
+    }
+
+    #[test]
+    fn test_private_files() {
+        let dir = private_temp_dir().unwrap();
+        assert_eq!(fs::metadata(&dir).unwrap().permissions().mode() & 0o777, 0o700);
+        write_private(&dir.join("body"), b"x").unwrap();
+        assert!(write_private(&dir.join("body"), b"y").is_err());
+        fs::remove_dir_all(&dir).unwrap();
+
+        let config = curl_config(&[("url", "https://h/x"), ("header", r#"Authorization: Bearer a"b\c"#)]);
+        assert_eq!(config, "url = \"https://h/x\"\nheader = \"Authorization: Bearer a\\\"b\\\\c\"\n");
+    }
+}
@@ -0,0 +1,203 @@
+use crate::core::{identifiers, probe};
+use crate::types::{DependencyInfo, PythonInterpreter, PythonEcosystem};
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Lock files read offline: uv and PDM both write `[[package]]` tables
+static LOCK_FILES: &[&str] = &["uv.lock", "pdm.lock"];
+
+/// Locked dependencies from uv.lock/pdm.lock, and pyproject.toml's
+/// `requires-python` checked against the interpreters on PATH
+pub fn inspect(dir: &Path) -> Option<(Option<DependencyInfo>, PythonEcosystem)> {
+    let lock = LOCK_FILES.iter().find_map(|f| fs::read_to_string(dir.join(f)).ok().map(|c| (*f, c)));
+    let requires_python = fs::read_to_string(dir.join("pyproject.toml"))
+        .ok()
+        .and_then(|c| c.parse::<toml::Table>().ok())
+        .and_then(|t| t.get("project")?.get("requires-python")?.as_str().map(String::from));
+    if lock.is_none() && requires_python.is_none() {
+        return None;
+    }
+
+    let dependencies = lock.as_ref().and_then(|(_, content)| locked_packages(content));
+    let interpreters = match &requires_python {
+        Some(constraint) => installed_interpreters()
+            .into_iter()
+            .map(|(name, path, version)| PythonInterpreter {
+                satisfies: constraint_satisfied(constraint, &version),
+                name,
+                path,
+                version,
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    Some((
+        dependencies,
+        PythonEcosystem {
+            package_manager: None,
+            requires_python,
+            lock_file: lock.map(|(f, _)| f.to_string()),
+            interpreters,
+        },
+    ))
+}
+
+/// `[[package]]` entries, leaving out the project itself (uv records it as an
+/// editable or virtual source)
+fn locked_packages(content: &str) -> Option<DependencyInfo> {
+    let lock: toml::Table = content.parse().ok()?;
+    let packages: Vec<(String, Option<String>)> = lock
+        .get("package")?
+        .as_array()?
+        .iter()
+        .filter(|p| {
+            let source = p.get("source").and_then(|s| s.as_table());
+            !source.is_some_and(|s| s.contains_key("editable") || s.contains_key("virtual"))
+        })
+        .filter_map(|p| {
+            let name = p.get("name")?.as_str()?.to_string();
+            Some((name, p.get("version").and_then(|v| v.as_str()).map(String::from)))
+        })
+        .collect();
+    if packages.is_empty() {
+        return None;
+    }
+    Some(DependencyInfo {
+        count: packages.len(),
+        sample: packages.iter().take(5).map(|(name, _)| name.clone()).collect(),
+        purls: packages
+            .iter()
+            .filter_map(|(name, version)| version.as_deref().map(|v| identifiers::package_purl("pypi", name, v)))
+            .collect(),
+    })
+}
+
+/// python3 and versioned python3.X binaries on PATH, one entry per interpreter
+fn installed_interpreters() -> Vec<(String, PathBuf, String)> {
+    let names = ["python3".to_string(), "python".to_string()].into_iter().chain((6..=15).map(|m| format!("python3.{}", m)));
+    let mut seen: Vec<PathBuf> = Vec::new();
+    let mut found = Vec::new();
+    for name in names {
+        let Ok(path) = which::which(&name) else {
+            continue;
+        };
+        let real = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+        if seen.contains(&real) {
+            continue;
+        }
+        seen.push(real);
+        // pyenv and asdf shims are separate scripts that may run the same interpreter
+        if let Some(version) = probe::probe_version(&path.to_string_lossy()).version {
+            if !found.iter().any(|(_, _, v)| *v == version) {
+                found.push((name, path, version));
+            }
+        }
+    }
+    found
+}
+
+/// Evaluate a PEP 440 specifier set such as ">=3.9,<3.13", "~=3.11" or "==3.12.*".
+/// Versions compare by release segments, zero-padded, so "==3.12" is 3.12.0
+/// only and "3.1" is not a prefix of "3.12"
+pub fn constraint_satisfied(constraint: &str, version: &str) -> bool {
+    let clause = Regex::new(r"^(===|==|!=|~=|>=|<=|>|<)\s*([\d.]+?)(\.\*)?$").unwrap();
+    let actual = release(version);
+    constraint.split(',').map(str::trim).filter(|c| !c.is_empty()).all(|part| {
+        let Some(caps) = clause.captures(part) else {
+            return true;
+        };
+        let wanted = release(&caps[2]);
+        let wildcard = caps.get(3).is_some();
+        let order = compare_release(&actual, &wanted);
+        match &caps[1] {
+            "===" => version == &caps[2],
+            "==" if wildcard => has_prefix(&actual, &wanted),
+            "!=" if wildcard => !has_prefix(&actual, &wanted),
+            "==" => order.is_eq(),
+            "!=" => order.is_ne(),
+            // ~=3.11 allows 3.x from 3.11; ~=3.11.2 allows 3.11.x from 3.11.2
+            "~=" => order.is_ge() && has_prefix(&actual, &wanted[..wanted.len().saturating_sub(1)]),
+            ">=" => order.is_ge(),
+            "<=" => order.is_le(),
+            ">" => order.is_gt(),
+            _ => order.is_lt(),
+        }
+    })
+}
+
+/// Release segments of a version: "3.13.0rc1" -> [3, 13, 0]
+fn release(version: &str) -> Vec<u64> {
+    let mut segments = Vec::new();
+    for part in version.trim_start_matches('v').split('.') {
+        let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+        let Ok(segment) = digits.parse() else { break };
+        segments.push(segment);
+        // A pre- or post-release tag ends the release segments
+        if digits.len() < part.len() {
+            break;
+        }
+    }
+    segments
+}
+
+fn compare_release(a: &[u64], b: &[u64]) -> std::cmp::Ordering {
+    (0..a.len().max(b.len()))
+        .map(|i| a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)))
+        .find(|o| o.is_ne())
+        .unwrap_or(std::cmp::Ordering::Equal)
+}
+
+/// Whether `version` starts with the segments of `prefix`, zero-padding it
+fn has_prefix(version: &[u64], prefix: &[u64]) -> bool {
+    prefix.iter().enumerate().all(|(i, segment)| version.get(i).unwrap_or(&0) == segment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constraint_satisfied() {
+        assert!(constraint_satisfied(">=3.9,<3.13", "3.12.3"));
+        assert!(!constraint_satisfied(">=3.9, <3.13", "3.13.0"));
+        assert!(constraint_satisfied("~=3.11", "3.12.1"));
+        assert!(!constraint_satisfied("~=3.11.2", "3.12.0"));
+        assert!(constraint_satisfied("==3.12.*", "3.12.7"));
+        assert!(!constraint_satisfied("!=3.10.*,>=3.8", "3.10.4"));
+        // Release segments, not string prefixes
+        assert!(!constraint_satisfied("==3.1.*", "3.12.0"));
+        assert!(!constraint_satisfied("==3.12", "3.12.7"));
+        assert!(constraint_satisfied("==3.12", "3.12.0"));
+        assert!(constraint_satisfied(">=3.9", "3.10.0"));
+        assert!(!constraint_satisfied("<3.13", "3.13.0rc1"));
+    }
+
+    #[test]
+    fn test_locked_packages() {
+        let uv = r#"
+version = 1
+requires-python = ">=3.11"
+
+[[package]]
+name = "app"
+version = "0.1.0"
+source = { editable = "." }
+
+[[package]]
+name = "httpx"
+version = "0.27.0"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "Typing_Extensions"
+version = "4.11.0"
+source = { registry = "https://pypi.org/simple" }
+"#;
+        let deps = locked_packages(uv).unwrap();
+        assert_eq!(deps.count, 2);
+        assert_eq!(deps.sample, vec!["httpx", "Typing_Extensions"]);
+        assert_eq!(deps.purls[1], "pkg:pypi/typing-extensions@4.11.0");
+    }
+}
@@ -0,0 +1,129 @@
+use crate::core::{paths, system};
+use crate::types::ScanResult;
+use anyhow::Result;
+use regex::Regex;
+use serde_json::Value;
+use std::env;
+
+/// Hide personal details so the scan can be shared: `home` turns paths under
+/// the home directory into ~/..., `hostname` and `username` replace the
+/// hostname field and the git/forge user and email fields with placeholders
+/// (and the user's directory name in home paths). Only those values are
+/// touched; field names, enum tags and tool names are left alone.
+pub fn apply(result: ScanResult, what: &[String]) -> Result<ScanResult> {
+    if what.is_empty() {
+        return Ok(result);
+    }
+    let home = paths::home_dir().map(|h| h.to_string_lossy().trim_end_matches(['/', '\\']).to_string());
+    let user = env::var("USER").ok().or_else(|| env::var("USERNAME").ok());
+    redact(result, what, home.as_deref(), user.as_deref())
+}
+
+fn redact(mut result: ScanResult, what: &[String], home: Option<&str>, user: Option<&str>) -> Result<ScanResult> {
+    let wanted = |name: &str| what.iter().any(|w| w == name);
+    if wanted("hostname") {
+        result.hostname = result.hostname.or_else(system::hostname).map(|_| "<hostname>".to_string());
+    }
+    if wanted("username") {
+        let placeholder = |value: &mut Option<String>, with: &str| {
+            if value.is_some() {
+                *value = Some(with.to_string());
+            }
+        };
+        if let Some(git) = &mut result.git {
+            placeholder(&mut git.user_name, "<username>");
+            placeholder(&mut git.user_email, "<email>");
+        }
+        for login in result.forge_clis.iter_mut().flat_map(|f| f.logins.iter_mut().flatten()) {
+            placeholder(&mut login.user, "<username>");
+        }
+    }
+
+    let Some(home) = home.filter(|h| !h.is_empty()) else {
+        return Ok(result);
+    };
+    let replacement = if wanted("home") {
+        "~".to_string()
+    } else {
+        // Keep the path readable but hide the directory named after the user
+        match (wanted("username"), user, home.rsplit_once(['/', '\\'])) {
+            (true, Some(user), Some((parent, name))) if name.eq_ignore_ascii_case(user) => format!("{}/<username>", parent),
+            _ => return Ok(result),
+        }
+    };
+    let rules: Vec<_> = rule(home, replacement).into_iter().collect();
+    Ok(serde_json::from_value(rewrite(serde_json::to_value(result)?, &rules))?)
+}
+
+/// Matches the path `text` where a path component ends, so home "/home/al"
+/// leaves "/home/alice" alone
+pub fn rule(text: &str, replacement: impl Into<String>) -> Option<(Regex, String)> {
+    if text.is_empty() {
+        return None;
+    }
+    let bounded = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let start = if bounded(text.chars().next()) { r"\b" } else { "" };
+    let end = if bounded(text.chars().last()) { r"\b" } else { "" };
+    Regex::new(&format!("(?i){}{}{}", start, regex::escape(text), end)).ok().map(|re| (re, replacement.into()))
+}
+
+/// Apply the rules to every string value; object keys are never rewritten
+pub fn rewrite(value: Value, rules: &[(Regex, String)]) -> Value {
+    match value {
+        Value::String(s) => {
+            Value::String(rules.iter().fold(s, |s, (re, with)| re.replace_all(&s, regex::NoExpand(with)).into_owned()))
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(|v| rewrite(v, rules)).collect()),
+        Value::Object(fields) => Value::Object(fields.into_iter().map(|(k, v)| (k, rewrite(v, rules))).collect()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_rewrite() {
+        let rules: Vec<_> = rule("/Users/alice", "~").into_iter().collect();
+        let scan = json!({
+            "tools": ["/Users/alice/.cargo/bin/cargo", "/Users/alicex/bin/go", "/opt/malice/bin/x"],
+            "note": {"/Users/alice": "/Users/alice"}
+        });
+        assert_eq!(
+            rewrite(scan, &rules),
+            json!({
+                "tools": ["~/.cargo/bin/cargo", "/Users/alicex/bin/go", "/opt/malice/bin/x"],
+                "note": {"/Users/alice": "~"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_username_that_is_also_a_tag() {
+        let scan = json!({
+            "hostname": "devbox",
+            "global_tools": [{"name": "node", "path": "/home/node/.volta/bin/node", "version": "20.11.1",
+                              "category": "LanguageToolchain", "scope": "User"}],
+            "project_info": {"path": "/home/node/app", "markers": [{"file": "package.json", "ecosystem": "Node.js"}],
+                             "ecosystems": {"Node.js": {"name": "Node.js", "tool_version": "20.11.1", "dependencies": null,
+                                                        "details": {"kind": "node"}}}}
+        });
+        let result: ScanResult = serde_json::from_value(scan).unwrap();
+        for user in ["node", "user"] {
+            let what = ["username".to_string(), "hostname".to_string()];
+            let home = format!("/home/{}", user);
+            let redacted = redact(result.clone(), &what, Some(&home), Some(user)).unwrap();
+            let json = serde_json::to_value(&redacted).unwrap();
+            assert_eq!(json["hostname"], "<hostname>");
+            assert_eq!(json["global_tools"][0]["name"], "node");
+            assert_eq!(json["global_tools"][0]["scope"], "User");
+            assert_eq!(json["project_info"]["markers"][0]["ecosystem"], "Node.js");
+            assert_eq!(json["project_info"]["ecosystems"]["Node.js"]["details"]["kind"], "node");
+        }
+        let redacted = redact(result, &["username".to_string()], Some("/home/node"), Some("node")).unwrap();
+        assert_eq!(redacted.global_tools[0].path, std::path::Path::new("/home/<username>/.volta/bin/node"));
+        assert_eq!(redacted.project_info.unwrap().path, std::path::Path::new("/home/<username>/app"));
+    }
+}
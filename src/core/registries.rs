@@ -0,0 +1,228 @@
+use crate::core::{exec, paths};
+use crate::types::{RegistryAuth, RegistryLogin, RegistryReference};
+use regex::Regex;
+use serde_json::Value;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Docker Hub's names in auth files, all meaning docker.io
+static DOCKER_HUB_ALIASES: &[&str] = &["https://index.docker.io/v1/", "index.docker.io", "registry-1.docker.io", "docker.io"];
+
+static COMPOSE_FILES: &[&str] = &["docker-compose.yml", "docker-compose.yaml", "compose.yml", "compose.yaml"];
+
+/// Which registries the user is logged into (Docker and Podman auth files;
+/// credentials are never read, only registry names), matched against the
+/// registries the project's compose files, Dockerfiles and CI configs pull from
+pub fn check_registry_auth(project: Option<&Path>) -> RegistryAuth {
+    let mut logins = Vec::new();
+    if let Some(home) = paths::home_dir() {
+        logins.extend(docker_logins(&home.join(".docker/config.json")));
+    }
+    for file in podman_auth_files() {
+        for login in auth_file_logins(&file, "podman") {
+            if !logins.iter().any(|l: &RegistryLogin| l.registry == login.registry) {
+                logins.push(login);
+            }
+        }
+    }
+
+    let referenced = project
+        .map(|dir| {
+            referenced_registries(dir)
+                .into_iter()
+                .map(|(registry, files)| RegistryReference {
+                    logged_in: logins.iter().any(|l| l.registry == registry),
+                    registry,
+                    files,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    RegistryAuth { logins, referenced }
+}
+
+/// Strip scheme and path so `https://ghcr.io/v2/` and `ghcr.io` compare equal
+fn normalize_registry(server: &str) -> String {
+    if DOCKER_HUB_ALIASES.contains(&server) {
+        return "docker.io".to_string();
+    }
+    let host = server.trim_start_matches("https://").trim_start_matches("http://");
+    host.split('/').next().unwrap_or(host).to_string()
+}
+
+fn docker_logins(config: &Path) -> Vec<RegistryLogin> {
+    let mut logins = auth_file_logins(config, "docker");
+    let Some(json) = fs::read_to_string(config).ok().and_then(|c| serde_json::from_str::<Value>(&c).ok()) else {
+        return logins;
+    };
+
+    // Per-registry helpers are configured, but may or may not hold a login
+    if let Some(helpers) = json.get("credHelpers").and_then(|h| h.as_object()) {
+        for (server, helper) in helpers {
+            let registry = normalize_registry(server);
+            let helper = helper.as_str().unwrap_or_default().to_string();
+            match logins.iter_mut().find(|l| l.registry == registry) {
+                Some(login) => login.helper = Some(helper),
+                None => logins.push(RegistryLogin { registry, source: "docker".to_string(), helper: Some(helper) }),
+            }
+        }
+    }
+
+    // With a global credsStore, `auths` entries are empty and the store knows the logins
+    if let Some(store) = json.get("credsStore").and_then(|s| s.as_str()) {
+        let helper = format!("docker-credential-{}", store);
+        if exec::command_exists(&helper) {
+            let listed = exec::execute_for_output(&helper, &["list"])
+                .and_then(|out| serde_json::from_str::<Value>(&out).ok());
+            for server in listed.iter().filter_map(|l| l.as_object()).flat_map(|m| m.keys()) {
+                let registry = normalize_registry(server);
+                match logins.iter_mut().find(|l| l.registry == registry) {
+                    Some(login) => login.helper = Some(store.to_string()),
+                    None => logins.push(RegistryLogin {
+                        registry,
+                        source: "docker".to_string(),
+                        helper: Some(store.to_string()),
+                    }),
+                }
+            }
+        }
+    }
+    logins
+}
+
+/// Registry names under `auths` in a Docker/Podman auth file
+fn auth_file_logins(file: &Path, source: &str) -> Vec<RegistryLogin> {
+    let Some(json) = fs::read_to_string(file).ok().and_then(|c| serde_json::from_str::<Value>(&c).ok()) else {
+        return Vec::new();
+    };
+    let Some(auths) = json.get("auths").and_then(|a| a.as_object()) else {
+        return Vec::new();
+    };
+    auths
+        .keys()
+        .map(|server| RegistryLogin { registry: normalize_registry(server), source: source.to_string(), helper: None })
+        .collect()
+}
+
+/// Podman's auth.json: $REGISTRY_AUTH_FILE, then the runtime dir, then ~/.config
+fn podman_auth_files() -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = env::var_os("REGISTRY_AUTH_FILE").map(PathBuf::from).into_iter().collect();
+    if let Some(runtime) = env::var_os("XDG_RUNTIME_DIR") {
+        files.push(PathBuf::from(runtime).join("containers/auth.json"));
+    }
+    if let Some(home) = paths::home_dir() {
+        files.push(home.join(".config/containers/auth.json"));
+    }
+    files
+}
+
+/// Registry an image reference pulls from: the first path component when it
+/// looks like a host (has a dot or port, or is localhost), else Docker Hub
+fn image_registry(image: &str) -> Option<String> {
+    let image = image.trim().trim_matches(['"', '\'']);
+    if image.is_empty() || image.starts_with('$') || image == "scratch" {
+        return None;
+    }
+    match image.split_once('/') {
+        Some((host, _)) if host.contains('.') || host.contains(':') || host == "localhost" => {
+            Some(normalize_registry(host))
+        }
+        _ => Some("docker.io".to_string()),
+    }
+}
+
+/// Registries referenced by `image:`/`FROM` lines and `docker login` commands,
+/// with the files that reference each
+fn referenced_registries(dir: &Path) -> Vec<(String, Vec<String>)> {
+    let image_line = Regex::new(r"(?m)^\s*-?\s*image:\s*(\S+)").unwrap();
+    let from_line = Regex::new(r"(?mi)^\s*FROM\s+(?:--platform=\S+\s+)?(\S+)").unwrap();
+    let login = Regex::new(r"(?:docker|podman) login\s+(?:-\S+\s+\S+\s+)*([A-Za-z0-9.-]+\.[A-Za-z]{2,}(?::\d+)?)").unwrap();
+    let registry_input = Regex::new(r"(?m)^\s*registry:\s*(\S+)").unwrap();
+    let stage_name = Regex::new(r"(?mi)\bAS\s+(\S+)\s*$").unwrap();
+
+    let mut files: Vec<String> = COMPOSE_FILES.iter().map(|f| f.to_string()).collect();
+    files.extend(["Dockerfile", "Containerfile", ".gitlab-ci.yml"].iter().map(|f| f.to_string()));
+    if let Ok(entries) = fs::read_dir(dir.join(".github/workflows")) {
+        for entry in entries.flatten() {
+            files.push(format!(".github/workflows/{}", entry.file_name().to_string_lossy()));
+        }
+    }
+
+    let mut found: Vec<(String, Vec<String>)> = Vec::new();
+    for file in files {
+        let Ok(content) = fs::read_to_string(dir.join(&file)) else {
+            continue;
+        };
+        let is_dockerfile = file.ends_with("Dockerfile") || file.ends_with("Containerfile");
+        let mut registries: Vec<String> = Vec::new();
+        if is_dockerfile {
+            // Build stages referenced by name (FROM builder) are not images
+            let stages: Vec<String> = stage_name.captures_iter(&content).map(|c| c[1].to_lowercase()).collect();
+            registries.extend(
+                from_line
+                    .captures_iter(&content)
+                    .filter(|c| !stages.contains(&c[1].to_lowercase()))
+                    .filter_map(|c| image_registry(&c[1])),
+            );
+        } else {
+            registries.extend(image_line.captures_iter(&content).filter_map(|c| image_registry(&c[1])));
+            registries.extend(login.captures_iter(&content).map(|c| normalize_registry(&c[1])));
+            registries.extend(
+                registry_input
+                    .captures_iter(&content)
+                    .filter(|c| !c[1].contains("${{"))
+                    .map(|c| normalize_registry(c[1].trim_matches(['"', '\'']))),
+            );
+        }
+
+        for registry in registries {
+            match found.iter_mut().find(|(r, _)| *r == registry) {
+                Some((_, files)) if !files.contains(&file) => files.push(file.clone()),
+                Some(_) => {}
+                None => found.push((registry, vec![file.clone()])),
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_image_registry() {
+        assert_eq!(image_registry("postgres:16").as_deref(), Some("docker.io"));
+        assert_eq!(image_registry("bitnami/redis").as_deref(), Some("docker.io"));
+        assert_eq!(image_registry("ghcr.io/org/app:1.2").as_deref(), Some("ghcr.io"));
+        assert_eq!(image_registry("localhost:5000/app").as_deref(), Some("localhost:5000"));
+        assert_eq!(image_registry("${IMAGE}"), None);
+        assert_eq!(normalize_registry("https://index.docker.io/v1/"), "docker.io");
+    }
+
+    #[test]
+    fn test_referenced_registries() {
+        let dir = TempDir::new("registries");
+        fs::create_dir_all(dir.join(".github/workflows")).unwrap();
+        fs::write(
+            dir.join("Dockerfile"),
+            "FROM golang:1.22 AS builder\nRUN go build\nFROM gcr.io/distroless/static\nCOPY --from=builder /app /app\n",
+        )
+        .unwrap();
+        fs::write(dir.join("compose.yaml"), "services:\n  db:\n    image: postgres:16\n").unwrap();
+        fs::write(
+            dir.join(".github/workflows/ci.yml"),
+            "steps:\n  - uses: docker/login-action@v3\n    with:\n      registry: ghcr.io\n",
+        )
+        .unwrap();
+
+        let mut found = referenced_registries(&dir);
+        found.sort();
+        let registries: Vec<&str> = found.iter().map(|(r, _)| r.as_str()).collect();
+        assert_eq!(registries, vec!["docker.io", "gcr.io", "ghcr.io"]);
+        assert_eq!(found[0].1, vec!["compose.yaml", "Dockerfile"]);
+    }
+}
@@ -0,0 +1,111 @@
+use crate::core::docs::{configured, npm_pin};
+use crate::types::ConfiguredTool;
+use std::fs;
+use std::path::Path;
+
+static SEMANTIC_RELEASE_CONFIGS: &[&str] = &[
+    ".releaserc",
+    ".releaserc.json",
+    ".releaserc.yaml",
+    ".releaserc.yml",
+    ".releaserc.js",
+    ".releaserc.cjs",
+    "release.config.js",
+    "release.config.cjs",
+    "release.config.mjs",
+];
+
+/// Detect release automation configured in the project, with pinned versions
+pub fn detect_release_tools(dir: &Path) -> Vec<ConfiguredTool> {
+    let mut tools = Vec::new();
+    let manifest = fs::read_to_string(dir.join("package.json")).unwrap_or_default();
+
+    let semantic_release = SEMANTIC_RELEASE_CONFIGS.iter().copied().find(|c| dir.join(c).is_file()).or_else(|| {
+        let json: serde_json::Value = serde_json::from_str(&manifest).ok()?;
+        json.get("release").map(|_| "package.json")
+    });
+    if let Some(config) = semantic_release {
+        let pin = npm_pin(&manifest, "semantic-release");
+        tools.push(configured(dir, "semantic-release", config, "semantic-release", pin, "npx semantic-release --dry-run"));
+    }
+
+    if let Some(config) = ["release-please-config.json", ".release-please-manifest.json"]
+        .into_iter()
+        .find(|c| dir.join(c).is_file())
+    {
+        let pin = npm_pin(&manifest, "release-please");
+        tools.push(configured(dir, "release-please", config, "release-please", pin, "release-please release-pr"));
+    }
+
+    let cargo = fs::read_to_string(dir.join("Cargo.toml")).unwrap_or_default();
+    let cargo_release = if dir.join("release.toml").is_file() {
+        Some("release.toml")
+    } else if cargo.contains("metadata.release]") {
+        Some("Cargo.toml")
+    } else {
+        None
+    };
+    if let Some(config) = cargo_release {
+        tools.push(configured(dir, "cargo-release", config, "cargo-release", None, "cargo release patch"));
+    }
+
+    if let Some(config) = [".goreleaser.yml", ".goreleaser.yaml", "goreleaser.yml", "goreleaser.yaml"]
+        .into_iter()
+        .find(|c| dir.join(c).is_file())
+    {
+        let pin = goreleaser_action_version(dir);
+        tools.push(configured(dir, "GoReleaser", config, "goreleaser", pin, "goreleaser release --snapshot --clean"));
+    }
+
+    if dir.join(".changeset/config.json").is_file() {
+        let pin = npm_pin(&manifest, "@changesets/cli");
+        tools.push(configured(dir, "Changesets", ".changeset/config.json", "changeset", pin, "npx changeset version"));
+    }
+
+    tools
+}
+
+/// The `version:` input given to goreleaser-action in GitHub workflows
+fn goreleaser_action_version(dir: &Path) -> Option<String> {
+    let workflows = fs::read_dir(dir.join(".github/workflows")).ok()?;
+    workflows.flatten().find_map(|entry| {
+        let content = fs::read_to_string(entry.path()).ok()?;
+        let lines: Vec<&str> = content.lines().collect();
+        let step = lines.iter().position(|l| l.contains("goreleaser/goreleaser-action"))?;
+        lines[step + 1..].iter().take(8).find_map(|l| {
+            let value = l.trim().strip_prefix("version:")?;
+            Some(value.trim().trim_matches(['"', '\'']).to_string())
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_detect_release_tools() {
+        let dir = TempDir::new("release");
+        fs::create_dir_all(dir.join(".changeset")).unwrap();
+        fs::create_dir_all(dir.join(".github/workflows")).unwrap();
+        fs::write(dir.join(".changeset/config.json"), "{}").unwrap();
+        fs::write(dir.join("package.json"), "{\n  \"devDependencies\": {\n    \"@changesets/cli\": \"^2.27.1\"\n  }\n}\n").unwrap();
+        fs::write(dir.join(".goreleaser.yaml"), "version: 2\nbuilds:\n  - main: ./cmd/app\n").unwrap();
+        fs::write(
+            dir.join(".github/workflows/release.yml"),
+            "jobs:\n  release:\n    steps:\n      - uses: goreleaser/goreleaser-action@v6\n        with:\n          distribution: goreleaser\n          version: \"~> v2\"\n",
+        )
+        .unwrap();
+
+        let found: Vec<(String, Option<String>)> =
+            detect_release_tools(&dir).into_iter().map(|t| (t.tool, t.pinned_version)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("GoReleaser".to_string(), Some("~> v2".to_string())),
+                ("Changesets".to_string(), Some("^2.27.1".to_string())),
+            ]
+        );
+    }
+}
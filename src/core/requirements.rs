@@ -0,0 +1,272 @@
+use crate::core::check::satisfies;
+use crate::core::doctor::{binary_name, version_matches};
+use crate::core::python::constraint_satisfied;
+use crate::types::{ConstraintSyntax, EcosystemDetails, RequirementCheck, RequirementStatus, ScanResult, VersionRequirement};
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Channels that any release satisfies
+static ANY_RELEASE: &[&str] = &["stable", "lts", "lts/*", "node", "latest"];
+
+/// package.json `engines` keys naming runtimes and package managers devfetch
+/// can probe; others (`vscode`, `electron`, ...) are host applications
+static ENGINES: &[&str] = &["node", "npm", "pnpm", "yarn", "bun", "deno"];
+
+/// Tool versions the project's files ask for: .nvmrc/.node-version,
+/// package.json `engines`, rust-toolchain(.toml), .tool-versions,
+/// pyproject.toml's Python constraint and go.mod's `go` directive
+pub fn detect_requirements(dir: &Path) -> Vec<VersionRequirement> {
+    let mut requirements = Vec::new();
+    let mut add = |tool: &str, constraint: &str, syntax: ConstraintSyntax, source: &str| {
+        requirements.push(VersionRequirement {
+            tool: tool.to_string(),
+            constraint: constraint.trim().to_string(),
+            syntax,
+            source: source.to_string(),
+        });
+    };
+
+    for file in [".nvmrc", ".node-version"] {
+        if let Some(version) = first_line(&dir.join(file)) {
+            add("node", version.trim_start_matches('v'), ConstraintSyntax::Exact, file);
+        }
+    }
+
+    let package_json = fs::read_to_string(dir.join("package.json")).ok().and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok());
+    if let Some(engines) = package_json.as_ref().and_then(|p| p.get("engines")?.as_object().cloned()) {
+        for (tool, range) in engines.iter().filter(|(tool, _)| ENGINES.contains(&tool.as_str())) {
+            if let Some(range) = range.as_str() {
+                add(tool, range, ConstraintSyntax::Semver, "package.json");
+            }
+        }
+    }
+
+    if let Some((channel, file)) = rust_channel(dir) {
+        add("rustc", &channel, ConstraintSyntax::Exact, file);
+    }
+
+    if let Ok(content) = fs::read_to_string(dir.join(".tool-versions")) {
+        for (tool, version) in parse_tool_versions(&content) {
+            if version != "system" {
+                add(binary_name(tool), version, ConstraintSyntax::Exact, ".tool-versions");
+            }
+        }
+    }
+
+    let pyproject = fs::read_to_string(dir.join("pyproject.toml")).ok().and_then(|c| c.parse::<toml::Table>().ok());
+    if let Some(pyproject) = &pyproject {
+        if let Some(spec) = pyproject.get("project").and_then(|p| p.get("requires-python")?.as_str()) {
+            add("python3", spec, ConstraintSyntax::Pep440, "pyproject.toml");
+        } else if let Some(spec) = pyproject
+            .get("tool")
+            .and_then(|t| t.get("poetry")?.get("dependencies")?.get("python")?.as_str())
+        {
+            // Poetry writes ranges the npm way ("^3.10")
+            add("python3", spec, ConstraintSyntax::Semver, "pyproject.toml");
+        }
+    }
+
+    let go_directive = Regex::new(r"(?m)^go\s+(\d[\d.]*)\s*$").unwrap();
+    if let Some(caps) = fs::read_to_string(dir.join("go.mod")).ok().and_then(|c| go_directive.captures(&c).map(|c| c[1].to_string())) {
+        add("go", &caps, ConstraintSyntax::Minimum, "go.mod");
+    }
+
+    requirements
+}
+
+/// (tool, version) for each line of a .tool-versions file; later versions on
+/// a line are fallbacks, so only the first, the one asdf selects, is kept
+pub fn parse_tool_versions(content: &str) -> Vec<(&str, &str)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('#').next()?.split_whitespace();
+            Some((fields.next()?, fields.next()?))
+        })
+        .collect()
+}
+
+/// The first line that isn't blank or a comment
+fn first_line(file: &Path) -> Option<String> {
+    let content = fs::read_to_string(file).ok()?;
+    content.lines().map(str::trim).find(|l| !l.is_empty() && !l.starts_with('#')).map(String::from)
+}
+
+/// `[toolchain] channel` from rust-toolchain.toml, or the legacy plain rust-toolchain file
+fn rust_channel(dir: &Path) -> Option<(String, &'static str)> {
+    for file in ["rust-toolchain.toml", "rust-toolchain"] {
+        let Ok(content) = fs::read_to_string(dir.join(file)) else {
+            continue;
+        };
+        let channel = match content.parse::<toml::Table>() {
+            Ok(table) => table.get("toolchain")?.get("channel")?.as_str()?.to_string(),
+            Err(_) => first_line(&dir.join(file))?,
+        };
+        return Some((channel, file));
+    }
+    None
+}
+
+/// Whether `version` meets the requirement; None when the constraint can't be
+/// checked against a version (e.g. "nightly-2024-05-01", "lts/hydrogen")
+pub fn requirement_met(requirement: &VersionRequirement, version: &str) -> Option<bool> {
+    let constraint = requirement.constraint.as_str();
+    match requirement.syntax {
+        ConstraintSyntax::Exact if constraint.starts_with(|c: char| c.is_ascii_digit()) => {
+            Some(version_matches(constraint, version))
+        }
+        ConstraintSyntax::Exact => ANY_RELEASE.contains(&constraint).then_some(true),
+        ConstraintSyntax::Minimum => Some(satisfies(">=", constraint, version)),
+        ConstraintSyntax::Pep440 => Some(constraint_satisfied(constraint, version)),
+        ConstraintSyntax::Semver => semver_range_met(constraint, version),
+    }
+}
+
+/// npm ranges: `||` alternatives of space-separated comparators, with x/*
+/// wildcards and hyphen ranges ("1.2 - 2.3")
+fn semver_range_met(range: &str, version: &str) -> Option<bool> {
+    let comparator = Regex::new(r"^(>=|<=|>|<|=|\^|~)?v?(\d+(?:\.\d+)*)((?:\.[xX*])*)$").unwrap();
+    let glued = Regex::new(r"(>=|<=|>|<|=|\^|~)\s+").unwrap();
+    let mut unknown = false;
+    for alternative in range.split("||") {
+        let alternative = glued.replace_all(alternative.trim(), "$1");
+        let tokens: Vec<&str> = alternative.split_whitespace().collect();
+        let tokens: Vec<String> = match tokens.as_slice() {
+            [low, "-", high] => vec![format!(">={}", low), format!("<={}", high)],
+            tokens => tokens.iter().map(|t| t.to_string()).collect(),
+        };
+        let mut met = Some(true);
+        for token in tokens.iter().filter(|t| !matches!(t.as_str(), "*" | "x" | "X")) {
+            let Some(caps) = comparator.captures(token) else {
+                met = None;
+                break;
+            };
+            // "18.x" and a bare "18" both mean any 18 release
+            let op = caps.get(1).map_or("=", |m| m.as_str());
+            if !satisfies(op, &caps[2], version) {
+                met = Some(false);
+            }
+        }
+        match met {
+            Some(true) => return Some(true),
+            None => unknown = true,
+            Some(false) => {}
+        }
+    }
+    (!unknown).then_some(false)
+}
+
+/// Check every requirement against the tools the scan found. A Python
+/// constraint passes when any interpreter meets it; other tools are checked
+/// against the copy that runs first on PATH.
+pub fn check(result: &ScanResult) -> Vec<RequirementCheck> {
+    result.requirements.iter().map(|requirement| check_requirement(requirement, result)).collect()
+}
+
+fn check_requirement(requirement: &VersionRequirement, result: &ScanResult) -> RequirementCheck {
+    let candidates: Vec<(PathBuf, Option<String>)> = if requirement.tool == "python3" {
+        let interpreters: Vec<(PathBuf, Option<String>)> = result
+            .project_info
+            .iter()
+            .flat_map(|p| p.ecosystems.values())
+            .filter_map(|e| match &e.details {
+                EcosystemDetails::Python(python) => Some(&python.interpreters),
+                _ => None,
+            })
+            .flatten()
+            .map(|i| (i.path.clone(), Some(i.version.clone())))
+            .collect();
+        if interpreters.is_empty() {
+            result
+                .global_tools
+                .iter()
+                .filter(|t| t.name == "python" || t.name == "python3" || t.name.starts_with("python3."))
+                .map(|t| (t.path.clone(), t.version.clone()))
+                .collect()
+        } else {
+            interpreters
+        }
+    } else {
+        let tools = &result.global_tools;
+        tools
+            .iter()
+            .find(|t| t.name == requirement.tool)
+            .or_else(|| tools.iter().find(|t| t.provides_command(&requirement.tool)))
+            .map(|t| (t.path.clone(), t.version.clone()))
+            .into_iter()
+            .collect()
+    };
+
+    let met = |(_, version): &&(PathBuf, Option<String>)| {
+        version.as_deref().and_then(|v| requirement_met(requirement, v)) == Some(true)
+    };
+    let chosen = candidates.iter().find(met).or(candidates.first());
+    let status = match chosen {
+        None => RequirementStatus::Fail,
+        Some((_, None)) => RequirementStatus::Warn,
+        Some((_, Some(version))) => match requirement_met(requirement, version) {
+            Some(true) => RequirementStatus::Pass,
+            Some(false) => RequirementStatus::Fail,
+            None => RequirementStatus::Warn,
+        },
+    };
+    RequirementCheck {
+        requirement: requirement.clone(),
+        status,
+        path: chosen.map(|(path, _)| path.clone()),
+        installed: chosen.and_then(|(_, version)| version.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_detect_requirements() {
+        let dir = TempDir::new("requirements");
+        fs::write(dir.join(".nvmrc"), "v20.11\n").unwrap();
+        fs::write(dir.join("package.json"), r#"{"engines": {"node": ">=18 <21", "vscode": "^1.80.0", "pnpm": "^8"}}"#).unwrap();
+        fs::write(dir.join("rust-toolchain.toml"), "[toolchain]\nchannel = \"1.75.0\"\n").unwrap();
+        fs::write(dir.join(".tool-versions"), "# pins\ngolang 1.22.1\nnodejs system\n").unwrap();
+        fs::write(dir.join("go.mod"), "module example.com/app\n\ngo 1.21\n").unwrap();
+
+        let found: Vec<(String, String, String)> = detect_requirements(&dir)
+            .into_iter()
+            .map(|r| (r.tool, r.constraint, r.source))
+            .collect();
+        let expected = [
+            ("node", "20.11", ".nvmrc"),
+            ("node", ">=18 <21", "package.json"),
+            ("pnpm", "^8", "package.json"),
+            ("rustc", "1.75.0", "rust-toolchain.toml"),
+            ("go", "1.22.1", ".tool-versions"),
+            ("go", "1.21", "go.mod"),
+        ];
+        let expected: Vec<(String, String, String)> =
+            expected.iter().map(|(t, c, s)| (t.to_string(), c.to_string(), s.to_string())).collect();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_requirement_met() {
+        let requirement = |constraint: &str, syntax| VersionRequirement {
+            tool: "node".to_string(),
+            constraint: constraint.to_string(),
+            syntax,
+            source: "package.json".to_string(),
+        };
+        assert_eq!(requirement_met(&requirement(">=18 <21", ConstraintSyntax::Semver), "20.11.1"), Some(true));
+        assert_eq!(requirement_met(&requirement(">= 18 < 20", ConstraintSyntax::Semver), "20.11.1"), Some(false));
+        assert_eq!(requirement_met(&requirement("^16 || 20.x", ConstraintSyntax::Semver), "20.11.1"), Some(true));
+        assert_eq!(requirement_met(&requirement("18 - 19.5", ConstraintSyntax::Semver), "19.9.0"), Some(false));
+        assert_eq!(requirement_met(&requirement("*", ConstraintSyntax::Semver), "20.11.1"), Some(true));
+        assert_eq!(requirement_met(&requirement("20.11", ConstraintSyntax::Exact), "20.11.1"), Some(true));
+        assert_eq!(requirement_met(&requirement("lts/*", ConstraintSyntax::Exact), "20.11.1"), Some(true));
+        assert_eq!(requirement_met(&requirement("lts/hydrogen", ConstraintSyntax::Exact), "20.11.1"), None);
+        assert_eq!(requirement_met(&requirement("1.22", ConstraintSyntax::Minimum), "1.21.5"), Some(false));
+        assert_eq!(requirement_met(&requirement(">=3.10,<3.13", ConstraintSyntax::Pep440), "3.12.1"), Some(true));
+    }
+}
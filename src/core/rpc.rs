@@ -0,0 +1,222 @@
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+/// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INTERNAL_ERROR: i64 = -32603;
+
+/// Largest message body accepted; requests are a few hundred bytes, so a
+/// bigger Content-Length is a broken or hostile client, not a real request
+const MAX_MESSAGE_BYTES: usize = 1024 * 1024;
+
+/// Notification carrying a stage of a request's result before the response
+pub const PARTIAL_RESULT: &str = "devfetch/partialResult";
+
+/// Requests an editor extension can make
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Global tools and the project; the project is sent first as a partial result
+    Scan,
+    /// Project only
+    Project,
+    /// Doctor findings, after the scan stages as partial results
+    Doctor,
+}
+
+impl Method {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "devfetch/scan" => Some(Method::Scan),
+            "devfetch/project" => Some(Method::Project),
+            "devfetch/doctor" => Some(Method::Doctor),
+            _ => None,
+        }
+    }
+
+    fn names() -> [&'static str; 3] {
+        ["devfetch/scan", "devfetch/project", "devfetch/doctor"]
+    }
+}
+
+/// Serve JSON-RPC 2.0 over LSP-style `Content-Length` framing until `exit`
+/// or end of input. `handler` answers devfetch requests for a project path
+/// and may report stages (`"project"`, `"scan"`) through its callback first
+pub fn serve<R, W, F>(mut input: R, mut output: W, mut handler: F) -> Result<()>
+where
+    R: BufRead,
+    W: Write,
+    F: FnMut(Method, PathBuf, &mut dyn FnMut(&str, Value)) -> Result<Value>,
+{
+    while let Some(body) = read_message(&mut input)? {
+        let request: Value = match serde_json::from_slice(&body) {
+            Ok(request) => request,
+            Err(e) => {
+                write_message(&mut output, &error_response(Value::Null, PARSE_ERROR, &e.to_string()))?;
+                continue;
+            }
+        };
+        let id = request.get("id").cloned();
+        let Some(method) = request.get("method").and_then(|m| m.as_str()) else {
+            write_message(&mut output, &error_response(id.unwrap_or(Value::Null), INVALID_REQUEST, "Missing method"))?;
+            continue;
+        };
+
+        let outcome = match method {
+            "exit" => return Ok(()),
+            "initialize" => Ok(json!({
+                "serverInfo": {"name": "devfetch", "version": env!("CARGO_PKG_VERSION")},
+                "capabilities": {"methods": Method::names(), "partialResults": PARTIAL_RESULT},
+            })),
+            "shutdown" => Ok(Value::Null),
+            name => match Method::from_name(name) {
+                Some(method) => {
+                    let path = request
+                        .pointer("/params/path")
+                        .and_then(|p| p.as_str())
+                        .map(PathBuf::from)
+                        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+                    let mut partial = |stage: &str, result: Value| {
+                        // Notifications can't be matched to a request without an id
+                        if let Some(id) = &id {
+                            let note = json!({"jsonrpc": "2.0", "method": PARTIAL_RESULT, "params": {"id": id, "stage": stage, "result": result}});
+                            if let Err(e) = write_message(&mut output, &note) {
+                                eprintln!("Failed to send partial result: {:#}", e);
+                            }
+                        }
+                    };
+                    handler(method, path, &mut partial).map_err(|e| (INTERNAL_ERROR, format!("{:#}", e)))
+                }
+                None => Err((METHOD_NOT_FOUND, format!("Unknown method '{}'", name))),
+            },
+        };
+
+        // Notifications (no id) get no response
+        let Some(id) = id else {
+            continue;
+        };
+        let response = match outcome {
+            Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+            Err((code, message)) => error_response(id, code, &message),
+        };
+        write_message(&mut output, &response)?;
+    }
+    Ok(())
+}
+
+/// Combine a project-only and a global-only scan: fields missing or empty in
+/// one are taken from the other
+pub fn merge_stages(project: Value, global: Value) -> Value {
+    let (Value::Object(mut merged), Value::Object(global)) = (project, global) else {
+        return Value::Null;
+    };
+    for (key, value) in global {
+        let empty = match merged.get(&key) {
+            None | Some(Value::Null) => true,
+            Some(Value::Array(items)) => items.is_empty(),
+            Some(_) => false,
+        };
+        if empty {
+            merged.insert(key, value);
+        }
+    }
+    Value::Object(merged)
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({"jsonrpc": "2.0", "id": id, "error": {"code": code, "message": message}})
+}
+
+/// One framed message body, or None at end of input
+fn read_message<R: BufRead>(input: &mut R) -> Result<Option<Vec<u8>>> {
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            if length.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                length = Some(value.trim().parse::<usize>().context("Invalid Content-Length")?);
+            }
+        }
+    }
+    let Some(length) = length else {
+        bail!("Message without Content-Length");
+    };
+    if length > MAX_MESSAGE_BYTES {
+        bail!("Message of {} bytes exceeds the {} byte limit", length, MAX_MESSAGE_BYTES);
+    }
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    Ok(Some(body))
+}
+
+fn write_message<W: Write>(output: &mut W, message: &Value) -> Result<()> {
+    let body = serde_json::to_string(message)?;
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    output.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(message: &str) -> String {
+        format!("Content-Length: {}\r\n\r\n{}", message.len(), message)
+    }
+
+    #[test]
+    fn test_serve() {
+        let input = [
+            frame(r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#),
+            frame(r#"{"jsonrpc":"2.0","id":2,"method":"devfetch/scan","params":{"path":"/src/app"}}"#),
+            frame(r#"{"jsonrpc":"2.0","id":3,"method":"devfetch/nope"}"#),
+            frame(r#"{"jsonrpc":"2.0","method":"exit"}"#),
+            frame(r#"{"jsonrpc":"2.0","id":4,"method":"shutdown"}"#),
+        ]
+        .concat();
+        let mut output = Vec::new();
+        serve(input.as_bytes(), &mut output, |method, path, partial| {
+            assert_eq!((method, path), (Method::Scan, PathBuf::from("/src/app")));
+            partial("project", json!({"project_info": {}}));
+            Ok(json!({"global_tools": []}))
+        })
+        .unwrap();
+
+        let mut reader = output.as_slice();
+        let messages: Vec<Value> =
+            std::iter::from_fn(|| read_message(&mut reader).unwrap()).map(|b| serde_json::from_slice(&b).unwrap()).collect();
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[0]["result"]["serverInfo"]["name"], "devfetch");
+        assert_eq!(messages[1]["method"], PARTIAL_RESULT);
+        assert_eq!(messages[1]["params"]["stage"], "project");
+        assert_eq!(messages[2]["id"], 2);
+        assert_eq!(messages[3]["error"]["code"], METHOD_NOT_FOUND);
+
+        // A huge Content-Length is refused before anything is allocated for it
+        let oversized = format!("Content-Length: {}\r\n\r\n{{}}", usize::MAX);
+        assert!(read_message(&mut oversized.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_merge_stages() {
+        let project = json!({"hostname": "dev", "global_tools": [], "project_info": {"path": "/src/app"}});
+        let global = json!({"hostname": "dev", "global_tools": [{"name": "rustc"}], "project_info": null, "shell": {}});
+        let merged = merge_stages(project, global);
+        assert_eq!(merged["global_tools"][0]["name"], "rustc");
+        assert_eq!(merged["project_info"]["path"], "/src/app");
+        assert!(merged.get("shell").is_some());
+    }
+}
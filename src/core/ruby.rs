@@ -0,0 +1,171 @@
+use crate::core::check::satisfies;
+use crate::core::{exec, identifiers, probe};
+use crate::types::{DependencyInfo, RubyRequirements};
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+/// Read the required Ruby from the Gemfile (or .ruby-version / Gemfile.lock),
+/// the locked gems and BUNDLED WITH, and compare against the installed ruby and bundler
+pub fn inspect(dir: &Path, ruby_version: Option<&str>) -> Option<(Option<DependencyInfo>, RubyRequirements)> {
+    let gemfile = fs::read_to_string(dir.join("Gemfile")).ok()?;
+    let lock = fs::read_to_string(dir.join("Gemfile.lock")).unwrap_or_default();
+
+    // JRuby and TruffleRuby number their own releases, so only MRI versions
+    // are checked against the ruby on PATH
+    let (ruby_constraint, ruby_source) = match gemfile_ruby(&gemfile) {
+        Some(GemfileRuby::Constraint(c)) => (Some(c), Some("Gemfile".to_string())),
+        Some(GemfileRuby::File(file)) => (read_version_file(&dir.join(&file)).and_then(mri_version), Some(file)),
+        None => match read_version_file(&dir.join(".ruby-version")) {
+            Some(v) => (mri_version(v), Some(".ruby-version".to_string())),
+            None => (lock_section(&lock, "RUBY VERSION").first().and_then(|l| lock_ruby(l)), Some("Gemfile.lock".to_string())),
+        },
+    };
+    let ruby_source = ruby_source.filter(|_| ruby_constraint.is_some());
+
+    let bundler_locked = lock_section(&lock, "BUNDLED WITH").first().map(|v| v.to_string());
+    let bundler_installed = bundler_locked
+        .as_ref()
+        .filter(|_| exec::command_exists("bundle"))
+        .and_then(|_| exec::execute_for_output_in(dir, "bundle", &["--version"]))
+        .and_then(|out| probe::extract_version(&out));
+
+    let requirements = RubyRequirements {
+        ruby_satisfied: ruby_constraint.as_deref().zip(ruby_version).map(|(c, v)| constraint_satisfied(c, v)),
+        ruby_constraint,
+        ruby_source,
+        bundler_locked,
+        bundler_installed,
+    };
+    Some((dependency_info(&lock), requirements))
+}
+
+enum GemfileRuby {
+    Constraint(String),
+    /// `ruby file: ".ruby-version"`
+    File(String),
+}
+
+/// The `ruby` directive: `ruby "3.2.2"`, `ruby "~> 3.2", ">= 3.2.1"` or `ruby file: ".ruby-version"`
+fn gemfile_ruby(gemfile: &str) -> Option<GemfileRuby> {
+    let directive = Regex::new(r#"(?m)^\s*ruby[\s(]\s*(.+?)\s*\)?\s*$"#).unwrap();
+    let quoted = Regex::new(r#"["']([^"']+)["']"#).unwrap();
+    let line = directive.captures(gemfile)?[1].to_string();
+    let values: Vec<String> = quoted.captures_iter(&line).map(|c| c[1].to_string()).collect();
+    if line.starts_with("file:") || line.starts_with(":file") {
+        return values.into_iter().next().map(GemfileRuby::File);
+    }
+    // Options such as engine: are not version constraints
+    let constraints: Vec<String> = values.into_iter().take_while(|v| v.chars().any(|c| c.is_ascii_digit())).collect();
+    (!constraints.is_empty()).then(|| GemfileRuby::Constraint(constraints.join(", ")))
+}
+
+fn read_version_file(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    let version = content.lines().next()?.trim();
+    (!version.is_empty()).then(|| version.to_string())
+}
+
+/// "ruby-3.3.0" and "3.3.0" -> 3.3.0; None for other engines ("jruby-9.4.5.0")
+fn mri_version(version: String) -> Option<String> {
+    match version.split_once('-') {
+        Some(("ruby", version)) => Some(version.to_string()),
+        Some((engine, _)) if engine.chars().all(|c| c.is_ascii_alphabetic()) => None,
+        _ => Some(version),
+    }
+}
+
+/// "ruby 3.2.2p53" -> 3.2.2; None for "ruby 3.1.4p0 (jruby 9.4.5.0)"
+fn lock_ruby(line: &str) -> Option<String> {
+    if line.contains('(') {
+        return None;
+    }
+    let version = line.trim_start_matches("ruby ");
+    Some(version.split('p').next().unwrap_or(version).to_string())
+}
+
+/// Indented lines under a top-level Gemfile.lock header, trimmed
+fn lock_section<'a>(lock: &'a str, header: &str) -> Vec<&'a str> {
+    lock.lines()
+        .skip_while(|l| *l != header)
+        .skip(1)
+        .take_while(|l| l.starts_with(' '))
+        .map(str::trim)
+        .collect()
+}
+
+/// Gems resolved in the GEM (and GIT/PATH) sources: `    name (version)` under `specs:`
+fn dependency_info(lock: &str) -> Option<DependencyInfo> {
+    let spec = Regex::new(r"^    ([A-Za-z0-9_.\-]+) \(([^)]+)\)$").unwrap();
+    let gems: Vec<(String, String)> = lock
+        .lines()
+        .filter_map(|l| spec.captures(l))
+        .map(|c| (c[1].to_string(), c[2].split('-').next().unwrap_or(&c[2]).to_string()))
+        .collect();
+    if gems.is_empty() {
+        return None;
+    }
+    Some(DependencyInfo {
+        count: gems.len(),
+        sample: gems.iter().take(5).map(|(name, _)| name.clone()).collect(),
+        purls: gems.iter().map(|(name, version)| identifiers::package_purl("gem", name, version)).collect(),
+    })
+}
+
+/// Evaluate a Gemfile-style constraint list ("~> 3.2, >= 3.2.1"); a bare
+/// version such as "3.2" matches by prefix
+fn constraint_satisfied(constraint: &str, version: &str) -> bool {
+    constraint.split(',').map(str::trim).all(|clause| {
+        let (op, wanted) = match clause.split_once(' ') {
+            Some((op, wanted)) => (op, wanted.trim()),
+            None => ("=", clause),
+        };
+        match op {
+            // Pessimistic: ~> 3.2 allows 3.x, ~> 3.2.1 allows 3.2.x
+            "~>" if wanted.split('.').count() == 2 => satisfies("^", wanted, version),
+            "~>" => satisfies("~", wanted, version),
+            _ => satisfies(op, wanted, version),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_constraint_satisfied() {
+        assert!(constraint_satisfied("~> 3.2", "3.3.0"));
+        assert!(!constraint_satisfied("~> 3.2.1", "3.3.0"));
+        assert!(constraint_satisfied("~> 3.2, >= 3.2.1", "3.2.4"));
+        assert!(!constraint_satisfied("3.2.2", "3.1.4"));
+        assert!(constraint_satisfied("3.2", "3.2.2"));
+
+        assert_eq!(mri_version("ruby-3.3.0".to_string()).as_deref(), Some("3.3.0"));
+        assert_eq!(mri_version("3.3.0-preview1".to_string()).as_deref(), Some("3.3.0-preview1"));
+        assert_eq!(mri_version("jruby-9.4.5.0".to_string()), None);
+        assert_eq!(lock_ruby("ruby 3.1.4p0 (jruby 9.4.5.0)"), None);
+    }
+
+    #[test]
+    fn test_inspect() {
+        let dir = TempDir::new("ruby");
+        fs::write(dir.join("Gemfile"), "source \"https://rubygems.org\"\n\nruby file: \".ruby-version\"\n\ngem \"rails\", \"~> 7.1\"\n").unwrap();
+        fs::write(dir.join(".ruby-version"), "ruby-3.3.0\n").unwrap();
+        fs::write(
+            dir.join("Gemfile.lock"),
+            "GEM\n  remote: https://rubygems.org/\n  specs:\n    nokogiri (1.16.2-x86_64-linux)\n      racc (~> 1.4)\n    racc (1.7.3)\n    rails (7.1.3)\n\nPLATFORMS\n  x86_64-linux\n\nDEPENDENCIES\n  rails (~> 7.1)\n\nBUNDLED WITH\n   2.5.6\n",
+        )
+        .unwrap();
+
+        let (deps, requirements) = inspect(&dir, Some("3.2.2")).unwrap();
+        let deps = deps.unwrap();
+        assert_eq!(deps.count, 3);
+        assert_eq!(deps.purls[0], "pkg:gem/nokogiri@1.16.2");
+        assert_eq!(requirements.ruby_constraint.as_deref(), Some("3.3.0"));
+        assert_eq!(requirements.ruby_source.as_deref(), Some(".ruby-version"));
+        assert_eq!(requirements.ruby_satisfied, Some(false));
+        assert_eq!(requirements.bundler_locked.as_deref(), Some("2.5.6"));
+    }
+}
@@ -0,0 +1,120 @@
+use crate::core::{exec, paths};
+use crate::types::SecurityFlag;
+use std::fs;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::Path;
+
+const SETUID_BIT: u32 = 0o4000;
+const SETGID_BIT: u32 = 0o2000;
+
+/// First uid handed to regular (human) accounts; macOS starts at 501, Linux at 1000
+const FIRST_REGULAR_UID: u32 = 500;
+
+/// Directories the OS ships its setuid helpers (sudo, passwd, ping) in
+const SYSTEM_PREFIXES: &[&str] = &["/bin", "/sbin", "/usr/bin", "/usr/sbin", "/usr/libexec", "/usr/lib", "/System"];
+
+/// The effective uid of the scan; /proc/self belongs to it. Where there is
+/// no /proc (macOS) the owner of $HOME stands in
+pub fn current_uid() -> Option<u32> {
+    fs::metadata("/proc/self")
+        .ok()
+        .or_else(|| paths::home_dir().and_then(|home| fs::metadata(home).ok()))
+        .map(|m| m.uid())
+}
+
+/// Whether `owner` is a regular account other than the scanning user; system
+/// accounts (root, bin, ...) and the user themselves are expected owners
+pub fn is_other_user(owner: u32, current_uid: Option<u32>) -> bool {
+    owner >= FIRST_REGULAR_UID && Some(owner) != current_uid
+}
+
+/// Flag unusual setuid binaries, setgid binaries and binaries owned by another
+/// regular user, from metadata the caller already has
+pub fn tool_security_flags(path: &Path, metadata: &fs::Metadata, current_uid: Option<u32>) -> Vec<SecurityFlag> {
+    flags_for(path, metadata.permissions().mode(), metadata.uid(), current_uid)
+}
+
+fn flags_for(path: &Path, mode: u32, owner: u32, current_uid: Option<u32>) -> Vec<SecurityFlag> {
+    let mut flags = Vec::new();
+
+    // Root-owned setuid helpers in the system directories are how the OS works
+    let system_setuid = owner == 0 && SYSTEM_PREFIXES.iter().any(|prefix| path.starts_with(prefix));
+    if mode & SETUID_BIT != 0 && !system_setuid {
+        flags.push(SecurityFlag::Setuid { owner });
+    }
+    if mode & SETGID_BIT != 0 {
+        flags.push(SecurityFlag::Setgid);
+    }
+
+    if is_other_user(owner, current_uid) {
+        flags.push(SecurityFlag::UnexpectedOwner { owner });
+    }
+
+    flags
+}
+
+/// On macOS, flag binaries Gatekeeper may block: quarantined downloads and
+/// unsigned or ad-hoc signed code. No-op on other platforms.
+pub fn gatekeeper_flags(path: &Path) -> Vec<SecurityFlag> {
+    if !cfg!(target_os = "macos") {
+        return Vec::new();
+    }
+
+    let path_str = path.to_string_lossy();
+    let mut flags = Vec::new();
+
+    let quarantined = exec::execute_command("xattr", &["-p", "com.apple.quarantine", &path_str])
+        .map(|out| out.status.success())
+        .unwrap_or(false);
+    if quarantined {
+        flags.push(SecurityFlag::Quarantined);
+    }
+
+    // codesign reports signature details on stderr
+    if let Ok(out) = exec::execute_command("codesign", &["-dv", &path_str]) {
+        if let Some(flag) = parse_codesign(&String::from_utf8_lossy(&out.stderr)) {
+            flags.push(flag);
+        }
+    }
+
+    flags
+}
+
+/// Interpret `codesign -dv` output; None means properly signed
+fn parse_codesign(output: &str) -> Option<SecurityFlag> {
+    if output.contains("not signed at all") {
+        Some(SecurityFlag::Unsigned)
+    } else if output.contains("Signature=adhoc") {
+        Some(SecurityFlag::AdhocSigned)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flags_for() {
+        let local = Path::new("/usr/local/bin/tool");
+        assert!(flags_for(local, 0o755, 0, Some(1000)).is_empty());
+        assert!(flags_for(local, 0o755, 1000, Some(1000)).is_empty());
+        assert_eq!(flags_for(local, 0o4755, 0, Some(1000)), vec![SecurityFlag::Setuid { owner: 0 }]);
+        // sudo is expected; a setuid binary in /usr/bin owned by someone else is not
+        assert!(flags_for(Path::new("/usr/bin/sudo"), 0o4755, 0, Some(1000)).is_empty());
+        assert!(!flags_for(Path::new("/usr/binx/sudo"), 0o4755, 0, Some(1000)).is_empty());
+        assert_eq!(flags_for(Path::new("/usr/bin/tool"), 0o4755, 2, Some(1000)), vec![SecurityFlag::Setuid { owner: 2 }]);
+        assert_eq!(
+            flags_for(local, 0o2755, 1001, Some(1000)),
+            vec![SecurityFlag::Setgid, SecurityFlag::UnexpectedOwner { owner: 1001 }]
+        );
+    }
+
+    #[test]
+    fn test_parse_codesign() {
+        assert_eq!(parse_codesign("/usr/local/bin/foo: code object is not signed at all"), Some(SecurityFlag::Unsigned));
+        assert_eq!(parse_codesign("Identifier=foo\nSignature=adhoc\nTeamIdentifier=not set"), Some(SecurityFlag::AdhocSigned));
+        assert_eq!(parse_codesign("Identifier=com.apple.git\nAuthority=Software Signing"), None);
+    }
+}
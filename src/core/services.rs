@@ -0,0 +1,207 @@
+use crate::core::{exec, paths};
+use crate::types::{ServiceCheck, ServiceRequirement};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::net::{SocketAddr, TcpStream};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// A service devfetch knows how to find
+struct KnownService {
+    names: &'static [&'static str],
+    port: Option<u16>,
+    /// Sockets relative to / (absolute) or ~ (starting with "~/")
+    sockets: &'static [&'static str],
+    processes: &'static [&'static str],
+    /// (linux, macos) start commands
+    start: (&'static str, &'static str),
+}
+
+static SERVICES: &[KnownService] = &[
+    KnownService {
+        names: &["docker"],
+        port: None,
+        sockets: &["/var/run/docker.sock", "~/.docker/run/docker.sock", "~/.colima/default/docker.sock", "~/.orbstack/run/docker.sock"],
+        processes: &["dockerd"],
+        start: ("sudo systemctl start docker", "open -a Docker"),
+    },
+    KnownService {
+        names: &["podman"],
+        port: None,
+        sockets: &[],
+        processes: &[],
+        start: ("systemctl --user start podman.socket", "podman machine start"),
+    },
+    KnownService {
+        names: &["postgres", "postgresql"],
+        port: Some(5432),
+        sockets: &["/var/run/postgresql/.s.PGSQL.5432", "/tmp/.s.PGSQL.5432"],
+        processes: &["postgres"],
+        start: ("sudo systemctl start postgresql", "brew services start postgresql"),
+    },
+    KnownService {
+        names: &["mysql", "mariadb"],
+        port: Some(3306),
+        sockets: &["/var/run/mysqld/mysqld.sock", "/tmp/mysql.sock"],
+        processes: &["mysqld", "mariadbd"],
+        start: ("sudo systemctl start mysql", "brew services start mysql"),
+    },
+    KnownService {
+        names: &["redis"],
+        port: Some(6379),
+        sockets: &[],
+        processes: &["redis-server"],
+        start: ("sudo systemctl start redis-server", "brew services start redis"),
+    },
+    KnownService {
+        names: &["mongodb", "mongo"],
+        port: Some(27017),
+        sockets: &["/tmp/mongodb-27017.sock"],
+        processes: &["mongod"],
+        start: ("sudo systemctl start mongod", "brew services start mongodb-community"),
+    },
+    KnownService {
+        names: &["memcached"],
+        port: Some(11211),
+        sockets: &[],
+        processes: &["memcached"],
+        start: ("sudo systemctl start memcached", "brew services start memcached"),
+    },
+    KnownService {
+        names: &["rabbitmq"],
+        port: Some(5672),
+        sockets: &[],
+        processes: &[],
+        start: ("sudo systemctl start rabbitmq-server", "brew services start rabbitmq"),
+    },
+    KnownService {
+        names: &["elasticsearch"],
+        port: Some(9200),
+        sockets: &[],
+        processes: &[],
+        start: ("sudo systemctl start elasticsearch", "brew services start elasticsearch"),
+    },
+    KnownService {
+        names: &["kafka"],
+        port: Some(9092),
+        sockets: &[],
+        processes: &[],
+        start: ("sudo systemctl start kafka", "brew services start kafka"),
+    },
+];
+
+/// Check that each service devfetch.toml lists is running: a socket or
+/// localhost port that accepts a connection, else a matching process
+pub fn check_services(required: &BTreeMap<String, ServiceRequirement>) -> Vec<ServiceCheck> {
+    required
+        .iter()
+        .map(|(name, requirement)| {
+            let known = SERVICES.iter().find(|s| s.names.contains(&name.to_lowercase().as_str()));
+            let sockets = sockets(requirement, known);
+            let port = requirement.port.or(known.and_then(|k| k.port));
+            let processes = if requirement.port.is_some() || requirement.socket.is_some() {
+                &[][..]
+            } else {
+                known.map(|k| k.processes).unwrap_or_default()
+            };
+
+            let via = sockets
+                .iter()
+                .find(|s| UnixStream::connect(s).is_ok())
+                .map(|s| s.display().to_string())
+                .or_else(|| port.filter(|p| port_open(*p)).map(|p| format!("port {}", p)))
+                .or_else(|| processes.iter().find(|p| process_running(p)).map(|p| format!("process {}", p)));
+            let start_hint = requirement.start.clone().or_else(|| {
+                let (linux, macos) = known?.start;
+                match env::consts::OS {
+                    "linux" => Some(linux.to_string()),
+                    "macos" => Some(macos.to_string()),
+                    _ => None,
+                }
+            });
+            ServiceCheck { name: name.clone(), running: via.is_some(), via, start_hint }
+        })
+        .collect()
+}
+
+/// The socket devfetch.toml names, else a known service's default sockets;
+/// none when it overrides the port, since the default socket belongs to
+/// another instance
+fn sockets(requirement: &ServiceRequirement, known: Option<&KnownService>) -> Vec<PathBuf> {
+    match (&requirement.socket, requirement.port) {
+        (Some(socket), _) => vec![paths::expand_tilde(&socket.to_string_lossy())],
+        (None, Some(_)) => Vec::new(),
+        (None, None) => known.map(default_sockets).unwrap_or_default(),
+    }
+}
+
+/// A known service's sockets, plus DOCKER_HOST and the rootless sockets under XDG_RUNTIME_DIR
+fn default_sockets(known: &KnownService) -> Vec<PathBuf> {
+    let mut sockets: Vec<PathBuf> = known.sockets.iter().map(|s| paths::expand_tilde(s)).collect();
+    let runtime = env::var_os("XDG_RUNTIME_DIR").filter(|d| !d.is_empty()).map(PathBuf::from);
+    match known.names[0] {
+        "docker" => {
+            let host = env::var("DOCKER_HOST").ok();
+            sockets.extend(host.as_deref().and_then(|h| h.strip_prefix("unix://")).map(PathBuf::from));
+            sockets.extend(runtime.map(|d| d.join("docker.sock")));
+        }
+        "podman" => {
+            sockets.extend(runtime.map(|d| d.join("podman/podman.sock")));
+            sockets.push(PathBuf::from("/run/podman/podman.sock"));
+        }
+        _ => {}
+    }
+    sockets
+}
+
+fn port_open(port: u16) -> bool {
+    ["127.0.0.1", "[::1]"].iter().any(|host| {
+        format!("{}:{}", host, port)
+            .parse::<SocketAddr>()
+            .is_ok_and(|addr| TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).is_ok())
+    })
+}
+
+/// Whether a process with this exact name runs, from /proc on Linux, else pgrep
+fn process_running(name: &str) -> bool {
+    match fs::read_dir("/proc") {
+        Ok(entries) => entries
+            .flatten()
+            .filter(|e| e.file_name().to_string_lossy().bytes().all(|b| b.is_ascii_digit()))
+            .any(|e| fs::read_to_string(e.path().join("comm")).is_ok_and(|comm| comm.trim() == name)),
+        Err(_) => exec::execute_command("pgrep", &["-x", name]).is_ok_and(|o| o.status.success()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_check_services() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let mut required = BTreeMap::new();
+        required.insert("api".to_string(), ServiceRequirement { port: Some(port), socket: None, start: Some("make api".to_string()) });
+        required.insert("cache".to_string(), ServiceRequirement::default());
+
+        let checks = check_services(&required);
+        assert!(checks[0].running);
+        assert_eq!(checks[0].via, Some(format!("port {}", port)));
+        assert_eq!(checks[0].start_hint.as_deref(), Some("make api"));
+        // Unknown services without a port or socket can't be found
+        assert!(!checks[1].running);
+        assert_eq!(checks[1].start_hint, None);
+
+        // A second postgres on another port is not the one on the default socket
+        let postgres = SERVICES.iter().find(|s| s.names.contains(&"postgres"));
+        let moved = ServiceRequirement { port: Some(5433), ..ServiceRequirement::default() };
+        assert!(sockets(&moved, postgres).is_empty());
+        assert!(!sockets(&ServiceRequirement::default(), postgres).is_empty());
+    }
+}
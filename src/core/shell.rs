@@ -0,0 +1,210 @@
+use crate::core::{exec, paths, probe};
+use crate::types::{ShellInfo, ShellInitHook};
+use anyhow::{anyhow, Result};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Login shells may source heavy rc files, so allow more than the probe timeout
+const LOGIN_SHELL_TIMEOUT_MS: u64 = 5000;
+
+/// Prefix that marks the PATH line amid any rc-file chatter
+const PATH_MARKER: &str = "__DEVFETCH_PATH__=";
+
+/// Shell frameworks and the home-relative directories that reveal them
+static FRAMEWORKS: &[(&str, &str)] = &[
+    ("oh-my-zsh", ".oh-my-zsh"),
+    ("prezto", ".zprezto"),
+    ("oh-my-bash", ".oh-my-bash"),
+    ("bash-it", ".bash_it"),
+    ("oh-my-fish", ".local/share/omf"),
+    ("oh-my-posh", ".cache/oh-my-posh"),
+    ("starship", ".config/starship.toml"),
+];
+
+/// Plugin managers and the home-relative paths that reveal them
+static PLUGIN_MANAGERS: &[(&str, &str)] = &[
+    ("fisher", ".config/fish/functions/fisher.fish"),
+    ("zinit", ".local/share/zinit"),
+    ("zinit", ".zinit"),
+    ("antigen", ".antigen"),
+    ("antidote", ".antidote"),
+    ("zplug", ".zplug"),
+    ("sheldon", ".config/sheldon"),
+    ("znap", ".znap"),
+];
+
+/// Shell startup files that commonly initialize version managers
+pub static RC_FILES: &[&str] = &[
+    ".zshenv", ".zprofile", ".zshrc", ".bash_profile", ".bashrc", ".profile",
+    ".config/fish/config.fish",
+];
+
+/// Snippets that version managers and PATH tools add to rc files
+static INIT_HOOK_PATTERNS: &[(&str, &str)] = &[
+    ("nvm", "nvm.sh"),
+    ("fnm", "fnm env"),
+    ("volta", "VOLTA_HOME"),
+    ("pyenv", "pyenv init"),
+    ("rbenv", "rbenv init"),
+    ("asdf", "asdf.sh"),
+    ("asdf", "asdf.fish"),
+    ("mise", "mise activate"),
+    ("rtx", "rtx activate"),
+    ("sdkman", "sdkman-init.sh"),
+    ("jenv", "jenv init"),
+    ("goenv", "goenv init"),
+    ("conda", "conda initialize"),
+    ("direnv", "direnv hook"),
+    ("homebrew", "brew shellenv"),
+    ("rustup", ".cargo/env"),
+];
+
+/// Report the login shell, its framework/plugin manager, and rc-file init hooks
+pub fn detect_shell() -> Option<ShellInfo> {
+    let shell_path = env::var_os("SHELL").filter(|s| !s.is_empty()).map(PathBuf::from)?;
+    let name = shell_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| shell_path.to_string_lossy().to_string());
+
+    let version = exec::execute_for_output(&shell_path.to_string_lossy(), &["--version"])
+        .and_then(|output| probe::extract_version(&output));
+
+    let home = paths::home_dir();
+    let (frameworks, plugin_managers, init_hooks) = match &home {
+        Some(home) => {
+            let mut frameworks = present_in_home(home, FRAMEWORKS);
+            // $ZSH points at a custom oh-my-zsh checkout
+            if env::var_os("ZSH").is_some() && !frameworks.iter().any(|f| f == "oh-my-zsh") {
+                frameworks.push("oh-my-zsh".to_string());
+            }
+            (frameworks, present_in_home(home, PLUGIN_MANAGERS), find_init_hooks(home))
+        }
+        None => (Vec::new(), Vec::new(), Vec::new()),
+    };
+
+    Some(ShellInfo {
+        path: shell_path,
+        name,
+        version,
+        frameworks,
+        plugin_managers,
+        init_hooks,
+    })
+}
+
+/// Resolve PATH as the user's interactive login shell sees it, so scans
+/// launched from GUI apps, cron or CI match the user's terminal
+pub fn login_shell_path() -> Result<String> {
+    let shell = env::var("SHELL")
+        .ok()
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| anyhow!("SHELL is not set"))?;
+
+    // fish stores PATH as a list
+    let script = if shell.ends_with("fish") {
+        format!("echo {}(string join : $PATH)", PATH_MARKER)
+    } else {
+        format!("echo \"{}$PATH\"", PATH_MARKER)
+    };
+
+    let output = exec::execute_command_with_timeout(
+        &shell,
+        &["-l", "-i", "-c", &script],
+        Duration::from_millis(LOGIN_SHELL_TIMEOUT_MS),
+    )?;
+
+    parse_marked_path(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| anyhow!("{} did not report a PATH", shell))
+}
+
+fn parse_marked_path(output: &str) -> Option<String> {
+    output
+        .lines()
+        .rev()
+        .find_map(|line| line.trim().strip_prefix(PATH_MARKER))
+        .map(String::from)
+        .filter(|p| !p.is_empty())
+}
+
+/// Names whose marker path exists under the home directory (deduplicated)
+fn present_in_home(home: &Path, markers: &[(&str, &str)]) -> Vec<String> {
+    let mut found: Vec<String> = Vec::new();
+    for (name, rel) in markers {
+        if home.join(rel).exists() && !found.iter().any(|f| f == name) {
+            found.push(name.to_string());
+        }
+    }
+    found
+}
+
+/// Scan rc files for version-manager initialization snippets
+fn find_init_hooks(home: &Path) -> Vec<ShellInitHook> {
+    let mut hooks = Vec::new();
+
+    for rc in RC_FILES {
+        let file = home.join(rc);
+        if let Ok(contents) = fs::read_to_string(&file) {
+            for manager in hooks_in_rc(&contents) {
+                hooks.push(ShellInitHook {
+                    manager,
+                    file: file.clone(),
+                });
+            }
+        }
+    }
+
+    hooks
+}
+
+/// Version managers initialized by an rc file's contents (ignoring comments)
+fn hooks_in_rc(contents: &str) -> Vec<String> {
+    let mut managers: Vec<String> = Vec::new();
+
+    for line in contents.lines() {
+        let trimmed = line.trim_start();
+        // conda's block is delimited by a comment, so keep that one
+        if trimmed.starts_with('#') && !trimmed.contains("conda initialize") {
+            continue;
+        }
+
+        for (manager, pattern) in INIT_HOOK_PATTERNS {
+            if line.contains(pattern) && !managers.iter().any(|m| m == manager) {
+                managers.push(manager.to_string());
+            }
+        }
+    }
+
+    managers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hooks_in_rc() {
+        let rc = r#"
+export NVM_DIR="$HOME/.nvm"
+[ -s "$NVM_DIR/nvm.sh" ] && \. "$NVM_DIR/nvm.sh"
+eval "$(pyenv init -)"
+# eval "$(rbenv init -)"
+# >>> conda initialize >>>
+"#;
+        assert_eq!(hooks_in_rc(rc), vec!["nvm", "pyenv", "conda"]);
+    }
+
+    #[test]
+    fn test_parse_marked_path() {
+        let output = "Welcome back!\n__DEVFETCH_PATH__=/usr/local/bin:/usr/bin\n";
+        assert_eq!(parse_marked_path(output), Some("/usr/local/bin:/usr/bin".to_string()));
+        assert_eq!(parse_marked_path("no marker here\n"), None);
+    }
+
+    #[test]
+    fn test_hooks_in_rc_empty() {
+        assert!(hooks_in_rc("alias ll='ls -la'\n").is_empty());
+    }
+}
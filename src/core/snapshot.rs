@@ -0,0 +1,276 @@
+use crate::core::{paths, time};
+use crate::types::{EcosystemDetails, ScanResult, SCAN_FORMAT_VERSION};
+use anyhow::{bail, Context, Result};
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use serde_json::{Map, Value};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Compression for scan files, picked by extension when writing and by the
+/// leading magic bytes when reading
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    Plain,
+    Gzip,
+    Zstd,
+}
+
+impl Codec {
+    /// `.gz` or `.zst`; anything else is written uncompressed
+    pub fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("gz") => Codec::Gzip,
+            Some("zst") => Codec::Zstd,
+            _ => Codec::Plain,
+        }
+    }
+
+    fn detect(bytes: &[u8]) -> Self {
+        match bytes {
+            [0x1f, 0x8b, ..] => Codec::Gzip,
+            [0x28, 0xb5, 0x2f, 0xfd, ..] => Codec::Zstd,
+            _ => Codec::Plain,
+        }
+    }
+
+    /// One gzip member or zstd frame; appended chunks still decompress as a
+    /// whole, which keeps `--format jsonl` logs appendable
+    pub fn compress(self, data: &[u8]) -> Result<Vec<u8>> {
+        Ok(match self {
+            Codec::Plain => data.to_vec(),
+            Codec::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()?
+            }
+            Codec::Zstd => zstd::encode_all(data, 0)?,
+        })
+    }
+
+    fn decompress(self, bytes: Vec<u8>) -> Result<Vec<u8>> {
+        Ok(match self {
+            Codec::Plain => bytes,
+            Codec::Gzip => {
+                let mut data = Vec::new();
+                MultiGzDecoder::new(&bytes[..]).read_to_end(&mut data)?;
+                data
+            }
+            Codec::Zstd => zstd::decode_all(&bytes[..])?,
+        })
+    }
+}
+
+/// A file's text, decompressed when it is gzip or zstd
+pub fn read(file: &Path) -> Result<String> {
+    let bytes = fs::read(file).with_context(|| format!("Failed to read {}", file.display()))?;
+    let data = Codec::detect(&bytes)
+        .decompress(bytes)
+        .with_context(|| format!("Failed to decompress {}", file.display()))?;
+    String::from_utf8(data).with_context(|| format!("{} is not UTF-8 text", file.display()))
+}
+
+/// What `snapshot save` writes, then the other names a saved snapshot may have
+static SNAPSHOT_EXTENSIONS: &[&str] = &["json.gz", "json.zst", "json"];
+
+/// Where `devfetch snapshot save` keeps scans
+pub fn snapshot_dir() -> Option<PathBuf> {
+    paths::data_dir().map(|d| d.join("snapshots"))
+}
+
+/// Store a scan as a gzipped snapshot named `name`, by default today's date
+/// (numbered when there are several a day); an existing one is only replaced with `force`
+pub fn save(result: &ScanResult, name: Option<&str>, force: bool) -> Result<PathBuf> {
+    save_in(&snapshot_dir().context("No home directory for snapshots")?, result, name, force)
+}
+
+fn save_in(dir: &Path, result: &ScanResult, name: Option<&str>, force: bool) -> Result<PathBuf> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let name = match name {
+        Some(name) if name.is_empty() || name.contains(['/', '\\']) => bail!("Invalid snapshot name {:?}", name),
+        Some(name) => {
+            match find(dir, name) {
+                Some(existing) if !force => bail!("Snapshot {:?} already exists at {}; pass --force to replace it", name, existing.display()),
+                // A plain or zstd one of that name would shadow the new file when read back
+                Some(existing) => fs::remove_file(&existing).with_context(|| format!("Failed to remove {}", existing.display()))?,
+                None => {}
+            }
+            name.to_string()
+        }
+        None => {
+            let today = time::today();
+            (1..).map(|n| if n == 1 { today.clone() } else { format!("{}-{}", today, n) }).find(|n| find(dir, n).is_none()).unwrap_or(today)
+        }
+    };
+    let file = dir.join(format!("{}.{}", name, SNAPSHOT_EXTENSIONS[0]));
+    fs::write(&file, Codec::for_path(&file).compress(&serde_json::to_vec(result)?)?)
+        .with_context(|| format!("Failed to write {}", file.display()))?;
+    Ok(file)
+}
+
+fn find(dir: &Path, name: &str) -> Option<PathBuf> {
+    SNAPSHOT_EXTENSIONS.iter().map(|ext| dir.join(format!("{}.{}", name, ext))).find(|f| f.is_file())
+}
+
+/// Names of the saved snapshots, oldest first
+pub fn list() -> Vec<String> {
+    let Some(entries) = snapshot_dir().and_then(|d| fs::read_dir(d).ok()) else {
+        return Vec::new();
+    };
+    let mut snapshots: Vec<(std::time::SystemTime, String)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let name = SNAPSHOT_EXTENSIONS.iter().find_map(|ext| file_name.strip_suffix(&format!(".{}", ext)))?;
+            Some((entry.metadata().and_then(|m| m.modified()).ok()?, name.to_string()))
+        })
+        .collect();
+    snapshots.sort();
+    snapshots.into_iter().map(|(_, name)| name).collect()
+}
+
+/// A saved snapshot by name, else a scan file by path, with the label to show for it
+pub fn open(name_or_file: &str) -> Result<(String, ScanResult)> {
+    let saved = snapshot_dir().and_then(|d| find(&d, name_or_file));
+    match saved {
+        Some(file) => Ok((name_or_file.to_string(), load(&file)?)),
+        None if Path::new(name_or_file).is_file() => Ok((name_or_file.to_string(), load(Path::new(name_or_file))?)),
+        None => bail!("No snapshot or scan file named {:?} (saved snapshots: {})", name_or_file, list().join(", ")),
+    }
+}
+
+/// Upgrades from each layout to the next; entry `n` turns version `n + 1` into `n + 2`
+static MIGRATIONS: &[fn(&mut Value)] = &[ecosystem_details];
+
+/// Read a scan written by `--json`, `-o` or `--push` (plain, gzip or zstd),
+/// upgrading older layouts
+pub fn load(file: &Path) -> Result<ScanResult> {
+    let content = read(file)?;
+    parse(&content).with_context(|| format!("{} is not a devfetch scan", file.display()))
+}
+
+/// Parse a stored scan, migrating it to the current layout first
+pub fn parse(content: &str) -> Result<ScanResult> {
+    let mut scan: Value = serde_json::from_str(content)?;
+    // Scans from before the version was recorded are version 1
+    let version = scan.get("format_version").and_then(Value::as_u64).unwrap_or(1).max(1) as u32;
+    if version > SCAN_FORMAT_VERSION {
+        bail!("written by a newer devfetch (format {}, this one reads up to {})", version, SCAN_FORMAT_VERSION);
+    }
+    for migrate in &MIGRATIONS[version as usize - 1..] {
+        migrate(&mut scan);
+    }
+    if let Some(fields) = scan.as_object_mut() {
+        fields.insert("format_version".to_string(), SCAN_FORMAT_VERSION.into());
+    }
+    Ok(serde_json::from_value(scan)?)
+}
+
+/// 1 → 2: the optional cargo/jvm/php/ruby/python/cxx/package_manager fields
+/// of an ecosystem moved into a `details` object tagged by `kind`
+fn ecosystem_details(scan: &mut Value) {
+    let Some(ecosystems) = scan.pointer_mut("/project_info/ecosystems").and_then(Value::as_object_mut) else {
+        return;
+    };
+    for (name, ecosystem) in ecosystems.iter_mut() {
+        let Some(ecosystem) = ecosystem.as_object_mut() else {
+            continue;
+        };
+        let mut take = |key: &str| match ecosystem.remove(key) {
+            Some(Value::Object(fields)) => Some(fields),
+            _ => None,
+        };
+        let mut details = match serde_json::to_value(EcosystemDetails::for_ecosystem(name)) {
+            Ok(Value::Object(fields)) => fields,
+            _ => Map::new(),
+        };
+        if let Some(mut cargo) = take("cargo") {
+            if let Some(msrv) = cargo.remove("rust_version") {
+                cargo.insert("msrv".to_string(), msrv);
+            }
+            details = tagged("rust", cargo);
+        }
+        for kind in ["jvm", "php", "ruby", "cxx", "python"] {
+            if let Some(fields) = take(kind) {
+                details = tagged(kind, fields);
+            }
+        }
+        if let Some(manager) = take("package_manager") {
+            if matches!(details.get("kind").and_then(Value::as_str), Some("node" | "python")) {
+                details.insert("package_manager".to_string(), Value::Object(manager));
+            }
+        }
+        ecosystem.insert("details".to_string(), Value::Object(details));
+    }
+}
+
+fn tagged(kind: &str, mut fields: Map<String, Value>) -> Map<String, Value> {
+    fields.insert("kind".to_string(), kind.into());
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_parse_migrates() {
+        let v1 = r#"{
+            "hostname": "old-laptop",
+            "global_tools": [],
+            "project_info": {"path": "/src/app", "markers": [], "scripts": [], "ecosystems": {
+                "Rust": {"name": "Rust", "tool_version": "1.78.0", "dependencies": null,
+                         "cargo": {"members": ["app"], "binaries": ["app"], "rust_version": "1.74"}},
+                "Node.js": {"name": "Node.js", "tool_version": null, "dependencies": null,
+                            "package_manager": {"name": "pnpm", "version": null, "source": "pnpm-lock.yaml"}},
+                "Go": {"name": "Go", "tool_version": "1.22", "dependencies": null}
+            }}
+        }"#;
+        let scan = parse(v1).unwrap();
+        assert_eq!(scan.format_version, SCAN_FORMAT_VERSION);
+        let ecosystems = &scan.project_info.unwrap().ecosystems;
+        assert!(matches!(&ecosystems["Rust"].details, EcosystemDetails::Rust(r) if r.msrv.as_deref() == Some("1.74")));
+        assert_eq!(ecosystems["Node.js"].details.package_manager().unwrap().name, "pnpm");
+        assert!(matches!(ecosystems["Go"].details, EcosystemDetails::Generic));
+
+        let current = serde_json::to_string(&ScanResult::new()).unwrap();
+        assert_eq!(parse(&current).unwrap().format_version, SCAN_FORMAT_VERSION);
+        assert!(parse(r#"{"format_version": 99}"#).unwrap_err().to_string().contains("newer devfetch"));
+    }
+
+    #[test]
+    fn test_codecs() {
+        let dir = TempDir::new("snapshot");
+        for name in ["scan.json", "scans.jsonl.gz", "scans.jsonl.zst"] {
+            let file = dir.join(name);
+            let codec = Codec::for_path(&file);
+            let mut bytes = codec.compress(b"{\"a\": 1}\n").unwrap();
+            bytes.extend(codec.compress(b"{\"b\": 2}\n").unwrap());
+            fs::write(&file, bytes).unwrap();
+            assert_eq!(read(&file).unwrap(), "{\"a\": 1}\n{\"b\": 2}\n", "{}", name);
+        }
+        assert_eq!(Codec::for_path(Path::new("scan.json.zst")), Codec::Zstd);
+    }
+
+    #[test]
+    fn test_save_numbers_same_day() {
+        let dir = TempDir::new("snapshots");
+        let mut scan = ScanResult::new();
+        scan.hostname = Some("laptop".to_string());
+        let first = save_in(&dir, &scan, None, false).unwrap();
+        let second = save_in(&dir, &scan, None, false).unwrap();
+        assert_eq!(second.file_name().unwrap().to_string_lossy(), format!("{}-2.json.gz", time::today()));
+        assert_eq!(find(&dir, &time::today()), Some(first));
+        assert_eq!(load(&second).unwrap().hostname.as_deref(), Some("laptop"));
+        assert!(save_in(&dir, &scan, Some("../escape"), false).is_err());
+
+        // A named snapshot is only replaced with --force
+        save_in(&dir, &scan, Some("before"), false).unwrap();
+        assert!(save_in(&dir, &scan, Some("before"), false).unwrap_err().to_string().contains("--force"));
+        scan.hostname = Some("desktop".to_string());
+        let replaced = save_in(&dir, &scan, Some("before"), true).unwrap();
+        assert_eq!(load(&replaced).unwrap().hostname.as_deref(), Some("desktop"));
+    }
+}
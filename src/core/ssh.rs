@@ -0,0 +1,143 @@
+use crate::core::{exec, paths};
+use crate::types::{SshHost, SshReadiness};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+
+/// Whether SSH is ready for the project's git remotes: a running agent with
+/// keys, and a known_hosts entry and usable identity for each remote host.
+/// Only key types and file names are reported, never key material
+pub fn check_ssh(dir: &Path) -> SshReadiness {
+    let agent_running = env::var_os("SSH_AUTH_SOCK").is_some_and(|sock| UnixStream::connect(sock).is_ok());
+    let agent_keys = if agent_running {
+        exec::execute_for_output("ssh-add", &["-l"]).map(|listing| key_types(&listing)).unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    let ssh_dir = paths::home_dir().map(|h| h.join(".ssh"));
+    let mut key_files: Vec<String> = ssh_dir
+        .iter()
+        .flat_map(|d| fs::read_dir(d).into_iter().flatten().flatten())
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .filter(|name| name.starts_with("id_") && !name.ends_with(".pub"))
+        .collect();
+    key_files.sort();
+    let config = ssh_dir.as_ref().and_then(|d| fs::read_to_string(d.join("config")).ok()).unwrap_or_default();
+
+    let remotes = exec::execute_for_output_in(dir, "git", &["remote", "-v"]).unwrap_or_default();
+    let mut by_alias: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (remote, alias) in ssh_remotes(&remotes) {
+        let names = by_alias.entry(alias).or_default();
+        if !names.contains(&remote) {
+            names.push(remote);
+        }
+    }
+
+    let hosts = by_alias
+        .into_iter()
+        .map(|(alias, remotes)| {
+            // `ssh -G` prints the effective config for a host without connecting
+            let effective = exec::execute_for_output("ssh", &["-G", &alias]).unwrap_or_default();
+            let (hostname, port, identities) = parse_effective_config(&effective, &alias);
+            let identity_files = identities
+                .into_iter()
+                .filter(|f| paths::expand_tilde(f).is_file())
+                .map(|f| Path::new(&f).file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or(f))
+                .collect();
+            let lookup = if port == 22 { hostname.clone() } else { format!("[{}]:{}", hostname, port) };
+            let known = ssh_dir.iter().map(|d| d.join("known_hosts")).chain([Path::new("/etc/ssh/ssh_known_hosts").to_path_buf()]).any(|file| {
+                file.is_file() && exec::execute_for_output("ssh-keygen", &["-F", &lookup, "-f", &file.to_string_lossy()]).is_some()
+            });
+            SshHost { configured: host_configured(&config, &alias), alias, hostname, port, remotes, known, identity_files }
+        })
+        .collect();
+
+    SshReadiness { agent_running, agent_keys, key_files, hosts }
+}
+
+/// Key types from `ssh-add -l` lines such as "256 SHA256:... user@host (ED25519)"
+fn key_types(listing: &str) -> Vec<String> {
+    listing
+        .lines()
+        .filter_map(|l| l.trim().strip_suffix(')')?.rsplit_once('(').map(|(_, kind)| kind.to_string()))
+        .collect()
+}
+
+/// (remote name, host or ~/.ssh/config alias) for every ssh:// or scp-style remote
+fn ssh_remotes(git_remote_v: &str) -> Vec<(String, String)> {
+    git_remote_v
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (remote, url) = (fields.next()?, fields.next()?);
+            let host = if let Some(rest) = url.strip_prefix("ssh://").or_else(|| url.strip_prefix("git+ssh://")) {
+                let authority = rest.split('/').next()?;
+                let host = authority.rsplit('@').next()?;
+                host.split(':').next()?.to_string()
+            } else if url.contains("://") || url.starts_with('/') {
+                return None;
+            } else {
+                // user@host:path; a bare "host:path" is scp-style too
+                let (authority, _) = url.split_once(':')?;
+                authority.rsplit('@').next()?.to_string()
+            };
+            Some((remote.to_string(), host))
+        })
+        .collect()
+}
+
+/// hostname, port and identity files from `ssh -G` output
+fn parse_effective_config(effective: &str, alias: &str) -> (String, u16, Vec<String>) {
+    let mut hostname = alias.to_string();
+    let mut port = 22;
+    let mut identities = Vec::new();
+    for (key, value) in effective.lines().filter_map(|l| l.split_once(' ')) {
+        match key {
+            "hostname" => hostname = value.to_string(),
+            "port" => port = value.parse().unwrap_or(22),
+            "identityfile" => identities.push(value.to_string()),
+            _ => {}
+        }
+    }
+    (hostname, port, identities)
+}
+
+/// Whether a `Host` block other than the catch-all `*` names the alias
+fn host_configured(config: &str, alias: &str) -> bool {
+    config.lines().filter_map(|l| l.trim().strip_prefix("Host ").or_else(|| l.trim().strip_prefix("host "))).any(|patterns| {
+        let patterns: Vec<&str> = patterns.split_whitespace().collect();
+        let matches = |p: &str| glob::Pattern::new(p).is_ok_and(|g| g.matches(alias));
+        !patterns.iter().any(|p| p.strip_prefix('!').is_some_and(matches))
+            && patterns.iter().any(|p| *p != "*" && !p.starts_with('!') && matches(p))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ssh_remotes() {
+        let remotes = "origin\tgit@github.com:acme/app.git (fetch)\norigin\tgit@github.com:acme/app.git (push)\n\
+                       work\tssh://git@git.acme.io:2222/team/app.git (fetch)\nmirror\thttps://gitlab.com/acme/app.git (fetch)\n\
+                       alias\twork-gh:acme/app.git (fetch)\n";
+        let hosts: Vec<(String, String)> = ssh_remotes(remotes);
+        let hosts: Vec<(&str, &str)> = hosts.iter().map(|(r, h)| (r.as_str(), h.as_str())).collect();
+        assert_eq!(
+            hosts,
+            vec![("origin", "github.com"), ("origin", "github.com"), ("work", "git.acme.io"), ("alias", "work-gh")]
+        );
+
+        let (hostname, port, ids) = parse_effective_config("user git\nhostname github.com\nport 443\nidentityfile ~/.ssh/id_work\n", "work-gh");
+        assert_eq!((hostname.as_str(), port, ids), ("github.com", 443, vec!["~/.ssh/id_work".to_string()]));
+
+        let config = "Host *\n  AddKeysToAgent yes\nHost work-* !work-old\n  HostName github.com\n";
+        assert!(host_configured(config, "work-gh"));
+        assert!(!host_configured(config, "work-old"));
+        assert!(!host_configured(config, "github.com"));
+        assert_eq!(key_types("256 SHA256:abc me@laptop (ED25519)\n3072 SHA256:def ci (RSA)\n"), vec!["ED25519", "RSA"]);
+    }
+}
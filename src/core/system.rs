@@ -0,0 +1,185 @@
+use crate::core::doctor::compare_versions;
+use crate::core::{check, exec, probe};
+use crate::types::{SystemCheck, SystemCheckKind, SystemRequirements};
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Name of the machine running the scan
+pub fn hostname() -> Option<String> {
+    env::var("HOSTNAME")
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .or_else(|| exec::execute_for_output("hostname", &[]))
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty())
+}
+
+/// Evaluate devfetch.toml's `[system]` requirements against this machine
+pub fn check_requirements(dir: &Path, requirements: &SystemRequirements) -> Vec<SystemCheck> {
+    let mut checks = Vec::new();
+    if let Some(min) = requirements.min_free_disk_gb {
+        let free = free_disk_gib(dir);
+        checks.push(SystemCheck {
+            kind: SystemCheckKind::Disk,
+            required: format!("{} GiB free", min),
+            actual: free.map(|f| format!("{:.1} GiB free", f)),
+            satisfied: free.is_some_and(|f| f >= min),
+        });
+    }
+    if let Some(min) = requirements.min_memory_gb {
+        let total = memory_gib();
+        checks.push(SystemCheck {
+            kind: SystemCheckKind::Memory,
+            required: format!("{} GiB RAM", min),
+            actual: total.map(|t| format!("{:.1} GiB RAM", t)),
+            // Firmware reserves some RAM, so "16" should accept 15.6 GiB reported
+            satisfied: total.is_some_and(|t| t >= min * 0.95),
+        });
+    }
+    if !requirements.os.is_empty() {
+        let systems = os_identities();
+        checks.push(SystemCheck {
+            kind: SystemCheckKind::Os,
+            required: requirements.os.join(" or "),
+            actual: systems.iter().rev().find(|(_, v)| v.is_some()).or(systems.first()).map(|(name, version)| match version {
+                Some(v) => format!("{} {}", name, v),
+                None => name.clone(),
+            }),
+            satisfied: requirements.os.iter().any(|r| os_matches(r, &systems)),
+        });
+    }
+    if let Some(min) = &requirements.min_kernel {
+        let release = kernel_release();
+        checks.push(SystemCheck {
+            kind: SystemCheckKind::Kernel,
+            required: format!(">= {}", min),
+            satisfied: release
+                .as_deref()
+                .and_then(probe::extract_version)
+                .is_some_and(|v| compare_versions(&v, min).is_ge()),
+            actual: release,
+        });
+    }
+    for module in &requirements.kernel_modules {
+        let loaded = module_loaded(module);
+        checks.push(SystemCheck {
+            kind: SystemCheckKind::Module,
+            required: module.clone(),
+            actual: loaded.map(|l| if l { "loaded" } else { "not loaded" }.to_string()),
+            satisfied: loaded == Some(true),
+        });
+    }
+    checks
+}
+
+/// Available space on the filesystem holding `dir`, from POSIX `df -Pk`
+fn free_disk_gib(dir: &Path) -> Option<f64> {
+    let output = exec::execute_for_output("df", &["-Pk", &dir.to_string_lossy()])?;
+    parse_df(&output)
+}
+
+fn parse_df(output: &str) -> Option<f64> {
+    // Filesystem 1024-blocks Used Available Capacity Mounted-on
+    let available: f64 = output.lines().nth(1)?.split_whitespace().nth(3)?.parse().ok()?;
+    Some(available / (1024.0 * 1024.0))
+}
+
+fn memory_gib() -> Option<f64> {
+    let bytes = match fs::read_to_string("/proc/meminfo") {
+        Ok(meminfo) => {
+            let line = meminfo.lines().find(|l| l.starts_with("MemTotal:"))?;
+            line.split_whitespace().nth(1)?.parse::<f64>().ok()? * 1024.0
+        }
+        Err(_) => exec::execute_for_output("sysctl", &["-n", "hw.memsize"])?.trim().parse().ok()?,
+    };
+    Some(bytes / (1024.0 * 1024.0 * 1024.0))
+}
+
+/// Names this system answers to, most general first: the OS family, then on
+/// Linux the distributions in os-release's ID_LIKE and its ID
+fn os_identities() -> Vec<(String, Option<String>)> {
+    let family = env::consts::OS.to_string();
+    if family == "macos" {
+        let version = exec::execute_for_output("sw_vers", &["-productVersion"]).map(|v| v.trim().to_string());
+        return vec![(family, version)];
+    }
+    let mut systems = vec![(family, None)];
+    let release = fs::read_to_string("/etc/os-release").unwrap_or_default();
+    let field = |key: &str| {
+        release
+            .lines()
+            .find_map(|l| l.strip_prefix(key)?.strip_prefix('='))
+            .map(|v| v.trim().trim_matches('"').to_lowercase())
+    };
+    for like in field("ID_LIKE").unwrap_or_default().split_whitespace() {
+        systems.push((like.to_string(), None));
+    }
+    if let Some(id) = field("ID") {
+        systems.push((id, field("VERSION_ID")));
+    }
+    systems
+}
+
+/// Whether any identity meets an `os` entry such as "ubuntu>=22.04" or "linux"
+fn os_matches(requirement: &str, systems: &[(String, Option<String>)]) -> bool {
+    let Ok((name, constraint)) = check::parse_constraint(requirement) else {
+        return false;
+    };
+    systems.iter().any(|(system, version)| {
+        system.eq_ignore_ascii_case(&name)
+            && match (&constraint, version) {
+                (None, _) => true,
+                (Some((op, wanted)), Some(actual)) => check::satisfies(op, wanted, actual),
+                (Some(_), None) => false,
+            }
+    })
+}
+
+fn kernel_release() -> Option<String> {
+    fs::read_to_string("/proc/sys/kernel/osrelease")
+        .ok()
+        .or_else(|| exec::execute_for_output("uname", &["-r"]))
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty())
+}
+
+/// Loaded or built-in modules appear under /sys/module (with `-` written as `_`);
+/// None off Linux, where there is nothing to check against
+fn module_loaded(name: &str) -> Option<bool> {
+    if env::consts::OS != "linux" {
+        return None;
+    }
+    let name = name.replace('-', "_");
+    let listed = fs::read_to_string("/proc/modules")
+        .is_ok_and(|modules| modules.lines().any(|l| l.split(' ').next() == Some(name.as_str())));
+    Some(listed || Path::new("/sys/module").join(&name).is_dir())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_df() {
+        let output = "Filesystem     1024-blocks      Used Available Capacity Mounted on\n/dev/nvme0n1p2   490617784 201234560 264382000      44% /\n";
+        let free = parse_df(output).unwrap();
+        assert!((free - 252.13).abs() < 0.01);
+        assert_eq!(parse_df("df: /nope: No such file or directory\n"), None);
+    }
+
+    #[test]
+    fn test_os_matches() {
+        let systems = vec![
+            ("linux".to_string(), None),
+            ("debian".to_string(), None),
+            ("ubuntu".to_string(), Some("22.04".to_string())),
+        ];
+        assert!(os_matches("linux", &systems));
+        assert!(os_matches("ubuntu>=22.04", &systems));
+        assert!(os_matches("Debian", &systems));
+        assert!(!os_matches("ubuntu>=24.04", &systems));
+        assert!(!os_matches("debian>=12", &systems));
+        assert!(!os_matches("macos", &systems));
+    }
+}
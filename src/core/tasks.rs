@@ -0,0 +1,307 @@
+use crate::core::exec;
+use crate::types::ProjectScript;
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+/// Makefile names GNU make looks for, in its search order
+static MAKEFILES: &[&str] = &["GNUmakefile", "makefile", "Makefile"];
+static JUSTFILES: &[&str] = &["justfile", "Justfile", ".justfile"];
+static TASKFILES: &[&str] = &["Taskfile.yml", "Taskfile.yaml", "taskfile.yml", "taskfile.yaml"];
+
+/// An entry point name and its doc comment
+type Entry = (String, Option<String>);
+
+/// Collect the project's runnable entry points: package.json scripts, Make
+/// targets, just recipes and Taskfile tasks
+pub fn detect_entry_points(path: &Path) -> Vec<ProjectScript> {
+    let mut scripts = npm_scripts(path);
+    scripts.extend(file_entries(path, MAKEFILES, "make", parse_make_targets));
+    scripts.extend(file_entries(path, JUSTFILES, "just", parse_just_recipes));
+    scripts.extend(file_entries(path, TASKFILES, "task", parse_taskfile_tasks));
+    scripts
+}
+
+/// Entries parsed from the first of `names` that exists, run as `<runner> <name>`
+fn file_entries(
+    path: &Path,
+    names: &[&str],
+    runner: &str,
+    parse: fn(&str) -> Vec<Entry>,
+) -> Vec<ProjectScript> {
+    let Some((file, content)) = names
+        .iter()
+        .find_map(|name| fs::read_to_string(path.join(name)).ok().map(|c| (*name, c)))
+    else {
+        return Vec::new();
+    };
+
+    let runner_missing = !exec::command_exists(runner);
+    parse(&content)
+        .into_iter()
+        .map(|(name, description)| ProjectScript {
+            run: format!("{} {}", runner, name),
+            name,
+            source: file.to_string(),
+            command: None,
+            description,
+            runner_missing,
+        })
+        .collect()
+}
+
+/// Read the `scripts` section of package.json, with the command to run each
+/// through the package manager the project's lockfile points to
+fn npm_scripts(path: &Path) -> Vec<ProjectScript> {
+    let Some(manifest) = fs::read_to_string(path.join("package.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+    else {
+        return Vec::new();
+    };
+    let Some(scripts) = manifest.get("scripts").and_then(|s| s.as_object()) else {
+        return Vec::new();
+    };
+
+    let runner = if path.join("yarn.lock").exists() {
+        "yarn"
+    } else if path.join("pnpm-lock.yaml").exists() {
+        "pnpm"
+    } else if path.join("bun.lockb").exists() || path.join("bun.lock").exists() {
+        "bun run"
+    } else {
+        "npm run"
+    };
+    let runner_missing = !exec::command_exists(runner.split(' ').next().unwrap_or(runner));
+
+    scripts
+        .iter()
+        .filter_map(|(name, command)| {
+            Some(ProjectScript {
+                name: name.clone(),
+                source: "package.json".to_string(),
+                command: Some(command.as_str()?.to_string()),
+                description: None,
+                run: format!("{} {}", runner, name),
+                runner_missing,
+            })
+        })
+        .collect()
+}
+
+/// Explicit targets with their `##` docs, either trailing (`build: ## Build it`)
+/// or on the line above. Pattern rules, variables and special targets are skipped.
+fn parse_make_targets(content: &str) -> Vec<Entry> {
+    let rule = Regex::new(r"^([A-Za-z0-9][A-Za-z0-9_./ -]*?)\s*::?([^=]*)$").unwrap();
+    let mut targets: Vec<Entry> = Vec::new();
+    let mut pending_doc: Option<String> = None;
+
+    for line in content.lines() {
+        if let Some(doc) = line.trim_start().strip_prefix("##") {
+            pending_doc = Some(doc.trim().to_string());
+            continue;
+        }
+        if line.starts_with('\t') || line.trim().is_empty() {
+            pending_doc = None;
+            continue;
+        }
+
+        let Some(caps) = rule.captures(line) else {
+            pending_doc = None;
+            continue;
+        };
+        let trailing_doc = caps[2].split_once("##").map(|(_, doc)| doc.trim().to_string());
+        let description = trailing_doc.or(pending_doc.take()).filter(|d| !d.is_empty());
+
+        for name in caps[1].split_whitespace() {
+            if !targets.iter().any(|(existing, _)| existing == name) {
+                targets.push((name.to_string(), description.clone()));
+            }
+        }
+    }
+    targets
+}
+
+/// Public recipes with the `#` comment above them; `_`-prefixed and
+/// [private] recipes, settings, aliases and assignments are skipped
+fn parse_just_recipes(content: &str) -> Vec<Entry> {
+    let recipe = Regex::new(r"^@?([A-Za-z_][A-Za-z0-9_-]*)[^:]*?:([^=]|$)").unwrap();
+    let mut recipes = Vec::new();
+    let mut pending_doc: Option<String> = None;
+    let mut private = false;
+
+    for line in content.lines() {
+        if line.starts_with(char::is_whitespace) || line.trim().is_empty() {
+            pending_doc = None;
+            private = false;
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            pending_doc = Some(comment.trim().to_string());
+            continue;
+        }
+        if line.starts_with('[') {
+            private |= line.contains("private");
+            continue;
+        }
+        let keyword = line.split_whitespace().next().unwrap_or("");
+        if ["set", "alias", "export", "import", "mod"].contains(&keyword) {
+            pending_doc = None;
+            continue;
+        }
+
+        if let Some(caps) = recipe.captures(line) {
+            let name = caps[1].to_string();
+            if !name.starts_with('_') && !private {
+                recipes.push((name, pending_doc.take().filter(|d| !d.is_empty())));
+            }
+        }
+        pending_doc = None;
+        private = false;
+    }
+    recipes
+}
+
+/// Task names under the top-level `tasks:` key with their `desc:`; tasks
+/// marked `internal: true` are skipped
+fn parse_taskfile_tasks(content: &str) -> Vec<Entry> {
+    let mut tasks: Vec<(String, Option<String>, bool)> = Vec::new();
+    let mut in_tasks = false;
+    let mut task_indent: Option<usize> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        if indent == 0 {
+            in_tasks = trimmed == "tasks:";
+            task_indent = None;
+            continue;
+        }
+        if !in_tasks {
+            continue;
+        }
+
+        let indent_of_task = *task_indent.get_or_insert(indent);
+        if indent == indent_of_task {
+            if let Some(name) = trimmed.strip_suffix(':').or_else(|| trimmed.split_once(':').map(|(n, _)| n)) {
+                tasks.push((name.trim_matches(['"', '\'']).to_string(), None, false));
+            }
+        } else if indent > indent_of_task {
+            if let Some(task) = tasks.last_mut() {
+                if let Some(desc) = trimmed.strip_prefix("desc:") {
+                    task.1 = Some(desc.trim().trim_matches(['"', '\'']).to_string());
+                } else if trimmed.replace(' ', "") == "internal:true" {
+                    task.2 = true;
+                }
+            }
+        }
+    }
+
+    tasks
+        .into_iter()
+        .filter(|(_, _, internal)| !internal)
+        .map(|(name, desc, _)| (name, desc))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_npm_scripts() {
+        let dir = TempDir::new("tasks");
+        fs::write(dir.join("package.json"), r#"{"scripts": {"dev": "vite", "test": "vitest run"}}"#).unwrap();
+        fs::write(dir.join("yarn.lock"), "").unwrap();
+
+        let scripts = npm_scripts(&dir);
+        assert_eq!(scripts.len(), 2);
+        assert_eq!(scripts[0].name, "dev");
+        assert_eq!(scripts[1].command.as_deref(), Some("vitest run"));
+        assert_eq!(scripts[1].run, "yarn test");
+    }
+
+    #[test]
+    fn test_parse_just_and_task() {
+        let justfile = "\
+set dotenv-load
+alias b := build
+version := \"1.0\"
+
+# Build the project
+build target='debug':
+    cargo build
+
+[private]
+setup:
+    ./setup.sh
+
+_helper:
+    echo hi
+
+test: build
+    cargo test
+";
+        assert_eq!(
+            parse_just_recipes(justfile),
+            vec![("build".to_string(), Some("Build the project".to_string())), ("test".to_string(), None)]
+        );
+
+        let taskfile = "\
+version: '3'
+
+tasks:
+  build:
+    desc: Build the app
+    cmds:
+      - go build ./...
+  lint:
+    cmds:
+      - golangci-lint run
+  gen:
+    internal: true
+    cmds:
+      - go generate
+";
+        assert_eq!(
+            parse_taskfile_tasks(taskfile),
+            vec![("build".to_string(), Some("Build the app".to_string())), ("lint".to_string(), None)]
+        );
+    }
+
+    #[test]
+    fn test_parse_make_targets() {
+        let makefile = "\
+CC := gcc
+.PHONY: build test
+
+## Compile the binary
+build: main.o
+\t$(CC) -o app main.o
+
+test: build ## Run the test suite
+\t./app --test
+
+%.o: %.c
+\t$(CC) -c $<
+
+install uninstall:
+\t./install.sh
+";
+        let targets = parse_make_targets(makefile);
+        let expected = [
+            ("build", Some("Compile the binary")),
+            ("test", Some("Run the test suite")),
+            ("install", None),
+            ("uninstall", None),
+        ];
+        assert_eq!(
+            targets,
+            expected.iter().map(|(n, d)| (n.to_string(), d.map(String::from))).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// A scratch directory for a test's fixture files, removed when dropped, so a
+/// failing assertion doesn't leave it behind for the next run to trip over
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// An empty `devfetch-<label>-<pid>` under the system temp directory
+    pub fn new(label: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("devfetch-{}-{}", label, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        TempDir(dir)
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_temp_dir_removed_on_drop() {
+        let dir = TempDir::new("test-util");
+        fs::write(dir.join("file"), "stale").unwrap();
+        let path = dir.to_path_buf();
+        drop(dir);
+        assert!(!path.exists());
+        assert_eq!(fs::read_dir(&*TempDir::new("test-util")).unwrap().count(), 0);
+    }
+}
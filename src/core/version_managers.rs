@@ -0,0 +1,336 @@
+use crate::core::{doctor, exec, paths, requirements};
+use crate::types::{ManagedTool, VersionManager};
+use serde_json::Value;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long a rustup query may take; a toolchain install it triggers is not waited for
+const RUSTUP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Managers that pick a version the way pyenv does: env var, nearest version
+/// file, then `<root>/version`
+/// (manager, tool, binary, root env var, default root, version env var, version file)
+static FILE_MANAGERS: &[(&str, &str, &str, &str, &str, &str, &str)] = &[
+    ("pyenv", "python", "python", "PYENV_ROOT", ".pyenv", "PYENV_VERSION", ".python-version"),
+    ("rbenv", "ruby", "ruby", "RBENV_ROOT", ".rbenv", "RBENV_VERSION", ".ruby-version"),
+];
+
+/// Version managers installed for the current user, with every version they
+/// hold and the one each selects in `dir`
+pub fn detect_version_managers(dir: &Path) -> Vec<VersionManager> {
+    let home = paths::home_dir();
+    let root = |var: &str, rel: &str| {
+        env::var_os(var)
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| home.as_ref().map(|h| h.join(rel)))
+            .filter(|r| r.is_dir())
+    };
+
+    let mut managers = Vec::new();
+    if let Some(root) = root("ASDF_DATA_DIR", ".asdf") {
+        managers.push(asdf(root, dir, home.as_deref()));
+    }
+    if let Some(root) = root("NVM_DIR", ".nvm") {
+        managers.push(nvm(root, dir));
+    }
+    for (name, tool, binary, root_var, default_root, version_var, file) in FILE_MANAGERS {
+        if let Some(root) = root(root_var, default_root) {
+            let selected = env::var(version_var).ok().filter(|v| !v.is_empty());
+            let pick = selected.map(|v| (v, version_var.to_string()));
+            managers.push(file_manager(name, tool, binary, root, dir, pick, file));
+        }
+    }
+    if exec::command_exists("rustup") {
+        let root = root("RUSTUP_HOME", ".rustup")
+            .or_else(|| exec::execute_for_output_or_timeout("rustup", &["show", "home"], RUSTUP_TIMEOUT).ok()?.map(PathBuf::from))
+            .unwrap_or_default();
+        managers.push(rustup(root, dir));
+    }
+    if let Some(root) = root("SDKMAN_DIR", ".sdkman") {
+        managers.push(sdkman(root));
+    }
+    if let Some(root) = root("VOLTA_HOME", ".volta") {
+        managers.push(volta(root, dir));
+    }
+    managers
+}
+
+/// One entry per plugin in `installs/`; versions come from ASDF_<TOOL>_VERSION
+/// or the nearest .tool-versions naming the tool
+fn asdf(root: PathBuf, dir: &Path, home: Option<&Path>) -> VersionManager {
+    let files: Vec<PathBuf> = dir.ancestors().chain(home).map(|d| d.join(".tool-versions")).collect();
+    let tools = subdirs(&root.join("installs"))
+        .into_iter()
+        .map(|tool| {
+            let var = format!("ASDF_{}_VERSION", tool.to_uppercase().replace('-', "_"));
+            let (active, reason) = match env::var(&var).ok().filter(|v| !v.is_empty()) {
+                Some(version) => (Some(version), Some(var)),
+                None => files
+                    .iter()
+                    .find_map(|file| Some((tool_versions_entry(file, &tool)?, file.display().to_string())))
+                    .map_or((None, None), |(v, file)| (Some(v), Some(file))),
+            };
+            ManagedTool {
+                installed: versions(&root.join("installs").join(&tool)),
+                on_path: on_path(doctor::binary_name(&tool), &root),
+                tool,
+                active,
+                reason,
+            }
+        })
+        .collect();
+    VersionManager { name: "asdf".to_string(), root, tools }
+}
+
+/// nvm has no shims: the active node is whichever version dir is on PATH,
+/// otherwise the nearest .nvmrc (what `nvm use` picks), otherwise the
+/// `default` alias new shells get
+fn nvm(root: PathBuf, dir: &Path) -> VersionManager {
+    let versions_dir = root.join("versions/node");
+    let current = which::which("node").ok().and_then(|node| {
+        let version = node.strip_prefix(&versions_dir).ok()?.components().next()?;
+        Some(version.as_os_str().to_string_lossy().into_owned())
+    });
+    let nvmrc = dir.ancestors().map(|d| d.join(".nvmrc")).find(|f| f.is_file()).and_then(|file| {
+        Some((first_word(&file)?, file.display().to_string()))
+    });
+    let (active, reason) = match (&current, nvmrc) {
+        // An .nvmrc naming the version on PATH is why it is there
+        (Some(version), Some((wanted, file))) if version.trim_start_matches('v') == wanted.trim_start_matches('v') => {
+            (Some(version.clone()), file)
+        }
+        (Some(version), _) => (Some(version.clone()), "PATH".to_string()),
+        (None, Some((wanted, file))) => (Some(wanted), file),
+        (None, None) => (first_word(&root.join("alias/default")), "nvm alias default".to_string()),
+    };
+    let tool = ManagedTool {
+        tool: "node".to_string(),
+        installed: versions(&versions_dir),
+        reason: active.is_some().then_some(reason),
+        active,
+        on_path: current.is_some(),
+    };
+    VersionManager { name: "nvm".to_string(), root, tools: vec![tool] }
+}
+
+fn file_manager(
+    name: &str,
+    tool: &str,
+    binary: &str,
+    root: PathBuf,
+    dir: &Path,
+    from_env: Option<(String, String)>,
+    version_file: &str,
+) -> VersionManager {
+    let global = root.join("version");
+    let (active, reason) = from_env
+        .or_else(|| {
+            let file = dir.ancestors().map(|d| d.join(version_file)).find(|f| f.is_file())?;
+            Some((first_word(&file)?, file.display().to_string()))
+        })
+        .or_else(|| Some((first_word(&global)?, global.display().to_string())))
+        .unwrap_or_else(|| ("system".to_string(), "default".to_string()));
+    let tool = ManagedTool {
+        tool: tool.to_string(),
+        installed: versions(&root.join("versions")),
+        active: Some(active),
+        reason: Some(reason),
+        on_path: on_path(binary, &root),
+    };
+    VersionManager { name: name.to_string(), root, tools: vec![tool] }
+}
+
+/// `rustup toolchain list`, and `rustup show active-toolchain` run in the
+/// project so directory overrides and rust-toolchain.toml apply
+fn rustup(root: PathBuf, dir: &Path) -> VersionManager {
+    let installed = exec::execute_for_output_or_timeout("rustup", &["toolchain", "list"], RUSTUP_TIMEOUT)
+        .ok()
+        .flatten()
+        .map(|out| out.lines().filter_map(|l| l.split_whitespace().next()).map(String::from).collect())
+        .unwrap_or_default();
+    let (active, reason) = exec::execute_for_output_in_with_timeout(dir, "rustup", &["show", "active-toolchain"], RUSTUP_TIMEOUT)
+        .and_then(|out| parse_active_toolchain(&out))
+        .map_or((None, None), |(name, reason)| (Some(name), reason));
+    // rustc on PATH is a rustup proxy when it sits next to rustup itself
+    let proxies = which::which("rustup").ok().and_then(|r| Some(r.parent()?.to_path_buf()));
+    let tool = ManagedTool {
+        tool: "rust".to_string(),
+        installed,
+        active,
+        reason,
+        on_path: proxies.is_some_and(|dir| on_path("rustc", &dir)),
+    };
+    VersionManager { name: "rustup".to_string(), root, tools: vec![tool] }
+}
+
+/// "stable-x86_64-unknown-linux-gnu (overridden by '/src/rust-toolchain.toml')"
+fn parse_active_toolchain(output: &str) -> Option<(String, Option<String>)> {
+    let line = output.lines().find(|l| !l.trim().is_empty())?.trim();
+    match line.split_once(" (") {
+        Some((name, reason)) => Some((name.to_string(), Some(reason.trim_end_matches(')').to_string()))),
+        None => Some((line.to_string(), None)),
+    }
+}
+
+/// Every candidate in `candidates/`; `current` links to the default version
+fn sdkman(root: PathBuf) -> VersionManager {
+    let tools = subdirs(&root.join("candidates"))
+        .into_iter()
+        .map(|tool| {
+            let candidate = root.join("candidates").join(&tool);
+            let active = fs::read_link(candidate.join("current"))
+                .ok()
+                .and_then(|target| Some(target.file_name()?.to_string_lossy().into_owned()));
+            let binary = match tool.as_str() {
+                "maven" => "mvn",
+                "springboot" => "spring",
+                other => other,
+            };
+            ManagedTool {
+                installed: versions(&candidate).into_iter().filter(|v| v != "current").collect(),
+                reason: active.is_some().then(|| "sdk default".to_string()),
+                active,
+                on_path: on_path(binary, &candidate),
+                tool,
+            }
+        })
+        .collect();
+    VersionManager { name: "sdkman".to_string(), root, tools }
+}
+
+/// Tools in `tools/image/`; a package.json `volta` pin beats the user default
+fn volta(root: PathBuf, dir: &Path) -> VersionManager {
+    let pin = dir.ancestors().map(|d| d.join("package.json")).find_map(|file| {
+        let pins = read_json(&file)?.get("volta")?.clone();
+        Some((pins, file.display().to_string()))
+    });
+    let platform = read_json(&root.join("tools/user/platform.json"));
+    let tools = subdirs(&root.join("tools/image"))
+        .into_iter()
+        .map(|tool| {
+            let pinned = pin.as_ref().and_then(|(pins, file)| Some((pins.get(&tool)?.as_str()?.to_string(), file.clone())));
+            let default = platform.as_ref().and_then(|p| {
+                let version = match tool.as_str() {
+                    "node" => p.get("node")?.get("runtime")?,
+                    "npm" => p.get("node")?.get("npm")?,
+                    other => p.get(other)?,
+                };
+                Some((version.as_str()?.to_string(), "volta default".to_string()))
+            });
+            let (active, reason) = pinned.or(default).map_or((None, None), |(v, r)| (Some(v), Some(r)));
+            ManagedTool {
+                installed: versions(&root.join("tools/image").join(&tool)),
+                on_path: on_path(&tool, &root),
+                tool,
+                active,
+                reason,
+            }
+        })
+        .collect();
+    VersionManager { name: "volta".to_string(), root, tools }
+}
+
+/// Version directories, oldest first
+fn versions(dir: &Path) -> Vec<String> {
+    let mut versions = subdirs(dir);
+    versions.sort_by(|a, b| doctor::compare_versions(a, b).then_with(|| a.cmp(b)));
+    versions
+}
+
+fn subdirs(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .filter(|name| !name.starts_with('.'))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Whether the first `binary` on PATH lives under `prefix` (a shim, proxy or installed version)
+fn on_path(binary: &str, prefix: &Path) -> bool {
+    which::which(binary).is_ok_and(|path| path.starts_with(prefix))
+}
+
+/// First version in a version file, skipping comments
+fn first_word(file: &Path) -> Option<String> {
+    let content = fs::read_to_string(file).ok()?;
+    content
+        .lines()
+        .map(|l| l.split('#').next().unwrap_or("").trim())
+        .find_map(|l| l.split_whitespace().next())
+        .map(String::from)
+}
+
+/// The first version a .tool-versions file lists for `tool`
+fn tool_versions_entry(file: &Path, tool: &str) -> Option<String> {
+    let content = fs::read_to_string(file).ok()?;
+    requirements::parse_tool_versions(&content)
+        .into_iter()
+        .find(|(name, _)| *name == tool)
+        .map(|(_, version)| version.to_string())
+}
+
+fn read_json(file: &Path) -> Option<Value> {
+    serde_json::from_str(&fs::read_to_string(file).ok()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_file_manager_selection() {
+        let dir = TempDir::new("version-managers");
+        let root = dir.join("pyenv");
+        for version in ["3.9.18", "3.12.1", "3.11.7"] {
+            fs::create_dir_all(root.join("versions").join(version)).unwrap();
+        }
+        fs::create_dir_all(dir.join("project/src")).unwrap();
+        fs::write(root.join("version"), "3.11.7\n").unwrap();
+        let project = dir.join("project/src");
+
+        let pick = |env| file_manager("pyenv", "python", "python", root.clone(), &project, env, ".python-version").tools.remove(0);
+        let global = pick(None);
+        assert_eq!(global.installed, ["3.9.18", "3.11.7", "3.12.1"]);
+        assert_eq!((global.active.as_deref(), global.reason), (Some("3.11.7"), Some(root.join("version").display().to_string())));
+
+        fs::write(dir.join("project/.python-version"), "# pinned\n3.12.1 3.11.7\n").unwrap();
+        assert_eq!(pick(None).active.as_deref(), Some("3.12.1"));
+        let from_env = pick(Some(("3.9.18".to_string(), "PYENV_VERSION".to_string())));
+        assert_eq!((from_env.active.as_deref(), from_env.reason.as_deref()), (Some("3.9.18"), Some("PYENV_VERSION")));
+    }
+
+    #[test]
+    fn test_asdf_and_toolchain_parsing() {
+        let dir = TempDir::new("asdf");
+        fs::create_dir_all(dir.join("asdf/installs/nodejs/20.11.1")).unwrap();
+        fs::create_dir_all(dir.join("asdf/installs/nodejs/18.19.0")).unwrap();
+        fs::create_dir_all(dir.join("app")).unwrap();
+        fs::write(dir.join(".tool-versions"), "python 3.12.1\nnodejs 20.11.1 18.19.0 # fallback\n").unwrap();
+
+        let asdf = asdf(dir.join("asdf"), &dir.join("app"), None);
+        let node = &asdf.tools[0];
+        assert_eq!((node.tool.as_str(), node.installed.clone()), ("nodejs", vec!["18.19.0".to_string(), "20.11.1".to_string()]));
+        assert_eq!(node.active.as_deref(), Some("20.11.1"));
+        assert_eq!(node.reason, Some(dir.join(".tool-versions").display().to_string()));
+
+        // No nvm node on PATH: the project's .nvmrc is what `nvm use` selects
+        fs::create_dir_all(dir.join("nvm/versions/node/v20.11.1")).unwrap();
+        fs::write(dir.join(".nvmrc"), "v20.11.1\n").unwrap();
+        let node = &nvm(dir.join("nvm"), &dir.join("app")).tools[0];
+        assert_eq!(node.active.as_deref(), Some("v20.11.1"));
+        assert_eq!(node.reason, Some(dir.join(".nvmrc").display().to_string()));
+
+        let (name, reason) = parse_active_toolchain("1.75-x86_64-unknown-linux-gnu (overridden by '/src/rust-toolchain.toml')\n").unwrap();
+        assert_eq!((name.as_str(), reason.as_deref()), ("1.75-x86_64-unknown-linux-gnu", Some("overridden by '/src/rust-toolchain.toml'")));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::core::{exec, probe};
+use crate::types::VirtTool;
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Virtualization and emulation frontends worth reporting
+static VIRT_TOOLS: &[&str] = &[
+    "qemu-img", "limactl", "colima", "utmctl", "VBoxManage", "vfkit", "tart",
+];
+
+/// UTM ships its CLI inside the app bundle rather than on PATH
+const UTM_BUNDLE_CTL: &str = "/Applications/UTM.app/Contents/MacOS/utmctl";
+
+/// Detect emulator and virtualization tooling, optionally listing running instances
+pub fn detect_virt_tools(check_status: bool) -> Vec<VirtTool> {
+    let mut found: Vec<(String, PathBuf)> = VIRT_TOOLS
+        .iter()
+        .filter_map(|name| which::which(name).ok().map(|p| (name.to_string(), p)))
+        .collect();
+
+    if !found.iter().any(|(name, _)| name == "utmctl") && Path::new(UTM_BUNDLE_CTL).exists() {
+        found.push(("utmctl".to_string(), PathBuf::from(UTM_BUNDLE_CTL)));
+    }
+
+    found.extend(qemu_system_binaries());
+
+    let mut tools: Vec<VirtTool> = found
+        .into_iter()
+        .map(|(name, path)| {
+            let path_str = path.to_string_lossy().to_string();
+            let version = probe::probe_version(&path_str).version;
+            let running = if check_status {
+                running_instances(&name, &path_str)
+            } else {
+                None
+            };
+
+            VirtTool {
+                name,
+                path,
+                version,
+                running,
+            }
+        })
+        .collect();
+
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+    tools
+}
+
+/// Find qemu-system-<arch> emulators in PATH
+fn qemu_system_binaries() -> Vec<(String, PathBuf)> {
+    let path_var = match env::var("PATH") {
+        Ok(p) => p,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut seen = BTreeSet::new();
+    let mut binaries = Vec::new();
+    for dir in env::split_paths(&path_var) {
+        if let Ok(entries) = fs::read_dir(&dir) {
+            for entry in entries.flatten() {
+                if let Some(name) = entry.file_name().to_str() {
+                    if name.starts_with("qemu-system-") && seen.insert(name.to_string()) {
+                        binaries.push((name.to_string(), entry.path()));
+                    }
+                }
+            }
+        }
+    }
+    binaries
+}
+
+/// Ask a virtualization frontend which of its VMs are running.
+/// Returns None when the tool has no status command we understand.
+fn running_instances(name: &str, path: &str) -> Option<Vec<String>> {
+    match name {
+        "limactl" => exec::execute_for_output(path, &["list", "--format", "{{.Name}} {{.Status}}"])
+            .map(|out| parse_status_table(&out, 0, "Running")),
+        "colima" => exec::execute_for_output(path, &["list", "--json"])
+            .map(|out| parse_colima_list(&out)),
+        "VBoxManage" => exec::execute_for_output(path, &["list", "runningvms"])
+            .map(|out| parse_vbox_running(&out)),
+        "utmctl" => exec::execute_for_output(path, &["list"])
+            .map(|out| parse_status_table(&out, 2, "started")),
+        "tart" => exec::execute_for_output(path, &["list"])
+            .map(|out| parse_status_table(&out, 1, "running")),
+        _ => None,
+    }
+}
+
+/// Pick the name column from whitespace-separated rows whose state matches
+fn parse_status_table(output: &str, name_col: usize, state: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter(|line| line.split_whitespace().any(|col| col.eq_ignore_ascii_case(state)))
+        .filter_map(|line| line.split_whitespace().nth(name_col).map(String::from))
+        .collect()
+}
+
+/// `colima list --json` prints one JSON object per line
+fn parse_colima_list(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|v| v.get("status").and_then(|s| s.as_str()) == Some("Running"))
+        .filter_map(|v| v.get("name").and_then(|n| n.as_str()).map(String::from))
+        .collect()
+}
+
+/// `VBoxManage list runningvms` prints `"name" {uuid}` per line
+fn parse_vbox_running(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.split('"').nth(1).map(String::from))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status_table() {
+        let lima = "default Running\ndocker Stopped\n";
+        assert_eq!(parse_status_table(lima, 0, "Running"), vec!["default"]);
+
+        let utm = "UUID                                 Status   Name\n\
+                   1D1C2B3A-0000-0000-0000-000000000000 started  Ubuntu\n\
+                   2D1C2B3A-0000-0000-0000-000000000000 stopped  Windows\n";
+        assert_eq!(parse_status_table(utm, 2, "started"), vec!["Ubuntu"]);
+    }
+
+    #[test]
+    fn test_parse_colima_and_vbox() {
+        let colima = "{\"name\":\"default\",\"status\":\"Running\"}\n{\"name\":\"k8s\",\"status\":\"Stopped\"}";
+        assert_eq!(parse_colima_list(colima), vec!["default"]);
+
+        let vbox = "\"dev-box\" {0b1e6e1c-0000-0000-0000-000000000000}\n";
+        assert_eq!(parse_vbox_running(vbox), vec!["dev-box"]);
+    }
+}
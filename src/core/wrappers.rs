@@ -0,0 +1,106 @@
+use crate::core::exec;
+use crate::types::BuildWrapper;
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+/// Detect project-local build tool wrappers and the versions they pin
+pub fn detect_wrappers(dir: &Path) -> Vec<BuildWrapper> {
+    let mut wrappers = Vec::new();
+
+    if dir.join("gradlew").is_file() {
+        let source = "gradle/wrapper/gradle-wrapper.properties";
+        wrappers.push(BuildWrapper {
+            wrapper: "./gradlew".to_string(),
+            tool: "gradle".to_string(),
+            version: read_capture(&dir.join(source), r"gradle-([\w.\-]+?)-(?:bin|all)\.zip"),
+            source: source.to_string(),
+            global_version: None,
+        });
+    }
+
+    if dir.join("mvnw").is_file() {
+        let source = ".mvn/wrapper/maven-wrapper.properties";
+        wrappers.push(BuildWrapper {
+            wrapper: "./mvnw".to_string(),
+            tool: "mvn".to_string(),
+            version: read_capture(&dir.join(source), r"apache-maven-([\w.\-]+?)-bin\.zip"),
+            source: source.to_string(),
+            global_version: None,
+        });
+    }
+
+    // bazelisk reads .bazelversion; `bazel` itself is often a bazelisk symlink
+    if let Ok(content) = fs::read_to_string(dir.join(".bazelversion")) {
+        let wrapper = if dir.join("tools/bazel").is_file() {
+            "tools/bazel"
+        } else if exec::command_exists("bazelisk") {
+            "bazelisk"
+        } else {
+            "bazel"
+        };
+        wrappers.push(BuildWrapper {
+            wrapper: wrapper.to_string(),
+            tool: "bazel".to_string(),
+            version: content.lines().map(str::trim).find(|l| !l.is_empty()).map(String::from),
+            source: ".bazelversion".to_string(),
+            global_version: None,
+        });
+    }
+
+    if dir.join("batect").is_file() {
+        wrappers.push(BuildWrapper {
+            wrapper: "./batect".to_string(),
+            tool: "batect".to_string(),
+            version: read_capture(&dir.join("batect"), r#"VERSION="([^"]+)""#),
+            source: "batect".to_string(),
+            global_version: None,
+        });
+    }
+
+    wrappers
+}
+
+/// First capture group of a pattern in a file
+fn read_capture(file: &Path, pattern: &str) -> Option<String> {
+    let content = fs::read_to_string(file).ok()?;
+    let re = Regex::new(pattern).ok()?;
+    re.captures(&content).map(|caps| caps[1].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+
+    #[test]
+    fn test_detect_wrappers() {
+        let dir = TempDir::new("wrappers");
+        fs::create_dir_all(dir.join("gradle/wrapper")).unwrap();
+        fs::create_dir_all(dir.join(".mvn/wrapper")).unwrap();
+        fs::write(dir.join("gradlew"), "#!/bin/sh\n").unwrap();
+        fs::write(
+            dir.join("gradle/wrapper/gradle-wrapper.properties"),
+            "distributionUrl=https\\://services.gradle.org/distributions/gradle-8.5-bin.zip\n",
+        )
+        .unwrap();
+        fs::write(dir.join("mvnw"), "#!/bin/sh\n").unwrap();
+        fs::write(
+            dir.join(".mvn/wrapper/maven-wrapper.properties"),
+            "distributionUrl=https://repo.maven.apache.org/maven2/org/apache/maven/apache-maven/3.9.6/apache-maven-3.9.6-bin.zip\n",
+        )
+        .unwrap();
+        fs::write(dir.join(".bazelversion"), "7.0.2\n").unwrap();
+
+        let found: Vec<(String, Option<String>)> =
+            detect_wrappers(&dir).into_iter().map(|w| (w.tool, w.version)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("gradle".to_string(), Some("8.5".to_string())),
+                ("mvn".to_string(), Some("3.9.6".to_string())),
+                ("bazel".to_string(), Some("7.0.2".to_string())),
+            ]
+        );
+    }
+}
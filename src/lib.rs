@@ -0,0 +1,22 @@
+//! devfetch's discovery engine, for embedding in other tools:
+//!
+//! ```no_run
+//! let result = devfetch::Scanner::new().project_path("/src/app").run()?;
+//! for tool in &result.global_tools {
+//!     println!("{} {:?}", tool.name, tool.version);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+//!
+//! The public API is [`Scanner`] and the result [`types`]; `config` and
+//! `core` are public only for the devfetch binary and may change at any time.
+
+#[doc(hidden)]
+pub mod config;
+#[doc(hidden)]
+pub mod core;
+mod scanner;
+pub mod types;
+
+pub use scanner::Scanner;
@@ -1,11 +1,12 @@
 mod cli;
-mod core;
-mod types;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
-use cli::Cli;
-use types::ScanResult;
+use cli::{CacheCommand, Cli, Command, FleetCommand, ScanArgs, SnapshotCommand, StatsCommand};
+use devfetch::config::{Config, ProjectConfig};
+use devfetch::core;
+use devfetch::core::output::{FileFormat, FileSink, OutputSink, TerminalSink};
+use devfetch::types::{self, ScanResult};
 
 fn main() -> Result<()> {
     let args = Cli::parse();
@@ -14,60 +15,372 @@ fn main() -> Result<()> {
     if args.no_color {
         colored::control::set_override(false);
     }
+    core::i18n::init(args.lang.as_deref());
 
-    // Perform the scan
-    let result = perform_scan(&args)?;
+    match &args.command {
+        Some(Command::Path) => {
+            let audit = core::path_audit::audit_path();
+            if args.json {
+                core::output::print_json(&audit)?;
+            } else {
+                core::output::print_path_audit(&audit);
+            }
+            if core::doctor::fails(&audit.findings, args.error_on) {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(Command::Tool { name, explain }) => {
+            let project = args.scan.target_path();
+            let user = Config::load()?;
+            let mut project_config = ProjectConfig::load(&project)?;
+            project_config.restrict(&user, &project);
+            let config = user.with_project(&project_config);
+            let timeout = std::time::Duration::from_millis(config.probe_timeout_ms.unwrap_or(core::exec::COMMAND_TIMEOUT_MS));
+            let explanation = core::explain::explain_tool(name, args.scan.local.then_some(project.as_path()), timeout, &config.category_overrides()?)?;
+            if args.json {
+                core::output::print_json(&explanation)?;
+            } else {
+                core::output::print_tool_explanation(&explanation, *explain);
+            }
+            return Ok(());
+        }
+        Some(Command::Cache { action }) => {
+            let file = core::cache::cache_file().context("No home directory for the cache")?;
+            let cache = core::cache::ProbeCache::load_from(&file);
+            match action {
+                CacheCommand::Stats => {
+                    let stats = cache.stats(&file);
+                    if args.json {
+                        core::output::print_json(&stats)?;
+                    } else {
+                        core::output::print_cache_stats(&stats);
+                    }
+                }
+                CacheCommand::Show { tool } => {
+                    let entries = cache.lookup(tool);
+                    if entries.is_empty() {
+                        anyhow::bail!("Nothing cached for '{}'", tool);
+                    }
+                    if args.json {
+                        let entries: Vec<serde_json::Value> = entries
+                            .iter()
+                            .map(|(path, entry, fresh)| serde_json::json!({"path": path, "fresh": fresh, "entry": entry}))
+                            .collect();
+                        core::output::print_json(&entries)?;
+                    } else {
+                        core::output::print_cache_entries(&entries);
+                    }
+                }
+            }
+            return Ok(());
+        }
+        Some(Command::Check { constraints }) => {
+            let checks = core::check::check_constraints(constraints)?;
+            if args.json {
+                core::output::print_json(&checks)?;
+            } else {
+                core::output::print_checks(&checks);
+            }
+            if checks.iter().any(|c| !c.satisfied) {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(Command::Diff { old, new, mismatched_only }) => {
+            let (mut old_label, old) = core::snapshot::open(old)?;
+            let (mut new_label, new) = match new {
+                Some(new) => core::snapshot::open(new)?,
+                None => ("now".to_string(), perform_scan(&args.scan, args.verbose, &Config::load()?)?),
+            };
+            if let (Some(old_host), Some(new_host)) = (&old.hostname, &new.hostname) {
+                if old_host != new_host {
+                    (old_label, new_label) = (old_host.clone(), new_host.clone());
+                }
+            }
+            let diff = core::diff::compare(&old_label, &old, &new_label, &new);
+            if args.json {
+                core::output::print_json(&diff)?;
+            } else {
+                core::output::print_scan_diff(&diff, *mismatched_only);
+            }
+            return Ok(());
+        }
+        Some(Command::Snapshot { action: SnapshotCommand::List }) => {
+            let names = core::snapshot::list();
+            if args.json {
+                core::output::print_json(&names)?;
+            } else {
+                for name in names {
+                    println!("{}", name);
+                }
+            }
+            return Ok(());
+        }
+        Some(Command::Stats { action }) => {
+            let config = Config::load()?;
+            let file = core::stats::stats_file().context("No home directory for usage statistics")?;
+            let stats = core::stats::load_from(&file);
+            match action {
+                StatsCommand::Show => {
+                    if args.json {
+                        core::output::print_json(&stats)?;
+                    } else {
+                        core::output::print_usage_stats(&stats, &file, config.stats.enabled);
+                    }
+                }
+                StatsCommand::Push { dry_run } => {
+                    let endpoint = config.stats.endpoint.as_deref().context("No [stats] endpoint in the config file")?;
+                    core::stats::push(&stats, endpoint, *dry_run)?;
+                }
+            }
+            return Ok(());
+        }
+        Some(Command::Fleet { action: FleetCommand::Merge { files, require, notify } }) => {
+            let scans = core::fleet::load_scans(files)?;
+            let report = core::fleet::merge(&scans, require);
+            if let Some(url) = &notify.notify {
+                let summary = core::notify::summarize_fleet(&report);
+                core::notify::notify(url, notify.notify_format.as_deref(), &summary)?;
+            }
+            if args.json {
+                core::output::print_json(&report)?;
+            } else {
+                core::output::print_fleet_report(&report);
+            }
+            return Ok(());
+        }
+        Some(Command::Fleet { action: FleetCommand::Dashboard { files, require, output } }) => {
+            let scans = core::fleet::load_scans(files)?;
+            let report = core::fleet::merge(&scans, require);
+            std::fs::write(output, core::dashboard::render_html(&report))
+                .with_context(|| format!("Failed to write {}", output.display()))?;
+            eprintln!("Wrote fleet dashboard to {}", output.display());
+            return Ok(());
+        }
+        Some(Command::Daemon { interval, scan }) => {
+            let config = Config::load()?;
+            // The daemon owns the global scan; clients add their own project scan
+            let mut scan = scan.clone();
+            scan.global = true;
+            scan.local = false;
+            scan.from_daemon = false;
+            let verbose = args.verbose;
+            return core::daemon::serve(
+                move || perform_scan(&scan, false, &config),
+                std::time::Duration::from_secs(*interval),
+                verbose,
+            );
+        }
+        Some(Command::Hook { shell, summary: false, .. }) => {
+            let exe = std::env::current_exe().context("Could not locate the devfetch binary")?;
+            print!("{}", core::hook::snippet(shell.as_deref().unwrap_or_default(), &exe)?);
+            return Ok(());
+        }
+        Some(Command::Hook { scan, .. }) => {
+            if let Some(summary) = cached_summary(scan, args.verbose)? {
+                if args.json {
+                    core::output::print_json(&summary)?;
+                } else {
+                    core::output::print_project_summary(&summary);
+                }
+            }
+            return Ok(());
+        }
+        Some(Command::LspExt { scan }) => {
+            let config = Config::load()?;
+            return core::rpc::serve(std::io::stdin().lock(), std::io::stdout(), |method, path, partial| {
+                let mut local = scan.clone();
+                local.path = Some(path);
+                local.global = false;
+                local.local = true;
+                let project = perform_scan(&local, false, &config)?;
+                if method == core::rpc::Method::Project {
+                    return Ok(serde_json::to_value(&project)?);
+                }
+                partial("project", serde_json::to_value(&project)?);
+
+                // The global stage comes from a running daemon when there is one
+                let mut global = scan.clone();
+                global.global = true;
+                global.local = false;
+                global.from_daemon = true;
+                let merged = core::rpc::merge_stages(
+                    serde_json::to_value(&project)?,
+                    serde_json::to_value(perform_scan(&global, false, &config)?)?,
+                );
+                let mut result: ScanResult = serde_json::from_value(merged)?;
+                if let Some(project) = &result.project_info {
+                    result.lsp_coverage = core::lsp::match_project(&result.lsp_servers, project);
+                }
+                if method == core::rpc::Method::Scan {
+                    return Ok(serde_json::to_value(&result)?);
+                }
+                partial("scan", serde_json::to_value(&result)?);
+                let (findings, _) = core::ignore::filter(&local.target_path(), core::doctor::diagnose(&result))?;
+                Ok(serde_json::to_value(findings)?)
+            });
+        }
+        Some(Command::Osquery { schema: true, .. }) => {
+            print!("{}", core::osquery::schema_sql());
+            return Ok(());
+        }
+        Some(Command::Scan(_)) | Some(Command::Osquery { .. }) | Some(Command::Doctor { .. })
+        | Some(Command::Snapshot { action: SnapshotCommand::Save { .. } })
+        | None => {}
+    }
 
-    // Output results
-    if args.json {
-        core::output::print_json(&result)?;
-    } else {
-        core::output::print_pretty(&result);
+    let scan = args.scan_args();
+    match scan.format.as_deref() {
+        Some("tmux") => {
+            if let Some(summary) = cached_summary(scan, args.verbose)? {
+                print!("{}", core::hook::render_tmux(&summary));
+            }
+            return Ok(());
+        }
+        Some(_) if scan.output.is_none() => anyhow::bail!("--format json|jsonl requires --output"),
+        _ => {}
     }
 
-    Ok(())
-}
+    let config = Config::load()?;
+
+    // Adopt the login shell's PATH before anything resolves or spawns tools
+    if scan.login_path {
+        match core::shell::login_shell_path() {
+            Ok(path) => {
+                if args.verbose {
+                    eprintln!("Using login shell PATH: {}", path);
+                }
+                std::env::set_var("PATH", path);
+            }
+            Err(e) => eprintln!("Warning: could not resolve login shell PATH ({}); using inherited PATH", e),
+        }
+    }
 
-/// Orchestrate the complete scan operation
-fn perform_scan(args: &Cli) -> Result<ScanResult> {
-    let mut result = ScanResult::new();
-    
-    // Scan for global tools if requested
-    if args.should_scan_global() {
-        if args.verbose {
-            eprintln!("Scanning PATH for developer tools...");
+    // Perform the scan; Ctrl-C stops it early and prints what was found
+    core::interrupt::install();
+    let started = std::time::Instant::now();
+    let result = perform_scan(scan, args.verbose, &config)?;
+    // A partial result is printed, but never recorded, uploaded or announced
+    let interrupted = core::interrupt::interrupted();
+    if config.stats.enabled && !interrupted {
+        if let Err(e) = core::stats::record(&result, started.elapsed()) {
+            if args.verbose {
+                eprintln!("Could not save usage statistics: {:#}", e);
+            }
         }
+    }
 
-        let mut tools = core::path_scan::discover_tools(args.verbose);
-        
-        // Classify discovered tools
-        core::classify::classify_tools(&mut tools);
+    let mut result = core::redact::apply(result, &scan.redact)?;
+    if scan.deterministic {
+        result = core::deterministic::apply(result, &scan.target_path())?;
+    }
 
-        result.global_tools = tools;
+    if let Some(target) = scan.push.as_ref().filter(|_| !interrupted) {
+        let headers: Vec<String> = config.push_headers.iter().chain(&scan.push_headers).cloned().collect();
+        core::push::push_result(&result, target, &headers, scan.dry_run, args.verbose)?;
+    }
 
-        if args.verbose {
-            eprintln!("Found {} developer tools", result.global_tools.len());
-        }
+    if let Some(url) = scan.notify.notify.as_ref().filter(|_| !interrupted) {
+        let (findings, _) = core::ignore::filter(&scan.target_path(), core::doctor::diagnose(&result))?;
+        let summary = core::notify::summarize_scan(&result, &findings);
+        core::notify::notify(url, scan.notify.notify_format.as_deref(), &summary)?;
     }
 
-    // Scan for project-specific information if requested
-    if args.should_scan_local() {
-        let target_path = args.target_path();
-        
-        if args.verbose {
-            eprintln!("Scanning project directory: {}", target_path.display());
+    // The scan goes to every sink; doctor and osquery print their own views instead of the terminal one
+    let mut sinks: Vec<Box<dyn OutputSink>> = Vec::new();
+    if matches!(args.command, None | Some(Command::Scan(_))) {
+        sinks.push(Box::new(TerminalSink { json: args.json }));
+    }
+    if let Some(path) = &scan.output {
+        let format = FileFormat::parse(scan.format.as_deref().unwrap_or("json")).context("Unsupported --format")?;
+        sinks.push(Box::new(FileSink { path: path.clone(), format }));
+    }
+    for sink in &sinks {
+        sink.emit(&result)?;
+    }
+
+    if let Some(Command::Doctor { .. }) = &args.command {
+        let (findings, ignored) = core::ignore::filter(&scan.target_path(), core::doctor::diagnose(&result))?;
+        if args.json {
+            core::output::print_json(&findings)?;
+        } else {
+            core::output::print_doctor(&core::requirements::check(&result), &findings, ignored);
+        }
+        if interrupted {
+            std::process::exit(130);
+        }
+        if core::doctor::fails(&findings, args.error_on) {
+            std::process::exit(1);
+        }
+    } else if let Some(Command::Snapshot { action: SnapshotCommand::Save { name, force, .. } }) = &args.command {
+        if interrupted {
+            eprintln!("Scan interrupted; snapshot not saved");
+            std::process::exit(130);
+        }
+        let file = core::snapshot::save(&result, name.as_deref(), *force)?;
+        eprintln!("Saved snapshot to {}", file.display());
+    } else if let Some(Command::Osquery { table, .. }) = &args.command {
+        match table {
+            Some(table) => core::output::print_json(&core::osquery::table_rows(table, &result))?,
+            None => {
+                let tables: std::collections::BTreeMap<&str, Vec<core::osquery::Row>> = core::osquery::TABLES
+                    .iter()
+                    .map(|(name, _)| (*name, core::osquery::table_rows(name, &result)))
+                    .collect();
+                core::output::print_json(&tables)?;
+            }
         }
+    }
 
-        result.project_info = core::project_detect::detect_project(&target_path, args.verbose);
+    if core::interrupt::interrupted() {
+        std::process::exit(130);
+    }
+    Ok(())
+}
 
-        if args.verbose {
-            if result.project_info.is_some() {
-                eprintln!("Project ecosystems detected");
-            } else {
-                eprintln!("No project markers found");
+/// Project summary for the shell hook and tmux, from the summary cache when
+/// the directory and PATH are unchanged
+fn cached_summary(scan: &ScanArgs, verbose: bool) -> Result<Option<types::ProjectSummary>> {
+    let config = Config::load()?;
+    let mut scan = scan.clone();
+    scan.global = false;
+    scan.local = true;
+    let dir = scan.target_path();
+    let mut cache = core::hook::SummaryCache::load();
+    let summary = cache.get_or_compute(&dir, || {
+        let mut result = perform_scan(&scan, false, &config)?;
+        // Pins and version requirements can only be checked against a daemon's warm tool list
+        let complete = match core::daemon::fetch() {
+            Ok(warm) => {
+                result.global_tools = warm.global_tools;
+                true
             }
+            Err(_) => {
+                result.pinned_tools.clear();
+                result.requirements.clear();
+                false
+            }
+        };
+        let (findings, _) = core::ignore::filter(&dir, core::doctor::diagnose(&result))?;
+        Ok((core::hook::summarize(&result, &findings), complete))
+    })?;
+    if let Err(e) = cache.save() {
+        if verbose {
+            eprintln!("Could not save the summary cache: {:#}", e);
         }
     }
+    Ok(summary)
+}
 
-    Ok(result)
+/// Run a scan with the CLI's options; verbose scans log each step instead of
+/// drawing a progress line
+fn perform_scan(args: &ScanArgs, verbose: bool, config: &Config) -> Result<ScanResult> {
+    args.scanner(config.clone())
+        .on_progress(|event| match event {
+            types::ScanProgress::Message(message) if verbose => eprintln!("{}", message),
+            event if !verbose => core::output::print_progress(&event),
+            _ => {}
+        })
+        .run()
 }
@@ -0,0 +1,422 @@
+use crate::config::{self, Config, ProjectConfig};
+use crate::core;
+use crate::core::path_scan::PathScanOptions;
+use crate::types::{ScanProgress, ScanResult, ScanWarning, WarningKind};
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Builder for a scan of the machine's tools and a project directory, e.g.
+/// `Scanner::new().local(false).timeout(Duration::from_secs(2)).run()`.
+/// Nothing is printed; progress and verbose details go to `on_progress`.
+pub struct Scanner<'a> {
+    global: bool,
+    local: bool,
+    project_path: Option<PathBuf>,
+    depth: usize,
+    config: Config,
+    timeout: Option<Duration>,
+    budget: Option<Duration>,
+    extra_dirs: Vec<String>,
+    skip_dirs: Vec<PathBuf>,
+    from_daemon: bool,
+    incremental: bool,
+    refresh: bool,
+    cache: bool,
+    verify_integrity: bool,
+    vm_status: bool,
+    git_auth: bool,
+    registry_auth: bool,
+    network: bool,
+    ssh: bool,
+    progress: Box<dyn Fn(ScanProgress) + Sync + 'a>,
+}
+
+impl Default for Scanner<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Scanner<'a> {
+    /// Scan PATH and the current directory with the default settings and the probe cache
+    pub fn new() -> Self {
+        Scanner {
+            global: true,
+            local: true,
+            project_path: None,
+            depth: 0,
+            config: Config::default(),
+            timeout: None,
+            budget: None,
+            extra_dirs: Vec::new(),
+            skip_dirs: Vec::new(),
+            from_daemon: false,
+            incremental: false,
+            refresh: false,
+            cache: true,
+            verify_integrity: false,
+            vm_status: false,
+            git_auth: false,
+            registry_auth: false,
+            network: false,
+            ssh: false,
+            progress: Box::new(|_| {}),
+        }
+    }
+
+    /// Discover tools on PATH and in well-known tool directories
+    pub fn global(mut self, global: bool) -> Self {
+        self.global = global;
+        self
+    }
+
+    /// Inspect the project directory
+    pub fn local(mut self, local: bool) -> Self {
+        self.local = local;
+        self
+    }
+
+    /// Project directory to inspect (default: the current directory)
+    pub fn project_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.project_path = Some(path.into());
+        self
+    }
+
+    /// Directory levels below the project to search for sub-projects (default: 0)
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Settings normally read from `~/.config/devfetch/config.toml`; the
+    /// project's devfetch.toml is applied on top while scanning
+    #[doc(hidden)]
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Honor the user's `~/.config/devfetch/config.toml`, as the CLI does
+    pub fn user_config(self) -> Result<Self> {
+        Ok(self.config(Config::load()?))
+    }
+
+    /// How long a tool gets to answer each version flag, over the config's `probe_timeout_ms`
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Return what has been found when this time runs out; the result is marked partial
+    pub fn budget(mut self, budget: Duration) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Extra directories to scan for tools not on PATH
+    pub fn extra_dirs(mut self, dirs: Vec<String>) -> Self {
+        self.extra_dirs = dirs;
+        self
+    }
+
+    /// PATH entries to exclude, in addition to the config's `skip_dirs`
+    pub fn skip_dirs(mut self, dirs: Vec<PathBuf>) -> Self {
+        self.skip_dirs = dirs;
+        self
+    }
+
+    /// Take global tools from a running `devfetch daemon`, scanning when there is none
+    pub fn from_daemon(mut self, from_daemon: bool) -> Self {
+        self.from_daemon = from_daemon;
+        self
+    }
+
+    /// Reuse the last scan's tools while no tool directory has changed
+    pub fn incremental(mut self, incremental: bool) -> Self {
+        self.incremental = incremental;
+        self
+    }
+
+    /// Re-probe every tool instead of trusting the probe cache
+    pub fn refresh(mut self, refresh: bool) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    /// Read and write the probe cache (default: true)
+    pub fn cache(mut self, cache: bool) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Verify tool binaries against package manager records
+    pub fn verify_integrity(mut self, verify: bool) -> Self {
+        self.verify_integrity = verify;
+        self
+    }
+
+    /// Check whether VMs of detected virtualization tools are running
+    pub fn vm_status(mut self, vm_status: bool) -> Self {
+        self.vm_status = vm_status;
+        self
+    }
+
+    /// Ask gh, glab and tea which hosts they're logged into
+    pub fn git_auth(mut self, git_auth: bool) -> Self {
+        self.git_auth = git_auth;
+        self
+    }
+
+    /// Report which container registries you're logged into
+    pub fn registry_auth(mut self, registry_auth: bool) -> Self {
+        self.registry_auth = registry_auth;
+        self
+    }
+
+    /// Check that the project's package hosts and git remotes are reachable
+    pub fn network(mut self, network: bool) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Check the ssh-agent and known_hosts for the project's SSH git remotes
+    pub fn ssh(mut self, ssh: bool) -> Self {
+        self.ssh = ssh;
+        self
+    }
+
+    /// Called with progress events and verbose details, from the probing threads too
+    pub fn on_progress(mut self, progress: impl Fn(ScanProgress) + Sync + 'a) -> Self {
+        self.progress = Box::new(progress);
+        self
+    }
+
+    fn target_path(&self) -> PathBuf {
+        self.project_path
+            .clone()
+            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
+    }
+
+    fn log(&self, message: impl Into<String>) {
+        (self.progress)(ScanProgress::Message(message.into()));
+    }
+
+    /// Run the scan
+    pub fn run(&self) -> Result<ScanResult> {
+        let progress = &*self.progress;
+        let target_path = self.target_path();
+        let warm = if self.from_daemon && self.global {
+            core::daemon::fetch().map_err(|e| self.log(format!("{:#}; scanning instead", e))).ok()
+        } else {
+            None
+        };
+        let scan_global = warm.is_none() && self.global;
+        let mut result = warm.unwrap_or_else(ScanResult::new);
+        result.hostname = core::system::hostname();
+        let deadline = match self.budget {
+            Some(budget) => Some(Instant::now().checked_add(budget).context("The scan budget is too large")?),
+            None => None,
+        };
+        // Ctrl-C or the end of the budget stops the slower checks that haven't started
+        let keep_going = || !core::interrupt::interrupted() && deadline.is_none_or(|deadline| Instant::now() < deadline);
+
+        // The project's devfetch.toml overrides the user config for this scan
+        let mut project_config = if self.local {
+            ProjectConfig::load(&target_path).unwrap_or_else(|e| {
+                result.warnings.push(ScanWarning {
+                    kind: WarningKind::ParseFailure,
+                    path: ProjectConfig::file(&target_path).unwrap_or_default(),
+                    message: format!("{:#}", e),
+                });
+                ProjectConfig::default()
+            })
+        } else {
+            ProjectConfig::default()
+        };
+        if project_config.restrict(&self.config, &target_path) {
+            result.warnings.push(ScanWarning {
+                kind: WarningKind::UntrustedConfig,
+                path: ProjectConfig::file(&target_path).unwrap_or_default(),
+                message: "probe commands and tool patterns ignored; add the project to trusted_projects in config.toml to run them".to_string(),
+            });
+        }
+        let config = &self.config.with_project(&project_config);
+
+        // Scan for project-specific information if requested; it runs first so a
+        // budgeted scan always has it
+        if self.local {
+            self.log(format!("Scanning project directory: {}", target_path.display()));
+
+            result.project_info = core::project_detect::detect_project_tree(&target_path, &config.markers, self.depth, deadline, progress);
+            result.pinned_tools = core::pins::detect_pins(&target_path);
+            result.requirements = core::requirements::detect_requirements(&target_path);
+            result.dev_environments = core::devenv::detect_dev_environments(&target_path);
+            result.orchestrators = core::monorepo::detect_orchestrators(&target_path);
+            result.migrations = core::migrations::detect_migrations(&target_path);
+            result.databases = core::databases::detect_databases(&target_path);
+            result.api_specs = core::apis::detect_api_specs(&target_path);
+            let has_proto_files = result.api_specs.iter().flat_map(|a| &a.specs).any(|s| s.kind == "Protobuf");
+            result.proto_toolchain = core::protobuf::check_toolchain(&target_path, has_proto_files);
+            result.docs_tools = core::docs::detect_docs_tools(&target_path);
+            result.release_tools = core::release::detect_release_tools(&target_path);
+            result.system_checks = core::system::check_requirements(&target_path, &project_config.system);
+            result.services = core::services::check_services(&project_config.services);
+
+            if let Some(project) = &result.project_info {
+                result.warnings.extend(core::project_detect::manifest_warnings(project));
+                result.linters = core::linters::cross_reference(project);
+                result.ci_tools = core::coverage::ci_expected_tools(&project.path);
+            }
+
+            self.log(if result.project_info.is_some() { "Project ecosystems detected" } else { "No project markers found" });
+        }
+
+        let project = self.local.then(|| target_path.clone());
+        result.package_registries = core::package_registries::detect_registries(project.as_deref());
+        result.git = core::git::inspect(&target_path);
+
+        // Scan for global tools if requested
+        if scan_global {
+            self.log("Scanning PATH for developer tools...");
+
+            let options = PathScanOptions {
+                path: None,
+                extra_dirs: self.extra_dirs.clone(),
+                skip_dirs: config
+                    .skip_dirs
+                    .iter()
+                    .map(|d| core::paths::expand_tilde(d))
+                    .chain(self.skip_dirs.iter().cloned())
+                    .collect(),
+                deadline,
+                probe_timeout: self.timeout.or(config.probe_timeout_ms.map(Duration::from_millis)),
+                ignore_tools: config
+                    .ignore_tools
+                    .iter()
+                    .map(|p| config::tool_pattern(p, "ignore_tools"))
+                    .collect::<Result<_>>()?,
+                tool_patterns: config.tools.iter().map(|p| config::tool_pattern(p, "tools")).collect::<Result<_>>()?,
+            };
+            let mut cache = if self.cache { core::cache::ProbeCache::load() } else { core::cache::ProbeCache::default() };
+            if self.refresh {
+                cache.refresh();
+            }
+            // Whatever the last run recorded must not pass for this scan's figures
+            cache.last_scan = None;
+            let mut tools = if self.incremental {
+                core::path_scan::rescan_tools(&options, &mut cache, &mut result.warnings, progress)
+            } else {
+                core::path_scan::discover_tools(&options, &mut cache, &mut result.warnings, progress)
+            };
+            if let Some(usage) = &cache.last_scan {
+                self.log(format!(
+                    "Probe cache: {} hits, {} misses ({:.0}% hit rate)",
+                    usage.hits,
+                    usage.misses,
+                    usage.hit_rate()
+                ));
+            }
+            // Ctrl-C also reaches the probes, so their failures after it aren't kept
+            if core::interrupt::interrupted() {
+                self.log("Interrupted; not saving the probe cache");
+            } else if self.cache {
+                if let Err(e) = cache.save() {
+                    self.log(format!("Could not save the probe cache: {:#}", e));
+                }
+            }
+            core::aliases::merge_aliases(&mut tools);
+
+            // Classify discovered tools
+            core::classify::classify_tools(&mut tools, project.as_deref(), &config.category_overrides()?);
+            core::nix::annotate_tools(&mut tools);
+            result.guix = core::guix::detect_guix();
+            if let Some(guix) = &result.guix {
+                core::guix::annotate_tools(&mut tools, guix);
+            }
+
+            if self.verify_integrity && keep_going() {
+                self.log("Verifying tool binaries against package manager records...");
+                core::integrity::verify_tools(&mut tools);
+            }
+            core::integrity::flag_manual_installs(&mut tools);
+            core::identifiers::assign_identifiers(&mut tools);
+
+            result.compat_env = core::compat_env::detect_compat_env(&tools);
+            result.shell = core::shell::detect_shell();
+            result.proxy = core::proxy::detect_proxy_env();
+            if keep_going() {
+                result.cross_compile = core::cross::detect_cross_targets();
+                result.virtualization = core::virt::detect_virt_tools(self.vm_status);
+                result.forge_clis = core::forges::detect_forge_clis(self.git_auth);
+                result.dotfiles = core::dotfiles::detect_dotfiles();
+                result.lsp_servers = core::lsp::detect_lsp_servers();
+                result.jdks = core::jdk::installed_jdks();
+                result.version_managers = core::version_managers::detect_version_managers(&target_path);
+            } else {
+                result.partial = true;
+            }
+            result.global_tools = tools;
+        }
+
+        let requested = [self.registry_auth, self.network && self.local, self.ssh && self.local];
+        if requested.contains(&true) && !keep_going() {
+            result.partial = true;
+        } else {
+            if self.registry_auth {
+                result.registry_auth = Some(core::registries::check_registry_auth(project.as_deref()));
+            }
+
+            if self.network && self.local {
+                self.log("Checking that the project's package hosts are reachable...");
+                result.network = core::network::check_hosts(&target_path, &result.package_registries);
+            }
+
+            if self.ssh && self.local {
+                result.ssh = Some(core::ssh::check_ssh(&target_path));
+            }
+        }
+
+        // Language server coverage needs both the global and the project scan
+        if self.global {
+            if let Some(project) = &result.project_info {
+                result.lsp_coverage = core::lsp::match_project(&result.lsp_servers, project);
+            }
+        }
+
+        // Running past the budget means some probe or check was cut short or skipped
+        result.partial |= core::interrupt::interrupted()
+            || deadline.is_some_and(|deadline| Instant::now() >= deadline)
+            || result.warnings.iter().any(|w| w.kind == WarningKind::BudgetExhausted);
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_util::TempDir;
+    use std::fs;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_project_only_scan() {
+        let dir = TempDir::new("scanner");
+        fs::write(dir.join("go.mod"), "module example.com/app\n\ngo 1.22\n").unwrap();
+
+        let messages = Mutex::new(Vec::new());
+        let result = Scanner::new()
+            .global(false)
+            .project_path(dir.to_path_buf())
+            .on_progress(|event| {
+                if let ScanProgress::Message(message) = event {
+                    messages.lock().unwrap().push(message);
+                }
+            })
+            .run()
+            .unwrap();
+        assert!(result.global_tools.is_empty());
+        assert!(result.project_info.unwrap().markers.iter().any(|m| m.ecosystem == "Go"));
+        assert!(messages.into_inner().unwrap().contains(&"Found marker: go.mod".to_string()));
+    }
+}
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// Represents a discovered developer tool
@@ -9,15 +9,118 @@ pub struct Tool {
     pub path: PathBuf,
     pub version: Option<String>,
     pub category: ToolCategory,
+    /// False for tools found in well-known install dirs that are not on PATH
+    #[serde(default = "default_true")]
+    pub on_path: bool,
+    #[serde(default)]
+    pub scope: InstallScope,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub security: Vec<SecurityFlag>,
+    /// Package manager verification, filled by --verify-integrity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrity: Option<IntegrityCheck>,
+    /// Package URL, e.g. pkg:deb/debian/git@1:2.39.2-1.1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purl: Option<String>,
+    /// Best-effort CPE 2.3 name for vulnerability matching
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpe: Option<String>,
+    /// Nix/Guix package the binary belongs to, for tools living in a store
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store: Option<StoreProvenance>,
+    /// In /usr/local or /opt with no package manager claiming it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub manual_install: bool,
+    /// Other names on PATH for the same binary, e.g. python3.11 for python3
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Generic commands this tool can stand in for, e.g. python3.11 provides python3
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub provides: Vec<String>,
+    /// Other binaries of the same name later on PATH, hidden by this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shadows: Vec<PathBuf>,
+    /// Why `version` is null when the tool is installed but its probe failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub probe_error: Option<String>,
+}
+
+impl Tool {
+    /// A system tool on PATH with nothing else known yet
+    pub fn new(name: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        Tool {
+            name: name.into(),
+            path: path.into(),
+            version: None,
+            category: ToolCategory::Unknown,
+            on_path: true,
+            scope: InstallScope::System,
+            security: Vec::new(),
+            integrity: None,
+            purl: None,
+            cpe: None,
+            store: None,
+            manual_install: false,
+            aliases: Vec::new(),
+            provides: Vec::new(),
+            shadows: Vec::new(),
+            probe_error: None,
+        }
+    }
+
+    /// Whether running `command` would reach this tool: its name or one of the
+    /// aliases found on PATH, exactly. `provides` is not consulted, so
+    /// python3.11 alone doesn't satisfy `python`
+    pub fn provides_command(&self, command: &str) -> bool {
+        self.name == command || self.aliases.iter().any(|n| n == command)
+    }
+}
+
+/// Where a tool from a functional package store (Nix, Guix) came from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoreProvenance {
+    /// nix or guix
+    pub manager: String,
+    /// Store directory, e.g. /nix/store/<hash>-nodejs-20.11.1
+    pub store_path: PathBuf,
+    pub package: String,
+    pub version: Option<String>,
+    /// Profile that put the tool on PATH, e.g. "user profile" or "home-manager"
+    pub profile: String,
+    /// Flake reference, channel or configuration the package came from
+    pub source: Option<String>,
+}
+
+/// Result of checking a binary against its package manager's records
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityCheck {
+    /// dpkg, rpm or homebrew
+    pub manager: String,
+    pub package: String,
+    /// Some(false) means the file on disk no longer matches; None means unverifiable
+    pub verified: Option<bool>,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 /// Categories for discovered tools based on heuristics
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ToolCategory {
+    #[serde(alias = "language_toolchain")]
     LanguageToolchain,
+    #[serde(alias = "package_manager")]
     PackageManager,
+    #[serde(alias = "build_system")]
     BuildSystem,
+    #[serde(alias = "formatter_linter")]
+    FormatterLinter,
+    #[serde(alias = "coverage_profiler")]
+    CoverageProfiler,
+    #[serde(alias = "developer_tool")]
     DeveloperTool,
+    #[serde(alias = "unknown")]
     Unknown,
 }
 
@@ -27,41 +130,367 @@ impl ToolCategory {
             ToolCategory::LanguageToolchain => "Language Toolchains",
             ToolCategory::PackageManager => "Package Managers",
             ToolCategory::BuildSystem => "Build Systems",
+            ToolCategory::FormatterLinter => "Formatters & Linters",
+            ToolCategory::CoverageProfiler => "Coverage & Profiling",
             ToolCategory::DeveloperTool => "Developer Tools",
             ToolCategory::Unknown => "Other Tools",
         }
     }
 }
 
+/// Security-relevant property of a discovered binary
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SecurityFlag {
+    /// Runs with the privileges of its owner
+    Setuid { owner: u32 },
+    /// Runs with the privileges of its group
+    Setgid,
+    /// Owned by a regular user account other than the one scanning
+    UnexpectedOwner { owner: u32 },
+    /// Carries macOS's com.apple.quarantine attribute (Gatekeeper may block it)
+    Quarantined,
+    /// Not code-signed (macOS)
+    Unsigned,
+    /// Ad-hoc signed without a Developer ID (macOS)
+    AdhocSigned,
+}
+
+impl SecurityFlag {
+    pub fn describe(&self) -> String {
+        match self {
+            SecurityFlag::Setuid { owner: 0 } => "setuid root".to_string(),
+            SecurityFlag::Setuid { owner } => format!("setuid uid {}", owner),
+            SecurityFlag::Setgid => "setgid".to_string(),
+            SecurityFlag::UnexpectedOwner { owner } => format!("owned by uid {}", owner),
+            SecurityFlag::Quarantined => "quarantined".to_string(),
+            SecurityFlag::Unsigned => "unsigned".to_string(),
+            SecurityFlag::AdhocSigned => "ad-hoc signed".to_string(),
+        }
+    }
+}
+
+/// Who manages a tool's installation, judged by where it lives
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InstallScope {
+    /// Provisioned system-wide (/usr, /opt, images, IT tooling)
+    #[default]
+    System,
+    /// Installed under the user's home directory
+    User,
+    /// Local to the scanned project (node_modules/.bin, .venv, ...)
+    Project,
+}
+
+impl InstallScope {
+    pub fn display_name(&self) -> &str {
+        match self {
+            InstallScope::System => "system",
+            InstallScope::User => "user",
+            InstallScope::Project => "project",
+        }
+    }
+}
+
 /// Represents a project marker file and its associated ecosystem
 #[derive(Debug, Clone)]
 pub struct ProjectMarker {
     pub file_name: String,
     pub ecosystem: String,
+    /// Set for lock files that pick the ecosystem's package manager (uv.lock,
+    /// poetry.lock); their commands probe the manager rather than the runtime
+    pub package_manager: Option<String>,
     pub commands: Vec<EcosystemCommand>,
 }
 
 /// Command to extract ecosystem information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct EcosystemCommand {
     pub tool: String,
+    #[serde(default)]
     pub args: Vec<String>,
+    #[serde(default)]
     pub parser: CommandParser,
 }
 
 /// How to parse command output
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub enum CommandParser {
+    #[serde(rename = "json")]
     Json,
+    #[default]
+    #[serde(rename = "text")]
     PlainText,
 }
 
+/// Config changes to a built-in marker's probe commands, or a new marker
+/// (file name or glob) when `ecosystem` is set
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MarkerOverride {
+    /// Ecosystem the marker belongs to; required for markers that aren't built in
+    pub ecosystem: Option<String>,
+    /// Replaces the built-in commands; an empty list skips probing
+    pub commands: Option<Vec<EcosystemCommand>>,
+    /// Run after the built-in (or replacement) commands
+    pub extra_commands: Vec<EcosystemCommand>,
+}
+
+/// `[system]` in a project's devfetch.toml: what the machine needs beyond tools
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SystemRequirements {
+    /// Free space on the filesystem holding the project, in GiB
+    pub min_free_disk_gb: Option<f64>,
+    /// Total RAM, in GiB
+    pub min_memory_gb: Option<f64>,
+    /// Accepted systems, any of: "linux", "macos>=13", "ubuntu>=22.04" (os-release ID)
+    pub os: Vec<String>,
+    /// Minimum kernel release, e.g. "5.15"
+    pub min_kernel: Option<String>,
+    /// Kernel modules that must be loaded or built in, e.g. "kvm"
+    pub kernel_modules: Vec<String>,
+}
+
+/// Which `[system]` requirement a check evaluates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SystemCheckKind {
+    Disk,
+    Memory,
+    Os,
+    Kernel,
+    Module,
+}
+
+impl SystemCheckKind {
+    pub fn display_name(&self) -> &str {
+        match self {
+            SystemCheckKind::Disk => "disk",
+            SystemCheckKind::Memory => "memory",
+            SystemCheckKind::Os => "os",
+            SystemCheckKind::Kernel => "kernel",
+            SystemCheckKind::Module => "module",
+        }
+    }
+}
+
+/// One evaluated `[system]` requirement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemCheck {
+    pub kind: SystemCheckKind,
+    /// What devfetch.toml asks for, e.g. "20 GiB free" or "kvm"
+    pub required: String,
+    /// What this machine has, when it could be determined
+    pub actual: Option<String>,
+    pub satisfied: bool,
+}
+
+/// A `[services]` entry in devfetch.toml; known services (docker, postgres,
+/// redis, ...) need no settings, others need a port or socket
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServiceRequirement {
+    /// TCP port on localhost, replacing the service's default
+    pub port: Option<u16>,
+    /// Unix socket, replacing the service's default
+    pub socket: Option<PathBuf>,
+    /// How to start it, shown when it isn't running
+    pub start: Option<String>,
+}
+
+/// Whether a service the project needs is running locally
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceCheck {
+    pub name: String,
+    pub running: bool,
+    /// What answered: "port 5432", a socket path or "process dockerd"
+    pub via: Option<String>,
+    /// Command that starts it on this platform
+    pub start_hint: Option<String>,
+}
+
+/// Whether a host the project downloads from accepts connections (`--network`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkCheck {
+    pub host: String,
+    pub port: u16,
+    /// Files that name the host, e.g. ".npmrc" or "~/.config/pip/pip.conf"
+    pub sources: Vec<String>,
+    /// Whether DNS returned an address; private names often only resolve on the VPN
+    pub resolved: bool,
+    pub reachable: bool,
+    pub error: Option<String>,
+}
+
+/// A custom registry, index or mirror in npm, pip, Cargo or Maven settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageRegistry {
+    /// npm, pip, cargo or maven
+    pub ecosystem: String,
+    /// registry, index, extra index, mirror or repository
+    pub kind: String,
+    /// npm scope, Cargo registry/source name or Maven id
+    pub name: Option<String>,
+    /// Host (and port) only; credentials in the URL are dropped
+    pub host: Option<String>,
+    /// Port connections go to, from the URL or its scheme
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    /// File or environment variable it is set in
+    pub source: String,
+    /// Why the entry looks misconfigured
+    pub problem: Option<String>,
+}
+
+/// Proxy and CA certificate settings from the environment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxyEnv {
+    pub proxies: Vec<ProxySetting>,
+    pub ca_bundles: Vec<CaBundle>,
+}
+
+/// A proxy variable, with credentials in its URL masked
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxySetting {
+    pub var: String,
+    pub value: String,
+    /// Set on the lowercase variable when its uppercase twin holds a different
+    /// value, compared before masking
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub differs_by_case: bool,
+}
+
+/// A variable naming a CA certificate file or directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaBundle {
+    pub var: String,
+    pub path: PathBuf,
+    /// Tools that read the variable
+    pub used_by: String,
+    pub exists: bool,
+    /// PEM certificates in the file; None for directories and unreadable files
+    pub certificates: Option<usize>,
+}
+
+/// Git settings relevant to building and committing
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GitSetup {
+    pub version: Option<String>,
+    pub user_name: Option<String>,
+    pub user_email: Option<String>,
+    pub autocrlf: Option<String>,
+    pub credential_helpers: Vec<String>,
+    /// Signature format (openpgp, ssh or x509) when commit.gpgsign is on
+    pub signing: Option<String>,
+    /// Whether the key commits would be signed with exists here (when signing is on)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_key_available: Option<bool>,
+    /// Installed signing programs: gpg, gpgsm, ssh-keygen, gitsign
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signing_tools: Vec<String>,
+    /// CI or repository settings files that verify commit signatures
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signatures_required_by: Vec<String>,
+    pub lfs_installed: bool,
+    /// Whether the LFS filters are configured (`git lfs install` was run)
+    pub lfs_initialized: bool,
+    /// Whether the project's .gitattributes stores files in LFS
+    pub repo_uses_lfs: bool,
+    /// Whether the repository's pre-push hook runs git lfs
+    #[serde(default)]
+    pub lfs_hooks: bool,
+    /// LFS files still checked out as pointers
+    #[serde(default)]
+    pub lfs_missing: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub submodules: Vec<Submodule>,
+}
+
+/// A submodule and whether it is checked out at the recorded commit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Submodule {
+    pub path: String,
+    /// ok, uninitialized, out of date or conflict
+    pub state: String,
+}
+
+/// SSH agent, keys and known hosts for the project's git remotes (`--ssh`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshReadiness {
+    pub agent_running: bool,
+    /// Types of the keys loaded in the agent, e.g. ED25519
+    pub agent_keys: Vec<String>,
+    /// Private key file names in ~/.ssh, e.g. id_ed25519
+    pub key_files: Vec<String>,
+    pub hosts: Vec<SshHost>,
+}
+
+/// A host the project's git remotes reach over SSH
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshHost {
+    /// Host as written in the remote URL (may be a ~/.ssh/config alias)
+    pub alias: String,
+    /// Host and port after applying ~/.ssh/config
+    pub hostname: String,
+    pub port: u16,
+    /// Remote names that use it
+    pub remotes: Vec<String>,
+    /// Whether known_hosts has its host key
+    pub known: bool,
+    /// Whether ~/.ssh/config has a Host block for it
+    pub configured: bool,
+    /// Identity files configured for it that exist
+    pub identity_files: Vec<String>,
+}
+
 /// Detected project information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectInfo {
     pub path: PathBuf,
     pub markers: Vec<DetectedMarker>,
-    pub ecosystems: HashMap<String, EcosystemInfo>,
+    pub ecosystems: BTreeMap<String, EcosystemInfo>,
+    /// Runnable entry points: package.json scripts, Make targets, just recipes, Taskfile tasks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scripts: Vec<ProjectScript>,
+    /// The members this directory's manifest declares, when it is a workspace root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<WorkspaceRoot>,
+    /// Projects in subdirectories (--depth), each with its own sub-projects
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subprojects: Vec<ProjectInfo>,
+}
+
+/// A workspace declared by pnpm-workspace.yaml, package.json `workspaces`,
+/// Cargo.toml `[workspace]` or go.work
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceRoot {
+    /// npm, yarn, pnpm, cargo or go
+    pub kind: String,
+    /// File that declares the members
+    pub manifest: String,
+    /// Member paths or globs as written
+    pub members: Vec<String>,
+}
+
+/// A runnable entry point declared by the project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectScript {
+    pub name: String,
+    /// File that declares it, e.g. package.json or Makefile
+    pub source: String,
+    /// Command the script runs, when declared inline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Doc comment, e.g. a Makefile `## ...` annotation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// How to invoke it, e.g. "yarn test" or "make build"
+    pub run: String,
+    /// The runner (just, task, ...) is not installed
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub runner_missing: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -75,40 +504,1128 @@ pub struct EcosystemInfo {
     pub name: String,
     pub tool_version: Option<String>,
     pub dependencies: Option<DependencyInfo>,
+    /// Project-local wrapper whose pinned version `tool_version` reports
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wrapper: Option<BuildWrapper>,
+    /// What only this ecosystem reports, e.g. the MSRV for Rust
+    #[serde(default)]
+    pub details: EcosystemDetails,
+    /// Frameworks found in the manifest or config files, e.g. Next.js 14
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub frameworks: Vec<Framework>,
+}
+
+/// Ecosystem-specific details, tagged by `kind` in JSON
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum EcosystemDetails {
+    /// Nothing beyond the common fields (Go, .NET, ...)
+    #[default]
+    Generic,
+    Node(NodeEcosystem),
+    Rust(RustEcosystem),
+    Python(PythonEcosystem),
+    /// Plugin and toolchain versions pinned in pom.xml or the Gradle build
+    Jvm(JvmBuild),
+    /// PHP version constraint and extensions required by Composer
+    Php(PhpRequirements),
+    /// Required Ruby and locked Bundler
+    Ruby(RubyRequirements),
+    /// Compilers, pkg-config and CMake package lookups
+    Cxx(CxxToolchain),
+}
+
+impl EcosystemDetails {
+    /// Empty details of the kind an ecosystem name calls for
+    pub fn for_ecosystem(name: &str) -> Self {
+        match name {
+            "Node.js" => EcosystemDetails::Node(NodeEcosystem::default()),
+            "Rust" => EcosystemDetails::Rust(RustEcosystem::default()),
+            "Python" => EcosystemDetails::Python(PythonEcosystem::default()),
+            _ => EcosystemDetails::Generic,
+        }
+    }
+
+    /// Package manager picked by the lock file, for Node.js and Python
+    pub fn package_manager(&self) -> Option<&PackageManager> {
+        match self {
+            EcosystemDetails::Node(node) => node.package_manager.as_ref(),
+            EcosystemDetails::Python(python) => python.package_manager.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Where to record a package manager; None for ecosystems without one
+    pub fn package_manager_mut(&mut self) -> Option<&mut Option<PackageManager>> {
+        match self {
+            EcosystemDetails::Node(node) => Some(&mut node.package_manager),
+            EcosystemDetails::Python(python) => Some(&mut python.package_manager),
+            _ => None,
+        }
+    }
+}
+
+/// Node.js project details
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeEcosystem {
+    /// Package manager picked by the lock file or package.json's `packageManager`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_manager: Option<PackageManager>,
+}
+
+/// Cargo workspace members, binaries and minimum supported Rust version
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RustEcosystem {
+    /// Highest `rust-version` declared by any member
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub msrv: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub binaries: Vec<String>,
+}
+
+/// Python project details: the package manager, and pyproject.toml's
+/// `requires-python` checked against interpreters on PATH
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PythonEcosystem {
+    /// Package manager picked by the lock file, e.g. poetry for poetry.lock
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_manager: Option<PackageManager>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requires_python: Option<String>,
+    /// uv.lock or pdm.lock, when the dependency count came from a lock file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub interpreters: Vec<PythonInterpreter>,
+}
+
+/// C/C++ compilers and the libraries a CMake build looks for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CxxToolchain {
+    pub compilers: Vec<CCompiler>,
+    /// pkg-config version, when installed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pkg_config: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub libraries: Vec<CxxLibrary>,
+    /// Oldest cmake the project accepts, from cmake_minimum_required or CMakePresets.json
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cmake_required: Option<String>,
+    /// Configure presets from CMakePresets.json and CMakeUserPresets.json
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub presets: Vec<CMakePreset>,
+}
+
+/// A CMake configure preset and whether its generator can run here
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CMakePreset {
+    pub name: String,
+    /// Generator, inherited presets included, e.g. "Ninja"
+    pub generator: Option<String>,
+    /// Program the generator drives, e.g. ninja, and whether it is installed
+    pub generator_tool: Option<String>,
+    pub generator_installed: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toolchain_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toolchain_exists: Option<bool>,
+}
+
+/// A C compiler driver and what it does without flags
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CCompiler {
+    pub name: String,
+    pub version: String,
+    /// Target triple from -dumpmachine, e.g. x86_64-linux-gnu
+    pub target: Option<String>,
+    /// Default C standard, e.g. gnu17
+    pub c_std: Option<String>,
+    /// Default C++ standard of the matching C++ driver, e.g. gnu++17
+    pub cxx_std: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sysroot: Option<String>,
+}
+
+/// A package CMakeLists.txt asks for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CxxLibrary {
+    pub name: String,
+    /// find_package or pkg_check_modules
+    pub via: String,
+    /// None when only CMake's Find module can tell, at configure time
+    pub found: Option<bool>,
+    pub version: Option<String>,
+}
+
+/// The package manager a project uses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageManager {
+    pub name: String,
+    /// Installed version, when the manager is on PATH
+    pub version: Option<String>,
+    /// File that selected it, e.g. poetry.lock
+    pub source: String,
+    /// Version the project pins, e.g. from package.json's `packageManager`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<String>,
+    /// Lock files of other package managers also present
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<String>,
+    /// Corepack state, for yarn and pnpm pinned by `packageManager`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub corepack: Option<CorepackStatus>,
+}
+
+/// Whether corepack will run the pinned yarn/pnpm
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorepackStatus {
+    /// `corepack` is on PATH
+    pub installed: bool,
+    /// The manager on PATH is corepack's shim (`corepack enable` has run)
+    pub enabled: bool,
+    /// The pinned version is in corepack's cache, so it runs without a download
+    pub cached: bool,
+}
+
+/// Build plugins and toolchain versions a JVM project pins
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JvmBuild {
+    /// File the versions were read from, e.g. pom.xml or gradle/libs.versions.toml
+    pub source: String,
+    pub plugins: Vec<PinnedPlugin>,
+    /// Java release targeted (maven.compiler.release, Gradle toolchain languageVersion)
+    pub java_version: Option<String>,
+    pub kotlin_version: Option<String>,
+    /// Range maven-enforcer's requireJavaVersion allows, e.g. "[17,21)"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub java_range: Option<String>,
+    /// Installed JDKs checked against the requirement
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub jdks: Vec<JdkCandidate>,
+}
+
+/// A JDK installation found on disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Jdk {
+    pub home: PathBuf,
+    pub version: String,
+    /// JAVA_HOME, PATH, system, sdkman, asdf, mise or jabba
+    pub source: String,
+    /// Distribution, e.g. Temurin, Corretto, GraalVM, Zulu
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vendor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arch: Option<String>,
+    /// The JDK whose java is first on PATH
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub on_path: bool,
+}
+
+/// A version manager (asdf, nvm, pyenv, rbenv, rustup, sdkman, volta) and
+/// the tools it installs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionManager {
+    pub name: String,
+    /// Data directory holding the installed versions
+    pub root: PathBuf,
+    pub tools: Vec<ManagedTool>,
+}
+
+/// One tool under a version manager: every installed version and the one
+/// the project directory selects
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManagedTool {
+    pub tool: String,
+    pub installed: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active: Option<String>,
+    /// What selects the active version: an env var, a version file, or the manager's default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// The binary first on PATH is this manager's shim, proxy or install
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub on_path: bool,
+}
+
+/// An installed JDK and whether it meets the build's Java requirement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JdkCandidate {
+    #[serde(flatten)]
+    pub jdk: Jdk,
+    pub satisfies: bool,
+}
+
+/// What composer.json and composer.lock require of the PHP runtime
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhpRequirements {
+    /// `require.php` constraint, e.g. "^8.2"
+    pub php_constraint: Option<String>,
+    /// Whether the installed php meets the constraint; None when either is unknown
+    pub php_satisfied: Option<bool>,
+    pub extensions: Vec<PhpExtension>,
+}
+
+/// A PHP extension required via an `ext-*` package
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhpExtension {
+    /// Extension name as `php -m` lists it, e.g. intl
+    pub name: String,
+    /// composer.json and/or the locked packages that require it
+    pub required_by: Vec<String>,
+    /// Whether `php -m` lists it; None when php isn't installed
+    pub loaded: Option<bool>,
+}
+
+/// The Ruby and Bundler versions a Gemfile and Gemfile.lock expect
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RubyRequirements {
+    /// Gemfile `ruby` constraint, else the version from .ruby-version or Gemfile.lock
+    pub ruby_constraint: Option<String>,
+    /// Gemfile, .ruby-version or Gemfile.lock
+    pub ruby_source: Option<String>,
+    /// Whether the installed ruby meets the constraint; None when either is unknown
+    pub ruby_satisfied: Option<bool>,
+    /// BUNDLED WITH in Gemfile.lock
+    pub bundler_locked: Option<String>,
+    pub bundler_installed: Option<String>,
+}
+
+/// A Python interpreter on PATH and whether it meets `requires-python`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PythonInterpreter {
+    pub name: String,
+    pub path: PathBuf,
+    pub version: String,
+    pub satisfies: bool,
+}
+
+/// A Maven or Gradle build plugin, e.g. org.springframework.boot 3.2.4
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinnedPlugin {
+    pub id: String,
+    pub version: Option<String>,
+}
+
+/// An application framework and its major version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Framework {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+impl Framework {
+    /// "Next.js 14", or just the name when the version is unknown
+    pub fn label(&self) -> String {
+        match &self.version {
+            Some(v) => format!("{} {}", self.name, v),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// A project-local build tool wrapper (./gradlew, ./mvnw, bazelisk, ./batect)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildWrapper {
+    pub wrapper: String,
+    /// Global tool the wrapper stands in for
+    pub tool: String,
+    pub version: Option<String>,
+    /// File the pinned version was read from
+    pub source: String,
+    /// Version of the global tool, when it differs from the pinned one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub global_version: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DependencyInfo {
     pub count: usize,
     pub sample: Vec<String>,
+    /// Package URLs for every dependency whose version is known
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub purls: Vec<String>,
 }
 
-/// Complete scan result
+/// GNU Guix profiles on this machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuixInfo {
+    pub profiles: Vec<GuixProfile>,
+}
+
+/// A Guix profile and its active generation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuixProfile {
+    /// "user profile", "guix home", "guix pull" or "system"
+    pub name: String,
+    pub path: PathBuf,
+    pub generation: Option<u32>,
+}
+
+/// Unix-on-Windows environment details (MSYS2, MinGW, Cygwin)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompatEnvInfo {
+    /// Environment the current shell runs under, e.g. "MSYS2 (UCRT64)"
+    pub active: Option<String>,
+    pub providers: Vec<CompatProvider>,
+    pub warnings: Vec<String>,
+}
+
+/// Tools provided by a single Unix-on-Windows environment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompatProvider {
+    pub environment: String,
+    pub tools: Vec<String>,
+}
+
+/// Installed cross-compilation targets per toolchain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossCompileInfo {
+    pub rust_targets: Vec<String>,
+    pub zig_targets: Vec<String>,
+    pub gcc_cross_prefixes: Vec<String>,
+    pub go_targets: Vec<String>,
+}
+
+/// Emulator or virtualization frontend (QEMU, Lima, Colima, UTM, ...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VirtTool {
+    pub name: String,
+    pub path: PathBuf,
+    pub version: Option<String>,
+    /// Running VMs/instances; None when not checked or not supported
+    pub running: Option<Vec<String>>,
+}
+
+/// A git hosting CLI (gh, glab, tea)
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgeCli {
+    pub name: String,
+    pub path: PathBuf,
+    pub version: Option<String>,
+    /// Hosts it is logged into; None when not checked (see --git-auth)
+    pub logins: Option<Vec<ForgeLogin>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgeLogin {
+    pub host: String,
+    pub user: Option<String>,
+}
+
+/// Login shell, its framework/plugin manager, and version-manager init hooks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShellInfo {
+    pub path: PathBuf,
+    pub name: String,
+    pub version: Option<String>,
+    pub frameworks: Vec<String>,
+    pub plugin_managers: Vec<String>,
+    pub init_hooks: Vec<ShellInitHook>,
+}
+
+/// A version manager initialized from a shell rc file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShellInitHook {
+    pub manager: String,
+    pub file: PathBuf,
+}
+
+/// Dotfiles manager (chezmoi, stow, yadm, bare repo) and what it manages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DotfilesInfo {
+    pub manager: String,
+    pub repo: PathBuf,
+    /// Date of the newest commit in the dotfiles repo
+    pub last_sync: Option<String>,
+    /// Home-relative shell rc and version pin files under dotfile management
+    pub managed_files: Vec<String>,
+}
+
+/// An installed language server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LspServer {
+    pub name: String,
+    pub path: PathBuf,
+    pub version: Option<String>,
+    /// Ecosystem name prefixes the server provides editor support for
+    pub ecosystems: Vec<String>,
+}
+
+/// Language server availability for a detected project ecosystem
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LspCoverage {
+    pub ecosystem: String,
+    /// Installed servers for this ecosystem (empty means an editor setup gap)
+    pub servers: Vec<String>,
+    pub suggested: Option<String>,
+}
+
+/// A formatter/linter cross-referenced with the current project's config
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinterStatus {
+    pub name: String,
+    pub installed: bool,
+    /// Where the project configures it, if anywhere
+    pub config: Option<String>,
+}
+
+/// A tool the project's CI configuration expects to be available
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CiToolRequirement {
+    pub tool: String,
+    /// CI config file that references the tool
+    pub ci_file: String,
+    pub installed: bool,
+}
+
+/// A tool version pinned for the project by a toolchain manager (Hermit, proto)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolPin {
+    pub tool: String,
+    /// Exact version or a channel/alias such as "stable" or "lts"
+    pub version: String,
+    pub manager: String,
+    /// File or directory the pin was read from
+    pub source: String,
+}
+
+/// How a requirement's constraint is written, which decides how it is matched
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConstraintSyntax {
+    /// A version or prefix ("20", "1.75.0") or a channel ("lts/*", "stable")
+    Exact,
+    /// npm semver ranges, as in `engines` (">=18 <21 || ^22")
+    Semver,
+    /// PEP 440 specifiers, as in `requires-python` (">=3.10,<3.13")
+    Pep440,
+    /// The version or anything newer, as with go.mod's `go` directive
+    Minimum,
+}
+
+/// A tool version a project file asks for (.nvmrc, engines, go.mod, ...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionRequirement {
+    /// Command the requirement applies to, e.g. node or rustc
+    pub tool: String,
+    /// The constraint as written in the file
+    pub constraint: String,
+    pub syntax: ConstraintSyntax,
+    /// File the requirement was read from
+    pub source: String,
+}
+
+/// Outcome of checking a version requirement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RequirementStatus {
+    Pass,
+    /// Installed, but the version or the constraint couldn't be checked
+    Warn,
+    Fail,
+}
+
+/// A version requirement checked against the tools found on this machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequirementCheck {
+    #[serde(flatten)]
+    pub requirement: VersionRequirement,
+    pub status: RequirementStatus,
+    /// The binary the check used, or None when the tool isn't installed
+    pub path: Option<PathBuf>,
+    pub installed: Option<String>,
+}
+
+/// A declared project environment (Devbox, Flox, Conda)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DevEnvironment {
+    /// devbox, flox or conda
+    pub tool: String,
+    /// Manifest the packages were read from
+    pub manifest: String,
+    /// Declared packages, as name@version when a version is given
+    pub packages: Vec<String>,
+    pub cli_installed: bool,
+    /// Whether the environment has been installed/built locally
+    pub built: bool,
+    /// Environment name, for conda's named environments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Python version the environment declares
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub python: Option<String>,
+    /// Packages resolved in a lock file (conda-lock)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locked_packages: Option<usize>,
+}
+
+/// A monorepo orchestrator (nx, turborepo, lerna, rush) configured in the project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonorepoOrchestrator {
+    pub name: String,
+    /// Config file that enabled detection, e.g. nx.json
+    pub config: String,
+    /// Version the repo asks for (package.json spec, or rushVersion)
+    pub pinned_version: Option<String>,
+    /// Version that will actually run: node_modules copy, else global binary
+    pub installed_version: Option<String>,
+    /// Number of workspace packages/projects it manages
+    pub packages: usize,
+}
+
+/// A database migration framework configured in the project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationTool {
+    /// alembic, flyway, liquibase, diesel, sqlx, prisma or knex
+    pub tool: String,
+    /// File that enabled detection
+    pub config: String,
+    /// Where the migrations live, relative to the project
+    pub directory: String,
+    /// Migration files found; whether they are applied needs a database
+    pub migrations: usize,
+    pub cli: String,
+    pub cli_installed: bool,
+}
+
+/// ORMs and drivers a project uses and the database engines they need
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseInfo {
+    pub libraries: Vec<DatabaseLibrary>,
+    pub engines: Vec<DatabaseEngine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseLibrary {
+    pub name: String,
+    /// orm or driver
+    pub kind: String,
+    /// Manifest or config file it was found in
+    pub source: String,
+    /// Engines this library implies for the project
+    pub engines: Vec<String>,
+}
+
+/// A database engine the project needs, and what this machine has of it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseEngine {
+    pub engine: String,
+    pub required_by: Vec<String>,
+    /// Runs in-process (SQLite, H2), so no server is needed
+    pub embedded: bool,
+    /// Server binary found on this machine
+    pub server: Option<String>,
+    /// Client binary found on this machine
+    pub client: Option<String>,
+}
+
+/// API specifications in the project and the tooling that consumes them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiInfo {
+    pub specs: Vec<ApiSpec>,
+    pub tools: Vec<ApiTool>,
+}
+
+/// Spec files of one kind and version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiSpec {
+    /// OpenAPI, GraphQL or Protobuf
+    pub kind: String,
+    /// e.g. "OpenAPI 3.0.3", "Swagger 2.0" or "proto3"
+    pub version: Option<String>,
+    /// Paths relative to the project
+    pub files: Vec<String>,
+}
+
+/// A code generation or validation tool for a spec kind
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiTool {
+    pub name: String,
+    pub kind: String,
+    pub installed: bool,
+}
+
+/// A tool the project's protobuf codegen needs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtoRequirement {
+    /// buf, protoc or a protoc-gen-* plugin
+    pub tool: String,
+    /// What asks for it, e.g. buf.gen.yaml or "Cargo.toml (tonic-build)"
+    pub source: String,
+    pub installed: bool,
+}
+
+/// A workflow tool (docs generator, release automation) the project configures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfiguredTool {
+    pub tool: String,
+    /// Config file that enabled detection
+    pub config: String,
+    /// Version the project pins, as written (e.g. "^3.1.0", "==7.2.6")
+    pub pinned_version: Option<String>,
+    pub cli: String,
+    pub cli_installed: bool,
+    /// How to run it, e.g. "mkdocs build"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+}
+
+/// Container registry logins, and the registries the project pulls from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryAuth {
+    pub logins: Vec<RegistryLogin>,
+    pub referenced: Vec<RegistryReference>,
+}
+
+/// A registry the user has credentials for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryLogin {
+    /// Host, e.g. ghcr.io; Docker Hub is reported as docker.io
+    pub registry: String,
+    /// docker or podman
+    pub source: String,
+    /// Credential helper holding the login, if not stored in the auth file
+    pub helper: Option<String>,
+}
+
+/// A registry referenced by compose files, Dockerfiles or CI configs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryReference {
+    pub registry: String,
+    pub files: Vec<String>,
+    pub logged_in: bool,
+}
+
+/// Result of one `devfetch check` constraint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstraintCheck {
+    /// The constraint as given, e.g. "node>=18"
+    pub constraint: String,
+    pub tool: String,
+    pub path: Option<PathBuf>,
+    pub version: Option<String>,
+    pub satisfied: bool,
+}
+
+/// A problem reported by `devfetch doctor` or the `devfetch path` audit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Finding {
+    pub severity: Severity,
+    /// Which check raised it, e.g. "pin" or "git-lfs"; with the subject this
+    /// is the stable id `.devfetchignore` entries match
+    #[serde(default)]
+    pub check: String,
+    /// Tool, PATH entry or file the finding is about. Doctor's JSON has always
+    /// called it "tool"; the PATH audit writes it as "entry"
+    #[serde(rename = "tool", alias = "entry")]
+    pub subject: String,
+    pub issue: String,
+    /// Why it matters, when that isn't obvious from the issue
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rationale: Option<String>,
+    /// Suggested command or action to resolve it
+    pub fix: Option<String>,
+}
+
+impl Finding {
+    /// Stable id, `check:subject`, e.g. "pin:python2"
+    pub fn id(&self) -> String {
+        format!("{}:{}", self.check, self.subject)
+    }
+}
+
+/// How serious a reported problem is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn display_name(&self) -> &str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// A single PATH entry, in search order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathEntry {
+    pub index: usize,
+    pub dir: PathBuf,
+    pub exists: bool,
+}
+
+/// Result of `devfetch path`: PATH entries plus security findings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathAudit {
+    pub entries: Vec<PathEntry>,
+    #[serde(serialize_with = "path_findings")]
+    pub findings: Vec<Finding>,
+}
+
+/// A `Finding` as `devfetch path --json` has always written it
+#[derive(Serialize)]
+struct PathFinding<'a> {
+    severity: Severity,
+    check: &'a str,
+    entry: &'a str,
+    issue: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rationale: Option<&'a str>,
+    fix: Option<&'a str>,
+}
+
+fn path_findings<S: serde::Serializer>(findings: &[Finding], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(findings.iter().map(|f| PathFinding {
+        severity: f.severity,
+        check: &f.check,
+        entry: &f.subject,
+        issue: &f.issue,
+        rationale: f.rationale.as_deref(),
+        fix: f.fix.as_deref(),
+    }))
+}
+
+/// Aggregated view of scans from many machines
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FleetReport {
+    pub machines: Vec<FleetMachine>,
+    pub tools: Vec<FleetTool>,
+}
+
+/// A machine in the fleet and the team it belongs to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FleetMachine {
+    pub name: String,
+    pub team: Option<String>,
+}
+
+/// One tool across the fleet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FleetTool {
+    pub name: String,
+    /// Version -> machines running it
+    pub versions: BTreeMap<String, Vec<String>>,
+    /// Machines lacking a tool that is required or common in the fleet
+    pub missing_on: Vec<String>,
+    pub outliers: Vec<FleetOutlier>,
+}
+
+/// A machine on a different major version than most of the fleet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FleetOutlier {
+    pub machine: String,
+    pub version: String,
+}
+
+/// How a tool compares between two scans
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffStatus {
+    Same,
+    /// Different versions that don't compare numerically (e.g. one unknown)
+    Changed,
+    /// Newer on the right, e.g. after a brew or apt upgrade
+    Upgraded,
+    /// Older on the right
+    Downgraded,
+    OnlyLeft,
+    OnlyRight,
+}
+
+/// One tool's versions on each side of a comparison
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolDiff {
+    pub name: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+    pub status: DiffStatus,
+}
+
+/// Side-by-side comparison of two scans
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanDiff {
+    pub left: String,
+    pub right: String,
+    pub tools: Vec<ToolDiff>,
+    /// Project ecosystems whose dependency count differs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<DependencyDiff>,
+}
+
+/// An ecosystem's dependency count on each side of a comparison
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyDiff {
+    pub ecosystem: String,
+    pub left: Option<usize>,
+    pub right: Option<usize>,
+}
+
+/// Layout version written into every scan; bump it and add a migration in
+/// `core::snapshot` whenever stored scans would no longer parse
+pub const SCAN_FORMAT_VERSION: u32 = 2;
+
+/// Complete scan result
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ScanResult {
+    /// `SCAN_FORMAT_VERSION` of the devfetch that wrote it; missing before 2
+    #[serde(default)]
+    pub format_version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    /// Problems that left parts of the scan incomplete, e.g. unreadable PATH entries
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ScanWarning>,
     pub global_tools: Vec<Tool>,
     pub project_info: Option<ProjectInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compat_env: Option<CompatEnvInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cross_compile: Option<CrossCompileInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guix: Option<GuixInfo>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub virtualization: Vec<VirtTool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forge_clis: Vec<ForgeCli>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell: Option<ShellInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<ProxyEnv>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<GitSetup>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dotfiles: Option<DotfilesInfo>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub jdks: Vec<Jdk>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub version_managers: Vec<VersionManager>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lsp_servers: Vec<LspServer>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lsp_coverage: Vec<LspCoverage>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub linters: Vec<LinterStatus>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ci_tools: Vec<CiToolRequirement>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned_tools: Vec<ToolPin>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requirements: Vec<VersionRequirement>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dev_environments: Vec<DevEnvironment>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub orchestrators: Vec<MonorepoOrchestrator>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub migrations: Vec<MigrationTool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub databases: Option<DatabaseInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_specs: Option<ApiInfo>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub proto_toolchain: Vec<ProtoRequirement>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub docs_tools: Vec<ConfiguredTool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub release_tools: Vec<ConfiguredTool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry_auth: Option<RegistryAuth>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub system_checks: Vec<SystemCheck>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub services: Vec<ServiceCheck>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub network: Vec<NetworkCheck>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh: Option<SshReadiness>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub package_registries: Vec<PackageRegistry>,
+    /// The --budget ran out or Ctrl-C was pressed before every probe finished
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub partial: bool,
 }
 
 impl ScanResult {
     pub fn new() -> Self {
-        Self::default()
+        Self { format_version: SCAN_FORMAT_VERSION, ..Self::default() }
     }
 }
 
-impl Default for ScanResult {
-    fn default() -> Self {
-        Self {
-            global_tools: Vec::new(),
-            project_info: None,
-        }
-    }
+/// Something the scan couldn't look at, and why
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanWarning {
+    pub kind: WarningKind,
+    /// Directory, binary or file the warning is about
+    pub path: PathBuf,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningKind {
+    /// A PATH or tool directory that exists but couldn't be listed
+    UnreadableDir,
+    /// A tool whose version probe ran past the timeout
+    ProbeTimeout,
+    /// A project manifest that isn't valid JSON/TOML
+    ParseFailure,
+    /// A tool-like entry that couldn't be inspected, e.g. a broken symlink
+    SkippedTool,
+    /// Probes left unrun when the --budget ran out
+    BudgetExhausted,
+    /// Probes left unrun after Ctrl-C
+    Interrupted,
+    /// Commands in a project config that were ignored because the project isn't trusted
+    UntrustedConfig,
+}
+
+/// Progress of a scan, reported from the probing threads so callers can
+/// render it themselves; new stages may be added, so match with a `_` arm
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ScanProgress {
+    /// Probing is about to start on this many executables
+    Started { total: usize },
+    /// A tool was probed or taken from the cache
+    Probed { name: String, done: usize, total: usize },
+    /// Probing is over; this many tools were kept
+    Finished { tools: usize },
+    /// A detail for verbose output, e.g. "Found marker: Cargo.toml"
+    Message(String),
 }
 
+/// Probe error for a binary that answered none of the version flags with a version
+pub const NO_VERSION_ERROR: &str = "no version in the output of --version, -v, version or -V";
+
 /// Version probe result
 #[derive(Debug, Clone)]
 pub struct ProbeResult {
     pub success: bool,
     pub output: String,
     pub version: Option<String>,
+    /// No flag produced a version and at least one ran past the timeout
+    pub timed_out: bool,
+    /// Why no version was found
+    pub error: Option<String>,
+}
+
+/// One version flag tried by `devfetch tool --explain`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProbeAttempt {
+    pub flag: String,
+    /// What came back, e.g. "found version 3.11.4" or "timed out after 1500ms"
+    pub outcome: String,
+    pub version: Option<String>,
+}
+
+/// How devfetch sees one tool, and why (`devfetch tool <name> --explain`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolExplanation {
+    pub name: String,
+    pub path: PathBuf,
+    pub category: ToolCategory,
+    /// Name pattern or path indicator in the classifier that picked the category
+    pub category_reason: String,
+    pub scope: InstallScope,
+    pub scope_reason: String,
+    /// Version flags tried, in order, up to the first that worked
+    pub probes: Vec<ProbeAttempt>,
+    /// Whether a scan would take the version from the probe cache, and why not
+    pub cache: String,
+}
+
+/// A cached version probe and the binary it was taken from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// Binary after following symlinks
+    pub target: PathBuf,
+    pub size: u64,
+    /// Modification time in nanoseconds since the Unix epoch
+    pub mtime: u64,
+    /// Unix time the probe ran
+    pub probed_at: u64,
+    pub success: bool,
+    pub version: Option<String>,
+    pub output: String,
+}
+
+impl CacheEntry {
+    pub fn probe(&self) -> ProbeResult {
+        ProbeResult {
+            success: self.success,
+            output: self.output.clone(),
+            version: self.version.clone(),
+            timed_out: false,
+            error: (!self.success).then(|| NO_VERSION_ERROR.to_string()),
+        }
+    }
+}
+
+/// Cache hits and misses of one scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheUsage {
+    /// Unix time of the scan
+    pub at: u64,
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl CacheUsage {
+    /// Percentage of probes served from the cache
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 * 100.0 / total as f64,
+        }
+    }
+}
+
+/// Summary for `devfetch cache stats`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheStats {
+    pub path: PathBuf,
+    pub entries: usize,
+    /// Entries whose binary changed or disappeared since it was probed
+    pub stale: usize,
+    pub size_bytes: u64,
+    pub last_scan: Option<CacheUsage>,
+    /// Entry counts by age, youngest bucket first
+    pub ages: Vec<(String, usize)>,
+}
+
+/// Opt-in usage statistics: counts and durations only, never names, paths or versions of tools
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsageStats {
+    /// devfetch release that last recorded a scan
+    pub devfetch_version: String,
+    pub scans: u64,
+    /// Scans that found a project in the target directory
+    pub project_scans: u64,
+    pub total_duration_ms: u64,
+    pub max_duration_ms: u64,
+    /// Global tools found, summed over scans (for the average)
+    pub total_tools: u64,
+    pub max_tools: u64,
+}
+
+/// What the shell cd-hook prints on entering a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectSummary {
+    pub path: PathBuf,
+    /// Ecosystem of the first marker found (language runtimes come before build tools)
+    #[serde(default)]
+    pub primary: Option<String>,
+    /// Ecosystems sorted by name, with the tool version when known
+    pub ecosystems: Vec<(String, Option<String>)>,
+    /// Doctor errors and warnings, as "tool: issue"
+    pub mismatches: Vec<String>,
 }